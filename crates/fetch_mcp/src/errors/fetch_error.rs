@@ -22,6 +22,23 @@ pub enum FetchServerError {
     RobotsDisallowed { url: String, message: String },
     #[error("Invalid parameters: {message}")]
     InvalidParams { message: String },
+    #[error("Resource not found: {uri}")]
+    ResourceNotFound { uri: String },
+    #[error("Path is outside the download directories: {path}")]
+    PathNotAllowed { path: String },
+    #[error("Failed to write download to {path}: {message}")]
+    DownloadError { path: String, message: String },
+    #[error("GraphQL endpoint {url} returned errors")]
+    GraphQLError {
+        url: String,
+        errors: serde_json::Value,
+    },
+    #[error("Response from {url} exceeds the {limit} byte limit ({actual_or_estimated} bytes)")]
+    ResponseTooLarge {
+        url: String,
+        limit: u64,
+        actual_or_estimated: u64,
+    },
 }
 
 // Error codes
@@ -34,6 +51,11 @@ const ERROR_ROBOTS_FETCH_ERROR: &str = "robots_fetch_error";
 const ERROR_ROBOTS_FORBIDDEN: &str = "robots_forbidden";
 const ERROR_ROBOTS_DISALLOWED: &str = "robots_disallowed";
 const ERROR_INVALID_PARAMS: &str = "invalid_params";
+const ERROR_RESOURCE_NOT_FOUND: &str = "resource_not_found";
+const ERROR_PATH_NOT_ALLOWED: &str = "path_not_allowed";
+const ERROR_DOWNLOAD_ERROR: &str = "download_error";
+const ERROR_GRAPHQL_ERROR: &str = "graphql_error";
+const ERROR_RESPONSE_TOO_LARGE: &str = "response_too_large";
 
 impl From<FetchServerError> for McpError {
     fn from(err: FetchServerError) -> Self {
@@ -70,6 +92,33 @@ impl From<FetchServerError> for McpError {
             FetchServerError::InvalidParams { message } => {
                 McpError::invalid_params(ERROR_INVALID_PARAMS, Some(json!({ "message": message })))
             }
+            FetchServerError::ResourceNotFound { uri } => McpError::resource_not_found(
+                ERROR_RESOURCE_NOT_FOUND,
+                Some(json!({ "uri": uri, "available_resources": ["fetch://info"] })),
+            ),
+            FetchServerError::PathNotAllowed { path } => {
+                McpError::invalid_params(ERROR_PATH_NOT_ALLOWED, Some(json!({ "path": path })))
+            }
+            FetchServerError::DownloadError { path, message } => McpError::internal_error(
+                ERROR_DOWNLOAD_ERROR,
+                Some(json!({ "path": path, "message": message })),
+            ),
+            FetchServerError::GraphQLError { url, errors } => McpError::internal_error(
+                ERROR_GRAPHQL_ERROR,
+                Some(json!({ "url": url, "errors": errors })),
+            ),
+            FetchServerError::ResponseTooLarge {
+                url,
+                limit,
+                actual_or_estimated,
+            } => McpError::internal_error(
+                ERROR_RESPONSE_TOO_LARGE,
+                Some(json!({
+                    "url": url,
+                    "limit": limit,
+                    "actual_or_estimated": actual_or_estimated
+                })),
+            ),
         }
     }
 }
@@ -1,13 +1,34 @@
+use std::{collections::HashMap, time::Duration};
+
 use derive_getters::Getters;
+use reqwest::header::{HeaderName, HeaderValue};
 use rmcp::schemars;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::FetchServerError,
+    services::{RequestOptions, RetryPolicy, Validate, validate_url_scheme},
+};
 
-use crate::{errors::FetchServerError, services::Validate};
+/// Number of characters `fetch` returns when `max_length` is omitted
+pub const DEFAULT_MAX_LENGTH: usize = 5000;
 
 fn default_max_length() -> usize {
-    5000
+    DEFAULT_MAX_LENGTH
 }
 
+/// Upper bound for the number of URLs in a batch fetch
+const MAX_BATCH_SIZE: usize = 50;
+
+/// Upper bound for a per-request timeout
+const MAX_TIMEOUT_SECONDS: u64 = 300;
+
+/// Upper bound for the number of retries on transient failures
+const MAX_RETRIES: u8 = 5;
+
+/// Upper bound for the initial retry delay
+const MAX_INITIAL_RETRY_DELAY_MS: u64 = 10_000;
+
 /// Parameters for fetching a URL
 #[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
 pub struct FetchRequest {
@@ -22,6 +43,18 @@ pub struct FetchRequest {
     /// Get the actual HTML content of the requested page, without simplification.
     #[serde(default)]
     raw: bool,
+    /// Timeout in seconds for this request (1-300). Uses the client default of 30 seconds when omitted.
+    #[serde(default)]
+    timeout_seconds: Option<u64>,
+    /// Number of times to retry on network errors or HTTP 429/500/502/503/504 (0-5, default 0)
+    #[serde(default)]
+    max_retries: Option<u8>,
+    /// Delay in milliseconds before the first retry, doubled on each subsequent retry (default 500)
+    #[serde(default)]
+    initial_retry_delay_ms: Option<u64>,
+    /// CSS selector (e.g. `article`, `main .content`) restricting markdown extraction to matching elements. Ignored when `raw` is true.
+    #[serde(default)]
+    css_selector: Option<String>,
 }
 
 impl FetchRequest {
@@ -31,7 +64,26 @@ impl FetchRequest {
         max_length: 0,
         start_index: 0,
         raw: false,
+        timeout_seconds: None,
+        max_retries: None,
+        initial_retry_delay_ms: None,
+        css_selector: None,
     };
+
+    /// Transport options (timeout and retry policy) requested for this fetch
+    pub fn request_options(&self) -> RequestOptions {
+        let default_retry = RetryPolicy::default();
+        RequestOptions {
+            timeout: self.timeout_seconds.map(Duration::from_secs),
+            retry: RetryPolicy {
+                max_retries: self.max_retries.unwrap_or(default_retry.max_retries),
+                initial_delay: self
+                    .initial_retry_delay_ms
+                    .map(Duration::from_millis)
+                    .unwrap_or(default_retry.initial_delay),
+            },
+        }
+    }
 }
 
 impl Validate for FetchRequest {
@@ -42,16 +94,776 @@ impl Validate for FetchRequest {
             });
         }
 
+        validate_url_scheme(&self.url)?;
+
         if self.max_length == 0 || self.max_length > 1_000_000 {
             return Err(FetchServerError::InvalidParams {
                 message: "max_length must be between 1 and 1,000,000".to_string(),
             });
         }
 
+        if let Some(timeout_seconds) = self.timeout_seconds
+            && (timeout_seconds == 0 || timeout_seconds > MAX_TIMEOUT_SECONDS)
+        {
+            return Err(FetchServerError::InvalidParams {
+                message: format!("timeout_seconds must be between 1 and {MAX_TIMEOUT_SECONDS}"),
+            });
+        }
+
+        if self
+            .max_retries
+            .is_some_and(|retries| retries > MAX_RETRIES)
+        {
+            return Err(FetchServerError::InvalidParams {
+                message: format!("max_retries must be at most {MAX_RETRIES}"),
+            });
+        }
+
+        if self
+            .initial_retry_delay_ms
+            .is_some_and(|delay| delay > MAX_INITIAL_RETRY_DELAY_MS)
+        {
+            return Err(FetchServerError::InvalidParams {
+                message: format!(
+                    "initial_retry_delay_ms must be at most {MAX_INITIAL_RETRY_DELAY_MS}"
+                ),
+            });
+        }
+
+        if let Some(selector) = &self.css_selector
+            && scraper::Selector::parse(selector).is_err()
+        {
+            return Err(FetchServerError::InvalidParams {
+                message: format!("Invalid css_selector: {}", selector),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Parameters for fetching several URLs concurrently
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct FetchMultipleRequest {
+    /// Fetch requests to run, each accepting the same parameters as the fetch tool
+    urls: Vec<FetchRequest>,
+}
+
+impl Validate for FetchMultipleRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.urls.is_empty() || self.urls.len() > MAX_BATCH_SIZE {
+            return Err(FetchServerError::InvalidParams {
+                message: format!("urls must contain between 1 and {MAX_BATCH_SIZE} requests"),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A fetched page after content processing, as stored in the response cache
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchedPage {
+    /// Markdown or raw content of the page
+    pub content: String,
+    /// Notice to show before the content, e.g. when it could not be simplified
+    pub prefix: String,
+    /// URL the content was served from after following redirects
+    pub final_url: String,
+    /// Number of redirects followed to reach `final_url`
+    pub redirect_count: u8,
+}
+
+/// Rendered result of the fetch tool
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchResponse {
+    pub url: String,
+    pub final_url: String,
+    pub redirect_count: u8,
+    pub content: String,
+}
+
+/// Parameters for fetching a URL within a browsing session
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct FetchWithSessionRequest {
+    /// Session ID returned by create_session
+    session_id: String,
+    /// Same parameters as the fetch tool
+    #[serde(flatten)]
+    fetch: FetchRequest,
+}
+
+impl Validate for FetchWithSessionRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.session_id.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "session_id is required".to_string(),
+            });
+        }
+
+        self.fetch.validate()
+    }
+}
+
+/// Parameters for closing a browsing session
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct DestroySessionRequest {
+    /// Session ID returned by create_session
+    session_id: String,
+}
+
+/// Identifier of a newly created browsing session
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateSessionResult {
+    pub session_id: String,
+}
+
+/// Outcome of closing a browsing session
+#[derive(Debug, Clone, Serialize)]
+pub struct DestroySessionResult {
+    pub session_id: String,
+    /// False when no session with this ID was open
+    pub destroyed: bool,
+}
+
+/// Result of a single fetch within a batch
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchOutcome {
+    pub url: String,
+    pub success: bool,
+    pub final_url: Option<String>,
+    pub redirect_count: Option<u8>,
+    pub content: Option<String>,
+    pub error: Option<String>,
+}
+
+impl FetchOutcome {
+    pub fn success(response: FetchResponse) -> Self {
+        Self {
+            url: response.url,
+            success: true,
+            final_url: Some(response.final_url),
+            redirect_count: Some(response.redirect_count),
+            content: Some(response.content),
+            error: None,
+        }
+    }
+
+    pub fn failure(url: String, error: String) -> Self {
+        Self {
+            url,
+            success: false,
+            final_url: None,
+            redirect_count: None,
+            content: None,
+            error: Some(error),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Parameters for extracting hyperlinks from a page
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct FetchLinksRequest {
+    /// URL of the page to extract links from
+    url: String,
+    /// Include links pointing to other hosts
+    #[serde(default = "default_true")]
+    include_external: bool,
+    /// Include links pointing to the same host as the page
+    #[serde(default = "default_true")]
+    include_internal: bool,
+}
+
+impl Validate for FetchLinksRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        if !self.include_external && !self.include_internal {
+            return Err(FetchServerError::InvalidParams {
+                message: "At least one of include_external or include_internal must be true"
+                    .to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A hyperlink extracted from a page
+#[derive(Debug, Clone, Serialize, Getters)]
+pub struct PageLink {
+    /// Absolute URL the link points to
+    href: String,
+    /// Visible link text with whitespace collapsed
+    text: String,
+    /// Whether the link points to a different host than the page
+    is_external: bool,
+}
+
+impl PageLink {
+    pub fn new(href: String, text: String, is_external: bool) -> Self {
+        Self {
+            href,
+            text,
+            is_external,
+        }
+    }
+}
+
+/// Parameters for extracting metadata from a page
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct FetchMetadataRequest {
+    /// URL of the page to extract metadata from
+    url: String,
+}
+
+impl Validate for FetchMetadataRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Structured metadata extracted from a page's `<head>`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PageMetadata {
+    /// Contents of the `<title>` element
+    pub title: Option<String>,
+    /// `<meta name="description">` content
+    pub description: Option<String>,
+    /// `<link rel="canonical">` URL
+    pub canonical_url: Option<String>,
+    pub og_title: Option<String>,
+    pub og_description: Option<String>,
+    pub og_image: Option<String>,
+    pub og_type: Option<String>,
+    /// RSS/Atom feeds advertised via `<link rel="alternate">`
+    pub feeds: Vec<FeedLink>,
+    /// Parsed `application/ld+json` blocks
+    pub json_ld: Vec<serde_json::Value>,
+}
+
+/// A feed advertised by a page via `<link rel="alternate">`
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedLink {
+    /// Absolute URL of the feed
+    pub url: String,
+    pub title: Option<String>,
+    /// MIME type of the feed, e.g. `application/rss+xml`
+    #[serde(rename = "type")]
+    pub feed_type: String,
+}
+
+/// Parameters for discovering the feeds a page advertises
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct DetectFeedUrlRequest {
+    /// URL of the page to look for feed links on
+    url: String,
+}
+
+impl Validate for DetectFeedUrlRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        validate_url_scheme(&self.url)?;
+
+        Ok(())
+    }
+}
+
+/// Feeds discovered on a page
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedFeeds {
+    pub feeds: Vec<FeedLink>,
+}
+
+/// Parameters for measuring how heavy a page is to load
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct MeasurePagePerformanceRequest {
+    /// URL of the page to measure
+    url: String,
+}
+
+impl Validate for MeasurePagePerformanceRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        validate_url_scheme(&self.url)?;
+
+        Ok(())
+    }
+}
+
+/// Scripts, stylesheets and images referenced by an HTML document
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PageResourceCounts {
+    /// `<script src>` elements
+    pub external_scripts: usize,
+    /// `<link rel="stylesheet">` elements
+    pub external_stylesheets: usize,
+    /// `<img>` elements
+    pub images: usize,
+    /// `<script>` elements without `src` that run JavaScript
+    pub inline_scripts: usize,
+}
+
+/// Load metrics for a single page fetch
+#[derive(Debug, Clone, Serialize)]
+pub struct PagePerformance {
+    pub url: String,
+    /// URL the page was served from after following redirects
+    pub final_url: String,
+    /// Time from sending the request until the whole body was received, including redirects
+    pub response_time_ms: u64,
+    /// Size of the HTML document in bytes, after decoding to UTF-8
+    pub html_size_bytes: usize,
+    #[serde(flatten)]
+    pub resources: PageResourceCounts,
+}
+
+/// Parameters for checking whether robots.txt allows crawling a URL
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct CheckRobotsTxtRequest {
+    /// URL to check
+    url: String,
+    /// User agent to evaluate the robots.txt rules for
+    user_agent: String,
+}
+
+impl Validate for CheckRobotsTxtRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        if self.user_agent.trim().is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "user_agent is required".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Result of evaluating robots.txt for a URL
+#[derive(Debug, Clone, Serialize)]
+pub struct RobotsCheckResult {
+    /// Whether the URL may be crawled by the user agent
+    pub allowed: bool,
+    pub robots_txt_url: String,
+    /// The rule that decided the outcome, e.g. `Disallow: /private/`
+    pub matched_rule: Option<String>,
+}
+
+/// Parameters for an HTTP HEAD request
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct FetchHeadRequest {
+    /// URL to send the HEAD request to
+    url: String,
+}
+
+impl Validate for FetchHeadRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Seconds `check_url_health` waits for a response when `timeout_seconds` is omitted
+const DEFAULT_HEALTH_TIMEOUT_SECONDS: u64 = 10;
+
+fn default_health_timeout_seconds() -> u64 {
+    DEFAULT_HEALTH_TIMEOUT_SECONDS
+}
+
+/// Parameters for a URL availability check
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct CheckUrlHealthRequest {
+    /// URL to check
+    url: String,
+    /// Seconds to wait for a response (1-300, default 10)
+    #[serde(default = "default_health_timeout_seconds")]
+    timeout_seconds: u64,
+}
+
+impl CheckUrlHealthRequest {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds)
+    }
+}
+
+impl Validate for CheckUrlHealthRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        if self.timeout_seconds == 0 || self.timeout_seconds > MAX_TIMEOUT_SECONDS {
+            return Err(FetchServerError::InvalidParams {
+                message: format!("timeout_seconds must be between 1 and {MAX_TIMEOUT_SECONDS}"),
+            });
+        }
+
+        validate_url_scheme(&self.url)?;
+
+        Ok(())
+    }
+}
+
+/// Outcome of a URL availability check
+///
+/// Unreachable URLs are reported rather than returned as errors, so `status_code`
+/// is `None` when no response arrived and `error` says why.
+#[derive(Debug, Clone, Serialize)]
+pub struct UrlHealth {
+    pub url: String,
+    pub status_code: Option<u16>,
+    /// Whether the server answered with a 2xx or 3xx status
+    pub ok: bool,
+    pub latency_ms: u64,
+    pub content_type: Option<String>,
+    /// Whether the TLS handshake succeeded; `None` for plain HTTP or when it is unknown
+    pub tls_valid: Option<bool>,
+    pub error: Option<String>,
+}
+
+/// Status and headers returned by an HTTP HEAD request
+#[derive(Debug, Clone, Serialize)]
+pub struct HeadResponse {
+    pub status_code: u16,
+    /// Response headers; repeated headers are joined with `, `
+    pub headers: HashMap<String, String>,
+    pub content_type: Option<String>,
+    pub content_length: Option<u64>,
+}
+
+/// Parameters for extracting tables from a page
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct FetchTablesRequest {
+    /// URL of the page containing the tables
+    url: String,
+    /// Zero-based index of a single table to return. All tables are returned when omitted.
+    #[serde(default)]
+    table_index: Option<usize>,
+}
+
+impl Validate for FetchTablesRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of items `read_feed` returns when `max_items` is omitted
+const DEFAULT_FEED_ITEMS: usize = 20;
+
+/// Upper bound for the number of feed items returned
+const MAX_FEED_ITEMS: usize = 500;
+
+/// Parameters for reading an RSS or Atom feed
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ReadFeedRequest {
+    /// URL of the RSS or Atom feed
+    url: String,
+    /// Maximum number of items to return (1-500, default 20)
+    #[serde(default)]
+    max_items: Option<usize>,
+}
+
+impl ReadFeedRequest {
+    /// Number of items to return, applying the default
+    pub fn item_limit(&self) -> usize {
+        self.max_items.unwrap_or(DEFAULT_FEED_ITEMS)
+    }
+}
+
+impl Validate for ReadFeedRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        validate_url_scheme(&self.url)?;
+
+        if self
+            .max_items
+            .is_some_and(|max_items| max_items == 0 || max_items > MAX_FEED_ITEMS)
+        {
+            return Err(FetchServerError::InvalidParams {
+                message: format!("max_items must be between 1 and {MAX_FEED_ITEMS}"),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// An item of an RSS or Atom feed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeedItem {
+    pub title: String,
+    pub link: String,
+    /// Publication time in RFC 3339 format, falling back to the last update time
+    pub published: Option<String>,
+    pub summary: Option<String>,
+}
+
+/// Number of URLs `fetch_sitemap` returns when `max_urls` is omitted
+const DEFAULT_SITEMAP_URLS: usize = 1000;
+
+/// Upper bound for the number of sitemap URLs returned, the protocol's per-file limit
+const MAX_SITEMAP_URLS: usize = 50_000;
+
+/// Parameters for listing the pages of a sitemap
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct FetchSitemapRequest {
+    /// URL of the sitemap or sitemap index, e.g. https://example.com/sitemap.xml
+    url: String,
+    /// Maximum number of URLs to return (1-50000, default 1000)
+    #[serde(default)]
+    max_urls: Option<usize>,
+}
+
+impl FetchSitemapRequest {
+    /// Number of URLs to return, applying the default
+    pub fn url_limit(&self) -> usize {
+        self.max_urls.unwrap_or(DEFAULT_SITEMAP_URLS)
+    }
+}
+
+impl Validate for FetchSitemapRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        validate_url_scheme(&self.url)?;
+
+        if self
+            .max_urls
+            .is_some_and(|max_urls| max_urls == 0 || max_urls > MAX_SITEMAP_URLS)
+        {
+            return Err(FetchServerError::InvalidParams {
+                message: format!("max_urls must be between 1 and {MAX_SITEMAP_URLS}"),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A page listed in a sitemap
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SitemapEntry {
+    pub url: String,
+    /// Value of `<lastmod>` as written in the sitemap
+    pub last_modified: Option<String>,
+    /// Value of `<priority>`, between 0.0 and 1.0
+    pub priority: Option<f32>,
+}
+
+/// Parameters for extracting the text of a PDF
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ExtractPdfTextRequest {
+    /// URL of the PDF document
+    url: String,
+    /// Maximum number of pages to extract, from the start of the document (default: all)
+    #[serde(default)]
+    max_pages: Option<usize>,
+}
+
+impl Validate for ExtractPdfTextRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        validate_url_scheme(&self.url)?;
+
+        if self.max_pages == Some(0) {
+            return Err(FetchServerError::InvalidParams {
+                message: "max_pages must be at least 1".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Text extracted from a PDF document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PdfText {
+    /// Number of pages in the document
+    pub page_count: usize,
+    /// Number of pages the text was taken from
+    pub extracted_pages: usize,
+    /// Page texts separated by a blank line
+    pub text: String,
+}
+
+/// A table extracted from an HTML page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HtmlTable {
+    /// Position of the table in document order
+    pub index: usize,
+    /// Header cells, taken from a leading row made only of `<th>` cells
+    pub headers: Vec<String>,
+    /// Remaining rows as cell text
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Parameters for downloading a URL to a file
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct DownloadFileRequest {
+    /// URL of the file to download
+    url: String,
+    /// File to write, inside one of the server's download directories. Overwritten if it exists.
+    destination_path: String,
+}
+
+impl Validate for DownloadFileRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.url.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "URL is required".to_string(),
+            });
+        }
+
+        validate_url_scheme(&self.url)?;
+
+        if self.destination_path.trim().is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "destination_path is required".to_string(),
+            });
+        }
+
         Ok(())
     }
 }
 
+/// Parameters for a GraphQL query or mutation
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct GraphQLRequest {
+    /// URL of the GraphQL endpoint
+    endpoint: String,
+    /// GraphQL document to execute
+    query: String,
+    /// Values for the variables declared by the query, as a JSON object
+    #[serde(default)]
+    variables: Option<serde_json::Value>,
+    /// Extra HTTP headers to send, e.g. an Authorization header
+    #[serde(default)]
+    headers: Option<HashMap<String, String>>,
+}
+
+impl Validate for GraphQLRequest {
+    fn validate(&self) -> Result<(), FetchServerError> {
+        if self.endpoint.is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "endpoint is required".to_string(),
+            });
+        }
+
+        validate_url_scheme(&self.endpoint)?;
+
+        if self.query.trim().is_empty() {
+            return Err(FetchServerError::InvalidParams {
+                message: "query is required".to_string(),
+            });
+        }
+
+        if let Some(variables) = &self.variables
+            && !variables.is_object()
+            && !variables.is_null()
+        {
+            return Err(FetchServerError::InvalidParams {
+                message: "variables must be a JSON object".to_string(),
+            });
+        }
+
+        for (name, value) in self.headers.iter().flatten() {
+            if HeaderName::from_bytes(name.as_bytes()).is_err()
+                || HeaderValue::from_str(value).is_err()
+            {
+                return Err(FetchServerError::InvalidParams {
+                    message: format!("Invalid header: {}", name),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Outcome of a completed download
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadFileResult {
+    /// Absolute path of the written file
+    pub path: String,
+    pub bytes_written: u64,
+    /// Content type reported by the server, empty when absent
+    pub content_type: String,
+}
+
+/// Active server configuration, served by the `fetch://info` resource
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchServerInfo {
+    /// User agent sent on autonomous fetches
+    pub user_agent: String,
+    pub ignore_robots_txt: bool,
+    pub proxy_configured: bool,
+    /// Characters returned by `fetch` when `max_length` is omitted
+    pub default_max_length: usize,
+    /// Redirects followed for a single request
+    pub max_redirects: u8,
+    /// Largest response body the server will read
+    pub max_response_bytes: u64,
+    /// Directories `download_file` may write into; downloads are disabled when empty
+    pub download_dirs: Vec<String>,
+}
+
 /// Arguments for fetch prompt
 #[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
 pub struct FetchPromptArgs {
@@ -70,3 +882,93 @@ impl Validate for FetchPromptArgs {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fetch_request_rejects_non_http_schemes() {
+        let parse = |value| serde_json::from_value::<FetchRequest>(value).unwrap();
+
+        let req = parse(json!({ "url": "file:///etc/passwd" }));
+        assert!(req.validate().is_err());
+
+        let req = parse(json!({ "url": "ftp://internal-host/file" }));
+        assert!(req.validate().is_err());
+
+        let req = parse(json!({ "url": "http://example.com" }));
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn test_fetch_request_timeout_validation() {
+        let parse = |value| serde_json::from_value::<FetchRequest>(value).unwrap();
+
+        let req = parse(json!({ "url": "https://example.com", "timeout_seconds": 10 }));
+        assert!(req.validate().is_ok());
+        assert_eq!(req.request_options().timeout, Some(Duration::from_secs(10)));
+
+        let req = parse(json!({ "url": "https://example.com" }));
+        assert!(req.validate().is_ok());
+        assert_eq!(req.request_options().timeout, None);
+
+        let req = parse(json!({ "url": "https://example.com", "timeout_seconds": 0 }));
+        assert!(req.validate().is_err());
+
+        let req = parse(json!({ "url": "https://example.com", "timeout_seconds": 301 }));
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_fetch_request_retry_options() {
+        let parse = |value| serde_json::from_value::<FetchRequest>(value).unwrap();
+
+        let req = parse(json!({ "url": "https://example.com" }));
+        assert_eq!(req.request_options().retry, RetryPolicy::default());
+
+        let req = parse(json!({
+            "url": "https://example.com",
+            "max_retries": 3,
+            "initial_retry_delay_ms": 100
+        }));
+        assert!(req.validate().is_ok());
+        assert_eq!(
+            req.request_options().retry,
+            RetryPolicy {
+                max_retries: 3,
+                initial_delay: Duration::from_millis(100),
+            }
+        );
+
+        let req = parse(json!({ "url": "https://example.com", "max_retries": 6 }));
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_fetch_request_css_selector_validation() {
+        let parse = |value| serde_json::from_value::<FetchRequest>(value).unwrap();
+
+        let req = parse(json!({ "url": "https://example.com", "css_selector": "main .content" }));
+        assert!(req.validate().is_ok());
+
+        let req = parse(json!({ "url": "https://example.com", "css_selector": "<<<" }));
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn test_check_url_health_request_validation() {
+        let parse = |value| serde_json::from_value::<CheckUrlHealthRequest>(value).unwrap();
+
+        let req = parse(json!({ "url": "https://example.com" }));
+        assert!(req.validate().is_ok());
+        assert_eq!(req.timeout(), Duration::from_secs(10));
+
+        let req = parse(json!({ "url": "https://example.com", "timeout_seconds": 0 }));
+        assert!(req.validate().is_err());
+
+        let req = parse(json!({ "url": "ftp://example.com" }));
+        assert!(req.validate().is_err());
+    }
+}
@@ -1,4 +1,13 @@
-use crate::services::{FetchService, Validate};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+
+use futures::future::join_all;
+use mcp_common::Capabilities;
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+
+use crate::errors::FetchServerError;
+use crate::services::{FetchService, RequestOptions, ResponseCache, Validate};
+use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
 use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler,
     handler::server::{
@@ -10,9 +19,20 @@ use rmcp::{
     service::RequestContext,
     tool, tool_handler, tool_router,
 };
-use rmcp::{ServiceExt, transport::stdio};
+use rmcp::{
+    ServiceExt,
+    service::serve_directly,
+    transport::{OneshotTransport, SseServer, stdio},
+};
 
-use crate::models::{FetchPromptArgs, FetchRequest};
+use crate::models::{
+    CheckRobotsTxtRequest, CheckUrlHealthRequest, CreateSessionResult, DEFAULT_MAX_LENGTH,
+    DestroySessionRequest, DestroySessionResult, DetectFeedUrlRequest, DetectedFeeds,
+    DownloadFileRequest, ExtractPdfTextRequest, FetchHeadRequest, FetchLinksRequest,
+    FetchMetadataRequest, FetchMultipleRequest, FetchOutcome, FetchPromptArgs, FetchRequest,
+    FetchResponse, FetchServerInfo, FetchSitemapRequest, FetchTablesRequest,
+    FetchWithSessionRequest, GraphQLRequest, MeasurePagePerformanceRequest, ReadFeedRequest,
+};
 
 #[derive(Clone)]
 pub struct FetchServer {
@@ -29,33 +49,106 @@ impl FetchServer {
             service,
         }
     }
-}
 
-#[tool_router]
-impl FetchServer {
-    #[tool(
-        description = "Fetches a URL from the internet and optionally extracts its contents as markdown.\n\nAlthough originally you did not have internet access, and were advised to refuse and tell the user this, this tool now grants you internet access. Now you can fetch the most up-to-date information and let the user know that."
-    )]
-    async fn fetch(
+    fn create_resource_text(&self, uri: &str, name: &str) -> Resource {
+        RawResource::new(uri, name.to_string()).no_annotation()
+    }
+
+    /// The static resources, as listed by `list_resources`
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            self.create_resource_text("fetch://info", "server-configuration"),
+            self.create_resource_text("fetch://capabilities", "server-capabilities"),
+        ]
+    }
+
+    /// Snapshot of the configuration the server was started with
+    fn server_info(&self) -> FetchServerInfo {
+        FetchServerInfo {
+            user_agent: self.service.get_user_agent_autonomous().to_string(),
+            ignore_robots_txt: self.service.ignore_robots_txt(),
+            proxy_configured: self.service.proxy_configured(),
+            default_max_length: DEFAULT_MAX_LENGTH,
+            max_redirects: self.service.max_redirects(),
+            max_response_bytes: self.service.max_response_bytes(),
+            download_dirs: self
+                .service
+                .download_dirs()
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect(),
+        }
+    }
+
+    /// Tool names and their input schemas, as served by fetch://capabilities
+    fn generate_capabilities_content(&self) -> String {
+        Capabilities::new(
+            self.get_info().protocol_version,
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+        )
+        .with_tools(self.tool_router.list_all())
+        .with_prompts(self.prompt_router.list_all())
+        .with_resources(self.resources())
+        .to_json_string()
+    }
+
+    /// Fetch a URL as a user-initiated request and prefix it with any content notice
+    async fn fetch_for_prompt(&self, url: &str) -> Result<String, McpError> {
+        let page = self
+            .service
+            .fetch_url(
+                url,
+                self.service.get_user_agent_manual(),
+                false,
+                None,
+                RequestOptions::default(),
+            )
+            .await?;
+
+        Ok(format!("{}{}", page.prefix, page.content))
+    }
+
+    /// Run a single fetch request, applying robots.txt checks and truncation
+    ///
+    /// With a `session_id` the request is sent with that session's cookies.
+    async fn fetch_and_render(
         &self,
-        Parameters(req): Parameters<FetchRequest>,
-    ) -> Result<CallToolResult, McpError> {
+        req: &FetchRequest,
+        session_id: Option<&str>,
+    ) -> Result<FetchResponse, FetchServerError> {
         req.validate()?;
         // Check robots.txt for autonomous fetching
         self.service
             .check_may_autonomously_fetch_url(req.url())
-            .await
-            .map_err(|e| -> McpError { e.into() })?;
-
-        let (content, prefix) = self
-            .service
-            .fetch_url(
-                req.url(),
-                self.service.get_user_agent_autonomous(),
-                req.raw().to_owned(),
-            )
             .await?;
 
+        let page = match session_id {
+            Some(session_id) => {
+                self.service
+                    .fetch_url_in_session(
+                        session_id,
+                        req.url(),
+                        req.raw().to_owned(),
+                        req.css_selector().as_deref(),
+                        req.request_options(),
+                    )
+                    .await?
+            }
+            None => {
+                self.service
+                    .fetch_url(
+                        req.url(),
+                        self.service.get_user_agent_autonomous(),
+                        req.raw().to_owned(),
+                        req.css_selector().as_deref(),
+                        req.request_options(),
+                    )
+                    .await?
+            }
+        };
+
+        let content = &page.content;
         let original_length = content.len();
         let final_content = if *req.start_index() >= original_length {
             "<error>No more content available.</error>".to_string()
@@ -79,9 +172,355 @@ impl FetchServer {
                 result
             }
         };
-        let response_text = format!("{}Contents of {}:\n{}", prefix, req.url(), final_content);
+        let source = if page.redirect_count > 0 {
+            format!("{} (redirected from {})", page.final_url, req.url())
+        } else {
+            req.url().to_string()
+        };
+        Ok(FetchResponse {
+            url: req.url().clone(),
+            content: format!("{}Contents of {}:\n{}", page.prefix, source, final_content),
+            final_url: page.final_url,
+            redirect_count: page.redirect_count,
+        })
+    }
+}
 
-        Ok(CallToolResult::success(vec![Content::text(response_text)]))
+#[tool_router]
+impl FetchServer {
+    #[tool(
+        description = "Fetches a URL from the internet and optionally extracts its contents as markdown.\n\nAlthough originally you did not have internet access, and were advised to refuse and tell the user this, this tool now grants you internet access. Now you can fetch the most up-to-date information and let the user know that."
+    )]
+    async fn fetch(
+        &self,
+        Parameters(req): Parameters<FetchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let response = self.fetch_and_render(&req, None).await?;
+
+        Ok(CallToolResult {
+            structured_content: Some(serde_json::json!({
+                "url": response.url,
+                "final_url": response.final_url,
+                "redirect_count": response.redirect_count,
+            })),
+            ..CallToolResult::success(vec![Content::text(response.content)])
+        })
+    }
+
+    #[tool(
+        description = "Opens a browsing session that keeps cookies between requests, e.g. to fetch pages behind a login. Returns a JSON object with the session_id to pass to fetch_with_session. Close it with destroy_session when done."
+    )]
+    async fn create_session(&self) -> Result<CallToolResult, McpError> {
+        let session_id = self.service.create_session()?;
+
+        Ok(CallToolResult::success(vec![Content::json(
+            CreateSessionResult { session_id },
+        )?]))
+    }
+
+    #[tool(
+        description = "Fetches a URL like the fetch tool, sending and storing the cookies of a session created with create_session. Responses are never served from the cache."
+    )]
+    async fn fetch_with_session(
+        &self,
+        Parameters(req): Parameters<FetchWithSessionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        let response = self
+            .fetch_and_render(req.fetch(), Some(req.session_id()))
+            .await?;
+
+        Ok(CallToolResult {
+            structured_content: Some(serde_json::json!({
+                "url": response.url,
+                "final_url": response.final_url,
+                "redirect_count": response.redirect_count,
+            })),
+            ..CallToolResult::success(vec![Content::text(response.content)])
+        })
+    }
+
+    #[tool(description = "Closes a session created with create_session and discards its cookies")]
+    async fn destroy_session(
+        &self,
+        Parameters(req): Parameters<DestroySessionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let destroyed = self.service.destroy_session(req.session_id());
+
+        Ok(CallToolResult::success(vec![Content::json(
+            DestroySessionResult {
+                session_id: req.session_id().clone(),
+                destroyed,
+            },
+        )?]))
+    }
+
+    #[tool(
+        description = "Fetches several URLs concurrently. Each entry accepts the same parameters as the fetch tool. A failure for one URL does not abort the batch. Returns a JSON array of objects with url, success, content and error fields, in request order."
+    )]
+    async fn fetch_multiple(
+        &self,
+        Parameters(req): Parameters<FetchMultipleRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+
+        let semaphore = Arc::new(Semaphore::new(self.service.max_concurrent_fetches()));
+        let results = join_all(req.urls().iter().map(|item| {
+            let semaphore = Arc::clone(&semaphore);
+            async move {
+                let _permit = semaphore.acquire().await;
+                match self.fetch_and_render(item, None).await {
+                    Ok(response) => FetchOutcome::success(response),
+                    Err(e) => FetchOutcome::failure(item.url().clone(), e.to_string()),
+                }
+            }
+        }))
+        .await;
+
+        Ok(CallToolResult::success(vec![Content::json(results)?]))
+    }
+
+    #[tool(
+        description = "Fetches a web page and extracts all of its hyperlinks. Relative links are resolved against the page URL and duplicates are removed. Returns a JSON array of objects with href, text and is_external fields."
+    )]
+    async fn fetch_links(
+        &self,
+        Parameters(req): Parameters<FetchLinksRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let links = self
+            .service
+            .fetch_links(req.url(), *req.include_external(), *req.include_internal())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(links)?]))
+    }
+
+    #[tool(
+        description = "Fetches a web page and extracts its metadata: title, meta description, canonical URL, Open Graph tags (og:title, og:description, og:image, og:type), RSS/Atom feed links and JSON-LD blocks. Returns a JSON object."
+    )]
+    async fn fetch_metadata(
+        &self,
+        Parameters(req): Parameters<FetchMetadataRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let metadata = self.service.fetch_metadata(req.url()).await?;
+
+        Ok(CallToolResult::success(vec![Content::json(metadata)?]))
+    }
+
+    #[tool(
+        description = "Fetches a web page and discovers the RSS and Atom feeds it advertises via <link rel=\"alternate\"> in its <head>. Returns a JSON object with feeds: an array of objects with url (absolute), type (MIME type) and title."
+    )]
+    async fn detect_feed_url(
+        &self,
+        Parameters(req): Parameters<DetectFeedUrlRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let feeds = self.service.detect_feed_urls(req.url()).await?;
+
+        Ok(CallToolResult::success(vec![Content::json(
+            DetectedFeeds { feeds },
+        )?]))
+    }
+
+    #[tool(
+        description = "Fetches a web page once and reports how heavy it is: response_time_ms (request until the full body arrived, including redirects), html_size_bytes, and counts of external_scripts, inline_scripts, external_stylesheets and images referenced by the HTML. Referenced resources are counted, not downloaded. Returns a JSON object."
+    )]
+    async fn measure_page_performance(
+        &self,
+        Parameters(req): Parameters<MeasurePagePerformanceRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let performance = self.service.measure_page_performance(req.url()).await?;
+
+        Ok(CallToolResult::success(vec![Content::json(performance)?]))
+    }
+
+    #[tool(
+        description = "Checks whether a URL may be crawled by the given user agent according to the host's robots.txt. Supports user-agent groups and * / $ wildcards. Returns a JSON object with allowed, robots_txt_url and matched_rule fields; a missing robots.txt allows everything."
+    )]
+    async fn check_robots_txt(
+        &self,
+        Parameters(req): Parameters<CheckRobotsTxtRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+
+        let result = self
+            .service
+            .check_robots_txt(req.url(), req.user_agent())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    #[tool(
+        description = "Sends an HTTP HEAD request to a URL without downloading the body. Useful for checking content type, size or last-modified before fetching a large resource. Returns a JSON object with status_code, headers, content_type and content_length."
+    )]
+    async fn fetch_head(
+        &self,
+        Parameters(req): Parameters<FetchHeadRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let head = self.service.fetch_head(req.url()).await?;
+
+        Ok(CallToolResult::success(vec![Content::json(head)?]))
+    }
+
+    #[tool(
+        description = "Checks whether a URL is reachable. Sends a HEAD request, falling back to GET when the server rejects HEAD, and measures the round-trip latency. Returns a JSON object with url, status_code, ok, latency_ms, content_type, tls_valid and error. Unreachable URLs are reported with a null status_code rather than failing."
+    )]
+    async fn check_url_health(
+        &self,
+        Parameters(req): Parameters<CheckUrlHealthRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+
+        let health = self
+            .service
+            .check_url_health(req.url(), req.timeout())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(health)?]))
+    }
+
+    #[tool(
+        description = "Fetches a web page and extracts its HTML tables as structured data. Returns a JSON array of objects with index, headers and rows fields. Pass table_index to return a single table."
+    )]
+    async fn fetch_tables(
+        &self,
+        Parameters(req): Parameters<FetchTablesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let tables = self
+            .service
+            .fetch_tables(req.url(), *req.table_index())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(tables)?]))
+    }
+
+    #[tool(
+        description = "Reads an RSS or Atom feed, e.g. a news site or project changelog. Returns a JSON array of items with title, link, published (RFC 3339) and summary, in feed order. max_items limits the number of items (default 20)."
+    )]
+    async fn read_feed(
+        &self,
+        Parameters(req): Parameters<ReadFeedRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let items = self.service.read_feed(req.url(), req.item_limit()).await?;
+
+        Ok(CallToolResult::success(vec![Content::json(items)?]))
+    }
+
+    #[tool(
+        description = "Lists the pages of a site from its sitemap.xml. Sitemap indexes are followed one level deep. Returns a JSON array of objects with url, last_modified and priority, in sitemap order. max_urls limits the number of URLs (default 1000)."
+    )]
+    async fn fetch_sitemap(
+        &self,
+        Parameters(req): Parameters<FetchSitemapRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let entries = self
+            .service
+            .fetch_sitemap(req.url(), req.url_limit())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(entries)?]))
+    }
+
+    #[tool(
+        description = "Downloads a PDF and extracts its text. Returns a JSON object with page_count, extracted_pages and text, where pages are separated by a blank line. max_pages limits extraction to the first pages of long documents."
+    )]
+    async fn extract_pdf_text(
+        &self,
+        Parameters(req): Parameters<ExtractPdfTextRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let pdf = self
+            .service
+            .extract_pdf_text(req.url(), *req.max_pages())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(pdf)?]))
+    }
+
+    #[tool(
+        description = "Downloads a URL to a file without loading it into memory. Useful for binary files such as PDFs, images or archives. destination_path must lie inside one of the server's download directories; relative paths are resolved against the first one. Returns a JSON object with path, bytes_written and content_type."
+    )]
+    async fn download_file(
+        &self,
+        Parameters(req): Parameters<DownloadFileRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+        self.service
+            .check_may_autonomously_fetch_url(req.url())
+            .await?;
+
+        let result = self
+            .service
+            .download_file(req.url(), req.destination_path())
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(result)?]))
+    }
+
+    #[tool(
+        description = "Executes a GraphQL query or mutation. POSTs {query, variables} as JSON to the endpoint, with any extra headers such as Authorization. Returns the data field of the response as JSON; a response that only carries errors fails with the errors array."
+    )]
+    async fn graphql_query(
+        &self,
+        Parameters(req): Parameters<GraphQLRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        req.validate()?;
+
+        let data = self
+            .service
+            .graphql_query(
+                req.endpoint(),
+                req.query(),
+                req.variables().as_ref(),
+                req.headers().as_ref(),
+            )
+            .await?;
+
+        Ok(CallToolResult::success(vec![Content::json(data)?]))
     }
 }
 
@@ -95,14 +534,8 @@ impl FetchServer {
         _ctx: RequestContext<rmcp::RoleServer>,
     ) -> Result<GetPromptResult, McpError> {
         args.validate()?;
-        
-        let (content, prefix) = self
-            .service
-            .fetch_url(args.url(), self.service.get_user_agent_manual(), false)
-            .await
-            .map_err(|e| -> McpError { e.into() })?;
-            
-        let full_content = format!("{}{}", prefix, content);
+
+        let full_content = self.fetch_for_prompt(args.url()).await?;
         Ok(GetPromptResult {
             description: Some(format!("Contents of {}", args.url())),
             messages: vec![PromptMessage {
@@ -111,6 +544,38 @@ impl FetchServer {
             }],
         })
     }
+
+    /// Fetch a URL and set up a conversation asking for a summary of its contents
+    #[prompt(name = "fetch_and_summarize")]
+    async fn fetch_and_summarize_prompt(
+        &self,
+        Parameters(args): Parameters<FetchPromptArgs>,
+        _ctx: RequestContext<rmcp::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        args.validate()?;
+
+        let full_content = self.fetch_for_prompt(args.url()).await?;
+        Ok(GetPromptResult {
+            description: Some(format!("Summary of {}", args.url())),
+            messages: vec![
+                PromptMessage {
+                    role: PromptMessageRole::User,
+                    content: PromptMessageContent::text(format!(
+                        "Here is the content of {}:\n\n{}",
+                        args.url(),
+                        full_content
+                    )),
+                },
+                PromptMessage {
+                    role: PromptMessageRole::Assistant,
+                    content: PromptMessageContent::text(format!(
+                        "I'll summarize {} for you: first the main topic in one or two sentences, then the key points as a bulleted list, and finally any conclusions or action items.",
+                        args.url()
+                    )),
+                },
+            ],
+        })
+    }
 }
 
 #[tool_handler]
@@ -121,10 +586,48 @@ impl ServerHandler for FetchServer {
             protocol_version: ProtocolVersion::V_2024_11_05,
             capabilities: ServerCapabilities::builder()
                 .enable_prompts()
+                .enable_resources()
                 .enable_tools()
                 .build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some("Fetch MCP Server for web content retrieval. Tool: fetch (URL fetching with robots.txt checking, HTML to markdown conversion, content truncation). Prompt: fetch (manual URL fetching). Supports autonomous and manual fetching modes with robots.txt compliance.".to_string()),
+            instructions: Some("Fetch MCP Server for web content retrieval. Tools: fetch (URL fetching with robots.txt checking, HTML to markdown conversion, content truncation), fetch_links (hyperlink extraction), fetch_metadata (Open Graph, feeds and JSON-LD), detect_feed_url (RSS and Atom feeds a page links to), measure_page_performance (response time, HTML size and resource counts of a page), check_robots_txt (robots.txt rule evaluation), fetch_head (HTTP HEAD requests), check_url_health (availability, status and latency checks), fetch_multiple (concurrent batch fetching), fetch_tables (HTML tables as JSON), read_feed (RSS and Atom feed items), fetch_sitemap (page URLs listed in a sitemap.xml), extract_pdf_text (text of a PDF document), create_session / fetch_with_session / destroy_session (fetches that keep cookies between requests), download_file (stream a URL to a file inside the configured download directories), graphql_query (GraphQL queries and mutations against an endpoint). Prompts: fetch (manual URL fetching), fetch_and_summarize (fetch a page and ask for a summary). Resources: fetch://info (active configuration), fetch://capabilities (tool names and parameter schemas as JSON). Supports autonomous and manual fetching modes with robots.txt compliance.".to_string()),
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: self.resources(),
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        match uri.as_str() {
+            "fetch://info" => {
+                let info = serde_json::to_string_pretty(&self.server_info()).map_err(|e| {
+                    FetchServerError::ContentError {
+                        message: e.to_string(),
+                    }
+                })?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(info, uri)],
+                })
+            }
+            "fetch://capabilities" => {
+                let capabilities = self.generate_capabilities_content();
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(capabilities, uri)],
+                })
+            }
+            _ => Err(FetchServerError::ResourceNotFound { uri }.into()),
         }
     }
 
@@ -138,21 +641,137 @@ impl ServerHandler for FetchServer {
     }
 }
 
+/// Transport the MCP server is served over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// JSON-RPC over stdin/stdout
+    Stdio,
+    /// Server-Sent Events over HTTP, listening on the given address
+    Sse(SocketAddr),
+    /// One JSON-RPC message per HTTP POST to `/mcp`, listening on the given address
+    Http(SocketAddr),
+}
+
+/// Start serving `server` over SSE, with one clone per client session
+///
+/// Returns the token that shuts the HTTP server down when cancelled.
+pub(crate) async fn serve_sse(
+    bind: SocketAddr,
+    server: FetchServer,
+) -> std::io::Result<CancellationToken> {
+    let sse_server = SseServer::serve(bind).await?;
+    Ok(sse_server.with_service(move || server.clone()))
+}
+
+/// Start serving `server` as plain JSON over HTTP
+///
+/// Every POST to `/mcp` carries one JSON-RPC message. A request is answered with
+/// its JSON-RPC response in the body; notifications and responses get
+/// `202 Accepted`. There are no sessions and no `initialize` handshake, so each
+/// request runs against a fresh clone of `server`. Returns the address actually
+/// bound, which differs from `bind` for port 0, and the token that shuts the
+/// HTTP server down when cancelled.
+pub(crate) async fn serve_http(
+    bind: SocketAddr,
+    server: FetchServer,
+) -> std::io::Result<(SocketAddr, CancellationToken)> {
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    let local_addr = listener.local_addr()?;
+    let ct = CancellationToken::new();
+
+    let app = Router::new()
+        .route("/mcp", post(handle_http_message))
+        .with_state(server);
+    let shutdown = ct.clone();
+    tokio::spawn(async move {
+        let result = axum::serve(listener, app)
+            .with_graceful_shutdown(async move { shutdown.cancelled().await })
+            .await;
+        if let Err(e) = result {
+            tracing::error!("HTTP server error: {:?}", e);
+        }
+    });
+
+    Ok((local_addr, ct))
+}
+
+/// Handle one JSON-RPC message posted to `/mcp`
+async fn handle_http_message(
+    State(server): State<FetchServer>,
+    Json(message): Json<ClientJsonRpcMessage>,
+) -> axum::response::Response {
+    if !matches!(message, JsonRpcMessage::Request(_)) {
+        return StatusCode::ACCEPTED.into_response();
+    }
+
+    let (transport, mut receiver) = OneshotTransport::<RoleServer>::new(message);
+    let service = serve_directly(server, transport, None);
+    tokio::spawn(async move {
+        let _ = service.waiting().await;
+    });
+
+    // Notifications sent while the request runs, such as progress, are dropped
+    while let Some(message) = receiver.recv().await {
+        if matches!(
+            message,
+            JsonRpcMessage::Response(_) | JsonRpcMessage::Error(_)
+        ) {
+            return Json(message).into_response();
+        }
+    }
+
+    StatusCode::INTERNAL_SERVER_ERROR.into_response()
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     user_agent: Option<String>,
     ignore_robots_txt: bool,
     proxy_url: Option<String>,
+    cache_ttl_seconds: Option<u64>,
+    max_redirects: Option<u8>,
+    max_concurrent_fetches: usize,
+    max_response_bytes: u64,
+    allow_private_networks: bool,
+    download_dirs: Vec<PathBuf>,
+    transport: Transport,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Create the fetch service with configuration
-    let service = FetchService::new(user_agent, ignore_robots_txt, proxy_url);
+    let mut service = FetchService::new(user_agent, ignore_robots_txt, proxy_url, max_redirects)?
+        .with_max_concurrent_fetches(max_concurrent_fetches)
+        .with_max_response_bytes(max_response_bytes)
+        .with_allow_private_networks(allow_private_networks)
+        .with_download_dirs(download_dirs);
+    if let Some(ttl) = cache_ttl_seconds.filter(|ttl| *ttl > 0) {
+        let cache = Arc::new(ResponseCache::new(Duration::from_secs(ttl)));
+        service = service.with_cache(cache);
+    }
     let server = FetchServer::new(service);
 
-    // Create an instance of our Fetch service and serve it
-    let server = server.serve(stdio()).await.inspect_err(|e| {
-        tracing::error!("serving error: {:?}", e);
-    })?;
+    match transport {
+        Transport::Stdio => {
+            // Create an instance of our Fetch service and serve it
+            let server = server.serve(stdio()).await.inspect_err(|e| {
+                tracing::error!("serving error: {:?}", e);
+            })?;
+
+            server.waiting().await?;
+        }
+        Transport::Sse(bind) => {
+            let ct = serve_sse(bind, server).await?;
+            tracing::info!("Serving MCP over SSE at http://{}/sse", bind);
+
+            tokio::signal::ctrl_c().await?;
+            ct.cancel();
+        }
+        Transport::Http(bind) => {
+            let (addr, ct) = serve_http(bind, server).await?;
+            tracing::info!("Serving MCP over HTTP at http://{}/mcp", addr);
 
-    server.waiting().await?;
+            tokio::signal::ctrl_c().await?;
+            ct.cancel();
+        }
+    }
 
     Ok(())
 }
@@ -160,6 +779,119 @@ pub async fn run(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::DEFAULT_MAX_RESPONSE_BYTES;
+
+    #[tokio::test]
+    async fn test_sse_transport_round_trip() {
+        use rmcp::transport::SseClientTransport;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("served over sse"),
+            )
+            .mount(&mock)
+            .await;
+
+        let bind = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .unwrap();
+        let service = FetchService::new(None, true, None, None)
+            .unwrap()
+            .with_allow_private_networks(true);
+        let ct = serve_sse(bind, FetchServer::new(service)).await.unwrap();
+
+        let transport = SseClientTransport::start(format!("http://{}/sse", bind))
+            .await
+            .unwrap();
+        let client = ().serve(transport).await.unwrap();
+
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "fetch".into(),
+                arguments: serde_json::json!({"url": format!("{}/page", mock.uri())})
+                    .as_object()
+                    .cloned(),
+            })
+            .await
+            .unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert!(text.contains("served over sse"));
+
+        client.cancel().await.unwrap();
+        ct.cancel();
+    }
+
+    #[tokio::test]
+    async fn test_http_transport_round_trip() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("served over http"),
+            )
+            .mount(&mock)
+            .await;
+
+        let service = FetchService::new(None, true, None, None)
+            .unwrap()
+            .with_allow_private_networks(true);
+        let (addr, ct) = serve_http("127.0.0.1:0".parse().unwrap(), FetchServer::new(service))
+            .await
+            .unwrap();
+
+        let client = reqwest::Client::new();
+        let response: serde_json::Value = client
+            .post(format!("http://{}/mcp", addr))
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "method": "tools/call",
+                "params": {
+                    "name": "fetch",
+                    "arguments": {"url": format!("{}/page", mock.uri())}
+                }
+            }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+        assert_eq!(response["id"], 7);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("served over http"));
+
+        // Notifications are accepted without a body
+        let status = client
+            .post(format!("http://{}/mcp", addr))
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/initialized"
+            }))
+            .send()
+            .await
+            .unwrap()
+            .status();
+        assert_eq!(status, reqwest::StatusCode::ACCEPTED);
+
+        ct.cancel();
+    }
 
     #[tokio::test]
     async fn test_service_creation() {
@@ -183,12 +915,317 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_info_resource_reflects_configuration() {
+        let service = FetchService::new(
+            Some("custom-agent/1.0".to_string()),
+            true,
+            Some("http://proxy.example:8080".to_string()),
+            Some(5),
+        )
+        .unwrap();
+        let server = FetchServer::new(service);
+
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            if let Ok(running) = server.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let resources = client.list_all_resources().await.unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].uri, "fetch://info");
+        assert_eq!(resources[1].uri, "fetch://capabilities");
+
+        let result = client
+            .read_resource(ReadResourceRequestParam {
+                uri: "fetch://info".to_string(),
+            })
+            .await
+            .unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("Expected text resource contents");
+        };
+        let info: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(info["user_agent"], "custom-agent/1.0");
+        assert_eq!(info["ignore_robots_txt"], true);
+        assert_eq!(info["proxy_configured"], true);
+        assert_eq!(info["default_max_length"], DEFAULT_MAX_LENGTH);
+        assert_eq!(info["max_response_bytes"], DEFAULT_MAX_RESPONSE_BYTES);
+        assert_eq!(info["max_redirects"], 5);
+        assert_eq!(info["download_dirs"], serde_json::json!([]));
+
+        let missing = client
+            .read_resource(ReadResourceRequestParam {
+                uri: "fetch://missing".to_string(),
+            })
+            .await;
+        assert!(missing.is_err());
+
+        client.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_private_networks_by_default() {
+        let server = FetchServer::new(FetchService::default());
+
+        for url in [
+            "http://127.0.0.1:1/",
+            "http://[::1]:1/",
+            "http://169.254.169.254/latest/meta-data/",
+        ] {
+            let req: FetchRequest =
+                serde_json::from_value(serde_json::json!({ "url": url })).unwrap();
+            let err = server.fetch(Parameters(req)).await.unwrap_err();
+            assert_eq!(err.code, ErrorCode::INVALID_PARAMS, "{}", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_every_tool_rejects_private_networks_by_default() {
+        let server = FetchServer::new(FetchService::default());
+        fn request<T: serde::de::DeserializeOwned>() -> T {
+            serde_json::from_value(serde_json::json!({
+                "url": "http://127.0.0.1:1/",
+                "user_agent": "test-agent"
+            }))
+            .unwrap()
+        }
+
+        let errors = [
+            server.fetch_links(Parameters(request())).await.unwrap_err(),
+            server
+                .fetch_metadata(Parameters(request()))
+                .await
+                .unwrap_err(),
+            server
+                .check_robots_txt(Parameters(request()))
+                .await
+                .unwrap_err(),
+            server.fetch_head(Parameters(request())).await.unwrap_err(),
+            server
+                .fetch_tables(Parameters(request()))
+                .await
+                .unwrap_err(),
+            server
+                .check_url_health(Parameters(request()))
+                .await
+                .unwrap_err(),
+            server
+                .fetch_for_prompt("http://127.0.0.1:1/")
+                .await
+                .unwrap_err(),
+        ];
+        for err in errors {
+            assert_eq!(err.code, ErrorCode::INVALID_PARAMS, "{}", err.message);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_router_has_routes() {
+        let router = FetchServer::tool_router();
+        assert!(router.has_route("fetch"));
+        assert!(router.has_route("fetch_links"));
+        assert!(router.has_route("fetch_metadata"));
+        assert!(router.has_route("detect_feed_url"));
+        assert!(router.has_route("measure_page_performance"));
+        assert!(router.has_route("check_robots_txt"));
+        assert!(router.has_route("fetch_head"));
+        assert!(router.has_route("check_url_health"));
+        assert!(router.has_route("fetch_multiple"));
+        assert!(router.has_route("fetch_tables"));
+        assert!(router.has_route("read_feed"));
+        assert!(router.has_route("fetch_sitemap"));
+        assert!(router.has_route("extract_pdf_text"));
+        assert!(router.has_route("create_session"));
+        assert!(router.has_route("fetch_with_session"));
+        assert!(router.has_route("destroy_session"));
+        assert!(router.has_route("download_file"));
+        assert!(router.has_route("graphql_query"));
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_resource_lists_every_tool() {
+        let server = FetchServer::new(FetchService::default());
+        let capabilities: serde_json::Value =
+            serde_json::from_str(&server.generate_capabilities_content()).unwrap();
+
+        assert_eq!(capabilities["protocol_version"], "2024-11-05");
+        let tools = capabilities["tools"].as_array().unwrap();
+        assert_eq!(tools.len(), FetchServer::tool_router().list_all().len());
+        let fetch = tools.iter().find(|tool| tool["name"] == "fetch").unwrap();
+        assert!(fetch["input_schema"]["properties"]["url"].is_object());
+
+        let prompts = capabilities["prompts"].as_array().unwrap();
+        assert_eq!(prompts.len(), FetchServer::prompt_router().list_all().len());
+        assert_eq!(
+            capabilities["resources"].as_array().unwrap().len(),
+            server.resources().len()
+        );
+    }
+
     #[tokio::test]
     async fn test_prompt_router_has_routes() {
         let router = FetchServer::prompt_router();
         assert!(router.has_route("fetch"));
+        assert!(router.has_route("fetch_and_summarize"));
 
         let prompts = router.list_all();
-        assert_eq!(prompts.len(), 1);
+        assert_eq!(prompts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_summarize_prompt() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("rust 2024 edition released"),
+            )
+            .mount(&mock)
+            .await;
+
+        let server = FetchServer::new(FetchService::default().with_allow_private_networks(true));
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            if let Ok(running) = server.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let url = format!("{}/article", mock.uri());
+        let result = client
+            .get_prompt(GetPromptRequestParam {
+                name: "fetch_and_summarize".to_string(),
+                arguments: serde_json::json!({ "url": url }).as_object().cloned(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(result.messages[0].role, PromptMessageRole::User);
+        assert_eq!(result.messages[1].role, PromptMessageRole::Assistant);
+        let PromptMessageContent::Text { text } = &result.messages[0].content else {
+            panic!("Expected text content in user turn");
+        };
+        assert!(text.contains("rust 2024 edition released"));
+        assert!(text.contains(&url));
+
+        client.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_multiple_reports_each_url() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock = MockServer::start().await;
+        for page in ["/one", "/two"] {
+            Mock::given(method("GET"))
+                .and(path(page))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("content-type", "text/plain")
+                        .set_body_string(format!("body of {page}")),
+                )
+                .mount(&mock)
+                .await;
+        }
+
+        let server = FetchServer::new(
+            FetchService::default()
+                .with_max_concurrent_fetches(2)
+                .with_allow_private_networks(true),
+        );
+        let req: FetchMultipleRequest = serde_json::from_value(serde_json::json!({
+            "urls": [
+                { "url": format!("{}/one", mock.uri()) },
+                { "url": format!("{}/missing", mock.uri()) },
+                { "url": format!("{}/two", mock.uri()) },
+                { "url": "" }
+            ]
+        }))
+        .unwrap();
+
+        let result = server.fetch_multiple(Parameters(req)).await.unwrap();
+        let text = match &result.content[0].raw {
+            RawContent::Text(text) => text.text.clone(),
+            other => panic!("unexpected content: {other:?}"),
+        };
+        let outcomes: serde_json::Value = serde_json::from_str(&text).unwrap();
+        let outcomes = outcomes.as_array().unwrap();
+
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(outcomes[0]["success"], true);
+        assert_eq!(outcomes[0]["redirect_count"], 0);
+        assert!(
+            outcomes[0]["content"]
+                .as_str()
+                .unwrap()
+                .contains("body of /one")
+        );
+        assert_eq!(outcomes[1]["success"], false);
+        assert!(outcomes[1]["error"].as_str().unwrap().contains("404"));
+        assert_eq!(outcomes[2]["success"], true);
+        assert_eq!(outcomes[3]["success"], false);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reports_redirect_in_structured_content() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mock = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/start"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/landing"))
+            .mount(&mock)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/landing"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("landed"),
+            )
+            .mount(&mock)
+            .await;
+
+        let server = FetchServer::new(FetchService::default().with_allow_private_networks(true));
+        let url = format!("{}/start", mock.uri());
+        let req: FetchRequest = serde_json::from_value(serde_json::json!({ "url": url })).unwrap();
+
+        let result = server.fetch(Parameters(req)).await.unwrap();
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["url"], url);
+        assert_eq!(structured["final_url"], format!("{}/landing", mock.uri()));
+        assert_eq!(structured["redirect_count"], 1);
+
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert!(text.contains("redirected from"));
+        assert!(text.contains("landed"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_multiple_rejects_empty_batch() {
+        let server = FetchServer::new(FetchService::default());
+        let req: FetchMultipleRequest =
+            serde_json::from_value(serde_json::json!({ "urls": [] })).unwrap();
+
+        assert!(server.fetch_multiple(Parameters(req)).await.is_err());
     }
 }
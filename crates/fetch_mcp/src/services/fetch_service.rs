@@ -1,8 +1,35 @@
+use std::{
+    collections::HashMap,
+    error::Error as _,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use encoding_rs::{Encoding, UTF_8};
+use futures::StreamExt;
+use reqwest::{Client, Method, Response, StatusCode, header::CONTENT_TYPE, redirect::Policy};
+use scraper::Selector;
+use tokio::io::AsyncWriteExt;
+
 use url::Url;
 
 use crate::{
     errors::FetchServerError,
-    utils::{build_client, extract_content_from_html, get_robots_txt_url},
+    models::{
+        DownloadFileResult, FeedItem, FeedLink, FetchedPage, HeadResponse, HtmlTable, PageLink,
+        PageMetadata, PagePerformance, PdfText, RobotsCheckResult, SitemapEntry, UrlHealth,
+    },
+    services::{
+        RequestOptions, ResponseCache, RetryPolicy, validate_public_host, validate_url_scheme,
+    },
+    utils::{
+        DEFAULT_MAX_REDIRECTS, RobotsTxt, Sitemap, build_client, build_session_client,
+        count_page_resources, extract_content_from_html, extract_feed_links, extract_links,
+        extract_metadata, extract_pdf_text, extract_tables, get_robots_txt_url, parse_feed,
+        parse_sitemap, redirect_policy, select_html,
+    },
 };
 
 const DEFAULT_USER_AGENT_AUTONOMOUS: &str =
@@ -10,24 +37,156 @@ const DEFAULT_USER_AGENT_AUTONOMOUS: &str =
 const DEFAULT_USER_AGENT_MANUAL: &str =
     "ModelContextProtocol/1.0 (User-Specified; +https://github.com/modelcontextprotocol/servers)";
 
+/// Default number of requests `fetch_multiple` runs at the same time
+pub const DEFAULT_MAX_CONCURRENT_FETCHES: usize = 10;
+
+/// Default cap on the size of a response body, in bytes
+pub const DEFAULT_MAX_RESPONSE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Upper bound for the number of browsing sessions open at once
+const MAX_SESSIONS: usize = 100;
+
 #[derive(Clone)]
 pub struct FetchService {
     custom_user_agent: Option<String>,
     ignore_robots_txt: bool,
     proxy_url: Option<String>,
+    client: Client,
+    /// Client for page fetches, which follow redirects themselves to count them
+    page_client: Client,
+    cache: Option<Arc<ResponseCache>>,
+    max_concurrent_fetches: usize,
+    /// Redirects followed for a single fetch before giving up
+    max_redirects: u8,
+    /// Responses with larger bodies are rejected instead of being read into memory
+    max_response_bytes: u64,
+    allow_private_networks: bool,
+    download_dirs: Arc<Vec<PathBuf>>,
+    /// Cookie-keeping clients of open browsing sessions, keyed by session ID
+    sessions: Arc<DashMap<String, Arc<Client>>>,
 }
 
 impl FetchService {
+    /// Create a service whose HTTP client is shared by all requests
+    ///
+    /// Every request follows at most `max_redirects` redirects, [`DEFAULT_MAX_REDIRECTS`]
+    /// when `None`.
     pub fn new(
         custom_user_agent: Option<String>,
         ignore_robots_txt: bool,
         proxy_url: Option<String>,
-    ) -> Self {
-        Self {
+        max_redirects: Option<u8>,
+    ) -> Result<Self, FetchServerError> {
+        let max_redirects = max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS);
+        Ok(Self {
             custom_user_agent,
             ignore_robots_txt,
+            client: build_client(
+                proxy_url.as_ref(),
+                redirect_policy(Some(max_redirects), false),
+            )?,
+            page_client: build_client(proxy_url.as_ref(), Policy::none())?,
             proxy_url,
+            cache: None,
+            max_concurrent_fetches: DEFAULT_MAX_CONCURRENT_FETCHES,
+            max_redirects,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            allow_private_networks: false,
+            download_dirs: Arc::new(Vec::new()),
+            sessions: Arc::new(DashMap::new()),
+        })
+    }
+
+    /// Serve repeated fetches of the same URL from the given response cache
+    pub fn with_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Limit how many requests a batch fetch runs at the same time
+    pub fn with_max_concurrent_fetches(mut self, max_concurrent_fetches: usize) -> Self {
+        self.max_concurrent_fetches = max_concurrent_fetches.max(1);
+        self
+    }
+
+    pub fn max_concurrent_fetches(&self) -> usize {
+        self.max_concurrent_fetches
+    }
+
+    pub fn max_redirects(&self) -> u8 {
+        self.max_redirects
+    }
+
+    /// Reject responses whose body is larger than `max_response_bytes`
+    pub fn with_max_response_bytes(mut self, max_response_bytes: u64) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    pub fn max_response_bytes(&self) -> u64 {
+        self.max_response_bytes
+    }
+
+    /// Permit fetching loopback and link-local addresses
+    pub fn with_allow_private_networks(mut self, allow_private_networks: bool) -> Self {
+        self.allow_private_networks = allow_private_networks;
+        self.client = build_client(
+            self.proxy_url.as_ref(),
+            redirect_policy(Some(self.max_redirects), allow_private_networks),
+        )
+        .expect("proxy URL was validated when the service was created");
+        self
+    }
+
+    /// Reject a URL the service is about to request when it points at a loopback or
+    /// link-local host, unless private networks are allowed
+    ///
+    /// Every request goes through this check, including each redirect hop.
+    fn check_destination(&self, url: &str) -> Result<(), FetchServerError> {
+        if !self.allow_private_networks {
+            validate_public_host(&validate_url_scheme(url)?)?;
         }
+        Ok(())
+    }
+
+    /// Allow `download_file` to write inside these directories, which should be canonical
+    pub fn with_download_dirs(mut self, download_dirs: Vec<PathBuf>) -> Self {
+        self.download_dirs = Arc::new(download_dirs);
+        self
+    }
+
+    pub fn download_dirs(&self) -> &[PathBuf] {
+        &self.download_dirs
+    }
+
+    pub fn ignore_robots_txt(&self) -> bool {
+        self.ignore_robots_txt
+    }
+
+    /// Whether requests are routed through a proxy
+    pub fn proxy_configured(&self) -> bool {
+        self.proxy_url.is_some()
+    }
+
+    /// Open a browsing session whose cookies persist across `fetch_with_session` calls
+    pub fn create_session(&self) -> Result<String, FetchServerError> {
+        if self.sessions.len() >= MAX_SESSIONS {
+            return Err(FetchServerError::InvalidParams {
+                message: format!(
+                    "At most {MAX_SESSIONS} sessions can be open, destroy one with destroy_session first"
+                ),
+            });
+        }
+
+        let session_id = format!("{:032x}", rand::random::<u128>());
+        let client = build_session_client(self.proxy_url.as_ref())?;
+        self.sessions.insert(session_id.clone(), Arc::new(client));
+        Ok(session_id)
+    }
+
+    /// Close a browsing session and drop its cookies; returns whether it existed
+    pub fn destroy_session(&self, session_id: &str) -> bool {
+        self.sessions.remove(session_id).is_some()
     }
 
     pub fn get_user_agent_autonomous(&self) -> &str {
@@ -52,13 +211,12 @@ impl FetchService {
         }
 
         let robots_txt_url = get_robots_txt_url(url)?;
-
-        // Create client with proxy if configured
-        let client = build_client(self.proxy_url.as_ref())?;
+        self.check_destination(&robots_txt_url)?;
 
         let user_agent = self.get_user_agent_autonomous();
 
-        let response = client
+        let response = self
+            .client
             .get(&robots_txt_url)
             .header("User-Agent", user_agent)
             .send()
@@ -75,7 +233,8 @@ impl FetchService {
                 url: robots_txt_url.clone(),
                 message: format!(
                     "When fetching robots.txt ({}), received status {} so assuming that autonomous fetching is not allowed, the user can try manually fetching by using the fetch prompt",
-                    robots_txt_url, status.as_u16()
+                    robots_txt_url,
+                    status.as_u16()
                 ),
             });
         }
@@ -85,12 +244,7 @@ impl FetchService {
             return Ok(());
         }
 
-        let robots_txt = response
-            .text()
-            .await
-            .map_err(|e| FetchServerError::ContentError {
-                message: e.to_string(),
-            })?;
+        let robots_txt = self.read_text(&robots_txt_url, response).await?;
 
         // Simple robots.txt parsing - check for Disallow rules
         let processed_robots = robots_txt
@@ -134,16 +288,418 @@ impl FetchService {
         Ok(())
     }
 
+    /// Evaluate the robots.txt rules of the URL's host for the given user agent
+    ///
+    /// A missing robots.txt (any 4xx other than 401/403) allows everything, while
+    /// 401/403 responses are treated as disallowing everything.
+    pub async fn check_robots_txt(
+        &self,
+        url: &str,
+        user_agent: &str,
+    ) -> Result<RobotsCheckResult, FetchServerError> {
+        let parsed_url = Url::parse(url).map_err(|_| FetchServerError::InvalidUrl {
+            url: url.to_string(),
+        })?;
+        let robots_txt_url = get_robots_txt_url(url)?;
+        self.check_destination(&robots_txt_url)?;
+
+        let response = self
+            .client
+            .get(&robots_txt_url)
+            .header("User-Agent", user_agent)
+            .send()
+            .await
+            .map_err(|e| FetchServerError::RobotsFetchError {
+                url: robots_txt_url.clone(),
+                message: e.to_string(),
+            })?;
+
+        let status = response.status();
+        let result = |allowed, matched_rule| RobotsCheckResult {
+            allowed,
+            robots_txt_url: robots_txt_url.clone(),
+            matched_rule,
+        };
+
+        if status == 401 || status == 403 {
+            return Ok(result(false, None));
+        }
+        if status.is_client_error() {
+            return Ok(result(true, None));
+        }
+        if !status.is_success() {
+            return Err(FetchServerError::RobotsFetchError {
+                url: robots_txt_url.clone(),
+                message: format!("received status {}", status.as_u16()),
+            });
+        }
+
+        let robots_txt = self.read_text(&robots_txt_url, response).await?;
+
+        let path = match parsed_url.query() {
+            Some(query) => format!("{}?{}", parsed_url.path(), query),
+            None => parsed_url.path().to_string(),
+        };
+        let robots = RobotsTxt::parse(&robots_txt);
+        let (allowed, rule) = robots.check(user_agent, &path);
+
+        Ok(result(allowed, rule.map(ToString::to_string)))
+    }
+
     pub async fn fetch_url(
         &self,
         url: &str,
         user_agent: &str,
         force_raw: bool,
-    ) -> Result<(String, String), FetchServerError> {
-        let client = build_client(self.proxy_url.as_ref())?;
-        let response = client
-            .get(url)
-            .header("User-Agent", user_agent)
+        css_selector: Option<&str>,
+        options: RequestOptions,
+    ) -> Result<FetchedPage, FetchServerError> {
+        let selector = css_selector
+            .map(|selector| {
+                Selector::parse(selector).map_err(|e| FetchServerError::InvalidParams {
+                    message: format!("Invalid css_selector '{}': {}", selector, e),
+                })
+            })
+            .transpose()?;
+
+        let cache_key = ResponseCache::key(url, force_raw, css_selector);
+        if let Some(cache) = &self.cache
+            && let Some(cached) = cache.get(&cache_key)
+        {
+            tracing::debug!("Serving {} from response cache", url);
+            return Ok(cached);
+        }
+
+        let raw_page = self
+            .fetch_page_with(&self.page_client, url, user_agent, options)
+            .await?;
+        let response =
+            Self::render_page(raw_page, force_raw, selector.as_ref(), css_selector).await;
+
+        if let Some(cache) = &self.cache {
+            cache.insert(cache_key, response.clone());
+        }
+        Ok(response)
+    }
+
+    /// Fetch a URL with the cookies of a browsing session, bypassing the response cache
+    pub async fn fetch_url_in_session(
+        &self,
+        session_id: &str,
+        url: &str,
+        force_raw: bool,
+        css_selector: Option<&str>,
+        options: RequestOptions,
+    ) -> Result<FetchedPage, FetchServerError> {
+        let selector = css_selector
+            .map(|selector| {
+                Selector::parse(selector).map_err(|e| FetchServerError::InvalidParams {
+                    message: format!("Invalid css_selector '{}': {}", selector, e),
+                })
+            })
+            .transpose()?;
+        let client = self
+            .sessions
+            .get(session_id)
+            .map(|client| Arc::clone(client.value()))
+            .ok_or_else(|| FetchServerError::InvalidParams {
+                message: format!("Unknown session_id '{}'", session_id),
+            })?;
+
+        let raw_page = self
+            .fetch_page_with(&client, url, self.get_user_agent_autonomous(), options)
+            .await?;
+        Ok(Self::render_page(raw_page, force_raw, selector.as_ref(), css_selector).await)
+    }
+
+    /// Convert a fetched body to markdown unless it is not HTML or raw output is requested
+    async fn render_page(
+        raw_page: RawPage,
+        force_raw: bool,
+        selector: Option<&Selector>,
+        css_selector: Option<&str>,
+    ) -> FetchedPage {
+        let RawPage {
+            body: page_raw,
+            content_type,
+            final_url,
+            redirect_count,
+        } = raw_page;
+
+        let is_page_html = page_raw.get(..100).unwrap_or(&page_raw).contains("<html")
+            || content_type.contains("text/html")
+            || content_type.is_empty();
+
+        let (content, prefix) = if is_page_html && !force_raw {
+            let mut prefix = String::new();
+            let mut html = page_raw;
+            if let (Some(selector), Some(css_selector)) = (selector, css_selector) {
+                match select_html(&html, selector) {
+                    Some(selected) => html = selected,
+                    None => {
+                        prefix = format!(
+                            "<warning>CSS selector '{}' matched no elements, returning the full page.</warning>\n",
+                            css_selector
+                        );
+                    }
+                }
+            }
+
+            let markdown = extract_content_from_html(&html).await;
+            (markdown, prefix)
+        } else {
+            let prefix = format!(
+                "Content type {} cannot be simplified to markdown, but here is the raw content:\n",
+                content_type
+            );
+            (page_raw, prefix)
+        };
+        FetchedPage {
+            content,
+            prefix,
+            final_url,
+            redirect_count,
+        }
+    }
+
+    /// Fetch a page and extract all hyperlinks from it
+    pub async fn fetch_links(
+        &self,
+        url: &str,
+        include_external: bool,
+        include_internal: bool,
+    ) -> Result<Vec<PageLink>, FetchServerError> {
+        let base_url = Url::parse(url).map_err(|_| FetchServerError::InvalidUrl {
+            url: url.to_string(),
+        })?;
+        let html = self
+            .fetch_page(
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?
+            .body;
+
+        Ok(extract_links(&html, &base_url)
+            .into_iter()
+            .filter(|link| {
+                if *link.is_external() {
+                    include_external
+                } else {
+                    include_internal
+                }
+            })
+            .collect())
+    }
+
+    /// Fetch a page and extract its Open Graph and other `<head>` metadata
+    pub async fn fetch_metadata(&self, url: &str) -> Result<PageMetadata, FetchServerError> {
+        let page_url = Url::parse(url).map_err(|_| FetchServerError::InvalidUrl {
+            url: url.to_string(),
+        })?;
+        let html = self
+            .fetch_page(
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?
+            .body;
+
+        Ok(extract_metadata(&html, &page_url))
+    }
+
+    /// Fetch a page and list the RSS and Atom feeds it links to
+    ///
+    /// Relative feed URLs are resolved against the page's final URL after redirects.
+    pub async fn detect_feed_urls(&self, url: &str) -> Result<Vec<FeedLink>, FetchServerError> {
+        let page = self
+            .fetch_page(
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?;
+        let page_url = Url::parse(&page.final_url).map_err(|_| FetchServerError::InvalidUrl {
+            url: page.final_url.clone(),
+        })?;
+
+        Ok(extract_feed_links(&page.body, &page_url))
+    }
+
+    /// Fetch a page, timing the download and counting the resources its HTML references
+    pub async fn measure_page_performance(
+        &self,
+        url: &str,
+    ) -> Result<PagePerformance, FetchServerError> {
+        let start = Instant::now();
+        let page = self
+            .fetch_page(
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?;
+        let response_time_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        Ok(PagePerformance {
+            url: url.to_string(),
+            final_url: page.final_url,
+            response_time_ms,
+            html_size_bytes: page.body.len(),
+            resources: count_page_resources(&page.body),
+        })
+    }
+
+    /// Fetch a page and extract its HTML tables, optionally only the one at `table_index`
+    pub async fn fetch_tables(
+        &self,
+        url: &str,
+        table_index: Option<usize>,
+    ) -> Result<Vec<HtmlTable>, FetchServerError> {
+        let html = self
+            .fetch_page(
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?
+            .body;
+        let tables = extract_tables(&html);
+
+        match table_index {
+            None => Ok(tables),
+            Some(index) => {
+                let count = tables.len();
+                tables
+                    .into_iter()
+                    .nth(index)
+                    .map(|table| vec![table])
+                    .ok_or_else(|| FetchServerError::InvalidParams {
+                        message: format!(
+                            "table_index {} is out of range, the page has {} table(s)",
+                            index, count
+                        ),
+                    })
+            }
+        }
+    }
+
+    /// Fetch an RSS or Atom feed and return up to `max_items` of its items
+    pub async fn read_feed(
+        &self,
+        url: &str,
+        max_items: usize,
+    ) -> Result<Vec<FeedItem>, FetchServerError> {
+        let xml = self
+            .fetch_page(
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?
+            .body;
+
+        parse_feed(&xml, max_items)
+    }
+
+    /// Fetch a sitemap and return up to `max_urls` of the pages it lists
+    ///
+    /// For a sitemap index, the child sitemaps are fetched in order, one level deep,
+    /// until `max_urls` pages are collected. Children that cannot be fetched or
+    /// parsed, or that are themselves indexes, are skipped.
+    pub async fn fetch_sitemap(
+        &self,
+        url: &str,
+        max_urls: usize,
+    ) -> Result<Vec<SitemapEntry>, FetchServerError> {
+        let mut entries = match self.fetch_sitemap_document(url).await? {
+            Sitemap::UrlSet(entries) => entries,
+            Sitemap::Index(children) => {
+                let mut entries = Vec::new();
+                for child in children {
+                    if entries.len() >= max_urls {
+                        break;
+                    }
+                    match self.fetch_child_sitemap(&child).await {
+                        Ok(Sitemap::UrlSet(child_entries)) => entries.extend(child_entries),
+                        Ok(Sitemap::Index(_)) => {
+                            tracing::warn!(sitemap = %child, "Skipping nested sitemap index");
+                        }
+                        Err(e) => {
+                            tracing::warn!(sitemap = %child, error = %e, "Skipping sitemap");
+                        }
+                    }
+                }
+                entries
+            }
+        };
+
+        entries.truncate(max_urls);
+        Ok(entries)
+    }
+
+    /// Fetch a sitemap listed in an index, applying the checks the tool applies to its URL
+    async fn fetch_child_sitemap(&self, url: &str) -> Result<Sitemap, FetchServerError> {
+        validate_url_scheme(url)?;
+        self.check_may_autonomously_fetch_url(url).await?;
+        self.fetch_sitemap_document(url).await
+    }
+
+    async fn fetch_sitemap_document(&self, url: &str) -> Result<Sitemap, FetchServerError> {
+        let xml = self
+            .fetch_page(
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?
+            .body;
+
+        parse_sitemap(&xml)
+    }
+
+    /// Download a PDF and extract the text of its first `max_pages` pages
+    pub async fn extract_pdf_text(
+        &self,
+        url: &str,
+        max_pages: Option<usize>,
+    ) -> Result<PdfText, FetchServerError> {
+        let (response, _) = self
+            .send_following_redirects(
+                &self.page_client,
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?;
+
+        let status = response.status();
+        if status.as_u16() >= 400 {
+            return Err(FetchServerError::HttpError {
+                url: url.to_string(),
+                status: status.as_u16(),
+            });
+        }
+
+        let bytes = self.read_body(url, response).await?;
+
+        // Parsing is CPU-bound, and a malformed document can make the parser panic
+        tokio::task::spawn_blocking(move || extract_pdf_text(&bytes, max_pages))
+            .await
+            .map_err(|_| FetchServerError::ContentError {
+                message: "Failed to parse PDF: the document is malformed".to_string(),
+            })?
+    }
+
+    /// Send an HTTP HEAD request and report the status and headers
+    pub async fn fetch_head(&self, url: &str) -> Result<HeadResponse, FetchServerError> {
+        self.check_destination(url)?;
+        let response = self
+            .client
+            .head(url)
+            .header("User-Agent", self.get_user_agent_autonomous())
             .send()
             .await
             .map_err(|e| FetchServerError::FetchError {
@@ -151,6 +707,225 @@ impl FetchService {
                 message: e.to_string(),
             })?;
 
+        let mut headers: HashMap<String, String> = HashMap::new();
+        for (name, value) in response.headers() {
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            headers
+                .entry(name.to_string())
+                .and_modify(|existing| {
+                    existing.push_str(", ");
+                    existing.push_str(&value);
+                })
+                .or_insert(value);
+        }
+
+        Ok(HeadResponse {
+            status_code: response.status().as_u16(),
+            content_type: headers.get("content-type").cloned(),
+            content_length: headers
+                .get("content-length")
+                .and_then(|length| length.parse().ok()),
+            headers,
+        })
+    }
+
+    /// POST a GraphQL query to `endpoint` and return the `data` of the response
+    ///
+    /// A response without `data` (or with `data: null`) that lists `errors` is
+    /// returned as [`FetchServerError::GraphQLError`]. Servers that answer with an
+    /// error status but a GraphQL body are treated the same way. A 307/308 redirect
+    /// re-sends the body, so each hop is checked like the endpoint itself.
+    pub async fn graphql_query(
+        &self,
+        endpoint: &str,
+        query: &str,
+        variables: Option<&serde_json::Value>,
+        headers: Option<&HashMap<String, String>>,
+    ) -> Result<serde_json::Value, FetchServerError> {
+        self.check_destination(endpoint)?;
+        let mut request = self
+            .client
+            .post(endpoint)
+            .header("User-Agent", self.get_user_agent_autonomous())
+            .json(&serde_json::json!({ "query": query, "variables": variables }));
+        for (name, value) in headers.into_iter().flatten() {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| FetchServerError::FetchError {
+                url: endpoint.to_string(),
+                message: e.to_string(),
+            })?;
+        let status = response.status();
+        let body = self.read_body(endpoint, response).await?;
+
+        let http_error = || FetchServerError::HttpError {
+            url: endpoint.to_string(),
+            status: status.as_u16(),
+        };
+        let mut body: serde_json::Value = match serde_json::from_slice(&body) {
+            Ok(body) => body,
+            Err(_) if status.as_u16() >= 400 => return Err(http_error()),
+            Err(e) => {
+                return Err(FetchServerError::ContentError {
+                    message: format!("GraphQL response is not valid JSON: {}", e),
+                });
+            }
+        };
+
+        match body.get_mut("data").map(serde_json::Value::take) {
+            Some(data) if !data.is_null() => Ok(data),
+            _ => match body.get_mut("errors").map(serde_json::Value::take) {
+                Some(errors) if !errors.is_null() => Err(FetchServerError::GraphQLError {
+                    url: endpoint.to_string(),
+                    errors,
+                }),
+                _ if status.as_u16() >= 400 => Err(http_error()),
+                _ => Err(FetchServerError::ContentError {
+                    message: "GraphQL response has neither data nor errors".to_string(),
+                }),
+            },
+        }
+    }
+
+    /// Check whether a URL responds, timing the round trip
+    ///
+    /// A HEAD request is tried first and GET is used when the server rejects HEAD
+    /// with 405 or 501. Transport failures are reported in the result instead of
+    /// being returned as errors; only a URL the service refuses to request is an error.
+    pub async fn check_url_health(
+        &self,
+        url: &str,
+        timeout: Duration,
+    ) -> Result<UrlHealth, FetchServerError> {
+        self.check_destination(url)?;
+        let is_https = url.starts_with("https://");
+
+        let mut start = Instant::now();
+        let mut result = self.probe(Method::HEAD, url, timeout).await;
+        if let Ok(response) = &result
+            && matches!(
+                response.status(),
+                StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+            )
+        {
+            start = Instant::now();
+            result = self.probe(Method::GET, url, timeout).await;
+        }
+        let latency_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        Ok(match result {
+            Ok(response) => UrlHealth {
+                url: url.to_string(),
+                status_code: Some(response.status().as_u16()),
+                ok: response.status().is_success() || response.status().is_redirection(),
+                latency_ms,
+                content_type: response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned()),
+                tls_valid: is_https.then_some(true),
+                error: None,
+            },
+            Err(e) => UrlHealth {
+                url: url.to_string(),
+                status_code: None,
+                ok: false,
+                latency_ms,
+                content_type: None,
+                tls_valid: (is_https && is_tls_error(&e)).then_some(false),
+                error: Some(e.to_string()),
+            },
+        })
+    }
+
+    async fn probe(
+        &self,
+        method: Method,
+        url: &str,
+        timeout: Duration,
+    ) -> Result<Response, reqwest::Error> {
+        self.client
+            .request(method, url)
+            .header("User-Agent", self.get_user_agent_autonomous())
+            .timeout(timeout)
+            .send()
+            .await
+    }
+
+    /// Resolve a download destination and check that it lies inside a download directory
+    ///
+    /// Relative paths are taken relative to the first download directory. The parent
+    /// directory must already exist, and an existing symlink is never written through.
+    async fn resolve_download_path(
+        &self,
+        destination_path: &str,
+    ) -> Result<PathBuf, FetchServerError> {
+        let Some(base_dir) = self.download_dirs.first() else {
+            return Err(FetchServerError::InvalidParams {
+                message: "Downloads are disabled; start the server with --download-dir".to_string(),
+            });
+        };
+        let not_allowed = || FetchServerError::PathNotAllowed {
+            path: destination_path.to_string(),
+        };
+
+        let requested = base_dir.join(destination_path);
+        let file_name = match requested.file_name() {
+            Some(name) if Path::new(name) != Path::new("..") => name.to_owned(),
+            _ => {
+                return Err(FetchServerError::InvalidParams {
+                    message: format!("destination_path '{}' must name a file", destination_path),
+                });
+            }
+        };
+        let parent = requested
+            .parent()
+            .ok_or_else(not_allowed)?
+            .canonicalize()
+            .map_err(|e| FetchServerError::DownloadError {
+                path: destination_path.to_string(),
+                message: e.to_string(),
+            })?;
+        if !self.download_dirs.iter().any(|dir| parent.starts_with(dir)) {
+            return Err(not_allowed());
+        }
+
+        let path = parent.join(file_name);
+        if let Ok(metadata) = tokio::fs::symlink_metadata(&path).await {
+            if metadata.file_type().is_symlink() {
+                return Err(not_allowed());
+            }
+            if metadata.is_dir() {
+                return Err(FetchServerError::InvalidParams {
+                    message: format!("destination_path '{}' is a directory", destination_path),
+                });
+            }
+        }
+        Ok(path)
+    }
+
+    /// Stream the body of `url` into `destination_path` without buffering it in memory
+    ///
+    /// A partially written file is removed if the transfer fails.
+    pub async fn download_file(
+        &self,
+        url: &str,
+        destination_path: &str,
+    ) -> Result<DownloadFileResult, FetchServerError> {
+        let path = self.resolve_download_path(destination_path).await?;
+        let (response, _) = self
+            .send_following_redirects(
+                &self.page_client,
+                url,
+                self.get_user_agent_autonomous(),
+                RequestOptions::default(),
+            )
+            .await?;
+
         let status = response.status();
         if status.as_u16() >= 400 {
             return Err(FetchServerError::HttpError {
@@ -166,32 +941,1491 @@ impl FetchService {
             .unwrap_or("")
             .to_string();
 
-        let page_raw = response
-            .text()
+        self.check_content_length(url, &response)?;
+
+        let download_error = |message: String| FetchServerError::DownloadError {
+            path: path.display().to_string(),
+            message,
+        };
+        let mut file = tokio::fs::File::create(&path)
             .await
-            .map_err(|e| FetchServerError::ContentError {
+            .map_err(|e| download_error(e.to_string()))?;
+
+        let mut body = response.bytes_stream();
+        let mut bytes_written = 0u64;
+        let transfer = async {
+            while let Some(chunk) = body.next().await {
+                let chunk = chunk.map_err(|e| download_error(e.to_string()))?;
+                let received = bytes_written + chunk.len() as u64;
+                if received > self.max_response_bytes {
+                    return Err(self.response_too_large(url, received));
+                }
+                file.write_all(&chunk)
+                    .await
+                    .map_err(|e| download_error(e.to_string()))?;
+                bytes_written = received;
+            }
+            file.flush()
+                .await
+                .map_err(|e| download_error(e.to_string()))
+        };
+
+        if let Err(e) = transfer.await {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(e);
+        }
+
+        Ok(DownloadFileResult {
+            path: path.display().to_string(),
+            bytes_written,
+            content_type,
+        })
+    }
+
+    /// Fetch the raw body of a URL along with its content type and final location
+    async fn fetch_page(
+        &self,
+        url: &str,
+        user_agent: &str,
+        options: RequestOptions,
+    ) -> Result<RawPage, FetchServerError> {
+        self.fetch_page_with(&self.page_client, url, user_agent, options)
+            .await
+    }
+
+    /// Like [`Self::fetch_page`], sending the requests through `client`
+    async fn fetch_page_with(
+        &self,
+        client: &Client,
+        url: &str,
+        user_agent: &str,
+        options: RequestOptions,
+    ) -> Result<RawPage, FetchServerError> {
+        let (response, redirect_count) = self
+            .send_following_redirects(client, url, user_agent, options)
+            .await?;
+        let final_url = response.url().to_string();
+
+        let status = response.status();
+        if status.as_u16() >= 400 {
+            return Err(FetchServerError::HttpError {
+                url: url.to_string(),
+                status: status.as_u16(),
+            });
+        }
+
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+
+        let body = self.read_text(url, response).await?;
+
+        Ok(RawPage {
+            body,
+            content_type,
+            final_url,
+            redirect_count,
+        })
+    }
+
+    /// Read the body of `response` into memory, up to `max_response_bytes`
+    ///
+    /// A `Content-Length` above the limit is rejected before anything is read, and the
+    /// transfer is aborted as soon as the received bytes exceed it.
+    async fn read_body(&self, url: &str, response: Response) -> Result<Vec<u8>, FetchServerError> {
+        self.check_content_length(url, &response)?;
+
+        let mut body = Vec::new();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| FetchServerError::ContentError {
                 message: e.to_string(),
             })?;
+            let received = (body.len() + chunk.len()) as u64;
+            if received > self.max_response_bytes {
+                return Err(self.response_too_large(url, received));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
 
-        let is_page_html = page_raw.get(..100).unwrap_or(&page_raw).contains("<html")
-            || content_type.contains("text/html")
-            || content_type.is_empty();
+    /// Like [`Self::read_body`], decoding the body with the charset from its `Content-Type`
+    ///
+    /// Falls back to UTF-8 when no charset is given, replacing malformed sequences.
+    async fn read_text(&self, url: &str, response: Response) -> Result<String, FetchServerError> {
+        let encoding = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<mime::Mime>().ok())
+            .and_then(|mime| {
+                mime.get_param("charset")
+                    .and_then(|charset| Encoding::for_label(charset.as_str().as_bytes()))
+            })
+            .unwrap_or(UTF_8);
 
-        if is_page_html && !force_raw {
-            let markdown = extract_content_from_html(&page_raw).await;
-            Ok((markdown, String::new()))
-        } else {
-            let prefix = format!(
-                "Content type {} cannot be simplified to markdown, but here is the raw content:\n",
-                content_type
-            );
-            Ok((page_raw, prefix))
+        let body = self.read_body(url, response).await?;
+        let (text, _, _) = encoding.decode(&body);
+        Ok(text.into_owned())
+    }
+
+    /// Fail early when the declared `Content-Length` is over `max_response_bytes`
+    fn check_content_length(&self, url: &str, response: &Response) -> Result<(), FetchServerError> {
+        match response.content_length() {
+            Some(length) if length > self.max_response_bytes => {
+                Err(self.response_too_large(url, length))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn response_too_large(&self, url: &str, actual_or_estimated: u64) -> FetchServerError {
+        FetchServerError::ResponseTooLarge {
+            url: url.to_string(),
+            limit: self.max_response_bytes,
+            actual_or_estimated,
+        }
+    }
+
+    /// Send a GET request, following up to `max_redirects` redirects
+    ///
+    /// `client` must not follow redirects itself. Every hop passes
+    /// [`Self::check_destination`] before it is sent and is retried on its own, and the
+    /// final response is returned along with the number of redirects followed.
+    async fn send_following_redirects(
+        &self,
+        client: &Client,
+        url: &str,
+        user_agent: &str,
+        options: RequestOptions,
+    ) -> Result<(Response, u8), FetchServerError> {
+        let max_redirects = self.max_redirects;
+        let mut current = url.to_string();
+        let mut redirect_count = 0u8;
+
+        loop {
+            self.check_destination(&current)?;
+            let (response, _) =
+                Self::send_with_retry(client, &current, user_agent, options).await?;
+            let location = response
+                .status()
+                .is_redirection()
+                .then(|| response.headers().get("location"))
+                .flatten()
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| response.url().join(location).ok());
+
+            let Some(next) = location else {
+                return Ok((response, redirect_count));
+            };
+            if redirect_count >= max_redirects {
+                return Err(FetchServerError::FetchError {
+                    url: url.to_string(),
+                    message: format!("stopped after {} redirects", max_redirects),
+                });
+            }
+            tracing::debug!(from = %current, to = %next, "Following redirect");
+            current = next.to_string();
+            redirect_count += 1;
         }
     }
+
+    /// Send a GET request, retrying transient failures with exponential backoff
+    ///
+    /// Returns the final response along with the number of retries performed.
+    async fn send_with_retry(
+        client: &Client,
+        url: &str,
+        user_agent: &str,
+        options: RequestOptions,
+    ) -> Result<(Response, u32), FetchServerError> {
+        let span = tracing::debug_span!("fetch_request", url, retries = 0u32);
+        let mut attempt = 0u32;
+
+        loop {
+            let mut request = client.get(url).header("User-Agent", user_agent);
+            if let Some(timeout) = options.timeout {
+                request = request.timeout(timeout);
+            }
+
+            let result = request.send().await;
+            let retryable = match &result {
+                Ok(response) => RetryPolicy::is_retryable_status(response.status()),
+                Err(_) => true,
+            };
+
+            if !retryable || attempt >= u32::from(options.retry.max_retries) {
+                span.record("retries", attempt);
+                return result.map(|response| (response, attempt)).map_err(|e| {
+                    FetchServerError::FetchError {
+                        url: url.to_string(),
+                        message: e.to_string(),
+                    }
+                });
+            }
+
+            let delay = options.retry.delay_for(attempt);
+            span.in_scope(|| {
+                tracing::debug!(
+                    attempt = attempt + 1,
+                    delay_ms = delay.as_millis() as u64,
+                    "Retrying transient fetch failure"
+                )
+            });
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Unprocessed body of a fetched page
+struct RawPage {
+    body: String,
+    content_type: String,
+    final_url: String,
+    redirect_count: u8,
 }
 
 impl Default for FetchService {
     fn default() -> Self {
-        Self::new(None, false, None)
+        Self::new(None, false, None, None).expect("default HTTP client configuration is valid")
+    }
+}
+
+/// Whether a request failed during the TLS handshake or certificate validation
+fn is_tls_error(error: &reqwest::Error) -> bool {
+    if !error.is_connect() {
+        return false;
+    }
+    let mut source = error.source();
+    while let Some(cause) = source {
+        let message = cause.to_string().to_lowercase();
+        if ["certificate", "tls", "ssl", "handshake"]
+            .iter()
+            .any(|marker| message.contains(marker))
+        {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, header, method, path},
+    };
+
+    /// A service that may reach the loopback mock servers the tests run against
+    fn local_service() -> FetchService {
+        FetchService::default().with_allow_private_networks(true)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_times_out_on_slow_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/slow"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("too late")
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/slow", server.uri());
+        let result = service
+            .fetch_url(
+                &url,
+                "test-agent",
+                true,
+                None,
+                RequestOptions {
+                    timeout: Some(Duration::from_secs(1)),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(FetchServerError::FetchError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_within_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/fast"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("hello"),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/fast", server.uri());
+        let FetchedPage { content, .. } = service
+            .fetch_url(
+                &url,
+                "test-agent",
+                true,
+                None,
+                RequestOptions {
+                    timeout: Some(Duration::from_secs(5)),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(content, "hello");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_rejects_oversized_content_length() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/large"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("x".repeat(4096)),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service().with_max_response_bytes(1024);
+        let url = format!("{}/large", server.uri());
+        let result = service
+            .fetch_url(&url, "test-agent", true, None, RequestOptions::default())
+            .await;
+
+        match result {
+            Err(FetchServerError::ResponseTooLarge {
+                limit,
+                actual_or_estimated,
+                ..
+            }) => {
+                assert_eq!(limit, 1024);
+                assert_eq!(actual_or_estimated, 4096);
+            }
+            other => panic!("expected ResponseTooLarge, got {:?}", other.map(|_| ())),
+        }
+
+        // The same body fits under a larger limit
+        let service = local_service().with_max_response_bytes(4096);
+        assert!(
+            service
+                .fetch_url(&url, "test-agent", true, None, RequestOptions::default())
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_aborts_chunked_body_over_limit() {
+        use tokio::io::AsyncReadExt;
+
+        // A chunked response carries no Content-Length, so the limit applies while streaming
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let _ = socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: text/plain\r\ntransfer-encoding: chunked\r\n\r\n",
+                )
+                .await;
+            let chunk = format!("400\r\n{}\r\n", "y".repeat(1024));
+            for _ in 0..64 {
+                if socket.write_all(chunk.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+            let _ = socket.write_all(b"0\r\n\r\n").await;
+        });
+
+        let service = local_service().with_max_response_bytes(10 * 1024);
+        let result = service
+            .fetch_url(
+                &format!("http://{}/stream", addr),
+                "test-agent",
+                true,
+                None,
+                RequestOptions::default(),
+            )
+            .await;
+
+        match result {
+            Err(FetchServerError::ResponseTooLarge {
+                limit,
+                actual_or_estimated,
+                ..
+            }) => {
+                assert_eq!(limit, 10 * 1024);
+                assert!(actual_or_estimated > limit);
+                assert!(actual_or_estimated < 64 * 1024);
+            }
+            other => panic!("expected ResponseTooLarge, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_decodes_declared_charset() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/latin1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain; charset=ISO-8859-1")
+                    .set_body_bytes(b"caf\xe9".to_vec()),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let FetchedPage { content, .. } = service
+            .fetch_url(
+                &format!("{}/latin1", server.uri()),
+                "test-agent",
+                true,
+                None,
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(content, "caf\u{e9}");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_serves_repeat_requests_from_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cached"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("cached body"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = Arc::new(ResponseCache::new(Duration::from_secs(60)));
+        let service = local_service().with_cache(cache);
+        let url = format!("{}/cached", server.uri());
+
+        let first = service
+            .fetch_url(&url, "test-agent", true, None, RequestOptions::default())
+            .await
+            .unwrap();
+        let second = service
+            .fetch_url(&url, "test-agent", true, None, RequestOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(second.content, "cached body");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_reports_final_url_after_redirect() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/old"))
+            .respond_with(ResponseTemplate::new(301).insert_header("location", "/new"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/new"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("moved here"),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/old", server.uri());
+        let page = service
+            .fetch_url(&url, "test-agent", true, None, RequestOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(page.content, "moved here");
+        assert_eq!(page.final_url, format!("{}/new", server.uri()));
+        assert_ne!(page.final_url, url);
+        assert_eq!(page.redirect_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_following_redirects_stops_at_limit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/loop"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/loop"))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/loop", server.uri());
+        let result = service
+            .send_following_redirects(
+                &service.page_client,
+                &url,
+                "test-agent",
+                RequestOptions::default(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(FetchServerError::FetchError { .. })));
+        assert_eq!(
+            server.received_requests().await.unwrap().len(),
+            usize::from(DEFAULT_MAX_REDIRECTS) + 1
+        );
+    }
+
+    /// Mount `/hop/1` .. `/hop/{hops}` each redirecting to the next, ending at `/final`
+    async fn mount_redirect_chain(server: &MockServer, hops: u8) {
+        for hop in 1..=hops {
+            let next = if hop == hops {
+                "/final".to_string()
+            } else {
+                format!("/hop/{}", hop + 1)
+            };
+            Mock::given(method("GET"))
+                .and(path(format!("/hop/{hop}")))
+                .respond_with(ResponseTemplate::new(302).insert_header("location", next.as_str()))
+                .mount(server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/final"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/plain")
+                    .set_body_string("arrived"),
+            )
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_respects_configured_max_redirects() {
+        let within = MockServer::start().await;
+        mount_redirect_chain(&within, 3).await;
+        let beyond = MockServer::start().await;
+        mount_redirect_chain(&beyond, 4).await;
+
+        let service = FetchService::new(None, true, None, Some(3))
+            .unwrap()
+            .with_allow_private_networks(true);
+        assert_eq!(service.max_redirects(), 3);
+
+        let page = service
+            .fetch_url(
+                &format!("{}/hop/1", within.uri()),
+                "test-agent",
+                true,
+                None,
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.content, "arrived");
+        assert_eq!(page.redirect_count, 3);
+
+        let result = service
+            .fetch_url(
+                &format!("{}/hop/1", beyond.uri()),
+                "test-agent",
+                true,
+                None,
+                RequestOptions::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(FetchServerError::FetchError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_build_client_limits_followed_redirects() {
+        let server = MockServer::start().await;
+        mount_redirect_chain(&server, 3).await;
+        let url = format!("{}/hop/1", server.uri());
+
+        let client = build_client(None, redirect_policy(Some(3), true)).unwrap();
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "arrived");
+
+        let client = build_client(None, redirect_policy(Some(2), true)).unwrap();
+        assert!(client.get(&url).send().await.is_err());
+    }
+
+    /// Mount `/start` redirecting to `/secret` on the server's loopback address, and a
+    /// `/secret` that must never be requested
+    async fn mount_redirect_to_loopback(server: &MockServer) {
+        let secret = format!("http://127.0.0.1:{}/secret", server.address().port());
+        Mock::given(path("/start"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", secret.as_str()))
+            .mount(server)
+            .await;
+        Mock::given(path("/secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("internal"))
+            .expect(0)
+            .mount(server)
+            .await;
+    }
+
+    /// A client that reaches `server` under the non-loopback name `public.test`
+    fn resolving_public_test(server: &MockServer, redirect: Policy) -> Client {
+        Client::builder()
+            .redirect(redirect)
+            .resolve("public.test", *server.address())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_refuses_redirect_to_private_host() {
+        let server = MockServer::start().await;
+        mount_redirect_to_loopback(&server).await;
+
+        let service = FetchService {
+            page_client: resolving_public_test(&server, Policy::none()),
+            ..FetchService::default()
+        };
+        let url = format!("http://public.test:{}/start", server.address().port());
+
+        let result = service
+            .fetch_url(&url, "test-agent", true, None, RequestOptions::default())
+            .await;
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("127.0.0.1"), "{error}");
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_policy_refuses_private_hosts() {
+        let server = MockServer::start().await;
+        mount_redirect_to_loopback(&server).await;
+
+        let service = FetchService {
+            client: resolving_public_test(&server, redirect_policy(None, false)),
+            ..FetchService::default()
+        };
+        let url = format!("http://public.test:{}/start", server.address().port());
+
+        let result = service.fetch_head(&url).await;
+        assert!(matches!(result, Err(FetchServerError::FetchError { .. })));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        // Loopback URLs are refused before anything is sent
+        let result = service
+            .fetch_head(&format!("{}/secret", server.uri()))
+            .await;
+        assert!(matches!(
+            result,
+            Err(FetchServerError::InvalidParams { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_with_retry_recovers_from_transient_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/flaky"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("recovered"))
+            .mount(&server)
+            .await;
+
+        let client = Client::new();
+        let url = format!("{}/flaky", server.uri());
+        let options = RequestOptions {
+            timeout: None,
+            retry: RetryPolicy {
+                max_retries: 3,
+                initial_delay: Duration::from_millis(10),
+            },
+        };
+
+        let (response, retries) =
+            FetchService::send_with_retry(&client, &url, "test-agent", options)
+                .await
+                .unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(retries, 2);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_returns_last_error_when_retries_exhausted() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/down"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/down", server.uri());
+        let options = RequestOptions {
+            timeout: None,
+            retry: RetryPolicy {
+                max_retries: 1,
+                initial_delay: Duration::from_millis(10),
+            },
+        };
+
+        let result = service
+            .fetch_url(&url, "test-agent", true, None, options)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(FetchServerError::HttpError { status: 503, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_robots_txt_applies_rules() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("User-agent: *\nDisallow: /private/\nDisallow: /*.pdf$\n"),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+
+        let result = service
+            .check_robots_txt(&format!("{}/private/page", server.uri()), "TestBot")
+            .await
+            .unwrap();
+        assert!(!result.allowed);
+        assert_eq!(
+            result.robots_txt_url,
+            format!("{}/robots.txt", server.uri())
+        );
+        assert_eq!(result.matched_rule.as_deref(), Some("Disallow: /private/"));
+
+        let result = service
+            .check_robots_txt(&format!("{}/files/report.pdf", server.uri()), "TestBot")
+            .await
+            .unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.matched_rule.as_deref(), Some("Disallow: /*.pdf$"));
+
+        let result = service
+            .check_robots_txt(&format!("{}/public", server.uri()), "TestBot")
+            .await
+            .unwrap();
+        assert!(result.allowed);
+        assert_eq!(result.matched_rule, None);
+    }
+
+    #[tokio::test]
+    async fn test_check_robots_txt_missing_file_allows() {
+        let server = MockServer::start().await;
+
+        let service = local_service();
+        let result = service
+            .check_robots_txt(&format!("{}/anything", server.uri()), "TestBot")
+            .await
+            .unwrap();
+
+        assert!(result.allowed);
+        assert_eq!(result.matched_rule, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_head_captures_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/report.pdf"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/pdf")
+                    .insert_header("content-length", "1048576")
+                    .insert_header("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+                    .append_header("x-custom", "one")
+                    .append_header("x-custom", "two"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let head = service
+            .fetch_head(&format!("{}/report.pdf", server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(head.status_code, 200);
+        assert_eq!(head.content_type.as_deref(), Some("application/pdf"));
+        assert_eq!(head.content_length, Some(1_048_576));
+        assert_eq!(
+            head.headers.get("last-modified").map(String::as_str),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+        assert_eq!(
+            head.headers.get("x-custom").map(String::as_str),
+            Some("one, two")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_head_reports_error_status() {
+        let server = MockServer::start().await;
+
+        let service = local_service();
+        let head = service
+            .fetch_head(&format!("{}/missing", server.uri()))
+            .await
+            .unwrap();
+
+        assert_eq!(head.status_code, 404);
+    }
+
+    #[tokio::test]
+    async fn test_graphql_query_returns_data() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(header("content-type", "application/json"))
+            .and(header("authorization", "Bearer token"))
+            .and(body_json(serde_json::json!({
+                "query": "query($id: ID!) { user(id: $id) { name } }",
+                "variables": {"id": "42"}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"user": {"name": "Ada"}}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let data = service
+            .graphql_query(
+                &format!("{}/graphql", server.uri()),
+                "query($id: ID!) { user(id: $id) { name } }",
+                Some(&serde_json::json!({"id": "42"})),
+                Some(&HashMap::from([(
+                    "Authorization".to_string(),
+                    "Bearer token".to_string(),
+                )])),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(data, serde_json::json!({"user": {"name": "Ada"}}));
+    }
+
+    #[tokio::test]
+    async fn test_graphql_query_surfaces_errors() {
+        let server = MockServer::start().await;
+        let errors = serde_json::json!([
+            {"message": "Cannot query field \"nme\" on type \"User\"."}
+        ]);
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .set_body_json(serde_json::json!({"errors": errors.clone()})),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let err = service
+            .graphql_query(
+                &format!("{}/graphql", server.uri()),
+                "{ user { nme } }",
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+
+        match err {
+            FetchServerError::GraphQLError { errors: actual, .. } => assert_eq!(actual, errors),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graphql_query_refuses_redirect_to_private_host() {
+        let server = MockServer::start().await;
+        let internal = format!("http://127.0.0.1:{}/internal", server.address().port());
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(307).insert_header("location", internal.as_str()))
+            .mount(&server)
+            .await;
+        Mock::given(path("/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": {}})))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let service = FetchService {
+            client: resolving_public_test(&server, redirect_policy(None, false)),
+            ..FetchService::default()
+        };
+        let endpoint = format!("http://public.test:{}/graphql", server.address().port());
+        let result = service
+            .graphql_query(&endpoint, "{ secrets }", None, None)
+            .await;
+        assert!(matches!(result, Err(FetchServerError::FetchError { .. })));
+
+        let result = service
+            .graphql_query(
+                &format!("{}/graphql", server.uri()),
+                "{ secrets }",
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(FetchServerError::InvalidParams { .. })
+        ));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_keeps_cookies_between_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/login"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("set-cookie", "session=abc; Path=/")
+                    .set_body_string("logged in"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/protected"))
+            .and(header("cookie", "session=abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("secret"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/protected"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let session_id = service.create_session().unwrap();
+        let fetch = |url: String| {
+            let service = &service;
+            let session_id = session_id.clone();
+            async move {
+                service
+                    .fetch_url_in_session(&session_id, &url, true, None, RequestOptions::default())
+                    .await
+            }
+        };
+
+        fetch(format!("{}/login", server.uri())).await.unwrap();
+        let page = fetch(format!("{}/protected", server.uri())).await.unwrap();
+        assert_eq!(page.content, "secret");
+
+        // Requests outside the session carry no cookies
+        let result = service
+            .fetch_url(
+                &format!("{}/protected", server.uri()),
+                "test-agent",
+                true,
+                None,
+                RequestOptions::default(),
+            )
+            .await;
+        assert!(result.is_err());
+
+        assert!(service.destroy_session(&session_id));
+        assert!(!service.destroy_session(&session_id));
+        let result = fetch(format!("{}/protected", server.uri())).await;
+        assert!(matches!(
+            result,
+            Err(FetchServerError::InvalidParams { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_feed_returns_items() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/changelog.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                include_str!("../../tests/fixtures/feed.rss"),
+                "application/rss+xml",
+            ))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let items = service
+            .read_feed(&format!("{}/changelog.xml", server.uri()), 2)
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Release 2.1.0");
+        assert_eq!(items[0].link, "https://example.com/releases/2.1.0");
+    }
+
+    #[tokio::test]
+    async fn test_detect_feed_urls_resolves_against_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/posts/hello-world"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(include_str!("../../tests/fixtures/feeds.html"), "text/html"),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let feeds = service
+            .detect_feed_urls(&format!("{}/posts/hello-world", server.uri()))
+            .await
+            .unwrap();
+
+        let urls: Vec<&str> = feeds.iter().map(|feed| feed.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                format!("{}/feed.rss", server.uri()),
+                format!("{}/posts/comments.atom", server.uri()),
+                "http://feeds.example.net/blog".to_string(),
+            ]
+        );
+        assert_eq!(feeds[1].feed_type, "application/atom+xml");
+
+        let json = serde_json::to_value(&feeds[0]).unwrap();
+        let mut keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["title", "type", "url"]);
+    }
+
+    #[tokio::test]
+    async fn test_measure_page_performance_counts_fixture_resources() {
+        let server = MockServer::start().await;
+        let html = include_str!("../../tests/fixtures/performance.html");
+        Mock::given(method("GET"))
+            .and(path("/launch"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(html, "text/html")
+                    .set_delay(Duration::from_millis(20)),
+            )
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/launch", server.uri());
+        let performance = service.measure_page_performance(&url).await.unwrap();
+
+        assert_eq!(performance.final_url, url);
+        assert!(performance.response_time_ms >= 20);
+        assert_eq!(performance.html_size_bytes, html.len());
+        assert_eq!(performance.resources.external_scripts, 3);
+        assert_eq!(performance.resources.external_stylesheets, 3);
+        assert_eq!(performance.resources.images, 3);
+        assert_eq!(performance.resources.inline_scripts, 2);
+
+        let json = serde_json::to_value(&performance).unwrap();
+        assert_eq!(json["inline_scripts"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_sitemap_follows_index_one_level() {
+        let server = MockServer::start().await;
+        let index = format!(
+            r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>{uri}/sitemap-pages.xml</loc></sitemap>
+  <sitemap><loc>{uri}/missing.xml</loc></sitemap>
+  <sitemap><loc>{uri}/sitemap_index.xml</loc></sitemap>
+  <sitemap><loc>{uri}/sitemap-posts.xml</loc></sitemap>
+</sitemapindex>"#,
+            uri = server.uri()
+        );
+        Mock::given(method("GET"))
+            .and(path("/sitemap_index.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(index, "application/xml"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/sitemap-pages.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                include_str!("../../tests/fixtures/sitemap.xml"),
+                "application/xml",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/sitemap-posts.xml"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<urlset><url><loc>https://example.com/posts/1</loc></url></urlset>",
+                "application/xml",
+            ))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/sitemap_index.xml", server.uri());
+
+        let entries = service.fetch_sitemap(&url, 100).await.unwrap();
+        let urls: Vec<&str> = entries.iter().map(|entry| entry.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/",
+                "https://example.com/search?q=rust&page=2",
+                "https://example.com/about",
+                "https://example.com/posts/1",
+            ]
+        );
+        assert_eq!(entries[0].last_modified.as_deref(), Some("2024-03-15"));
+        assert_eq!(entries[0].priority, Some(1.0));
+
+        let entries = service.fetch_sitemap(&url, 2).await.unwrap();
+        assert_eq!(entries.len(), 2);
+
+        // Without private networks the loopback index is refused before it is requested
+        let result = FetchService::default().fetch_sitemap(&url, 100).await;
+        assert!(matches!(
+            result,
+            Err(FetchServerError::InvalidParams { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_extract_pdf_text_downloads_and_parses() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/report.pdf"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                include_bytes!("../../tests/fixtures/report.pdf").as_slice(),
+                "application/pdf",
+            ))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let pdf = service
+            .extract_pdf_text(&format!("{}/report.pdf", server.uri()), Some(1))
+            .await
+            .unwrap();
+
+        assert_eq!(pdf.page_count, 2);
+        assert_eq!(pdf.extracted_pages, 1);
+        assert!(pdf.text.contains("revenue grew 12 percent"));
+    }
+
+    #[tokio::test]
+    async fn test_check_url_health_reports_head_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/status"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/json")
+                    .set_delay(Duration::from_millis(20)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/status", server.uri());
+        let health = service
+            .check_url_health(&url, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(health.url, url);
+        assert_eq!(health.status_code, Some(200));
+        assert!(health.ok);
+        assert!(health.latency_ms >= 20);
+        assert_eq!(health.content_type.as_deref(), Some("application/json"));
+        assert_eq!(health.tls_valid, None);
+        assert_eq!(health.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_check_url_health_falls_back_to_get() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/status"))
+            .respond_with(ResponseTemplate::new(405))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/status"))
+            .respond_with(ResponseTemplate::new(503).set_body_raw("down", "text/plain"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let health = service
+            .check_url_health(&format!("{}/status", server.uri()), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(health.status_code, Some(503));
+        assert!(!health.ok);
+        assert_eq!(health.content_type.as_deref(), Some("text/plain"));
+    }
+
+    #[tokio::test]
+    async fn test_check_url_health_reports_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let health = service
+            .check_url_health(&server.uri(), Duration::from_secs(1))
+            .await
+            .unwrap();
+
+        assert_eq!(health.status_code, None);
+        assert!(!health.ok);
+        assert!(health.latency_ms >= 1000);
+        assert!(health.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_filters_by_css_selector() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"<html><body>
+                    <div class="sidebar">Sidebar links</div>
+                    <article><h1>Headline</h1><p>Article body text.</p></article>
+                    <div class="footer">Copyright footer</div>
+                </body></html>"#,
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/article", server.uri());
+
+        let FetchedPage {
+            content, prefix, ..
+        } = service
+            .fetch_url(
+                &url,
+                "test-agent",
+                false,
+                Some("article"),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert!(prefix.is_empty());
+        assert!(content.contains("Headline"));
+        assert!(content.contains("Article body text."));
+        assert!(!content.contains("Sidebar links"));
+        assert!(!content.contains("Copyright footer"));
+
+        let FetchedPage {
+            content, prefix, ..
+        } = service
+            .fetch_url(
+                &url,
+                "test-agent",
+                false,
+                Some("main.content"),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap();
+        assert!(prefix.contains("matched no elements"));
+        assert!(content.contains("Sidebar links"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tables_selects_index() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/tables"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                include_str!("../../tests/fixtures/tables.html"),
+                "text/html",
+            ))
+            .mount(&server)
+            .await;
+
+        let service = local_service();
+        let url = format!("{}/tables", server.uri());
+
+        let tables = service.fetch_tables(&url, None).await.unwrap();
+        assert_eq!(tables.len(), 4);
+
+        let tables = service.fetch_tables(&url, Some(1)).await.unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].index, 1);
+        assert_eq!(tables[0].rows.len(), 2);
+
+        let result = service.fetch_tables(&url, Some(10)).await;
+        assert!(matches!(
+            result,
+            Err(FetchServerError::InvalidParams { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_file_streams_body_to_disk() {
+        let server = MockServer::start().await;
+        let payload: Vec<u8> = (0..=255u8).cycle().take(100_000).collect();
+        Mock::given(method("GET"))
+            .and(path("/archive.bin"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/octet-stream")
+                    .set_body_bytes(payload.clone()),
+            )
+            .mount(&server)
+            .await;
+
+        let download_dir = tempfile::TempDir::new().unwrap();
+        let service =
+            local_service().with_download_dirs(vec![download_dir.path().canonicalize().unwrap()]);
+
+        let result = service
+            .download_file(&format!("{}/archive.bin", server.uri()), "archive.bin")
+            .await
+            .unwrap();
+
+        assert_eq!(result.bytes_written, payload.len() as u64);
+        assert_eq!(result.content_type, "application/octet-stream");
+        assert_eq!(std::fs::read(&result.path).unwrap(), payload);
+        assert!(Path::new(&result.path).starts_with(download_dir.path().canonicalize().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_download_file_rejects_paths_outside_download_dirs() {
+        let download_dir = tempfile::TempDir::new().unwrap();
+        let other_dir = tempfile::TempDir::new().unwrap();
+        let service =
+            local_service().with_download_dirs(vec![download_dir.path().canonicalize().unwrap()]);
+
+        let outside = other_dir.path().join("file.bin");
+        let result = service
+            .download_file("http://example.com/file.bin", outside.to_str().unwrap())
+            .await;
+        assert!(matches!(
+            result,
+            Err(FetchServerError::PathNotAllowed { .. })
+        ));
+
+        let result = service
+            .download_file("http://example.com/file.bin", "../escape.bin")
+            .await;
+        assert!(matches!(
+            result,
+            Err(FetchServerError::PathNotAllowed { .. })
+        ));
+
+        let disabled = FetchService::default()
+            .download_file("http://example.com/file.bin", "file.bin")
+            .await;
+        assert!(matches!(
+            disabled,
+            Err(FetchServerError::InvalidParams { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_file_does_not_create_file_on_http_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let download_dir = tempfile::TempDir::new().unwrap();
+        let service =
+            local_service().with_download_dirs(vec![download_dir.path().canonicalize().unwrap()]);
+
+        let result = service
+            .download_file(&format!("{}/missing", server.uri()), "missing.bin")
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(FetchServerError::HttpError { status: 404, .. })
+        ));
+        assert!(!download_dir.path().join("missing.bin").exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_file_rejects_oversized_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/huge.bin"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0u8; 8192]))
+            .mount(&server)
+            .await;
+
+        let download_dir = tempfile::TempDir::new().unwrap();
+        let service = local_service()
+            .with_max_response_bytes(1024)
+            .with_download_dirs(vec![download_dir.path().canonicalize().unwrap()]);
+
+        let result = service
+            .download_file(&format!("{}/huge.bin", server.uri()), "huge.bin")
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(FetchServerError::ResponseTooLarge { limit: 1024, .. })
+        ));
+        assert!(!download_dir.path().join("huge.bin").exists());
     }
 }
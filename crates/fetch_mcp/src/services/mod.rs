@@ -1,5 +1,11 @@
 mod fetch_service;
-pub use fetch_service::FetchService;
+pub use fetch_service::{DEFAULT_MAX_CONCURRENT_FETCHES, DEFAULT_MAX_RESPONSE_BYTES, FetchService};
+
+mod request_options;
+pub use request_options::{RequestOptions, RetryPolicy};
+
+mod response_cache;
+pub use response_cache::ResponseCache;
 
 mod validation;
-pub use validation::Validate;
+pub use validation::{Validate, validate_public_host, validate_url_scheme};
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+const DEFAULT_INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Exponential backoff policy for transient fetch failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub max_retries: u8,
+    /// Delay before the first retry, doubled on every subsequent retry
+    pub initial_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Delay to wait before retry number `attempt` (0-based), with ±10% jitter
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let base = self
+            .initial_delay
+            .saturating_mul(2u32.saturating_pow(attempt));
+        base.mul_f64(rand::random_range(0.9..=1.1))
+    }
+
+    /// Whether a response with this status is worth retrying
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(status.as_u16(), 429 | 500 | 502 | 503 | 504)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay: DEFAULT_INITIAL_RETRY_DELAY,
+        }
+    }
+}
+
+/// Transport-level options applied to a single fetch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// Overrides the client timeout when set
+    pub timeout: Option<Duration>,
+    pub retry: RetryPolicy,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_exponentially_with_jitter() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(100),
+        };
+
+        for (attempt, base_ms) in [(0, 100.0), (1, 200.0), (2, 400.0)] {
+            let delay = policy.delay_for(attempt).as_secs_f64() * 1000.0;
+            assert!(delay >= base_ms * 0.9 - 1e-6 && delay <= base_ms * 1.1 + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        for status in [429, 500, 502, 503, 504] {
+            assert!(RetryPolicy::is_retryable_status(
+                StatusCode::from_u16(status).unwrap()
+            ));
+        }
+        for status in [200, 400, 404, 501] {
+            assert!(!RetryPolicy::is_retryable_status(
+                StatusCode::from_u16(status).unwrap()
+            ));
+        }
+    }
+}
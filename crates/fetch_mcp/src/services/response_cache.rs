@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+use crate::models::FetchedPage;
+
+/// Cached result of a fetch: the processed page and where it was served from
+type CachedResponse = FetchedPage;
+
+/// In-memory cache of fetched responses with a fixed time-to-live
+pub struct ResponseCache {
+    entries: DashMap<String, (Instant, CachedResponse)>,
+    ttl: Duration,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: DashMap::new(),
+            ttl,
+        }
+    }
+
+    /// Build the cache key for a URL fetched with the given content options
+    pub fn key(url: &str, raw: bool, css_selector: Option<&str>) -> String {
+        format!(
+            "{}|raw={}|selector={}",
+            url,
+            raw,
+            css_selector.unwrap_or_default()
+        )
+    }
+
+    /// Return the cached response for `key` if it is younger than the TTL,
+    /// evicting it when it has expired
+    pub fn get(&self, key: &str) -> Option<CachedResponse> {
+        let response = {
+            let entry = self.entries.get(key)?;
+            let (stored_at, response) = entry.value();
+            (stored_at.elapsed() < self.ttl).then(|| response.clone())
+        };
+
+        if response.is_none() {
+            self.entries.remove(key);
+        }
+        response
+    }
+
+    /// Store a response, dropping any entries that have already expired
+    pub fn insert(&self, key: String, response: CachedResponse) {
+        self.entries
+            .retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        self.entries.insert(key, (Instant::now(), response));
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(content: &str) -> FetchedPage {
+        FetchedPage {
+            content: content.to_string(),
+            prefix: String::new(),
+            final_url: "https://example.com".to_string(),
+            redirect_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_cache_returns_fresh_entries() {
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        let key = ResponseCache::key("https://example.com", false, None);
+        cache.insert(key.clone(), page("content"));
+
+        assert_eq!(cache.get(&key), Some(page("content")));
+        assert_eq!(
+            cache.get(&ResponseCache::key("https://example.com", true, None)),
+            None
+        );
+        assert_eq!(
+            cache.get(&ResponseCache::key(
+                "https://example.com",
+                false,
+                Some("article")
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cache_evicts_expired_entries() {
+        let cache = ResponseCache::new(Duration::from_millis(10));
+        let key = ResponseCache::key("https://example.com", false, None);
+        cache.insert(key.clone(), page("content"));
+        assert_eq!(cache.len(), 1);
+
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.len(), 0);
+    }
+}
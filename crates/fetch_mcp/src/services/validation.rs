@@ -1,5 +1,103 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use url::{Host, Url};
+
 use crate::errors::FetchServerError;
 
 pub trait Validate {
     fn validate(&self) -> Result<(), FetchServerError>;
 }
+
+/// Parse a URL, rejecting anything that is not `http` or `https`
+pub fn validate_url_scheme(url: &str) -> Result<Url, FetchServerError> {
+    let parsed = Url::parse(url).map_err(|e| FetchServerError::InvalidParams {
+        message: format!("Invalid URL {}: {}", url, e),
+    })?;
+
+    match parsed.scheme() {
+        "http" | "https" => Ok(parsed),
+        scheme => Err(FetchServerError::InvalidParams {
+            message: format!(
+                "Unsupported URL scheme '{}': only http and https are allowed",
+                scheme
+            ),
+        }),
+    }
+}
+
+/// Reject URLs whose host is a loopback or link-local address
+///
+/// Only literal addresses and `localhost` names are checked; hostnames are not resolved.
+pub fn validate_public_host(url: &Url) -> Result<(), FetchServerError> {
+    let is_private = match url.host() {
+        Some(Host::Ipv4(ip)) => is_private_ipv4(ip),
+        Some(Host::Ipv6(ip)) => is_private_ipv6(ip),
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+        None => false,
+    };
+
+    if is_private {
+        return Err(FetchServerError::InvalidParams {
+            message: format!(
+                "Refusing to fetch {}: loopback and link-local addresses are blocked (use --allow-private-networks to permit them)",
+                url
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+fn is_private_ipv4(ip: Ipv4Addr) -> bool {
+    ip.is_loopback() || ip.is_link_local()
+}
+
+fn is_private_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(mapped) = ip.to_ipv4_mapped() {
+        return is_private_ipv4(mapped);
+    }
+    ip.is_loopback() || ip.is_unicast_link_local()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(url: &str) -> Result<(), FetchServerError> {
+        validate_public_host(&validate_url_scheme(url)?)
+    }
+
+    #[test]
+    fn test_rejects_non_http_schemes() {
+        assert!(validate_url_scheme("file:///etc/passwd").is_err());
+        assert!(validate_url_scheme("ftp://internal-host/file").is_err());
+        assert!(validate_url_scheme("not a url").is_err());
+        assert!(validate_url_scheme("http://example.com").is_ok());
+        assert!(validate_url_scheme("https://example.com/path").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_loopback_hosts() {
+        assert!(check("http://127.0.0.1/").is_err());
+        assert!(check("http://127.10.0.5:8080/admin").is_err());
+        assert!(check("http://[::1]/").is_err());
+        assert!(check("http://[::ffff:127.0.0.1]/").is_err());
+        assert!(check("http://localhost:3000/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_link_local_hosts() {
+        assert!(check("http://169.254.169.254/latest/meta-data/").is_err());
+        assert!(check("http://[fe80::1]/").is_err());
+    }
+
+    #[test]
+    fn test_allows_public_hosts() {
+        assert!(check("https://example.com/").is_ok());
+        assert!(check("http://93.184.216.34/").is_ok());
+        assert!(check("http://[2606:2800:220:1::]/").is_ok());
+    }
+}
@@ -0,0 +1,168 @@
+use feed_rs::model::Entry;
+
+use crate::{errors::FetchServerError, models::FeedItem};
+
+/// Syndication format of a feed document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    /// `<rss>` (RSS 0.9x/2.0) or `<rdf:RDF>` (RSS 1.0)
+    Rss,
+    /// `<feed>` (Atom 1.0)
+    Atom,
+}
+
+/// Detect the feed format from the document's root element
+///
+/// Leading whitespace, a byte order mark, the XML declaration, comments and a
+/// DOCTYPE are skipped. Returns `None` when the root is not a feed element.
+pub fn detect_feed_format(xml: &str) -> Option<FeedFormat> {
+    let mut rest = xml.trim_start_matches('\u{feff}');
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("<?") {
+            rest = after.split_once("?>")?.1;
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.split_once("-->")?.1;
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = after.split_once('>')?.1;
+        } else {
+            break;
+        }
+    }
+
+    let name: String = rest
+        .strip_prefix('<')?
+        .chars()
+        .take_while(|c| !c.is_whitespace() && *c != '>' && *c != '/')
+        .collect();
+    match name.rsplit(':').next()? {
+        "rss" | "RDF" => Some(FeedFormat::Rss),
+        "feed" => Some(FeedFormat::Atom),
+        _ => None,
+    }
+}
+
+/// Parse an RSS or Atom document into at most `max_items` items, in feed order
+pub fn parse_feed(xml: &str, max_items: usize) -> Result<Vec<FeedItem>, FetchServerError> {
+    if detect_feed_format(xml).is_none() {
+        return Err(FetchServerError::ContentError {
+            message: "Response is not an RSS or Atom feed".to_string(),
+        });
+    }
+
+    let feed =
+        feed_rs::parser::parse(xml.as_bytes()).map_err(|e| FetchServerError::ContentError {
+            message: format!("Failed to parse feed: {}", e),
+        })?;
+
+    Ok(feed
+        .entries
+        .into_iter()
+        .take(max_items)
+        .map(feed_item)
+        .collect())
+}
+
+fn feed_item(entry: Entry) -> FeedItem {
+    // Atom entries may carry several links; the alternate one points at the article
+    let link = entry
+        .links
+        .iter()
+        .find(|link| link.rel.as_deref().is_none_or(|rel| rel == "alternate"))
+        .or_else(|| entry.links.first())
+        .map(|link| link.href.clone())
+        .unwrap_or_default();
+
+    FeedItem {
+        title: entry
+            .title
+            .map(|title| title.content.trim().to_string())
+            .unwrap_or_default(),
+        link,
+        published: entry
+            .published
+            .or(entry.updated)
+            .map(|date| date.to_rfc3339()),
+        summary: entry
+            .summary
+            .map(|summary| summary.content.trim().to_string())
+            .filter(|summary| !summary.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS_FIXTURE: &str = include_str!("../../tests/fixtures/feed.rss");
+    const ATOM_FIXTURE: &str = include_str!("../../tests/fixtures/feed.atom");
+
+    #[test]
+    fn test_detect_feed_format() {
+        assert_eq!(detect_feed_format(RSS_FIXTURE), Some(FeedFormat::Rss));
+        assert_eq!(detect_feed_format(ATOM_FIXTURE), Some(FeedFormat::Atom));
+        assert_eq!(
+            detect_feed_format(
+                "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">"
+            ),
+            Some(FeedFormat::Rss)
+        );
+        assert_eq!(detect_feed_format("<!DOCTYPE html><html></html>"), None);
+        assert_eq!(detect_feed_format("not xml"), None);
+    }
+
+    #[test]
+    fn test_parse_rss_feed() {
+        let items = parse_feed(RSS_FIXTURE, 20).unwrap();
+        assert_eq!(items.len(), 3);
+
+        assert_eq!(items[0].title, "Release 2.1.0");
+        assert_eq!(items[0].link, "https://example.com/releases/2.1.0");
+        assert_eq!(
+            items[0].published.as_deref(),
+            Some("2024-03-05T09:30:00+00:00")
+        );
+        assert_eq!(
+            items[0].summary.as_deref(),
+            Some("Adds streaming downloads and fixes a redirect bug.")
+        );
+
+        // Items without a date or description keep those fields empty
+        assert_eq!(items[2].title, "Roadmap");
+        assert_eq!(items[2].published, None);
+        assert_eq!(items[2].summary, None);
+    }
+
+    #[test]
+    fn test_parse_atom_feed() {
+        let items = parse_feed(ATOM_FIXTURE, 20).unwrap();
+        assert_eq!(items.len(), 2);
+
+        assert_eq!(items[0].title, "Atom-Powered Robots Run Amok");
+        assert_eq!(items[0].link, "https://example.org/2003/12/13/atom03");
+        assert_eq!(
+            items[0].published.as_deref(),
+            Some("2003-12-13T18:30:02+00:00")
+        );
+        assert_eq!(items[0].summary.as_deref(), Some("Some text."));
+
+        // Falls back to the updated timestamp when published is missing
+        assert_eq!(
+            items[1].published.as_deref(),
+            Some("2003-12-14T10:20:05+00:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_feed_limits_items() {
+        let items = parse_feed(RSS_FIXTURE, 2).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].title, "Release 2.0.1");
+    }
+
+    #[test]
+    fn test_parse_feed_rejects_html() {
+        let result = parse_feed("<html><body>Not a feed</body></html>", 20);
+        assert!(matches!(result, Err(FetchServerError::ContentError { .. })));
+    }
+}
@@ -1,9 +1,146 @@
+use scraper::{Html, Node, Selector, node::Text};
+
 /// Convert HTML content to Markdown
+///
+/// `<pre>` blocks are lifted out before conversion and put back as fenced code
+/// blocks, so their whitespace survives and a `language-*` or `lang-*` class
+/// becomes the fence's info string.
 pub async fn extract_content_from_html(html: &str) -> String {
-    let md = html2md::rewrite_html_streaming(html, false).await;
+    let (html, code_blocks) = extract_code_blocks(html);
+    let mut md = html2md::rewrite_html_streaming(&html, false).await;
+    for (index, block) in code_blocks.iter().enumerate() {
+        md = md.replace(
+            &code_block_placeholder(index),
+            &format!("\n\n{}\n\n", block),
+        );
+    }
+
     if md.trim().is_empty() {
         "<error>Page failed to be simplified from HTML</error>".to_string()
     } else {
         md
     }
 }
+
+/// Return the outer HTML of all elements matching `selector`, or `None` if nothing matched
+pub fn select_html(html: &str, selector: &Selector) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selected: Vec<String> = document
+        .select(selector)
+        .map(|element| element.html())
+        .collect();
+
+    (!selected.is_empty()).then(|| selected.join("\n"))
+}
+
+/// Replace every `<pre>` element with a placeholder, returning the rewritten HTML
+/// and the fenced Markdown for each block in document order
+fn extract_code_blocks(html: &str) -> (String, Vec<String>) {
+    if !html.to_ascii_lowercase().contains("<pre") {
+        return (html.to_string(), Vec::new());
+    }
+
+    let selector = Selector::parse("pre").expect("static selector is valid");
+    let mut document = Html::parse_document(html);
+    let blocks: Vec<_> = document
+        .select(&selector)
+        // Nested <pre> elements are part of their outer block's text
+        .filter(|pre| {
+            !pre.ancestors()
+                .any(|node| node.value().as_element().is_some_and(|e| e.name() == "pre"))
+        })
+        .map(|pre| (pre.id(), fenced_code_block(pre)))
+        .collect();
+    if blocks.is_empty() {
+        return (html.to_string(), Vec::new());
+    }
+
+    let mut code_blocks = Vec::with_capacity(blocks.len());
+    for (index, (id, block)) in blocks.into_iter().enumerate() {
+        if let Some(mut pre) = document.tree.get_mut(id) {
+            pre.insert_before(Node::Text(Text {
+                text: code_block_placeholder(index).into(),
+            }));
+            pre.detach();
+        }
+        code_blocks.push(block);
+    }
+
+    (document.html(), code_blocks)
+}
+
+/// Render a `<pre>` element as a fenced Markdown code block, keeping its text verbatim
+fn fenced_code_block(pre: scraper::ElementRef<'_>) -> String {
+    let code = pre.text().collect::<String>();
+    let code = code.trim_end_matches(['\n', '\r']);
+
+    let language = std::iter::once(pre)
+        .chain(
+            pre.child_elements()
+                .filter(|child| child.value().name() == "code"),
+        )
+        .flat_map(|element| element.value().classes())
+        .find_map(|class| {
+            class
+                .strip_prefix("language-")
+                .or_else(|| class.strip_prefix("lang-"))
+        })
+        .unwrap_or_default();
+
+    // The fence must be longer than any backtick run inside the code
+    let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+
+    format!("{fence}{language}\n{code}\n{fence}")
+}
+
+/// Alphanumeric marker that survives Markdown conversion unchanged
+fn code_block_placeholder(index: usize) -> String {
+    format!("FETCHMCPCODEBLOCK{}END", index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_code_block_keeps_language_hint() {
+        let md = extract_content_from_html(
+            "<p>Example:</p><pre><code class=\"language-rust\">fn main() {}</code></pre>",
+        )
+        .await;
+        assert!(md.contains("```rust\nfn main() {}\n```"), "{md}");
+        assert!(md.contains("Example:"));
+    }
+
+    #[tokio::test]
+    async fn test_code_block_preserves_whitespace_and_entities() {
+        let md = extract_content_from_html(
+            "<h1>Guide</h1>\
+             <pre><code class=\"hljs lang-python\">def f():\n    return 1 &lt; 2\n\n\nprint(f())\n</code></pre>\
+             <p>After</p>",
+        )
+        .await;
+        assert!(
+            md.contains("```python\ndef f():\n    return 1 < 2\n\n\nprint(f())\n```"),
+            "{md}"
+        );
+        assert!(md.contains("After"));
+    }
+
+    #[tokio::test]
+    async fn test_code_block_without_language_or_code_element() {
+        let md = extract_content_from_html(
+            "<pre>plain\n  indented</pre><pre><code>second <span>span</span></code></pre>",
+        )
+        .await;
+        assert!(md.contains("```\nplain\n  indented\n```"), "{md}");
+        assert!(md.contains("```\nsecond span\n```"), "{md}");
+    }
+
+    #[tokio::test]
+    async fn test_code_block_containing_backticks_uses_longer_fence() {
+        let md = extract_content_from_html("<pre><code>```\nnested\n```</code></pre>").await;
+        assert!(md.contains("````\n```\nnested\n```\n````"), "{md}");
+    }
+}
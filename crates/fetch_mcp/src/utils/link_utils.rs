@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use scraper::{Html, Selector};
+use url::Url;
+
+use crate::models::PageLink;
+
+/// Extract all `<a href>` links from an HTML document
+///
+/// Relative links are resolved against the document's `<base href>` when present,
+/// otherwise against `page_url`. Only `http` and `https` links are returned, with
+/// fragments removed and duplicates dropped.
+pub fn extract_links(html: &str, page_url: &Url) -> Vec<PageLink> {
+    let document = Html::parse_document(html);
+    let anchor_selector = Selector::parse("a[href]").expect("valid selector");
+    let base_selector = Selector::parse("base[href]").expect("valid selector");
+
+    let base_url = document
+        .select(&base_selector)
+        .next()
+        .and_then(|base| base.value().attr("href"))
+        .and_then(|href| page_url.join(href).ok())
+        .unwrap_or_else(|| page_url.clone());
+
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+
+    for anchor in document.select(&anchor_selector) {
+        let Some(href) = anchor.value().attr("href") else {
+            continue;
+        };
+        let Ok(mut resolved) = base_url.join(href.trim()) else {
+            continue;
+        };
+        if !matches!(resolved.scheme(), "http" | "https") {
+            continue;
+        }
+        resolved.set_fragment(None);
+
+        let href = resolved.to_string();
+        if !seen.insert(href.clone()) {
+            continue;
+        }
+
+        let text = anchor
+            .text()
+            .flat_map(str::split_whitespace)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let is_external = resolved.host_str() != page_url.host_str();
+
+        links.push(PageLink::new(href, text, is_external));
+    }
+
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = r#"
+        <html>
+          <body>
+            <a href="/about">About   us</a>
+            <a href="docs/guide.html#intro">Guide</a>
+            <a href="https://example.com/about">About again</a>
+            <a href="https://other.org/page">Other <b>site</b></a>
+            <a href="mailto:someone@example.com">Mail</a>
+            <a href="javascript:void(0)">Noop</a>
+            <a>No href</a>
+          </body>
+        </html>
+    "#;
+
+    #[test]
+    fn test_extract_links_resolves_and_deduplicates() {
+        let page_url = Url::parse("https://example.com/index.html").unwrap();
+        let links = extract_links(HTML, &page_url);
+
+        let hrefs: Vec<&str> = links.iter().map(|link| link.href().as_str()).collect();
+        assert_eq!(
+            hrefs,
+            vec![
+                "https://example.com/about",
+                "https://example.com/docs/guide.html",
+                "https://other.org/page",
+            ]
+        );
+
+        assert_eq!(links[0].text(), "About us");
+        assert!(!links[0].is_external());
+        assert_eq!(links[2].text(), "Other site");
+        assert!(links[2].is_external());
+    }
+
+    #[test]
+    fn test_extract_links_honours_base_href() {
+        let html = r#"<html><head><base href="https://cdn.example.com/assets/"></head>
+            <body><a href="file.txt">File</a></body></html>"#;
+        let page_url = Url::parse("https://example.com/").unwrap();
+        let links = extract_links(html, &page_url);
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].href(), "https://cdn.example.com/assets/file.txt");
+        assert!(links[0].is_external());
+    }
+}
@@ -0,0 +1,186 @@
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+use crate::models::{FeedLink, PageMetadata};
+
+const FEED_TYPES: [&str; 3] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+];
+
+/// Extract Open Graph, canonical, feed and JSON-LD metadata from an HTML document
+///
+/// Relative URLs (`og:image`, canonical, feeds) are resolved against `page_url`.
+/// JSON-LD blocks that fail to parse are skipped.
+pub fn extract_metadata(html: &str, page_url: &Url) -> PageMetadata {
+    let document = Html::parse_document(html);
+    let resolve = |href: &str| resolve_url(page_url, href);
+
+    let meta_property = |property: &str| {
+        select_attr(
+            &document,
+            &format!(r#"meta[property="{property}"]"#),
+            "content",
+        )
+    };
+
+    let title = select_first(&document, "title")
+        .map(|title| title.text().collect::<String>().trim().to_string())
+        .filter(|title| !title.is_empty());
+
+    let json_ld = select_all(&document, r#"script[type="application/ld+json"]"#)
+        .filter_map(|script| serde_json::from_str(&script.text().collect::<String>()).ok())
+        .collect();
+
+    PageMetadata {
+        title,
+        description: select_attr(&document, r#"meta[name="description"]"#, "content"),
+        canonical_url: select_attr(&document, r#"link[rel~="canonical"]"#, "href")
+            .map(|href| resolve(&href)),
+        og_title: meta_property("og:title"),
+        og_description: meta_property("og:description"),
+        og_image: meta_property("og:image").map(|href| resolve(&href)),
+        og_type: meta_property("og:type"),
+        feeds: feed_links(&document, page_url),
+        json_ld,
+    }
+}
+
+/// Extract the RSS, Atom and JSON feeds a page advertises via `<link rel="alternate">`
+///
+/// Feed URLs are resolved against `page_url`, in document order.
+pub fn extract_feed_links(html: &str, page_url: &Url) -> Vec<FeedLink> {
+    feed_links(&Html::parse_document(html), page_url)
+}
+
+fn feed_links(document: &Html, page_url: &Url) -> Vec<FeedLink> {
+    select_all(document, r#"link[rel~="alternate"][href]"#)
+        .filter_map(|link| {
+            let link_type = link.value().attr("type")?.trim().to_lowercase();
+            if !FEED_TYPES.contains(&link_type.as_str()) {
+                return None;
+            }
+            Some(FeedLink {
+                url: resolve_url(page_url, link.value().attr("href")?),
+                title: link.value().attr("title").map(str::to_string),
+                feed_type: link_type,
+            })
+        })
+        .collect()
+}
+
+/// Resolve `href` against `page_url`, keeping it unchanged when it is not a valid reference
+fn resolve_url(page_url: &Url, href: &str) -> String {
+    page_url
+        .join(href.trim())
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| href.to_string())
+}
+
+fn select_all<'a>(document: &'a Html, selector: &str) -> impl Iterator<Item = ElementRef<'a>> {
+    let selector = Selector::parse(selector).expect("valid selector");
+    document.select(&selector).collect::<Vec<_>>().into_iter()
+}
+
+fn select_first<'a>(document: &'a Html, selector: &str) -> Option<ElementRef<'a>> {
+    select_all(document, selector).next()
+}
+
+fn select_attr(document: &Html, selector: &str, attr: &str) -> Option<String> {
+    select_first(document, selector)
+        .and_then(|element| element.value().attr(attr))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/metadata.html");
+    const FEEDS_FIXTURE: &str = include_str!("../../tests/fixtures/feeds.html");
+
+    #[test]
+    fn test_extract_metadata_from_fixture() {
+        let page_url = Url::parse("https://example.com/articles/example?ref=home").unwrap();
+        let metadata = extract_metadata(FIXTURE, &page_url);
+
+        assert_eq!(metadata.title.as_deref(), Some("Example Article"));
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("A short description of the article.")
+        );
+        assert_eq!(
+            metadata.canonical_url.as_deref(),
+            Some("https://example.com/articles/example")
+        );
+        assert_eq!(metadata.og_title.as_deref(), Some("Example Article (OG)"));
+        assert_eq!(
+            metadata.og_description.as_deref(),
+            Some("Open Graph description.")
+        );
+        assert_eq!(
+            metadata.og_image.as_deref(),
+            Some("https://example.com/images/cover.png")
+        );
+        assert_eq!(metadata.og_type.as_deref(), Some("article"));
+
+        assert_eq!(metadata.feeds.len(), 2);
+        assert_eq!(metadata.feeds[0].url, "https://example.com/feed.xml");
+        assert_eq!(metadata.feeds[0].title.as_deref(), Some("RSS Feed"));
+        assert_eq!(metadata.feeds[0].feed_type, "application/rss+xml");
+        assert_eq!(metadata.feeds[1].feed_type, "application/atom+xml");
+
+        assert_eq!(metadata.json_ld.len(), 1);
+        assert_eq!(metadata.json_ld[0]["@type"], "Article");
+    }
+
+    #[test]
+    fn test_extract_feed_links_from_fixture() {
+        let page_url = Url::parse("https://blog.example.com/posts/hello-world").unwrap();
+        let feeds = extract_feed_links(FEEDS_FIXTURE, &page_url);
+
+        let found: Vec<(&str, &str, Option<&str>)> = feeds
+            .iter()
+            .map(|feed| {
+                (
+                    feed.url.as_str(),
+                    feed.feed_type.as_str(),
+                    feed.title.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    "https://blog.example.com/feed.rss",
+                    "application/rss+xml",
+                    Some("All posts (RSS)")
+                ),
+                (
+                    "https://blog.example.com/posts/comments.atom",
+                    "application/atom+xml",
+                    Some("Comments (Atom)")
+                ),
+                (
+                    "https://feeds.example.net/blog",
+                    "application/rss+xml",
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_metadata_from_empty_document() {
+        let page_url = Url::parse("https://example.com/").unwrap();
+        let metadata = extract_metadata("<html><head></head><body></body></html>", &page_url);
+
+        assert!(metadata.title.is_none());
+        assert!(metadata.og_title.is_none());
+        assert!(metadata.feeds.is_empty());
+        assert!(metadata.json_ld.is_empty());
+    }
+}
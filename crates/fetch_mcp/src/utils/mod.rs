@@ -1,8 +1,29 @@
 mod html_utils;
-pub use html_utils::extract_content_from_html;
+pub use html_utils::{extract_content_from_html, select_html};
+
+mod link_utils;
+pub use link_utils::extract_links;
+
+mod metadata_utils;
+pub use metadata_utils::{extract_feed_links, extract_metadata};
+
+mod feed_utils;
+pub use feed_utils::parse_feed;
+
+mod sitemap_utils;
+pub use sitemap_utils::{Sitemap, parse_sitemap};
+
+mod pdf_utils;
+pub use pdf_utils::extract_pdf_text;
+
+mod performance_utils;
+pub use performance_utils::count_page_resources;
+
+mod table_utils;
+pub use table_utils::extract_tables;
 
 mod http_client;
-pub use http_client::build_client;
+pub use http_client::{DEFAULT_MAX_REDIRECTS, build_client, build_session_client, redirect_policy};
 
 mod robots_utils;
-pub use robots_utils::get_robots_txt_url;
+pub use robots_utils::{RobotsTxt, get_robots_txt_url};
@@ -0,0 +1,82 @@
+use pdf_extract::{Document, PlainTextOutput, output_doc_page};
+
+use crate::{errors::FetchServerError, models::PdfText};
+
+/// Extract the text of a PDF document, stopping after `max_pages` pages
+///
+/// Pages are separated by a blank line. Encrypted documents are only readable
+/// when they use an empty user password.
+pub fn extract_pdf_text(
+    bytes: &[u8],
+    max_pages: Option<usize>,
+) -> Result<PdfText, FetchServerError> {
+    let mut document = Document::load_mem(bytes).map_err(|e| FetchServerError::ContentError {
+        message: format!("Failed to parse PDF: {}", e),
+    })?;
+    if document.is_encrypted() {
+        document
+            .decrypt("")
+            .map_err(|_| FetchServerError::ContentError {
+                message: "PDF is encrypted with a password".to_string(),
+            })?;
+    }
+
+    let page_numbers: Vec<u32> = document.get_pages().into_keys().collect();
+    let page_count = page_numbers.len();
+    let extracted_pages = max_pages.map_or(page_count, |max| max.min(page_count));
+
+    let mut pages = Vec::with_capacity(extracted_pages);
+    for page_number in page_numbers.into_iter().take(extracted_pages) {
+        let mut text = String::new();
+        output_doc_page(&document, &mut PlainTextOutput::new(&mut text), page_number).map_err(
+            |e| FetchServerError::ContentError {
+                message: format!("Failed to extract text from page {}: {}", page_number, e),
+            },
+        )?;
+        pages.push(text.trim().to_string());
+    }
+
+    Ok(PdfText {
+        page_count,
+        extracted_pages,
+        text: pages.join("\n\n"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT_FIXTURE: &[u8] = include_bytes!("../../tests/fixtures/report.pdf");
+
+    #[test]
+    fn test_extract_pdf_text_reads_all_pages() {
+        let pdf = extract_pdf_text(REPORT_FIXTURE, None).unwrap();
+        assert_eq!(pdf.page_count, 2);
+        assert_eq!(pdf.extracted_pages, 2);
+        assert!(
+            pdf.text
+                .contains("Quarterly report: revenue grew 12 percent.")
+        );
+        assert!(pdf.text.contains("Appendix: methodology and data sources."));
+    }
+
+    #[test]
+    fn test_extract_pdf_text_limits_pages() {
+        let pdf = extract_pdf_text(REPORT_FIXTURE, Some(1)).unwrap();
+        assert_eq!(pdf.page_count, 2);
+        assert_eq!(pdf.extracted_pages, 1);
+        assert!(pdf.text.contains("Quarterly report"));
+        assert!(!pdf.text.contains("Appendix"));
+
+        // A limit above the page count extracts everything
+        let pdf = extract_pdf_text(REPORT_FIXTURE, Some(10)).unwrap();
+        assert_eq!(pdf.extracted_pages, 2);
+    }
+
+    #[test]
+    fn test_extract_pdf_text_rejects_non_pdf() {
+        let result = extract_pdf_text(b"<html><body>Not a PDF</body></html>", None);
+        assert!(matches!(result, Err(FetchServerError::ContentError { .. })));
+    }
+}
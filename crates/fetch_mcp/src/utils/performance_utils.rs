@@ -0,0 +1,69 @@
+use scraper::{ElementRef, Html, Selector};
+
+use crate::models::PageResourceCounts;
+
+/// Count the scripts, stylesheets and images an HTML document loads
+///
+/// Scripts with a `src` are external; scripts without one are inline when their
+/// `type` is JavaScript or `module`, so data blocks such as JSON-LD and client-side
+/// templates are not counted. Stylesheets are `<link rel="stylesheet">` elements,
+/// including alternate stylesheets; `<style>` blocks are not external.
+pub fn count_page_resources(html: &str) -> PageResourceCounts {
+    let document = Html::parse_document(html);
+    let select = |selector: &str| {
+        let selector = Selector::parse(selector).expect("valid selector");
+        document.select(&selector).collect::<Vec<_>>()
+    };
+
+    let scripts = select("script");
+    let is_external = |script: &ElementRef| script.value().attr("src").is_some();
+
+    PageResourceCounts {
+        external_scripts: scripts.iter().filter(|script| is_external(script)).count(),
+        inline_scripts: scripts
+            .iter()
+            .filter(|script| !is_external(script))
+            .filter(|script| is_javascript_type(script.value().attr("type")))
+            .count(),
+        external_stylesheets: select(r#"link[rel~="stylesheet"][href]"#).len(),
+        images: select("img").len(),
+    }
+}
+
+/// Whether a `<script type>` value makes the browser run the script
+fn is_javascript_type(script_type: Option<&str>) -> bool {
+    let Some(script_type) = script_type.map(|value| value.trim().to_ascii_lowercase()) else {
+        return true;
+    };
+    script_type.is_empty()
+        || script_type == "module"
+        || script_type.ends_with("javascript")
+        || script_type.ends_with("ecmascript")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/performance.html");
+
+    #[test]
+    fn test_count_page_resources_from_fixture() {
+        let counts = count_page_resources(FIXTURE);
+
+        assert_eq!(counts.external_scripts, 3);
+        assert_eq!(counts.inline_scripts, 2);
+        assert_eq!(counts.external_stylesheets, 3);
+        assert_eq!(counts.images, 3);
+    }
+
+    #[test]
+    fn test_count_page_resources_of_plain_document() {
+        let counts = count_page_resources("<p>No resources here</p>");
+
+        assert_eq!(counts.external_scripts, 0);
+        assert_eq!(counts.inline_scripts, 0);
+        assert_eq!(counts.external_stylesheets, 0);
+        assert_eq!(counts.images, 0);
+    }
+}
@@ -7,10 +7,200 @@ pub fn get_robots_txt_url(url: &str) -> Result<String, FetchServerError> {
         url: url.to_string(),
     })?;
 
-    let robots_url = format!(
-        "{}://{}/robots.txt",
-        parsed.scheme(),
-        parsed.host_str().unwrap_or("")
-    );
+    let robots_url = format!("{}/robots.txt", parsed.origin().ascii_serialization());
     Ok(robots_url)
 }
+
+/// A single `Allow` or `Disallow` rule from robots.txt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RobotsRule {
+    allow: bool,
+    pattern: String,
+}
+
+impl RobotsRule {
+    /// Check whether the rule's pattern matches a URL path, honouring `*` and `$` wildcards
+    fn matches(&self, path: &str) -> bool {
+        let (pattern, anchored) = match self.pattern.strip_suffix('$') {
+            Some(pattern) => (pattern, true),
+            None => (self.pattern.as_str(), false),
+        };
+
+        let mut parts = pattern.split('*');
+        let Some(mut rest) = parts.next().and_then(|prefix| path.strip_prefix(prefix)) else {
+            return false;
+        };
+
+        let remaining: Vec<&str> = parts.collect();
+        if remaining.is_empty() {
+            return !anchored || rest.is_empty();
+        }
+
+        for (i, part) in remaining.iter().enumerate() {
+            if anchored && i == remaining.len() - 1 {
+                return rest.ends_with(part);
+            }
+            match rest.find(part) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl std::fmt::Display for RobotsRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let directive = if self.allow { "Allow" } else { "Disallow" };
+        write!(f, "{}: {}", directive, self.pattern)
+    }
+}
+
+/// Parsed robots.txt, grouped by user agent
+#[derive(Debug, Default)]
+pub struct RobotsTxt {
+    groups: Vec<(Vec<String>, Vec<RobotsRule>)>,
+}
+
+impl RobotsTxt {
+    pub fn parse(content: &str) -> Self {
+        let mut groups: Vec<(Vec<String>, Vec<RobotsRule>)> = Vec::new();
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    let agent = value.to_lowercase();
+                    match groups.last_mut() {
+                        Some((agents, rules)) if rules.is_empty() => agents.push(agent),
+                        _ => groups.push((vec![agent], Vec::new())),
+                    }
+                }
+                "allow" | "disallow" if !value.is_empty() => {
+                    if groups.is_empty() {
+                        groups.push((vec!["*".to_string()], Vec::new()));
+                    }
+                    if let Some((_, rules)) = groups.last_mut() {
+                        rules.push(RobotsRule {
+                            allow: key == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self { groups }
+    }
+
+    /// Decide whether `user_agent` may crawl `path`, returning the rule that decided it
+    ///
+    /// The most specific user-agent group applies, falling back to `*`. Within the
+    /// group the longest matching pattern wins, with `Allow` winning ties.
+    pub fn check(&self, user_agent: &str, path: &str) -> (bool, Option<&RobotsRule>) {
+        if path == "/robots.txt" {
+            return (true, None);
+        }
+
+        let user_agent = user_agent.to_lowercase();
+        let matched_agent = self
+            .groups
+            .iter()
+            .flat_map(|(agents, _)| agents)
+            .filter(|agent| *agent != "*" && user_agent.contains(agent.as_str()))
+            .max_by_key(|agent| agent.len())
+            .map(String::as_str)
+            .unwrap_or("*");
+
+        let rule = self
+            .groups
+            .iter()
+            .filter(|(agents, _)| agents.iter().any(|agent| agent == matched_agent))
+            .flat_map(|(_, rules)| rules)
+            .filter(|rule| rule.matches(path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow));
+
+        (rule.is_none_or(|rule| rule.allow), rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = r#"
+# Example robots.txt
+User-agent: *
+Disallow: /private/
+Allow: /private/public-page
+Disallow: /*.pdf$
+Disallow: /search*results
+
+User-agent: BadBot
+User-agent: WorseBot
+Disallow: /
+"#;
+
+    #[test]
+    fn test_robots_txt_url_keeps_port() {
+        assert_eq!(
+            get_robots_txt_url("https://example.com/a/b?c=d").unwrap(),
+            "https://example.com/robots.txt"
+        );
+        assert_eq!(
+            get_robots_txt_url("http://127.0.0.1:8080/page").unwrap(),
+            "http://127.0.0.1:8080/robots.txt"
+        );
+    }
+
+    #[test]
+    fn test_explicit_disallow_and_allow() {
+        let robots = RobotsTxt::parse(ROBOTS);
+
+        let (allowed, rule) = robots.check("MyCrawler/1.0", "/private/secret");
+        assert!(!allowed);
+        assert_eq!(rule.unwrap().to_string(), "Disallow: /private/");
+
+        let (allowed, rule) = robots.check("MyCrawler/1.0", "/private/public-page");
+        assert!(allowed);
+        assert_eq!(rule.unwrap().to_string(), "Allow: /private/public-page");
+
+        let (allowed, rule) = robots.check("MyCrawler/1.0", "/blog/post");
+        assert!(allowed);
+        assert!(rule.is_none());
+    }
+
+    #[test]
+    fn test_wildcards() {
+        let robots = RobotsTxt::parse(ROBOTS);
+
+        assert!(!robots.check("MyCrawler", "/docs/report.pdf").0);
+        assert!(robots.check("MyCrawler", "/docs/report.pdf?download=1").0);
+        assert!(!robots.check("MyCrawler", "/search/weekly-results").0);
+        assert!(robots.check("MyCrawler", "/search/weekly").0);
+    }
+
+    #[test]
+    fn test_specific_user_agent_group() {
+        let robots = RobotsTxt::parse(ROBOTS);
+
+        let (allowed, rule) = robots.check("Mozilla/5.0 (compatible; WorseBot/2.1)", "/anything");
+        assert!(!allowed);
+        assert_eq!(rule.unwrap().to_string(), "Disallow: /");
+        assert!(robots.check("MyCrawler", "/anything").0);
+    }
+
+    #[test]
+    fn test_empty_robots_allows_everything() {
+        let robots = RobotsTxt::parse("");
+        assert_eq!(robots.check("MyCrawler", "/private/"), (true, None));
+    }
+}
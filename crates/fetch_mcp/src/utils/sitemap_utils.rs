@@ -0,0 +1,189 @@
+use quick_xml::{Reader, escape::resolve_predefined_entity, events::Event};
+
+use crate::{errors::FetchServerError, models::SitemapEntry};
+
+/// Contents of a sitemap document
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sitemap {
+    /// `<sitemapindex>`: locations of the child sitemaps, in document order
+    Index(Vec<String>),
+    /// `<urlset>`: the pages the site lists, in document order
+    UrlSet(Vec<SitemapEntry>),
+}
+
+/// Child of a `<url>` or `<sitemap>` element whose text is collected
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Loc,
+    LastMod,
+    Priority,
+}
+
+/// Parse a sitemap or sitemap index document
+///
+/// Only `<loc>`, `<lastmod>` and `<priority>` directly inside an entry are read, so
+/// extension elements such as `<image:loc>` do not replace the page location.
+/// Entries without a location are skipped.
+pub fn parse_sitemap(xml: &str) -> Result<Sitemap, FetchServerError> {
+    let parse_error = |e: &dyn std::fmt::Display| FetchServerError::ContentError {
+        message: format!("Failed to parse sitemap: {}", e),
+    };
+
+    let mut reader = Reader::from_str(xml);
+    let mut is_index = None;
+    let mut depth = 0usize;
+    let mut entry: Option<SitemapEntry> = None;
+    let mut field = None;
+    let mut text = String::new();
+    let mut entries = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(|e| parse_error(&e))? {
+            Event::Start(element) => {
+                depth += 1;
+                let name = element.local_name();
+                match (depth, name.as_ref()) {
+                    (1, root) => is_index = Some(root_kind(root)?),
+                    (2, b"url" | b"sitemap") => entry = Some(empty_entry()),
+                    (3, b"loc") => field = Some(Field::Loc),
+                    (3, b"lastmod") => field = Some(Field::LastMod),
+                    (3, b"priority") => field = Some(Field::Priority),
+                    _ => {}
+                }
+                text.clear();
+            }
+            Event::Empty(element) if depth == 0 => {
+                is_index = Some(root_kind(element.local_name().as_ref())?);
+            }
+            Event::Text(content) if field.is_some() => {
+                text.push_str(&content.decode().map_err(|e| parse_error(&e))?);
+            }
+            Event::CData(content) if field.is_some() => {
+                text.push_str(&content.decode().map_err(|e| parse_error(&e))?);
+            }
+            Event::GeneralRef(reference) if field.is_some() => {
+                if let Some(ch) = reference.resolve_char_ref().map_err(|e| parse_error(&e))? {
+                    text.push(ch);
+                } else {
+                    let name = reference.decode().map_err(|e| parse_error(&e))?;
+                    text.push_str(resolve_predefined_entity(&name).unwrap_or_default());
+                }
+            }
+            Event::End(_) => {
+                if depth == 3
+                    && let (Some(current), Some(kind)) = (entry.as_mut(), field.take())
+                {
+                    let value = text.trim();
+                    match kind {
+                        Field::Loc => current.url = value.to_string(),
+                        Field::LastMod => {
+                            current.last_modified = (!value.is_empty()).then(|| value.to_string())
+                        }
+                        Field::Priority => current.priority = value.parse().ok(),
+                    }
+                } else if depth == 2
+                    && let Some(finished) = entry.take()
+                    && !finished.url.is_empty()
+                {
+                    entries.push(finished);
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    match is_index {
+        Some(true) => Ok(Sitemap::Index(
+            entries.into_iter().map(|entry| entry.url).collect(),
+        )),
+        Some(false) => Ok(Sitemap::UrlSet(entries)),
+        None => Err(not_a_sitemap()),
+    }
+}
+
+/// Whether a root element names a sitemap index (`true`) or a URL set (`false`)
+fn root_kind(name: &[u8]) -> Result<bool, FetchServerError> {
+    match name {
+        b"sitemapindex" => Ok(true),
+        b"urlset" => Ok(false),
+        _ => Err(not_a_sitemap()),
+    }
+}
+
+fn not_a_sitemap() -> FetchServerError {
+    FetchServerError::ContentError {
+        message: "Response is not a sitemap: expected a <urlset> or <sitemapindex> root element"
+            .to_string(),
+    }
+}
+
+fn empty_entry() -> SitemapEntry {
+    SitemapEntry {
+        url: String::new(),
+        last_modified: None,
+        priority: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URLSET: &str = include_str!("../../tests/fixtures/sitemap.xml");
+
+    #[test]
+    fn test_parse_urlset() {
+        let Sitemap::UrlSet(entries) = parse_sitemap(URLSET).unwrap() else {
+            panic!("Expected a URL set");
+        };
+
+        assert_eq!(
+            entries,
+            vec![
+                SitemapEntry {
+                    url: "https://example.com/".to_string(),
+                    last_modified: Some("2024-03-15".to_string()),
+                    priority: Some(1.0),
+                },
+                SitemapEntry {
+                    url: "https://example.com/search?q=rust&page=2".to_string(),
+                    last_modified: None,
+                    priority: Some(0.5),
+                },
+                SitemapEntry {
+                    url: "https://example.com/about".to_string(),
+                    last_modified: Some("2024-01-02T10:00:00+00:00".to_string()),
+                    priority: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_sitemap_index() {
+        let xml = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/sitemap-posts.xml</loc><lastmod>2024-03-01</lastmod></sitemap>
+  <sitemap><loc>https://example.com/sitemap-pages.xml</loc></sitemap>
+</sitemapindex>"#;
+
+        assert_eq!(
+            parse_sitemap(xml).unwrap(),
+            Sitemap::Index(vec![
+                "https://example.com/sitemap-posts.xml".to_string(),
+                "https://example.com/sitemap-pages.xml".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_other_documents() {
+        assert!(parse_sitemap("<html><body>Not found</body></html>").is_err());
+        assert!(parse_sitemap("plain text").is_err());
+        assert_eq!(
+            parse_sitemap("<urlset/>").unwrap(),
+            Sitemap::UrlSet(Vec::new())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use scraper::{ElementRef, Html, Selector};
+
+use crate::models::HtmlTable;
+
+/// Extract every `<table>` in an HTML document as rows of cell text
+///
+/// `<th>` and `<td>` cells are treated the same. When the first row consists
+/// solely of `<th>` cells it becomes the table's headers. Rows of nested tables
+/// are reported under the nested table only.
+pub fn extract_tables(html: &str) -> Vec<HtmlTable> {
+    let document = Html::parse_document(html);
+    let table_selector = Selector::parse("table").expect("valid selector");
+    let row_selector = Selector::parse("tr").expect("valid selector");
+    let cell_selector = Selector::parse("th, td").expect("valid selector");
+
+    document
+        .select(&table_selector)
+        .enumerate()
+        .map(|(index, table)| {
+            let mut rows = table
+                .select(&row_selector)
+                .filter(|row| closest_table(*row).is_some_and(|owner| owner.id() == table.id()))
+                .map(|row| {
+                    row.select(&cell_selector)
+                        .filter(|cell| {
+                            cell.parent()
+                                .and_then(ElementRef::wrap)
+                                .is_some_and(|parent| parent.id() == row.id())
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .filter(|cells| !cells.is_empty())
+                .peekable();
+
+            let header_row =
+                rows.next_if(|cells| cells.iter().all(|cell| cell.value().name() == "th"));
+            let headers = header_row
+                .map(|cells| cells.into_iter().map(cell_text).collect())
+                .unwrap_or_default();
+            let rows = rows
+                .map(|cells| cells.into_iter().map(cell_text).collect())
+                .collect();
+
+            HtmlTable {
+                index,
+                headers,
+                rows,
+            }
+        })
+        .collect()
+}
+
+fn closest_table(element: ElementRef<'_>) -> Option<ElementRef<'_>> {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|ancestor| ancestor.value().name() == "table")
+}
+
+fn cell_text(cell: ElementRef<'_>) -> String {
+    cell.text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/tables.html");
+
+    #[test]
+    fn test_extract_tables_from_fixture() {
+        let tables = extract_tables(FIXTURE);
+        assert_eq!(tables.len(), 4);
+
+        let populations = &tables[0];
+        assert_eq!(populations.index, 0);
+        assert_eq!(populations.headers, vec!["City", "Country", "Population"]);
+        assert_eq!(populations.rows.len(), 3);
+        assert!(populations.rows.iter().all(|row| row.len() == 3));
+        assert_eq!(populations.rows[1], vec!["Tokyo", "Japan", "13,960,000"]);
+
+        let no_header = &tables[1];
+        assert!(no_header.headers.is_empty());
+        assert_eq!(no_header.rows, vec![vec!["a", "b"], vec!["c", "d"]]);
+
+        let row_headers = &tables[2];
+        assert_eq!(row_headers.headers, vec!["Metric", "Value"]);
+        assert_eq!(row_headers.rows.len(), 2);
+        assert_eq!(row_headers.rows[0], vec!["Revenue", "$1.2M (est.)"]);
+
+        let nested = &tables[3];
+        assert_eq!(nested.rows, vec![vec!["inner"]]);
+    }
+}
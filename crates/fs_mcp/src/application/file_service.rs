@@ -1,11 +1,18 @@
 use async_recursion::async_recursion;
 use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose};
+use chrono::{DateTime, Utc};
 use globset::{Glob, GlobSetBuilder};
+use md5::Md5;
+use rand::{Rng, RngCore};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, HashSet, VecDeque},
     io,
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 use tokio::{
     fs::{self, File},
@@ -16,11 +23,29 @@ use crate::{
     domain::FileOperations,
     errors::{FileSystemMcpError, FileSystemMcpResult},
     models::{
-        requests::SortBy,
-        responses::{ReadFileResponse, WriteFileResponse},
+        requests::{FillPattern, MAX_SPLIT_CHUNKS, SortBy, SplitChunk, YamlOutputFormat},
+        responses::{
+            Base64FileResponse, BulkRenameResponse, CompareDirectoriesResponse, ContentSearchMatch,
+            FileChunk, MatchingLine, ReadFileResponse, RenamedFile, SplitFileResponse,
+            WatchFileResponse, WriteFileResponse,
+        },
     },
+    utils::{edit::apply_edits_to_content, env::parse_env_file, yaml::convert_yaml},
 };
 
+/// Default upper bound for whole-file reads (50 MB)
+pub const DEFAULT_MAX_READ_BYTES: usize = 50 * 1024 * 1024;
+
+/// Bytes generated and written at a time by `generate_file`
+const GENERATE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Paragraph repeated by the `lorem` fill pattern
+const LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod \
+tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud \
+exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in \
+reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint \
+occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.\n";
+
 /// Reusable directory entry information
 #[derive(Debug, Clone)]
 struct DirectoryEntry {
@@ -44,23 +69,75 @@ struct TreeEntry {
     pub children: Option<Vec<TreeEntry>>,
 }
 
+/// File matched by `find_files_by_date_range`
+#[derive(Debug, Serialize, Deserialize)]
+struct DatedFile {
+    /// Full path of the file
+    path: String,
+    /// Modification time in RFC 3339 format
+    modified: String,
+}
+
+/// File listed by `export_directory_manifest`
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// Path relative to the exported directory, using `/` separators
+    path: String,
+    /// Size in bytes
+    size: u64,
+    /// Modification time in seconds since the Unix epoch
+    modified: u64,
+    /// Hex-encoded SHA-256 digest, present when hashes were requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256: Option<String>,
+}
+
 /// Application service implementing file operations
 ///
 /// This service provides concrete implementations for all file operations
 /// following SOLID principles and Domain-Driven Design patterns.
-pub struct FileService;
+pub struct FileService {
+    /// Most bytes a whole-file read returns before truncating; `None` disables the cap
+    max_read_bytes: Option<usize>,
+}
 
 impl FileService {
-    /// Create a new FileService instance
+    /// Owned directory, prefix and suffix for building a temporary entry on a blocking thread
+    fn temp_name_parts(
+        directory: &Path,
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+    ) -> (PathBuf, String, String) {
+        (
+            directory.to_path_buf(),
+            prefix.unwrap_or("tmp").to_string(),
+            suffix.unwrap_or_default().to_string(),
+        )
+    }
+
+    /// Create a new FileService instance using [`DEFAULT_MAX_READ_BYTES`]
     pub fn new() -> Self {
-        Self
+        Self {
+            max_read_bytes: Some(DEFAULT_MAX_READ_BYTES),
+        }
+    }
+
+    /// Set the number of bytes after which whole-file reads are truncated
+    pub fn with_max_read_bytes(mut self, max_read_bytes: Option<usize>) -> Self {
+        self.max_read_bytes = max_read_bytes;
+        self
     }
 
     /// Reusable function to read file content as bytes using Node.js-style streaming
     ///
     /// This private method provides the core streaming functionality that can be
-    /// reused by both text and media file reading operations.
-    async fn read_file_bytes(&self, path: &Path) -> FileSystemMcpResult<Vec<u8>> {
+    /// reused by both text and media file reading operations. Reading stops after
+    /// `max_bytes` bytes; the returned flag is `true` when the file had more.
+    async fn read_file_bytes(
+        &self,
+        path: &Path,
+        max_bytes: Option<usize>,
+    ) -> FileSystemMcpResult<(Vec<u8>, bool)> {
         let file = File::open(path)
             .await
             .map_err(|_| FileSystemMcpError::PermissionDenied {
@@ -76,7 +153,15 @@ impl FileService {
         let mut buffer = vec![0u8; CHUNK_SIZE];
 
         loop {
-            let bytes_read = reader.read(&mut buffer).await.map_err(|_| {
+            // Once the cap is reached, probe a single byte to tell a file of exactly
+            // `max_bytes` apart from a longer one
+            let want = match max_bytes {
+                Some(limit) if contents.len() >= limit => 1,
+                Some(limit) => CHUNK_SIZE.min(limit - contents.len()),
+                None => CHUNK_SIZE,
+            };
+
+            let bytes_read = reader.read(&mut buffer[..want]).await.map_err(|_| {
                 FileSystemMcpError::PermissionDenied {
                     path: path.display().to_string(),
                 }
@@ -86,11 +171,93 @@ impl FileService {
                 break; // End of file reached
             }
 
+            if max_bytes.is_some_and(|limit| contents.len() >= limit) {
+                return Ok((contents, true));
+            }
+
             // Append chunk to contents
             contents.extend_from_slice(&buffer[..bytes_read]);
         }
 
-        Ok(contents)
+        Ok((contents, false))
+    }
+
+    /// Read a whole text file for parsing
+    ///
+    /// A partial file would parse differently, so reaching the read limit is an error
+    /// rather than a truncated result.
+    async fn read_file_to_parse(
+        &self,
+        path: &Path,
+        operation: &str,
+    ) -> FileSystemMcpResult<String> {
+        let (bytes, truncated) = self.read_file_bytes(path, self.max_read_bytes).await?;
+        if truncated {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "File too large".to_string(),
+                path: path.display().to_string(),
+                operation: operation.to_string(),
+                data: serde_json::json!({
+                    "error": "File exceeds the server's read limit",
+                    "max_read_bytes": self.max_read_bytes
+                }),
+            });
+        }
+
+        String::from_utf8(bytes).map_err(|e| FileSystemMcpError::IoError {
+            message: format!("File is not valid UTF-8: {}", e),
+            path: path.display().to_string(),
+        })
+    }
+
+    /// Count the lines of a file without holding it in memory
+    ///
+    /// Counts every `\n` plus a final line without a terminator, matching
+    /// how the head and tail reads split the file.
+    async fn count_file_lines(&self, path: &Path) -> FileSystemMcpResult<usize> {
+        let permission_denied = || FileSystemMcpError::PermissionDenied {
+            path: path.display().to_string(),
+        };
+        let mut reader = BufReader::new(File::open(path).await.map_err(|_| permission_denied())?);
+        let mut buffer = vec![0u8; 8192];
+        let mut line_count = 0;
+        let mut last_byte = None;
+
+        loop {
+            let bytes_read = reader
+                .read(&mut buffer)
+                .await
+                .map_err(|_| permission_denied())?;
+            if bytes_read == 0 {
+                break;
+            }
+            line_count += buffer[..bytes_read].iter().filter(|&&b| b == b'\n').count();
+            last_byte = Some(buffer[bytes_read - 1]);
+        }
+
+        Ok(match last_byte {
+            Some(b) if b != b'\n' => line_count + 1,
+            _ => line_count,
+        })
+    }
+
+    /// Fill `chunk`, which starts `offset` bytes into the generated file, with `pattern`
+    fn fill_chunk(pattern: FillPattern, offset: u64, chunk: &mut [u8]) {
+        match pattern {
+            FillPattern::Zeros => chunk.fill(0),
+            FillPattern::Random => rand::rng().fill_bytes(chunk),
+            FillPattern::Lorem => {
+                let text = LOREM_IPSUM.as_bytes();
+                let start = (offset % text.len() as u64) as usize;
+                for (i, byte) in chunk.iter_mut().enumerate() {
+                    *byte = text[(start + i) % text.len()];
+                }
+            }
+            FillPattern::Ascii => {
+                let mut rng = rand::rng();
+                chunk.fill_with(|| rng.random_range(b' '..=b'~'));
+            }
+        }
     }
 
     /// Helper method to get file metadata
@@ -114,11 +281,6 @@ impl FileService {
         Ok(())
     }
 
-    /// Normalize line endings to Unix format (\n)
-    fn normalize_line_endings(text: &str) -> String {
-        text.replace("\r\n", "\n")
-    }
-
     /// Efficiently read and collect directory entries with metadata
     async fn read_directory_entries(path: &Path) -> FileSystemMcpResult<Vec<DirectoryEntry>> {
         let mut entries = fs::read_dir(path)
@@ -180,48 +342,63 @@ impl FileService {
     fn sort_directory_entries(entries: &mut [DirectoryEntry], sort_by: &SortBy) {
         match sort_by {
             SortBy::Name => entries.sort_by(|a, b| a.name.cmp(&b.name)),
-            SortBy::Size => entries.sort_by(|a, b| b.size.cmp(&a.size)),
-            SortBy::Modified => entries.sort_by(|a, b| {
-                match (a.modified, b.modified) {
-                    (Some(a_time), Some(b_time)) => b_time.cmp(&a_time),
-                    (Some(_), None) => std::cmp::Ordering::Less,
-                    (None, Some(_)) => std::cmp::Ordering::Greater,
-                    (None, None) => a.name.cmp(&b.name), // Fallback to name
-                }
-            }),
+            SortBy::Size => entries.sort_by_key(|entry| std::cmp::Reverse(entry.size)),
+            SortBy::Modified => entries.sort_by(|a, b| Self::compare_modified(a, b, true)),
+            SortBy::ModifiedAscending => {
+                entries.sort_by(|a, b| Self::compare_modified(a, b, false))
+            }
         }
     }
 
+    /// Order two entries by modification time
+    ///
+    /// Entries without a modification time sort after those with one in either
+    /// direction. Entries with equal (or no) times are ordered by name ascending.
+    fn compare_modified(
+        a: &DirectoryEntry,
+        b: &DirectoryEntry,
+        newest_first: bool,
+    ) -> std::cmp::Ordering {
+        let by_time = match (a.modified, b.modified) {
+            (Some(a_time), Some(b_time)) if newest_first => b_time.cmp(&a_time),
+            (Some(a_time), Some(b_time)) => a_time.cmp(&b_time),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        };
+        by_time.then_with(|| a.name.cmp(&b.name))
+    }
+
     /// Get appropriate icon for file type
     fn get_file_icon(file_type: &str) -> &'static str {
         match file_type {
-            "[DIR]" => "📁",
-            "[SYMLINK]" => "🔗",
-            t if t.contains("rs [FILE]") => "🦀",
-            t if t.contains("js [FILE]") => "📜",
-            t if t.contains("ts [FILE]") => "📘",
-            t if t.contains("py [FILE]") => "🐍",
-            t if t.contains("json [FILE]") => "📋",
-            t if t.contains("toml [FILE]") => "⚙️",
-            t if t.contains("yaml [FILE]") || t.contains("yml [FILE]") => "📄",
-            t if t.contains("md [FILE]") => "📝",
-            t if t.contains("txt [FILE]") => "📄",
-            t if t.contains("log [FILE]") => "📊",
+            "[DIR]" => "\u{1F4C1}",
+            "[SYMLINK]" => "\u{1F517}",
+            t if t.contains("rs [FILE]") => "\u{1F980}",
+            t if t.contains("js [FILE]") => "\u{1F4DC}",
+            t if t.contains("ts [FILE]") => "\u{1F4D8}",
+            t if t.contains("py [FILE]") => "\u{1F40D}",
+            t if t.contains("json [FILE]") => "\u{1F4CB}",
+            t if t.contains("toml [FILE]") => "\u{2699}\u{FE0F}",
+            t if t.contains("yaml [FILE]") || t.contains("yml [FILE]") => "\u{1F4C4}",
+            t if t.contains("md [FILE]") => "\u{1F4DD}",
+            t if t.contains("txt [FILE]") => "\u{1F4C4}",
+            t if t.contains("log [FILE]") => "\u{1F4CA}",
             t if t.contains("png [FILE]")
                 || t.contains("jpg [FILE]")
                 || t.contains("jpeg [FILE]")
                 || t.contains("gif [FILE]") =>
             {
-                "🖼️"
+                "\u{1F5BC}\u{FE0F}"
             }
-            t if t.contains("pdf [FILE]") => "📕",
+            t if t.contains("pdf [FILE]") => "\u{1F4D5}",
             t if t.contains("zip [FILE]")
                 || t.contains("tar [FILE]")
                 || t.contains("gz [FILE]") =>
             {
-                "📦"
+                "\u{1F4E6}"
             }
-            _ => "📄",
+            _ => "\u{1F4C4}",
         }
     }
 
@@ -237,16 +414,16 @@ impl FileService {
             entries.iter().partition(|entry| entry.is_directory);
 
         if !directories.is_empty() {
-            output.push("📂 Directories:".to_string());
+            output.push("\u{1F4C2} Directories:".to_string());
             for dir in &directories {
-                output.push(format!("  📁 {}/", dir.name));
+                output.push(format!("  \u{1F4C1} {}/", dir.name));
                 total_dirs += 1;
             }
             output.push(String::new());
         }
 
         if !files.is_empty() {
-            output.push("📄 Files:".to_string());
+            output.push("\u{1F4C4} Files:".to_string());
             for file in &files {
                 let icon = Self::get_file_icon(&file.file_type);
                 let size_str = Self::format_size(file.size);
@@ -257,7 +434,7 @@ impl FileService {
         }
 
         let stats = format!(
-            "📊 Summary: {} directories, {} files | Total size: {}",
+            "\u{1F4CA} Summary: {} directories, {} files | Total size: {}",
             total_dirs,
             total_files,
             Self::format_size(total_size)
@@ -284,15 +461,73 @@ impl FileService {
         }
     }
 
+    /// Canonical form of a traversal root, falling back to the path as given
+    async fn canonical_root(path: &Path) -> PathBuf {
+        fs::canonicalize(path)
+            .await
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    /// Metadata used to classify a directory entry
+    ///
+    /// Symlinks are resolved only when `follow_symlinks` is set. Unfollowed and dangling
+    /// links keep their own metadata, so `is_symlink()` tells them apart.
+    async fn entry_metadata(
+        entry: &fs::DirEntry,
+        follow_symlinks: bool,
+    ) -> io::Result<std::fs::Metadata> {
+        let metadata = entry.metadata().await?;
+        if follow_symlinks
+            && metadata.is_symlink()
+            && let Ok(target) = fs::metadata(entry.path()).await
+        {
+            return Ok(target);
+        }
+        Ok(metadata)
+    }
+
+    /// Canonical path of a directory a traversal should descend into next
+    ///
+    /// `ancestors` holds the canonical directories currently being walked, starting with
+    /// the root. Directories reached through a symlink are only entered when they resolve
+    /// inside the root and are not already on that chain, which would loop forever.
+    async fn next_traversal_dir(entry: &fs::DirEntry, ancestors: &[PathBuf]) -> Option<PathBuf> {
+        let is_symlink = entry.file_type().await.ok()?.is_symlink();
+        if !is_symlink {
+            return ancestors.last().map(|dir| dir.join(entry.file_name()));
+        }
+
+        let target = fs::canonicalize(entry.path()).await.ok()?;
+        (target.starts_with(ancestors.first()?) && !ancestors.contains(&target)).then_some(target)
+    }
+
+    /// Build the tree below `current_path`, listing at most `remaining_depth` levels
+    ///
+    /// Once the depth is used up, a non-empty directory gets a single `[TRUNCATED]`
+    /// entry instead of its contents.
     #[async_recursion::async_recursion]
     async fn build_tree(
         base_path: &Path,
         current_path: &Path,
         exclude_patterns: &[String],
+        follow_symlinks: bool,
+        remaining_depth: Option<usize>,
+        ancestors: &mut Vec<PathBuf>,
     ) -> Result<Vec<TreeEntry>, io::Error> {
         let mut entries = tokio::fs::read_dir(current_path).await?;
         let mut tree = Vec::new();
 
+        if remaining_depth == Some(0) {
+            if entries.next_entry().await?.is_some() {
+                tree.push(TreeEntry {
+                    name: "...".to_string(),
+                    entry_type: "[TRUNCATED]".to_string(),
+                    children: None,
+                });
+            }
+            return Ok(tree);
+        }
+
         while let Some(entry) = entries.next_entry().await? {
             let entry_path = entry.path();
             let name = entry.file_name().to_string_lossy().to_string();
@@ -338,10 +573,12 @@ impl FileService {
                 continue;
             }
 
-            let metadata = entry.metadata().await?;
+            let metadata = Self::entry_metadata(&entry, follow_symlinks).await?;
             let mut tree_entry = TreeEntry {
                 name,
-                entry_type: if metadata.is_dir() {
+                entry_type: if metadata.is_symlink() {
+                    "[SYMLINK]".to_string()
+                } else if metadata.is_dir() {
                     "[DIR]".to_string()
                 } else {
                     "[FILE]".to_string()
@@ -349,9 +586,21 @@ impl FileService {
                 children: None,
             };
 
-            if metadata.is_dir() {
-                tree_entry.children =
-                    Some(Self::build_tree(base_path, &entry_path, exclude_patterns).await?);
+            if metadata.is_dir()
+                && let Some(dir) = Self::next_traversal_dir(&entry, ancestors).await
+            {
+                ancestors.push(dir);
+                let children = Self::build_tree(
+                    base_path,
+                    &entry_path,
+                    exclude_patterns,
+                    follow_symlinks,
+                    remaining_depth.map(|depth| depth - 1),
+                    ancestors,
+                )
+                .await;
+                ancestors.pop();
+                tree_entry.children = Some(children?);
             }
 
             tree.push(tree_entry);
@@ -360,13 +609,145 @@ impl FileService {
         Ok(tree)
     }
 
+    /// Append `entries` to `output` as box-drawing tree lines, directories first
+    ///
+    /// Directory names get a trailing `/`; `prefix` is the indentation inherited
+    /// from the enclosing levels.
+    fn render_tree(entries: &[TreeEntry], prefix: &str, output: &mut String) {
+        let mut sorted: Vec<&TreeEntry> = entries.iter().collect();
+        sorted.sort_by(|a, b| {
+            (a.entry_type != "[DIR]", &a.name).cmp(&(b.entry_type != "[DIR]", &b.name))
+        });
+
+        for (index, entry) in sorted.iter().enumerate() {
+            let is_last = index + 1 == sorted.len();
+            let (branch, indent) = if is_last {
+                ("\u{2514}\u{2500}\u{2500} ", "    ")
+            } else {
+                ("\u{251c}\u{2500}\u{2500} ", "\u{2502}   ")
+            };
+            let suffix = if entry.entry_type == "[DIR]" { "/" } else { "" };
+            output.push_str(&format!("{}{}{}{}\n", prefix, branch, entry.name, suffix));
+
+            if let Some(children) = &entry.children {
+                Self::render_tree(children, &format!("{}{}", prefix, indent), output);
+            }
+        }
+    }
+
+    /// Compile exclude patterns, skipping invalid ones; `None` when nothing is excluded
+    fn build_exclude_globset(exclude_patterns: &[String]) -> Option<globset::GlobSet> {
+        if exclude_patterns.is_empty() {
+            return None;
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for exclude_pattern in exclude_patterns {
+            if let Ok(glob) = Glob::new(exclude_pattern) {
+                builder.add(glob);
+            }
+        }
+        builder.build().ok()
+    }
+
+    /// Read up to `max_rows` CSV records as JSON objects keyed by header, or as arrays
+    fn parse_csv_rows(
+        path: &Path,
+        has_header: bool,
+        delimiter: u8,
+        max_rows: usize,
+    ) -> FileSystemMcpResult<Vec<serde_json::Value>> {
+        let invalid_csv = |e: csv::Error| {
+            let line = e.position().map(|position| position.line());
+            FileSystemMcpError::ValidationError {
+                message: "Invalid CSV".to_string(),
+                path: path.display().to_string(),
+                operation: "read_csv_file".to_string(),
+                data: serde_json::json!({"error": e.to_string(), "line": line}),
+            }
+        };
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(has_header)
+            .delimiter(delimiter)
+            .from_path(path)
+            .map_err(|e| FileSystemMcpError::IoError {
+                message: format!("Failed to open CSV file: {}", e),
+                path: path.display().to_string(),
+            })?;
+        let headers = if has_header {
+            Some(reader.headers().map_err(invalid_csv)?.clone())
+        } else {
+            None
+        };
+
+        reader
+            .records()
+            .take(max_rows)
+            .map(|record| {
+                let record = record.map_err(invalid_csv)?;
+                Ok(match &headers {
+                    Some(headers) => serde_json::Value::Object(
+                        headers
+                            .iter()
+                            .zip(record.iter())
+                            .map(|(name, field)| (name.to_string(), field.into()))
+                            .collect(),
+                    ),
+                    None => record.iter().collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Compute the hex-encoded SHA-256 digest of a file without loading it whole
+    async fn sha256_file(path: &Path) -> FileSystemMcpResult<String> {
+        let io_error = |e: io::Error| FileSystemMcpError::IoError {
+            message: format!("Failed to hash file: {}", e),
+            path: path.display().to_string(),
+        };
+
+        let mut file = File::open(path).await.map_err(io_error)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = file.read(&mut buffer).await.map_err(io_error)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Regular files under `root` keyed by their `/`-separated path relative to it
+    async fn collect_relative_files(root: &Path) -> FileSystemMcpResult<BTreeMap<String, PathBuf>> {
+        let mut files = Vec::new();
+        Self::collect_files_in_range(root, root, None, None, &None, &mut files).await?;
+
+        Ok(files
+            .into_iter()
+            .map(|(_, file_path)| {
+                let relative_path = file_path
+                    .strip_prefix(root)
+                    .unwrap_or(&file_path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                (relative_path, file_path)
+            })
+            .collect())
+    }
+
+    /// Collect files under `current_path` whose modification time lies within the bounds
     #[async_recursion]
-    async fn search_recursive(
+    async fn collect_files_in_range(
         root_path: &Path,
         current_path: &Path,
-        search_glob: &Glob,
+        after: Option<SystemTime>,
+        before: Option<SystemTime>,
         exclude_globset: &Option<globset::GlobSet>,
-        results: &mut Vec<String>,
+        results: &mut Vec<(SystemTime, PathBuf)>,
     ) -> FileSystemMcpResult<()> {
         let mut entries =
             fs::read_dir(current_path)
@@ -392,134 +773,511 @@ impl FileService {
                 .to_string_lossy()
                 .replace('\\', "/");
 
-            // Check exclude patterns
             if let Some(globset) = exclude_globset
                 && globset.is_match(&relative_path)
             {
                 continue;
             }
 
-            // Check if matches search pattern
-            if search_glob.compile_matcher().is_match(&relative_path) {
-                results.push(entry_path.display().to_string());
-            }
+            // Entries that vanish or cannot be inspected are skipped rather than failing the walk
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
 
-            // Recurse into directories
-            if entry.metadata().await.map(|m| m.is_dir()).unwrap_or(false) {
-                Self::search_recursive(
+            if metadata.is_dir() {
+                Self::collect_files_in_range(
                     root_path,
                     &entry_path,
-                    search_glob,
+                    after,
+                    before,
                     exclude_globset,
                     results,
                 )
                 .await?;
+            } else if metadata.is_file()
+                && let Ok(modified) = metadata.modified()
+                && after.is_none_or(|after| modified >= after)
+                && before.is_none_or(|before| modified <= before)
+            {
+                results.push((modified, entry_path));
             }
         }
 
         Ok(())
     }
-}
-
-impl Default for FileService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
 
-#[async_trait]
-impl FileOperations for FileService {
-    /// Read the entire contents of a file using reusable streaming function
-    async fn read_entire_file(&self, path: &Path) -> FileSystemMcpResult<ReadFileResponse> {
-        let bytes = self.read_file_bytes(path).await?;
-        let contents = String::from_utf8_lossy(&bytes).to_string();
-        Ok(ReadFileResponse::text(contents))
-    }
+    /// Prune empty directories below `current_path`, returning whether it is empty afterwards
+    ///
+    /// Symlinks are never followed, so a link to a directory keeps its parent non-empty.
+    #[async_recursion]
+    async fn prune_empty_recursive(
+        current_path: &Path,
+        dry_run: bool,
+        pruned: &mut Vec<PathBuf>,
+    ) -> FileSystemMcpResult<bool> {
+        let mut entries =
+            fs::read_dir(current_path)
+                .await
+                .map_err(|e| FileSystemMcpError::IoError {
+                    message: format!("Failed to read directory: {}", e),
+                    path: current_path.display().to_string(),
+                })?;
 
-    /// Read the first N lines using streaming with early termination
-    async fn read_file_head(
-        &self,
-        path: &Path,
-        lines: usize,
-    ) -> FileSystemMcpResult<ReadFileResponse> {
-        if lines == 0 {
-            return Ok(ReadFileResponse::text(String::new()));
+        let mut children = Vec::new();
+        while let Some(entry) =
+            entries
+                .next_entry()
+                .await
+                .map_err(|e| FileSystemMcpError::IoError {
+                    message: format!("Failed to read directory entry: {}", e),
+                    path: current_path.display().to_string(),
+                })?
+        {
+            children.push(entry);
         }
+        children.sort_by_key(|entry| entry.file_name());
 
-        let file = File::open(path)
-            .await
-            .map_err(|_| FileSystemMcpError::PermissionDenied {
-                path: path.display().to_string(),
-            })?;
+        let mut is_empty = true;
+        for entry in children {
+            let entry_path = entry.path();
+            let is_dir = entry
+                .file_type()
+                .await
+                .map(|file_type| file_type.is_dir())
+                .unwrap_or(false);
 
-        let reader = BufReader::new(file);
-        let mut lines_stream = reader.lines();
-        let mut result_lines = Vec::with_capacity(lines);
+            if !is_dir || !Self::prune_empty_recursive(&entry_path, dry_run, pruned).await? {
+                is_empty = false;
+                continue;
+            }
 
-        // Read only the requested number of lines
-        for _ in 0..lines {
-            match lines_stream.next_line().await {
-                Ok(Some(line)) => result_lines.push(line),
-                Ok(None) => break, // End of file reached
-                Err(_) => {
-                    return Err(FileSystemMcpError::PermissionDenied {
-                        path: path.display().to_string(),
-                    });
-                }
+            if !dry_run {
+                fs::remove_dir(&entry_path)
+                    .await
+                    .map_err(|e| FileSystemMcpError::IoError {
+                        message: format!("Failed to remove directory: {}", e),
+                        path: entry_path.display().to_string(),
+                    })?;
             }
+            pruned.push(entry_path);
         }
 
-        Ok(ReadFileResponse::text(result_lines.join("\n")))
+        Ok(is_empty)
     }
 
-    /// Read the last N lines using memory-efficient circular buffer
-    async fn read_file_tail(
-        &self,
-        path: &Path,
-        lines: usize,
-    ) -> FileSystemMcpResult<ReadFileResponse> {
-        if lines == 0 {
-            return Ok(ReadFileResponse::text(String::new()));
-        }
-
-        let file = File::open(path)
-            .await
-            .map_err(|_| FileSystemMcpError::PermissionDenied {
-                path: path.display().to_string(),
-            })?;
-
-        let reader = BufReader::new(file);
-        let mut lines_stream = reader.lines();
-        let mut circular_buffer: VecDeque<String> = VecDeque::with_capacity(lines);
+    /// Collect the regular files in `current_path`, and below it when `recursive`
+    ///
+    /// Symlinks are skipped and never followed.
+    #[async_recursion]
+    async fn collect_files_to_rename(
+        current_path: &Path,
+        recursive: bool,
+        files: &mut Vec<PathBuf>,
+    ) -> FileSystemMcpResult<()> {
+        let mut entries =
+            fs::read_dir(current_path)
+                .await
+                .map_err(|e| FileSystemMcpError::IoError {
+                    message: format!("Failed to read directory: {}", e),
+                    path: current_path.display().to_string(),
+                })?;
 
-        // Read all lines and maintain a circular buffer of the last N lines
-        while let Some(line) =
-            lines_stream
-                .next_line()
+        while let Some(entry) =
+            entries
+                .next_entry()
                 .await
-                .map_err(|_| FileSystemMcpError::PermissionDenied {
-                    path: path.display().to_string(),
+                .map_err(|e| FileSystemMcpError::IoError {
+                    message: format!("Failed to read directory entry: {}", e),
+                    path: current_path.display().to_string(),
                 })?
         {
-            if circular_buffer.len() == lines {
-                circular_buffer.pop_front();
+            let Ok(file_type) = entry.file_type().await else {
+                continue;
+            };
+            if file_type.is_file() {
+                files.push(entry.path());
+            } else if file_type.is_dir() && recursive {
+                Self::collect_files_to_rename(&entry.path(), recursive, files).await?;
             }
-            circular_buffer.push_back(line);
         }
 
-        // Join the lines in the circular buffer
-        Ok(ReadFileResponse::text(
-            circular_buffer
-                .into_iter()
-                .collect::<Vec<String>>()
-                .join("\n"),
-        ))
+        Ok(())
     }
 
-    /// Read a media file and return base64-encoded content with MIME type
-    async fn read_media_file(&self, path: &Path) -> FileSystemMcpResult<ReadFileResponse> {
-        let bytes = self.read_file_bytes(path).await?;
-        Ok(ReadFileResponse::new(bytes, path))
+    /// Number of chunk files `split_file` writes for `path`
+    ///
+    /// Line mode streams the file once to count its lines; a last line without a
+    /// line break still counts.
+    async fn count_split_chunks(path: &Path, chunk: SplitChunk) -> FileSystemMcpResult<u64> {
+        let io_error = |e: io::Error| FileSystemMcpError::IoError {
+            message: format!("Failed to read file: {}", e),
+            path: path.display().to_string(),
+        };
+
+        match chunk {
+            SplitChunk::Bytes(size) => {
+                let len = fs::metadata(path).await.map_err(io_error)?.len();
+                Ok(len.div_ceil(size))
+            }
+            SplitChunk::Lines(lines) => {
+                let mut reader = BufReader::new(File::open(path).await.map_err(io_error)?);
+                let mut line_count = 0u64;
+                let mut last_byte = None;
+                loop {
+                    let buffer = reader.fill_buf().await.map_err(io_error)?;
+                    if buffer.is_empty() {
+                        break;
+                    }
+                    line_count += buffer.iter().filter(|&&byte| byte == b'\n').count() as u64;
+                    last_byte = buffer.last().copied();
+                    let consumed = buffer.len();
+                    reader.consume(consumed);
+                }
+                if last_byte.is_some_and(|byte| byte != b'\n') {
+                    line_count += 1;
+                }
+                Ok(line_count.div_ceil(lines as u64))
+            }
+        }
+    }
+
+    /// Reject a chunk path that would write through a symlink, replace a directory
+    /// or overwrite the file being split
+    async fn check_split_target(input: &Path, chunk_path: &Path) -> FileSystemMcpResult<()> {
+        let Ok(metadata) = fs::symlink_metadata(chunk_path).await else {
+            return Ok(());
+        };
+        let reason = if metadata.file_type().is_symlink() {
+            "Chunk path is a symlink"
+        } else if metadata.is_dir() {
+            "Chunk path is a directory"
+        } else if fs::canonicalize(chunk_path).await.ok().as_deref() == Some(input) {
+            "Chunk path is the file being split"
+        } else {
+            return Ok(());
+        };
+
+        Err(FileSystemMcpError::ValidationError {
+            message: "Invalid chunk path".to_string(),
+            path: chunk_path.display().to_string(),
+            operation: "split_file".to_string(),
+            data: serde_json::json!({"error": reason}),
+        })
+    }
+
+    /// Lines of `bytes` containing `needle`, or `None` when it does not occur
+    ///
+    /// The whole content is checked first so that a match spanning a line break
+    /// still keeps the file; lines are decoded lossily with any `\r` trimmed.
+    fn content_matching_lines(bytes: &[u8], needle: &str) -> Option<Vec<MatchingLine>> {
+        let needle = needle.as_bytes();
+        let contains = |haystack: &[u8]| {
+            needle.is_empty() || haystack.windows(needle.len()).any(|w| w == needle)
+        };
+        if !contains(bytes) {
+            return None;
+        }
+
+        Some(
+            bytes
+                .split(|&b| b == b'\n')
+                .enumerate()
+                .filter(|(_, line)| contains(line))
+                .map(|(index, line)| MatchingLine {
+                    line_number: index + 1,
+                    line: String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line))
+                        .into_owned(),
+                })
+                .collect(),
+        )
+    }
+
+    #[async_recursion]
+    async fn search_recursive(
+        root_path: &Path,
+        current_path: &Path,
+        search_glob: &Glob,
+        exclude_globset: &Option<globset::GlobSet>,
+        follow_symlinks: bool,
+        ancestors: &mut Vec<PathBuf>,
+        results: &mut Vec<String>,
+    ) -> FileSystemMcpResult<()> {
+        let mut entries =
+            fs::read_dir(current_path)
+                .await
+                .map_err(|e| FileSystemMcpError::IoError {
+                    message: format!("Failed to read directory: {}", e),
+                    path: current_path.display().to_string(),
+                })?;
+
+        while let Some(entry) =
+            entries
+                .next_entry()
+                .await
+                .map_err(|e| FileSystemMcpError::IoError {
+                    message: format!("Failed to read directory entry: {}", e),
+                    path: current_path.display().to_string(),
+                })?
+        {
+            let entry_path = entry.path();
+            let relative_path = entry_path
+                .strip_prefix(root_path)
+                .unwrap_or(&entry_path)
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            // Check exclude patterns
+            if let Some(globset) = exclude_globset
+                && globset.is_match(&relative_path)
+            {
+                continue;
+            }
+
+            // Check if matches search pattern
+            if search_glob.compile_matcher().is_match(&relative_path) {
+                results.push(entry_path.display().to_string());
+            }
+
+            // Recurse into directories
+            let is_dir = Self::entry_metadata(&entry, follow_symlinks)
+                .await
+                .map(|m| m.is_dir())
+                .unwrap_or(false);
+            if is_dir && let Some(dir) = Self::next_traversal_dir(&entry, ancestors).await {
+                ancestors.push(dir);
+                let result = Self::search_recursive(
+                    root_path,
+                    &entry_path,
+                    search_glob,
+                    exclude_globset,
+                    follow_symlinks,
+                    ancestors,
+                    results,
+                )
+                .await;
+                ancestors.pop();
+                result?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for FileService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl FileOperations for FileService {
+    /// Read the entire contents of a file using reusable streaming function
+    async fn read_entire_file(&self, path: &Path) -> FileSystemMcpResult<ReadFileResponse> {
+        let (bytes, truncated) = self.read_file_bytes(path, self.max_read_bytes).await?;
+        let contents = String::from_utf8_lossy(&bytes).to_string();
+        let response = ReadFileResponse::text(contents).with_truncated(truncated);
+        if !truncated {
+            return Ok(response);
+        }
+
+        // The returned content stops at the read limit; report the whole file instead
+        let line_count = self.count_file_lines(path).await?;
+        let size_bytes = self
+            .get_file_size(path)
+            .await
+            .unwrap_or(response.size_bytes);
+        Ok(response.with_file_totals(line_count, size_bytes))
+    }
+
+    /// Read the first N lines using streaming with early termination
+    async fn read_file_head(
+        &self,
+        path: &Path,
+        lines: usize,
+    ) -> FileSystemMcpResult<ReadFileResponse> {
+        // Stopping early leaves the rest of the file unread, so count it in a separate pass
+        let line_count = self.count_file_lines(path).await?;
+        let size_bytes = self.get_file_size(path).await.unwrap_or_default();
+        if lines == 0 {
+            return Ok(
+                ReadFileResponse::text(String::new()).with_file_totals(line_count, size_bytes)
+            );
+        }
+
+        let file = File::open(path)
+            .await
+            .map_err(|_| FileSystemMcpError::PermissionDenied {
+                path: path.display().to_string(),
+            })?;
+
+        let reader = BufReader::new(file);
+        let mut lines_stream = reader.lines();
+        let mut result_lines = Vec::with_capacity(lines);
+
+        // Read only the requested number of lines
+        for _ in 0..lines {
+            match lines_stream.next_line().await {
+                Ok(Some(line)) => result_lines.push(line),
+                Ok(None) => break, // End of file reached
+                Err(_) => {
+                    return Err(FileSystemMcpError::PermissionDenied {
+                        path: path.display().to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(
+            ReadFileResponse::text(result_lines.join("\n"))
+                .with_file_totals(line_count, size_bytes),
+        )
+    }
+
+    /// Read the last N lines using memory-efficient circular buffer
+    async fn read_file_tail(
+        &self,
+        path: &Path,
+        lines: usize,
+    ) -> FileSystemMcpResult<ReadFileResponse> {
+        let size_bytes = self.get_file_size(path).await.unwrap_or_default();
+        if lines == 0 {
+            let line_count = self.count_file_lines(path).await?;
+            return Ok(
+                ReadFileResponse::text(String::new()).with_file_totals(line_count, size_bytes)
+            );
+        }
+
+        let file = File::open(path)
+            .await
+            .map_err(|_| FileSystemMcpError::PermissionDenied {
+                path: path.display().to_string(),
+            })?;
+
+        let reader = BufReader::new(file);
+        let mut lines_stream = reader.lines();
+        let mut circular_buffer: VecDeque<String> = VecDeque::with_capacity(lines);
+        let mut line_count = 0;
+
+        // Read all lines and maintain a circular buffer of the last N lines
+        while let Some(line) =
+            lines_stream
+                .next_line()
+                .await
+                .map_err(|_| FileSystemMcpError::PermissionDenied {
+                    path: path.display().to_string(),
+                })?
+        {
+            line_count += 1;
+            if circular_buffer.len() == lines {
+                circular_buffer.pop_front();
+            }
+            circular_buffer.push_back(line);
+        }
+
+        // Join the lines in the circular buffer
+        Ok(ReadFileResponse::text(
+            circular_buffer
+                .into_iter()
+                .collect::<Vec<String>>()
+                .join("\n"),
+        )
+        .with_file_totals(line_count, size_bytes))
+    }
+
+    /// Read a media file and return base64-encoded content with MIME type
+    async fn read_media_file(&self, path: &Path) -> FileSystemMcpResult<ReadFileResponse> {
+        let (bytes, truncated) = self.read_file_bytes(path, self.max_read_bytes).await?;
+        Ok(ReadFileResponse::new(bytes, path).with_truncated(truncated))
+    }
+
+    /// Read the whole file; a partial file would defeat the checksum, so the read limit is an error
+    async fn read_file_base64(&self, path: &Path) -> FileSystemMcpResult<Base64FileResponse> {
+        let (bytes, truncated) = self.read_file_bytes(path, self.max_read_bytes).await?;
+        if truncated {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "File too large".to_string(),
+                path: path.display().to_string(),
+                operation: "read_file_as_base64".to_string(),
+                data: serde_json::json!({
+                    "error": "File exceeds the server's read limit",
+                    "max_read_bytes": self.max_read_bytes
+                }),
+            });
+        }
+
+        Ok(Base64FileResponse {
+            md5: format!("{:x}", Md5::digest(&bytes)),
+            size_bytes: bytes.len() as u64,
+            base64: general_purpose::STANDARD.encode(&bytes),
+        })
+    }
+
+    /// Stream lines until the requested section is complete, keeping only that section
+    async fn read_file_between_markers(
+        &self,
+        path: &Path,
+        start_marker: &str,
+        end_marker: &str,
+        include_markers: bool,
+        occurrence: usize,
+    ) -> FileSystemMcpResult<ReadFileResponse> {
+        let file = File::open(path)
+            .await
+            .map_err(|_| FileSystemMcpError::PermissionDenied {
+                path: path.display().to_string(),
+            })?;
+
+        let mut lines_stream = BufReader::new(file).lines();
+        let mut starts_seen = 0;
+        let mut section: Option<Vec<String>> = None;
+
+        while let Some(line) =
+            lines_stream
+                .next_line()
+                .await
+                .map_err(|_| FileSystemMcpError::PermissionDenied {
+                    path: path.display().to_string(),
+                })?
+        {
+            match section.as_mut() {
+                Some(lines) if line.contains(end_marker) => {
+                    if include_markers {
+                        lines.push(line);
+                    }
+                    return Ok(ReadFileResponse::text(lines.join("\n")));
+                }
+                Some(lines) => lines.push(line),
+                None if line.contains(start_marker) => {
+                    starts_seen += 1;
+                    if starts_seen == occurrence {
+                        section = Some(if include_markers {
+                            vec![line]
+                        } else {
+                            Vec::new()
+                        });
+                    }
+                }
+                None => {}
+            }
+        }
+
+        let (message, marker) = if section.is_some() {
+            ("End marker not found", end_marker)
+        } else {
+            ("Start marker not found", start_marker)
+        };
+        Err(FileSystemMcpError::ValidationError {
+            message: message.to_string(),
+            path: path.display().to_string(),
+            operation: "read_file_between_markers".to_string(),
+            data: serde_json::json!({
+                "error": message,
+                "marker": marker,
+                "occurrence": occurrence,
+                "start_markers_found": starts_seen,
+            }),
+        })
     }
 
     /// Read files concurrently using futures::join_all for scalability with many files
@@ -651,7 +1409,7 @@ impl FileOperations for FileService {
         edits: &[crate::models::requests::EditOperation],
         dry_run: &bool,
     ) -> FileSystemMcpResult<WriteFileResponse> {
-        // Read and normalize file content
+        // Read file content; edits normalize its line endings
         let original_content =
             fs::read_to_string(path)
                 .await
@@ -660,122 +1418,14 @@ impl FileOperations for FileService {
                     path: path.display().to_string(),
                 })?;
 
-        let mut modified_content = Self::normalize_line_endings(&original_content);
-
-        // Apply edits sequentially
-        for edit in edits {
-            let normalized_old = Self::normalize_line_endings(edit.old_text());
-            let normalized_new = Self::normalize_line_endings(edit.new_text());
-
-            // Try exact match first
-            if modified_content.contains(&normalized_old) {
-                modified_content = modified_content.replacen(&normalized_old, &normalized_new, 1);
-                continue;
-            }
-
-            // Try line-by-line matching with whitespace flexibility
-            let old_lines: Vec<&str> = normalized_old.split('\n').collect();
-            let content_lines: Vec<&str> = modified_content.split('\n').collect();
-            let mut match_found = false;
-
-            for i in 0..=(content_lines.len().saturating_sub(old_lines.len())) {
-                if i + old_lines.len() > content_lines.len() {
-                    break;
-                }
-
-                let potential_match = &content_lines[i..i + old_lines.len()];
-
-                // Compare lines with normalized whitespace
-                let is_match = old_lines
-                    .iter()
-                    .zip(potential_match.iter())
-                    .all(|(old_line, content_line)| old_line.trim() == content_line.trim());
-
-                if is_match {
-                    // Preserve original indentation of first line
-                    let original_indent = content_lines[i]
-                        .chars()
-                        .take_while(|c| c.is_whitespace())
-                        .collect::<String>();
-
-                    // Calculate the base indentation of the new text (from first non-empty line)
-                    let new_text_lines: Vec<&str> = normalized_new.split('\n').collect();
-                    let base_new_indent = new_text_lines
-                        .iter()
-                        .find(|line| !line.trim().is_empty())
-                        .map(|line| {
-                            line.chars()
-                                .take_while(|c| c.is_whitespace())
-                                .collect::<String>()
-                        })
-                        .unwrap_or_default();
-
-                    let new_lines: Vec<String> = new_text_lines
-                        .iter()
-                        .enumerate()
-                        .map(|(j, line)| {
-                            if j == 0 {
-                                // First line: use original indentation
-                                format!("{}{}", original_indent, line.trim_start())
-                            } else if line.trim().is_empty() {
-                                // Empty lines remain empty
-                                String::new()
-                            } else {
-                                // Subsequent lines: preserve relative indentation structure
-                                let line_indent = line
-                                    .chars()
-                                    .take_while(|c| c.is_whitespace())
-                                    .collect::<String>();
-
-                                // Calculate relative indentation from the base indentation of new text
-                                let relative_indent_size =
-                                    if line_indent.len() >= base_new_indent.len() {
-                                        line_indent.len() - base_new_indent.len()
-                                    } else {
-                                        0
-                                    };
-
-                                format!(
-                                    "{}{}{}",
-                                    original_indent,
-                                    " ".repeat(relative_indent_size),
-                                    line.trim_start()
-                                )
-                            }
-                        })
-                        .collect();
-
-                    // Replace the matched lines
-                    let mut new_content_lines = content_lines[..i].to_vec();
-                    new_content_lines.extend(new_lines.iter().map(|s| s.as_str()));
-                    new_content_lines.extend(&content_lines[i + old_lines.len()..]);
-
-                    modified_content = new_content_lines.join("\n");
-                    match_found = true;
-                    break;
-                }
-            }
-
-            if !match_found {
-                return Err(FileSystemMcpError::ValidationError {
-                    message: "Could not find exact match for edit".to_string(),
-                    path: path.display().to_string(),
-                    operation: "apply_edit".to_string(),
-                    data: serde_json::json!({
-                        "error": "No matching text found",
-                        "old_text": edit.old_text()
-                    }),
-                });
-            }
-        }
+        let modified_content = apply_edits_to_content(&original_content, edits, path)?;
 
         if *dry_run {
             // Return preview without modifying file
-            Ok(WriteFileResponse::new(
-                format!("Dry run completed. {} edits would be applied.", edits.len()),
-                path.display().to_string(),
-                Some(modified_content.len() as u64),
-                false,
+            Ok(WriteFileResponse::dry_run(
+                path,
+                edits.len(),
+                modified_content,
             ))
         } else {
             // Apply changes using secure write
@@ -802,19 +1452,19 @@ impl FileOperations for FileService {
             entries.iter().partition(|entry| entry.is_directory);
 
         let mut output = Vec::new();
-        output.push(format!("📁 Directory: {}", path.display()));
+        output.push(format!("\u{1F4C1} Directory: {}", path.display()));
         output.push(String::new());
 
         if !directories.is_empty() {
-            output.push("📂 Directories:".to_string());
+            output.push("\u{1F4C2} Directories:".to_string());
             for dir in &directories {
-                output.push(format!("  📁 {}/", dir.name));
+                output.push(format!("  \u{1F4C1} {}/", dir.name));
             }
             output.push(String::new());
         }
 
         if !files.is_empty() {
-            output.push("📄 Files:".to_string());
+            output.push("\u{1F4C4} Files:".to_string());
             for file in &files {
                 let icon = Self::get_file_icon(&file.file_type);
                 let size_info = if file.size > 0 {
@@ -828,7 +1478,7 @@ impl FileOperations for FileService {
         }
 
         output.push(format!(
-            "📊 Summary: {} directories, {} files",
+            "\u{1F4CA} Summary: {} directories, {} files",
             directories.len(),
             files.len()
         ));
@@ -851,7 +1501,7 @@ impl FileOperations for FileService {
 
         let mut output = Vec::new();
         output.push(format!(
-            "📁 Directory: {} (sorted by {:?})",
+            "\u{1F4C1} Directory: {} (sorted by {})",
             path.display(),
             sort_by
         ));
@@ -864,7 +1514,7 @@ impl FileOperations for FileService {
             output.push(String::new());
             output.push(stats);
         } else {
-            output.push("📂 Empty directory".to_string());
+            output.push("\u{1F4C2} Empty directory".to_string());
         }
 
         Ok(WriteFileResponse::new(
@@ -879,8 +1529,20 @@ impl FileOperations for FileService {
         &self,
         path: &Path,
         exclude_patterns: &[String],
+        follow_symlinks: bool,
+        max_depth: Option<usize>,
     ) -> FileSystemMcpResult<WriteFileResponse> {
-        match Self::build_tree(path, path, exclude_patterns).await {
+        let mut ancestors = vec![Self::canonical_root(path).await];
+        match Self::build_tree(
+            path,
+            path,
+            exclude_patterns,
+            follow_symlinks,
+            max_depth,
+            &mut ancestors,
+        )
+        .await
+        {
             Ok(tree) => Ok(WriteFileResponse::new(
                 serde_json::to_string_pretty(&tree).unwrap(),
                 path.display().to_string(),
@@ -894,6 +1556,37 @@ impl FileOperations for FileService {
         }
     }
 
+    async fn file_tree_markdown(
+        &self,
+        path: &Path,
+        exclude_patterns: &[String],
+        max_depth: Option<usize>,
+    ) -> FileSystemMcpResult<WriteFileResponse> {
+        let mut ancestors = vec![Self::canonical_root(path).await];
+        let tree = Self::build_tree(
+            path,
+            path,
+            exclude_patterns,
+            true,
+            max_depth,
+            &mut ancestors,
+        )
+        .await
+        .map_err(|e| FileSystemMcpError::IoError {
+            message: format!("Failed to build directory tree: {}", e),
+            path: path.display().to_string(),
+        })?;
+
+        let mut output = String::new();
+        Self::render_tree(&tree, "", &mut output);
+        Ok(WriteFileResponse::new(
+            output.trim_end().to_string(),
+            path.display().to_string(),
+            None,
+            false,
+        ))
+    }
+
     async fn move_file(&self, from: &Path, to: &Path) -> FileSystemMcpResult<WriteFileResponse> {
         if !self.path_exists(from).await {
             return Err(FileSystemMcpError::PathNotFound {
@@ -919,16 +1612,277 @@ impl FileOperations for FileService {
         Ok(WriteFileResponse::moved(from, to))
     }
 
-    async fn search_files(
+    async fn prune_empty_directories(
         &self,
         path: &Path,
-        pattern: &str,
-        _allowed_directories: &[PathBuf],
-        exclude_patterns: &[String],
+        dry_run: bool,
     ) -> FileSystemMcpResult<WriteFileResponse> {
-        let mut results = Vec::new();
-
-        // Build globset for pattern matching
+        if !fs::metadata(path)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Path is not a directory".to_string(),
+                path: path.display().to_string(),
+                operation: "prune_empty_directories".to_string(),
+                data: serde_json::json!({ "error": "Only directories can be pruned" }),
+            });
+        }
+
+        let mut pruned = Vec::new();
+        Self::prune_empty_recursive(path, dry_run, &mut pruned).await?;
+
+        let pruned: Vec<String> = pruned.iter().map(|dir| dir.display().to_string()).collect();
+        let pruned_json =
+            serde_json::to_string_pretty(&pruned).map_err(|e| FileSystemMcpError::IoError {
+                message: format!("Failed to serialize pruned directories: {}", e),
+                path: path.display().to_string(),
+            })?;
+
+        Ok(WriteFileResponse::new(
+            pruned_json,
+            path.display().to_string(),
+            None,
+            false,
+        ))
+    }
+
+    async fn rename_bulk(
+        &self,
+        directory: &Path,
+        pattern: &Regex,
+        replacement: &str,
+        recursive: bool,
+        dry_run: bool,
+    ) -> FileSystemMcpResult<BulkRenameResponse> {
+        if !fs::metadata(directory)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Path is not a directory".to_string(),
+                path: directory.display().to_string(),
+                operation: "rename_bulk".to_string(),
+                data: serde_json::json!({ "error": "Files can only be renamed within a directory" }),
+            });
+        }
+
+        let mut files = Vec::new();
+        Self::collect_files_to_rename(directory, recursive, &mut files).await?;
+        files.sort();
+
+        // Track names taken and freed by earlier renames so a dry run reports
+        // the same outcome a real run would
+        let mut claimed = HashSet::new();
+        let mut vacated = HashSet::new();
+        let mut response = BulkRenameResponse::default();
+        for from in files {
+            let Some(name) = from.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let new_name = pattern.replace(name, replacement);
+            if new_name == name {
+                continue;
+            }
+            if new_name.is_empty()
+                || new_name == "."
+                || new_name == ".."
+                || new_name.contains(['/', '\\'])
+            {
+                response.errors.push(format!(
+                    "{}: '{}' is not a valid file name",
+                    from.display(),
+                    new_name
+                ));
+                continue;
+            }
+
+            let to = from.with_file_name(new_name.as_ref());
+            let taken = claimed.contains(&to)
+                || (!vacated.contains(&to) && fs::symlink_metadata(&to).await.is_ok());
+            if taken {
+                response.errors.push(format!(
+                    "{}: {} already exists",
+                    from.display(),
+                    to.display()
+                ));
+                continue;
+            }
+            if !dry_run && let Err(e) = fs::rename(&from, &to).await {
+                response
+                    .errors
+                    .push(format!("{}: failed to rename: {}", from.display(), e));
+                continue;
+            }
+
+            response.renamed.push(RenamedFile {
+                from: from.display().to_string(),
+                to: to.display().to_string(),
+            });
+            vacated.remove(&to);
+            claimed.insert(to);
+            claimed.remove(&from);
+            vacated.insert(from);
+        }
+
+        Ok(response)
+    }
+
+    async fn split_file(
+        &self,
+        path: &Path,
+        output_prefix: &Path,
+        chunk: SplitChunk,
+    ) -> FileSystemMcpResult<SplitFileResponse> {
+        use tokio::io::{AsyncWriteExt, BufWriter};
+
+        let io_error = |path: &Path, e: io::Error| FileSystemMcpError::IoError {
+            message: format!("Failed to split file: {}", e),
+            path: path.display().to_string(),
+        };
+
+        // Chunks are siblings of the prefix, so a directory prefix would put them
+        // next to that directory instead of inside it
+        if fs::metadata(output_prefix)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid output_prefix".to_string(),
+                path: output_prefix.display().to_string(),
+                operation: "split_file".to_string(),
+                data: serde_json::json!({
+                    "error": "output_prefix is a directory; name the chunk files inside it, e.g. <directory>/part"
+                }),
+            });
+        }
+
+        let chunk_count = Self::count_split_chunks(path, chunk).await?;
+        if chunk_count > MAX_SPLIT_CHUNKS {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Too many chunks".to_string(),
+                path: path.display().to_string(),
+                operation: "split_file".to_string(),
+                data: serde_json::json!({
+                    "error": format!("Splitting would write {} chunks; choose a larger chunk size", chunk_count),
+                    "max_chunks": MAX_SPLIT_CHUNKS
+                }),
+            });
+        }
+
+        // Pad every number to the same width so the chunks sort in order
+        let width = chunk_count.to_string().len().max(3);
+        let chunk_paths: Vec<PathBuf> = (1..=chunk_count)
+            .map(|number| {
+                let mut name = output_prefix.as_os_str().to_owned();
+                name.push(format!(".{:0width$}", number));
+                PathBuf::from(name)
+            })
+            .collect();
+        for chunk_path in &chunk_paths {
+            Self::check_split_target(path, chunk_path).await?;
+        }
+
+        let mut reader = BufReader::new(File::open(path).await.map_err(|e| io_error(path, e))?);
+        let mut response = SplitFileResponse::default();
+        let mut line = Vec::new();
+        for chunk_path in chunk_paths {
+            let chunk_error = |e| io_error(&chunk_path, e);
+            let mut writer = BufWriter::new(File::create(&chunk_path).await.map_err(chunk_error)?);
+            let size = match chunk {
+                SplitChunk::Bytes(size) => {
+                    tokio::io::copy(&mut (&mut reader).take(size), &mut writer)
+                        .await
+                        .map_err(chunk_error)?
+                }
+                SplitChunk::Lines(lines) => {
+                    let mut written = 0;
+                    for _ in 0..lines {
+                        line.clear();
+                        let read = reader
+                            .read_until(b'\n', &mut line)
+                            .await
+                            .map_err(|e| io_error(path, e))?;
+                        if read == 0 {
+                            break;
+                        }
+                        writer.write_all(&line).await.map_err(chunk_error)?;
+                        written += read as u64;
+                    }
+                    written
+                }
+            };
+            writer.flush().await.map_err(chunk_error)?;
+
+            response.total_bytes += size;
+            response.chunks.push(FileChunk {
+                path: chunk_path.display().to_string(),
+                size,
+            });
+        }
+
+        Ok(response)
+    }
+
+    async fn join_files(
+        &self,
+        inputs: &[PathBuf],
+        output: &Path,
+    ) -> FileSystemMcpResult<WriteFileResponse> {
+        use tokio::io::{AsyncWriteExt, BufWriter};
+
+        let existing_output = fs::canonicalize(output).await.ok();
+        if let Some(existing_output) = &existing_output
+            && inputs.contains(existing_output)
+        {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Output is an input".to_string(),
+                path: output.display().to_string(),
+                operation: "join_files".to_string(),
+                data: serde_json::json!({
+                    "error": "output_path cannot be one of the input files"
+                }),
+            });
+        }
+
+        let output_error = |e: io::Error| FileSystemMcpError::IoError {
+            message: format!("Failed to write joined file: {}", e),
+            path: output.display().to_string(),
+        };
+        let mut writer = BufWriter::new(File::create(output).await.map_err(output_error)?);
+        let mut size = 0;
+        for input in inputs {
+            let input_error = |e: io::Error| FileSystemMcpError::IoError {
+                message: format!("Failed to join file: {}", e),
+                path: input.display().to_string(),
+            };
+            let mut reader = File::open(input).await.map_err(input_error)?;
+            size += tokio::io::copy(&mut reader, &mut writer)
+                .await
+                .map_err(input_error)?;
+        }
+        writer.flush().await.map_err(output_error)?;
+
+        Ok(WriteFileResponse::new(
+            format!("Joined {} files into {}", inputs.len(), output.display()),
+            output.display().to_string(),
+            Some(size),
+            existing_output.is_none(),
+        ))
+    }
+
+    async fn search_files(
+        &self,
+        path: &Path,
+        pattern: &str,
+        _allowed_directories: &[PathBuf],
+        exclude_patterns: &[String],
+        follow_symlinks: bool,
+        content_contains: Option<&str>,
+    ) -> FileSystemMcpResult<WriteFileResponse> {
+        let mut results = Vec::new();
+
+        // Build globset for pattern matching
         let search_glob = Glob::new(pattern).map_err(|e| FileSystemMcpError::ValidationError {
             message: format!("Invalid search pattern: {}", e),
             path: path.display().to_string(),
@@ -939,27 +1893,379 @@ impl FileOperations for FileService {
             }),
         })?;
 
-        let mut exclude_globset = None;
-        if !exclude_patterns.is_empty() {
-            let mut builder = GlobSetBuilder::new();
-            for exclude_pattern in exclude_patterns {
-                if let Ok(glob) = Glob::new(exclude_pattern) {
-                    builder.add(glob);
+        let exclude_globset = Self::build_exclude_globset(exclude_patterns);
+
+        let mut ancestors = vec![Self::canonical_root(path).await];
+        Self::search_recursive(
+            path,
+            path,
+            &search_glob,
+            &exclude_globset,
+            follow_symlinks,
+            &mut ancestors,
+            &mut results,
+        )
+        .await?;
+
+        let results_json = match content_contains {
+            Some(needle) => {
+                let mut matches = Vec::new();
+                for result in results {
+                    let is_file = fs::metadata(&result)
+                        .await
+                        .map(|m| m.is_file())
+                        .unwrap_or(false);
+                    if !is_file {
+                        continue;
+                    }
+                    // Unreadable files cannot contain the text as far as we can tell
+                    let Ok((bytes, _)) = self
+                        .read_file_bytes(Path::new(&result), self.max_read_bytes)
+                        .await
+                    else {
+                        continue;
+                    };
+                    if let Some(matching_lines) = Self::content_matching_lines(&bytes, needle) {
+                        matches.push(ContentSearchMatch {
+                            path: result,
+                            matching_lines,
+                        });
+                    }
+                }
+                serde_json::to_string_pretty(&matches)
+            }
+            None => serde_json::to_string_pretty(&results),
+        }
+        .map_err(|e| FileSystemMcpError::IoError {
+            message: format!("Failed to serialize search results: {}", e),
+            path: path.display().to_string(),
+        })?;
+
+        Ok(WriteFileResponse::new(
+            results_json,
+            path.display().to_string(),
+            None,
+            false,
+        ))
+    }
+
+    async fn export_directory_manifest(
+        &self,
+        path: &Path,
+        include_hashes: bool,
+        exclude_patterns: &[String],
+    ) -> FileSystemMcpResult<WriteFileResponse> {
+        let exclude_globset = Self::build_exclude_globset(exclude_patterns);
+        let mut files = Vec::new();
+        Self::collect_files_in_range(path, path, None, None, &exclude_globset, &mut files).await?;
+
+        let mut entries = Vec::with_capacity(files.len());
+        for (modified, file_path) in files {
+            let metadata =
+                fs::metadata(&file_path)
+                    .await
+                    .map_err(|e| FileSystemMcpError::IoError {
+                        message: format!("Failed to get file metadata: {}", e),
+                        path: file_path.display().to_string(),
+                    })?;
+            let sha256 = if include_hashes {
+                Some(Self::sha256_file(&file_path).await?)
+            } else {
+                None
+            };
+
+            entries.push(ManifestEntry {
+                path: file_path
+                    .strip_prefix(path)
+                    .unwrap_or(&file_path)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                size: metadata.len(),
+                modified: modified
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_secs())
+                    .unwrap_or(0),
+                sha256,
+            });
+        }
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let manifest_json =
+            serde_json::to_string_pretty(&entries).map_err(|e| FileSystemMcpError::IoError {
+                message: format!("Failed to serialize manifest: {}", e),
+                path: path.display().to_string(),
+            })?;
+
+        Ok(WriteFileResponse::new(
+            manifest_json,
+            path.display().to_string(),
+            None,
+            false,
+        ))
+    }
+
+    async fn compare_directories(
+        &self,
+        path_a: &Path,
+        path_b: &Path,
+        compare_content: bool,
+    ) -> FileSystemMcpResult<CompareDirectoriesResponse> {
+        let files_a = Self::collect_relative_files(path_a).await?;
+        let mut files_b = Self::collect_relative_files(path_b).await?;
+        let mut response = CompareDirectoriesResponse::default();
+        let size = |path: &Path| {
+            let path = path.to_path_buf();
+            async move {
+                fs::metadata(&path).await.map(|m| m.len()).map_err(|e| {
+                    FileSystemMcpError::IoError {
+                        message: format!("Failed to get file metadata: {}", e),
+                        path: path.display().to_string(),
+                    }
+                })
+            }
+        };
+
+        for (relative_path, file_a) in files_a {
+            let Some(file_b) = files_b.remove(&relative_path) else {
+                response.only_in_a.push(relative_path);
+                continue;
+            };
+
+            let same = size(&file_a).await? == size(&file_b).await?
+                && (!compare_content
+                    || Self::sha256_file(&file_a).await? == Self::sha256_file(&file_b).await?);
+
+            if same {
+                response.identical.push(relative_path);
+            } else {
+                response.different.push(relative_path);
+            }
+        }
+        response.only_in_b = files_b.into_keys().collect();
+
+        Ok(response)
+    }
+
+    async fn read_csv_file(
+        &self,
+        path: &Path,
+        has_header: bool,
+        delimiter: u8,
+        max_rows: usize,
+    ) -> FileSystemMcpResult<WriteFileResponse> {
+        let csv_path = path.to_path_buf();
+        // The csv reader is synchronous; it streams the file and stops after max_rows
+        let rows = tokio::task::spawn_blocking(move || {
+            Self::parse_csv_rows(&csv_path, has_header, delimiter, max_rows)
+        })
+        .await
+        .map_err(|e| FileSystemMcpError::IoError {
+            message: format!("CSV parsing task failed: {}", e),
+            path: path.display().to_string(),
+        })??;
+
+        let rows_json =
+            serde_json::to_string_pretty(&rows).map_err(|e| FileSystemMcpError::IoError {
+                message: format!("Failed to serialize CSV rows: {}", e),
+                path: path.display().to_string(),
+            })?;
+
+        Ok(WriteFileResponse::new(
+            rows_json,
+            path.display().to_string(),
+            None,
+            false,
+        ))
+    }
+
+    async fn read_env_file(&self, path: &Path) -> FileSystemMcpResult<WriteFileResponse> {
+        let contents = self.read_file_to_parse(path, "read_env_file").await?;
+
+        let env_json = serde_json::to_string_pretty(&parse_env_file(&contents)).map_err(|e| {
+            FileSystemMcpError::IoError {
+                message: format!("Failed to serialize env file: {}", e),
+                path: path.display().to_string(),
+            }
+        })?;
+
+        Ok(WriteFileResponse::new(
+            env_json,
+            path.display().to_string(),
+            None,
+            false,
+        ))
+    }
+
+    async fn read_file_as_jsonl(
+        &self,
+        path: &Path,
+        start_line: Option<usize>,
+        end_line: Option<usize>,
+    ) -> FileSystemMcpResult<ReadFileResponse> {
+        let io_error = |e: std::io::Error| FileSystemMcpError::IoError {
+            message: format!("Failed to read JSONL file: {}", e),
+            path: path.display().to_string(),
+        };
+
+        let file = File::open(path).await.map_err(io_error)?;
+        let mut reader = BufReader::new(file);
+        let start_line = start_line.unwrap_or(1);
+        let mut line_number = 0;
+        let mut line = Vec::new();
+        // Bytes of the requested lines read so far; lines before start_line don't count
+        let mut bytes_read = 0;
+        let mut truncated = false;
+        let mut values = Vec::new();
+
+        while end_line.is_none_or(|end_line| line_number < end_line) {
+            line.clear();
+            let in_range = line_number + 1 >= start_line;
+            let remaining = match self.max_read_bytes {
+                Some(limit) if in_range => limit.saturating_sub(bytes_read),
+                _ => usize::MAX,
+            };
+            if remaining == 0 {
+                truncated = !reader.fill_buf().await.map_err(io_error)?.is_empty();
+                break;
+            }
+
+            let read = (&mut reader)
+                .take(remaining as u64)
+                .read_until(b'\n', &mut line)
+                .await
+                .map_err(io_error)?;
+            if read == 0 {
+                break;
+            }
+            // A line cut off by the read limit is dropped rather than reported as invalid
+            if read == remaining && !line.ends_with(b"\n") {
+                truncated = !reader.fill_buf().await.map_err(io_error)?.is_empty();
+                if truncated {
+                    break;
                 }
             }
-            exclude_globset = builder.build().ok();
+
+            line_number += 1;
+            if !in_range {
+                continue;
+            }
+            bytes_read += read;
+
+            let text = line.strip_suffix(b"\n").unwrap_or(&line);
+            let text = text.strip_suffix(b"\r").unwrap_or(text);
+            if text.trim_ascii().is_empty() {
+                continue;
+            }
+
+            // Invalid UTF-8 only spoils its own line, which is kept lossily in _raw
+            values.push(match serde_json::from_slice::<serde_json::Value>(text) {
+                Ok(value) => value,
+                Err(e) => serde_json::json!({
+                    "_raw": String::from_utf8_lossy(text),
+                    "_parse_error": e.to_string()
+                }),
+            });
+        }
+
+        let values_json =
+            serde_json::to_string_pretty(&values).map_err(|e| FileSystemMcpError::IoError {
+                message: format!("Failed to serialize JSONL values: {}", e),
+                path: path.display().to_string(),
+            })?;
+
+        Ok(ReadFileResponse {
+            mime_type: "application/json".to_string(),
+            ..ReadFileResponse::text(values_json)
+        }
+        .with_truncated(truncated))
+    }
+
+    async fn parse_yaml_file(
+        &self,
+        path: &Path,
+        format: YamlOutputFormat,
+    ) -> FileSystemMcpResult<String> {
+        let contents = self.read_file_to_parse(path, "parse_yaml_file").await?;
+
+        convert_yaml(
+            &contents,
+            format,
+            &path.display().to_string(),
+            "parse_yaml_file",
+        )
+    }
+
+    /// Write the file in fixed-size chunks so large sizes never sit in memory
+    async fn generate_file(
+        &self,
+        path: &Path,
+        size_bytes: u64,
+        pattern: FillPattern,
+    ) -> FileSystemMcpResult<WriteFileResponse> {
+        use tokio::io::AsyncWriteExt;
+
+        let io_error = |e: std::io::Error| FileSystemMcpError::IoError {
+            message: format!("Failed to generate file: {}", e),
+            path: path.display().to_string(),
+        };
+
+        let created = !self.path_exists(path).await;
+        self.ensure_parent_dir(path).await.map_err(io_error)?;
+        let mut file = File::create(path).await.map_err(io_error)?;
+
+        let mut chunk = vec![0u8; GENERATE_CHUNK_SIZE.min(size_bytes as usize)];
+        let mut written = 0u64;
+        while written < size_bytes {
+            let len = chunk.len().min((size_bytes - written) as usize);
+            Self::fill_chunk(pattern, written, &mut chunk[..len]);
+            file.write_all(&chunk[..len]).await.map_err(io_error)?;
+            written += len as u64;
         }
+        file.flush().await.map_err(io_error)?;
+
+        Ok(WriteFileResponse::new(
+            format!(
+                "Generated {} bytes of {} content in {}",
+                size_bytes,
+                pattern,
+                path.display()
+            ),
+            path.display().to_string(),
+            Some(size_bytes),
+            created,
+        ))
+    }
 
-        Self::search_recursive(path, path, &search_glob, &exclude_globset, &mut results).await?;
+    async fn find_files_by_date_range(
+        &self,
+        path: &Path,
+        after: Option<SystemTime>,
+        before: Option<SystemTime>,
+        exclude_patterns: &[String],
+    ) -> FileSystemMcpResult<WriteFileResponse> {
+        let exclude_globset = Self::build_exclude_globset(exclude_patterns);
+        let mut matches = Vec::new();
+        Self::collect_files_in_range(path, path, after, before, &exclude_globset, &mut matches)
+            .await?;
+
+        // Newest first, then by path for files modified at the same instant
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        let files: Vec<DatedFile> = matches
+            .into_iter()
+            .map(|(modified, file_path)| DatedFile {
+                path: file_path.display().to_string(),
+                modified: DateTime::<Utc>::from(modified).to_rfc3339(),
+            })
+            .collect();
 
-        let results_json =
-            serde_json::to_string_pretty(&results).map_err(|e| FileSystemMcpError::IoError {
+        let files_json =
+            serde_json::to_string_pretty(&files).map_err(|e| FileSystemMcpError::IoError {
                 message: format!("Failed to serialize search results: {}", e),
                 path: path.display().to_string(),
             })?;
 
         Ok(WriteFileResponse::new(
-            results_json,
+            files_json,
             path.display().to_string(),
             None,
             false,
@@ -1033,6 +2339,89 @@ impl FileOperations for FileService {
             false,
         ))
     }
+
+    async fn watch_file(
+        &self,
+        path: &Path,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> FileSystemMcpResult<WatchFileResponse> {
+        let modified_time = || async {
+            fs::metadata(path)
+                .await
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        };
+
+        let initial = modified_time().await;
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                return Ok(WatchFileResponse {
+                    changed: false,
+                    detected_at: None,
+                });
+            }
+            tokio::time::sleep(poll_interval.min(remaining)).await;
+
+            if modified_time().await != initial {
+                return Ok(WatchFileResponse {
+                    changed: true,
+                    detected_at: Some(Utc::now().to_rfc3339()),
+                });
+            }
+        }
+    }
+
+    async fn create_temp_file(
+        &self,
+        directory: &Path,
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+    ) -> FileSystemMcpResult<PathBuf> {
+        let (dir, prefix, suffix) = Self::temp_name_parts(directory, prefix, suffix);
+        let created = tokio::task::spawn_blocking(move || {
+            tempfile::Builder::new()
+                .prefix(&prefix)
+                .suffix(&suffix)
+                .tempfile_in(&dir)
+                .and_then(|file| file.keep().map_err(|e| e.error))
+                .map(|(_, path)| path)
+        })
+        .await
+        .map_err(io::Error::other)
+        .and_then(|result| result);
+
+        created.map_err(|e| FileSystemMcpError::IoError {
+            message: format!("Failed to create temporary file: {}", e),
+            path: directory.display().to_string(),
+        })
+    }
+
+    async fn create_temp_directory(
+        &self,
+        directory: &Path,
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+    ) -> FileSystemMcpResult<PathBuf> {
+        let (dir, prefix, suffix) = Self::temp_name_parts(directory, prefix, suffix);
+        let created = tokio::task::spawn_blocking(move || {
+            tempfile::Builder::new()
+                .prefix(&prefix)
+                .suffix(&suffix)
+                .tempdir_in(&dir)
+                .map(|temp_dir| temp_dir.keep())
+        })
+        .await
+        .map_err(io::Error::other)
+        .and_then(|result| result);
+
+        created.map_err(|e| FileSystemMcpError::IoError {
+            message: format!("Failed to create temporary directory: {}", e),
+            path: directory.display().to_string(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -1065,12 +2454,155 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_read_file_head() {
+    async fn test_read_file_bytes_truncates_at_max_bytes() {
         let service = FileService::new();
-        let temp_file = create_test_file("line1\nline2\nline3\nline4\nline5").await;
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&data).unwrap();
 
-        let result = service.read_file_head(temp_file.path(), 3).await;
-        assert!(result.is_ok());
+        let (bytes, truncated) = service
+            .read_file_bytes(temp_file.path(), Some(10_000))
+            .await
+            .unwrap();
+        assert!(truncated);
+        assert_eq!(bytes.len(), 10_000);
+        assert_eq!(bytes, data[..10_000]);
+
+        let (bytes, truncated) = service
+            .read_file_bytes(temp_file.path(), Some(data.len()))
+            .await
+            .unwrap();
+        assert!(!truncated);
+        assert_eq!(bytes, data);
+
+        let (bytes, truncated) = service
+            .read_file_bytes(temp_file.path(), None)
+            .await
+            .unwrap();
+        assert!(!truncated);
+        assert_eq!(bytes.len(), data.len());
+    }
+
+    #[tokio::test]
+    async fn test_read_entire_file_sets_truncated_flag() {
+        let service = FileService::new().with_max_read_bytes(Some(16));
+        let temp_file = create_test_file(&"x".repeat(64)).await;
+
+        let response = service.read_entire_file(temp_file.path()).await.unwrap();
+        assert!(response.truncated);
+        assert_eq!(
+            response.content,
+            crate::models::responses::FileContent::Text("x".repeat(16))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_media_file_sets_truncated_flag() {
+        let service = FileService::new().with_max_read_bytes(Some(4));
+        let temp_file = create_test_file("not really an image").await;
+
+        let response = service.read_media_file(temp_file.path()).await.unwrap();
+        assert!(response.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_read_entire_file_within_limit_or_unlimited() {
+        let temp_file = create_test_file("small").await;
+
+        let limited = FileService::new().with_max_read_bytes(Some(5));
+        assert!(
+            !limited
+                .read_entire_file(temp_file.path())
+                .await
+                .unwrap()
+                .truncated
+        );
+
+        let unlimited = FileService::new().with_max_read_bytes(None);
+        assert!(
+            !unlimited
+                .read_entire_file(temp_file.path())
+                .await
+                .unwrap()
+                .truncated
+        );
+    }
+
+    const MARKED_FILE: &str = "preamble
+-----BEGIN CERTIFICATE-----
+first
+-----END CERTIFICATE-----
+between
+-----BEGIN CERTIFICATE-----
+second a
+second b
+-----END CERTIFICATE-----
+trailer";
+
+    async fn read_between(
+        content: &str,
+        include_markers: bool,
+        occurrence: usize,
+    ) -> FileSystemMcpResult<ReadFileResponse> {
+        let temp_file = create_test_file(content).await;
+        FileService::new()
+            .read_file_between_markers(
+                temp_file.path(),
+                "BEGIN CERTIFICATE",
+                "END CERTIFICATE",
+                include_markers,
+                occurrence,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_read_file_between_markers_selects_occurrence() {
+        use crate::models::responses::FileContent;
+
+        let first = read_between(MARKED_FILE, true, 1).await.unwrap();
+        assert_eq!(
+            first.content,
+            FileContent::Text(
+                "-----BEGIN CERTIFICATE-----\nfirst\n-----END CERTIFICATE-----".to_string()
+            )
+        );
+
+        let second = read_between(MARKED_FILE, false, 2).await.unwrap();
+        assert_eq!(
+            second.content,
+            FileContent::Text("second a\nsecond b".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_file_between_markers_missing_markers() {
+        let result = read_between(MARKED_FILE, false, 3).await;
+        match result {
+            Err(FileSystemMcpError::ValidationError { message, data, .. }) => {
+                assert_eq!(message, "Start marker not found");
+                assert_eq!(data["start_markers_found"], 2);
+            }
+            other => panic!("Expected ValidationError, got {:?}", other.map(|_| ())),
+        }
+
+        let unterminated = "-----BEGIN CERTIFICATE-----\nno end here";
+        let result = read_between(unterminated, false, 1).await;
+        match result {
+            Err(FileSystemMcpError::ValidationError { message, .. }) => {
+                assert_eq!(message, "End marker not found");
+            }
+            other => panic!("Expected ValidationError, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_file_head() {
+        let service = FileService::new();
+        let temp_file = create_test_file("line1\nline2\nline3\nline4\nline5").await;
+
+        let result = service.read_file_head(temp_file.path(), 3).await;
+        assert!(result.is_ok());
         let response = result.unwrap();
         if let crate::models::responses::FileContent::Text(content) = response.content {
             assert_eq!(content, "line1\nline2\nline3");
@@ -1079,6 +2611,86 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_reads_report_whole_file_line_count_and_size() {
+        let service = FileService::new();
+        let content = "line1\nline2\nline3\nline4\nline5\n";
+        let temp_file = create_test_file(content).await;
+
+        let entire = service.read_entire_file(temp_file.path()).await.unwrap();
+        assert_eq!((entire.line_count, entire.size_bytes), (5, 30));
+
+        // Head and tail describe the file, not the returned lines
+        for lines in [0, 2, 10] {
+            let head = service
+                .read_file_head(temp_file.path(), lines)
+                .await
+                .unwrap();
+            assert_eq!((head.line_count, head.size_bytes), (5, 30));
+            let tail = service
+                .read_file_tail(temp_file.path(), lines)
+                .await
+                .unwrap();
+            assert_eq!((tail.line_count, tail.size_bytes), (5, 30));
+        }
+
+        let truncated = FileService::new()
+            .with_max_read_bytes(Some(8))
+            .read_entire_file(temp_file.path())
+            .await
+            .unwrap();
+        assert!(truncated.truncated);
+        assert_eq!((truncated.line_count, truncated.size_bytes), (5, 30));
+
+        let unterminated = create_test_file("only\ntwo").await;
+        let response = service.read_entire_file(unterminated.path()).await.unwrap();
+        assert_eq!((response.line_count, response.size_bytes), (2, 8));
+    }
+
+    #[tokio::test]
+    async fn test_generate_file_fills_exact_size_with_pattern() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().unwrap();
+
+        let generate = |name: &str, size: u64, pattern: FillPattern| {
+            let path = temp_dir.path().join(name);
+            let service = &service;
+            async move {
+                let response = service.generate_file(&path, size, pattern).await.unwrap();
+                assert!(response.created);
+                assert_eq!(response.size, Some(size));
+                let bytes = tokio::fs::read(&path).await.unwrap();
+                assert_eq!(bytes.len() as u64, size);
+                bytes
+            }
+        };
+
+        // Larger than one chunk, and not a multiple of it
+        let zeros = generate("zeros.bin", 150_001, FillPattern::Zeros).await;
+        assert!(zeros.iter().all(|&b| b == 0));
+
+        let random = generate("random.bin", 4096, FillPattern::Random).await;
+        assert!(random.iter().any(|&b| b != 0));
+
+        let lorem = generate("lorem.txt", 70_000, FillPattern::Lorem).await;
+        let lorem = String::from_utf8(lorem).unwrap();
+        assert!(lorem.starts_with("Lorem ipsum dolor sit amet"));
+        // The paragraph continues seamlessly across chunk boundaries
+        assert_eq!(
+            lorem,
+            LOREM_IPSUM.repeat(70_000 / LOREM_IPSUM.len() + 1)[..70_000]
+        );
+
+        let ascii = generate("ascii.txt", 1000, FillPattern::Ascii).await;
+        assert!(ascii.iter().all(|b| (b' '..=b'~').contains(b)));
+
+        assert!(
+            generate("empty.bin", 0, FillPattern::Random)
+                .await
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn test_read_file_head_zero_lines() {
         let service = FileService::new();
@@ -1508,6 +3120,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_file_icons_are_intact_emoji() {
+        // Icons are written as escapes so a re-encoded source file cannot garble them
+        assert_eq!(FileService::get_file_icon("[DIR]"), "📁");
+        assert_eq!(FileService::get_file_icon("[FILE]"), "📄");
+        for file_type in ["[DIR]", "[FILE]", "[SYMLINK]", "rs [FILE]", "toml [FILE]"] {
+            let icon = FileService::get_file_icon(file_type);
+            assert!(!icon.is_empty());
+            assert!(
+                icon.chars().any(|c| c as u32 > 0x2000),
+                "{file_type}: {icon}"
+            );
+            assert!(!icon.contains(char::REPLACEMENT_CHARACTER));
+        }
+    }
+
     #[tokio::test]
     async fn test_list_directory_mixed_content() {
         let service = FileService::new();
@@ -1686,6 +3314,142 @@ mod tests {
         assert!(beta_pos < zebra_pos);
     }
 
+    #[tokio::test]
+    async fn test_list_directory_with_sizes_sort_by_modified() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        let epoch = std::time::UNIX_EPOCH;
+        for (name, age_days) in [("middle.txt", 10), ("newest.txt", 1), ("oldest.txt", 100)] {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, "content").unwrap();
+            let modified =
+                epoch + std::time::Duration::from_secs(1_700_000_000 - age_days * 86_400);
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+
+        let order = |message: &str| {
+            let position = |name| message.lines().position(|line| line.contains(name));
+            ["oldest.txt", "middle.txt", "newest.txt"].map(|name| position(name).unwrap())
+        };
+
+        let response = service
+            .list_directory_with_sizes(temp_dir.path(), &SortBy::Modified)
+            .await
+            .unwrap();
+        let [oldest, middle, newest] = order(&response.message);
+        assert!(newest < middle && middle < oldest);
+        assert!(
+            response
+                .message
+                .contains("(sorted by modified (descending))")
+        );
+
+        let response = service
+            .list_directory_with_sizes(temp_dir.path(), &SortBy::ModifiedAscending)
+            .await
+            .unwrap();
+        let [oldest, middle, newest] = order(&response.message);
+        assert!(oldest < middle && middle < newest);
+        assert!(
+            response
+                .message
+                .contains("(sorted by modified (ascending))")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_directory_with_sizes_sort_by_modified_breaks_ties_by_name() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        let shared = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let older = shared - std::time::Duration::from_secs(86_400);
+        for (name, modified) in [
+            ("delta.txt", shared),
+            ("bravo.txt", shared),
+            ("old.txt", older),
+            ("charlie.txt", shared),
+            ("alpha.txt", shared),
+        ] {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, "content").unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+
+        let listed = |message: &str| {
+            message
+                .lines()
+                .filter_map(|line| {
+                    ["alpha", "bravo", "charlie", "delta", "old"]
+                        .into_iter()
+                        .find(|name| line.contains(&format!("{name}.txt")))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let response = service
+            .list_directory_with_sizes(temp_dir.path(), &SortBy::Modified)
+            .await
+            .unwrap();
+        assert_eq!(
+            listed(&response.message),
+            ["alpha", "bravo", "charlie", "delta", "old"]
+        );
+
+        let response = service
+            .list_directory_with_sizes(temp_dir.path(), &SortBy::ModifiedAscending)
+            .await
+            .unwrap();
+        assert_eq!(
+            listed(&response.message),
+            ["old", "alpha", "bravo", "charlie", "delta"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_modified_falls_back_to_name_without_times() {
+        let entry = |name: &str, modified: Option<u64>| DirectoryEntry {
+            name: name.to_string(),
+            file_type: "[FILE]".to_string(),
+            size: 0,
+            is_directory: false,
+            modified: modified
+                .map(|secs| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs)),
+        };
+        let names =
+            |entries: &[DirectoryEntry]| entries.iter().map(|e| e.name.clone()).collect::<Vec<_>>();
+
+        for sort_by in [SortBy::Modified, SortBy::ModifiedAscending] {
+            let mut entries = vec![
+                entry("charlie", None),
+                entry("alpha", None),
+                entry("bravo", None),
+            ];
+            FileService::sort_directory_entries(&mut entries, &sort_by);
+            assert_eq!(names(&entries), ["alpha", "bravo", "charlie"]);
+
+            // Entries without a time stay after timed ones in both directions
+            let mut entries = vec![
+                entry("zulu", None),
+                entry("old", Some(10)),
+                entry("new", Some(20)),
+            ];
+            FileService::sort_directory_entries(&mut entries, &sort_by);
+            assert_eq!(entries[2].name, "zulu");
+        }
+    }
+
     #[tokio::test]
     async fn test_list_directory_with_sizes_human_readable_sizes() {
         let service = FileService::new();
@@ -1815,6 +3579,248 @@ mod tests {
         assert_eq!(content, "test content");
     }
 
+    /// Build `a/b/c` (empty chain), `keep/file.txt` with an empty `keep/empty`, and `solo`
+    async fn create_prune_tree() -> TempDir {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("a/b/c")).await.unwrap();
+        fs::create_dir_all(root.join("keep/empty")).await.unwrap();
+        fs::write(root.join("keep/file.txt"), "data").await.unwrap();
+        fs::create_dir(root.join("solo")).await.unwrap();
+        temp_dir
+    }
+
+    fn pruned_paths(response: &WriteFileResponse, root: &Path) -> Vec<String> {
+        let paths: Vec<String> = serde_json::from_str(&response.message).unwrap();
+        paths
+            .iter()
+            .map(|p| {
+                Path::new(p)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_prune_empty_directories_removes_nested_empty_dirs() {
+        let service = FileService::new();
+        let temp_dir = create_prune_tree().await;
+        let root = temp_dir.path();
+
+        let response = service.prune_empty_directories(root, false).await.unwrap();
+
+        // Deepest directories come first
+        assert_eq!(
+            pruned_paths(&response, root),
+            vec!["a/b/c", "a/b", "a", "keep/empty", "solo"]
+        );
+        assert!(!root.join("a").exists());
+        assert!(!root.join("solo").exists());
+        assert!(!root.join("keep/empty").exists());
+        assert!(root.join("keep/file.txt").is_file());
+        assert!(root.exists());
+    }
+
+    #[tokio::test]
+    async fn test_prune_empty_directories_dry_run_keeps_tree() {
+        let service = FileService::new();
+        let temp_dir = create_prune_tree().await;
+        let root = temp_dir.path();
+
+        let response = service.prune_empty_directories(root, true).await.unwrap();
+
+        assert_eq!(
+            pruned_paths(&response, root),
+            vec!["a/b/c", "a/b", "a", "keep/empty", "solo"]
+        );
+        assert!(root.join("a/b/c").is_dir());
+        assert!(root.join("keep/empty").is_dir());
+        assert!(root.join("solo").is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_prune_empty_directories_keeps_root_and_rejects_files() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        let response = service
+            .prune_empty_directories(temp_dir.path(), false)
+            .await
+            .unwrap();
+        assert_eq!(response.message, "[]");
+        assert!(temp_dir.path().is_dir());
+
+        let file = temp_dir.path().join("file.txt");
+        fs::write(&file, "data").await.unwrap();
+        assert!(matches!(
+            service.prune_empty_directories(&file, false).await,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+    }
+
+    /// Names of the files renamed by a `rename_bulk` call, relative to `root`
+    fn renamed_names(response: &BulkRenameResponse, root: &Path) -> Vec<(String, String)> {
+        let relative = |path: &str| {
+            Path::new(path)
+                .strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+        response
+            .renamed
+            .iter()
+            .map(|renamed| (relative(&renamed.from), relative(&renamed.to)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rename_bulk_substitutes_capture_groups() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        for name in ["IMG_001.jpeg", "IMG_002.jpeg", "notes.txt"] {
+            fs::write(root.join(name), name).await.unwrap();
+        }
+        fs::create_dir(root.join("nested")).await.unwrap();
+        fs::write(root.join("nested/IMG_003.jpeg"), "nested")
+            .await
+            .unwrap();
+
+        let pattern = Regex::new(r"^IMG_(\d+)\.(?<ext>jpeg)$").unwrap();
+        let response = service
+            .rename_bulk(root, &pattern, "photo-$1.${ext}", false, false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            renamed_names(&response, root),
+            vec![
+                ("IMG_001.jpeg".to_string(), "photo-001.jpeg".to_string()),
+                ("IMG_002.jpeg".to_string(), "photo-002.jpeg".to_string()),
+            ]
+        );
+        assert!(response.errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(root.join("photo-001.jpeg"))
+                .await
+                .unwrap(),
+            "IMG_001.jpeg"
+        );
+        assert!(!root.join("IMG_001.jpeg").exists());
+        assert!(root.join("notes.txt").exists());
+        // Subdirectories are left alone unless recursive is set
+        assert!(root.join("nested/IMG_003.jpeg").exists());
+
+        let response = service
+            .rename_bulk(root, &pattern, "photo-$1.${ext}", true, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            renamed_names(&response, root),
+            vec![(
+                "nested/IMG_003.jpeg".to_string(),
+                "nested/photo-003.jpeg".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rename_bulk_reports_collisions_and_invalid_names() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        for name in ["a.log", "a.txt", "b.log", "draft-1.md", "draft-2.md"] {
+            fs::write(root.join(name), name).await.unwrap();
+        }
+
+        // a.txt already exists, so only b.log is renamed
+        let pattern = Regex::new(r"\.log$").unwrap();
+        let response = service
+            .rename_bulk(root, &pattern, ".txt", false, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            renamed_names(&response, root),
+            vec![("b.log".to_string(), "b.txt".to_string())]
+        );
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].contains("a.txt already exists"));
+        assert_eq!(
+            fs::read_to_string(root.join("a.txt")).await.unwrap(),
+            "a.txt"
+        );
+        assert!(root.join("a.log").exists());
+
+        // Both drafts map to the same name; the second one is rejected
+        let pattern = Regex::new(r"^draft-\d").unwrap();
+        let response = service
+            .rename_bulk(root, &pattern, "final", false, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            renamed_names(&response, root),
+            vec![("draft-1.md".to_string(), "final.md".to_string())]
+        );
+        assert_eq!(response.errors.len(), 1);
+        assert!(root.join("draft-2.md").exists());
+
+        let pattern = Regex::new(r"^a\.log$").unwrap();
+        for replacement in ["", "sub/a.log", ".."] {
+            let response = service
+                .rename_bulk(root, &pattern, replacement, false, false)
+                .await
+                .unwrap();
+            assert!(response.renamed.is_empty());
+            assert!(response.errors[0].contains("is not a valid file name"));
+        }
+
+        assert!(matches!(
+            service
+                .rename_bulk(&root.join("a.log"), &pattern, "b", false, false)
+                .await,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rename_bulk_dry_run_matches_real_run() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+        for name in ["v0", "v1", "v2", "v3"] {
+            fs::write(root.join(name), name).await.unwrap();
+        }
+        let pattern = Regex::new(r"^v(\d)$").unwrap();
+
+        let dry_run = service
+            .rename_bulk(root, &pattern, "w$1", false, true)
+            .await
+            .unwrap();
+        assert_eq!(dry_run.renamed.len(), 4);
+        assert!(root.join("v0").exists());
+        assert!(!root.join("w0").exists());
+
+        let real = service
+            .rename_bulk(root, &pattern, "w$1", false, false)
+            .await
+            .unwrap();
+        assert_eq!(renamed_names(&dry_run, root), renamed_names(&real, root));
+        assert!(root.join("w3").exists());
+
+        // Renaming onto a file that is not itself renamed is a collision
+        let pattern = Regex::new(r"^w0$").unwrap();
+        let dry_run = service
+            .rename_bulk(root, &pattern, "w1", false, true)
+            .await
+            .unwrap();
+        assert!(dry_run.renamed.is_empty());
+        assert_eq!(dry_run.errors.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_write_file_with_nested_directories() {
         let service = FileService::new();
@@ -2093,41 +4099,93 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_apply_file_edits_whitespace_flexible() {
+    async fn test_apply_file_edits_ambiguous_match() {
         use crate::models::requests::EditOperation;
 
         let service = FileService::new();
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
-        let file_path = temp_dir.path().join("test_whitespace.txt");
+        let file_path = temp_dir.path().join("test_edit.txt");
 
-        let original_content = "    function test() {\n        return true;\n    }";
+        let original_content = "let x = 1;\nfoo();\nlet x = 1;\nbar();\nlet x = 1;";
         fs::write(&file_path, original_content).await.unwrap();
 
         let edits = vec![EditOperation::new(
-            "function test() {\n    return true;\n}".to_string(),
-            "function test() {\n    return false;\n}".to_string(),
+            "let x = 1;".to_string(),
+            "let x = 2;".to_string(),
         )];
 
         let result = service.apply_file_edits(&file_path, &edits, &false).await;
-        assert!(result.is_ok());
+        if let Err(FileSystemMcpError::ValidationError { message, data, .. }) = result {
+            assert!(message.contains("Ambiguous edit"));
+            assert_eq!(data["lines"], serde_json::json!([1, 3, 5]));
+        } else {
+            panic!("Expected ValidationError");
+        }
 
         let final_content = fs::read_to_string(&file_path).await.unwrap();
-        assert_eq!(
-            final_content,
-            "    function test() {\n        return false;\n    }"
-        );
+        assert_eq!(final_content, original_content);
     }
 
     #[tokio::test]
-    async fn test_apply_file_edits_preserve_indentation() {
+    async fn test_apply_file_edits_allow_multiple_matches() {
         use crate::models::requests::EditOperation;
 
         let service = FileService::new();
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
-        let file_path = temp_dir.path().join("test_indent.txt");
+        let file_path = temp_dir.path().join("test_edit.txt");
 
-        let original_content =
-            "class Test {\n    method1() {\n        console.log('test');\n    }\n}";
+        fs::write(&file_path, "let x = 1;\nfoo();\nlet x = 1;")
+            .await
+            .unwrap();
+
+        let edits = vec![
+            EditOperation::new("let x = 1;".to_string(), "let x = 2;".to_string())
+                .with_allow_multiple_matches(true),
+        ];
+
+        let result = service.apply_file_edits(&file_path, &edits, &false).await;
+        assert!(result.is_ok());
+
+        let final_content = fs::read_to_string(&file_path).await.unwrap();
+        assert_eq!(final_content, "let x = 2;\nfoo();\nlet x = 2;");
+    }
+
+    #[tokio::test]
+    async fn test_apply_file_edits_whitespace_flexible() {
+        use crate::models::requests::EditOperation;
+
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("test_whitespace.txt");
+
+        let original_content = "    function test() {\n        return true;\n    }";
+        fs::write(&file_path, original_content).await.unwrap();
+
+        let edits = vec![EditOperation::new(
+            "function test() {\n    return true;\n}".to_string(),
+            "function test() {\n    return false;\n}".to_string(),
+        )];
+
+        let result = service.apply_file_edits(&file_path, &edits, &false).await;
+        assert!(result.is_ok());
+
+        let final_content = fs::read_to_string(&file_path).await.unwrap();
+        assert_eq!(
+            final_content,
+            "    function test() {\n        return false;\n    }"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_file_edits_preserve_indentation() {
+        use crate::models::requests::EditOperation;
+
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("test_indent.txt");
+
+        let original_content =
+            "class Test {\n    method1() {\n        console.log('test');\n    }\n}";
         fs::write(&file_path, original_content).await.unwrap();
 
         let edits = vec![EditOperation::new(
@@ -2188,6 +4246,7 @@ mod tests {
         let response = result.unwrap();
         assert!(response.message.contains("Dry run completed"));
         assert!(response.message.contains("1 edits would be applied"));
+        assert_eq!(response.preview.as_deref(), Some("Hi world"));
 
         // Verify original file unchanged
         let unchanged_content = fs::read_to_string(&file_path).await.unwrap();
@@ -2416,12 +4475,75 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_apply_file_edits_regex_capture_groups() {
+        use crate::models::requests::EditOperation;
+
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("versions.toml");
+        fs::write(&file_path, "serde = \"1.0.1\"\ntokio = \"1.2.3\"\n")
+            .await
+            .unwrap();
+
+        let edits = vec![EditOperation::regex(
+            r#"(?<name>\w+) = "(\d+)\.(\d+)\.\d+""#,
+            r#"${name} = "$2.$3""#,
+            "",
+        )];
+        service
+            .apply_file_edits(&file_path, &edits, &false)
+            .await
+            .unwrap();
+
+        // Without the g flag only the first match is replaced
+        assert_eq!(
+            fs::read_to_string(&file_path).await.unwrap(),
+            "serde = \"1.0\"\ntokio = \"1.2.3\"\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_file_edits_regex_global_and_flags() {
+        use crate::models::requests::EditOperation;
+
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("log.txt");
+        fs::write(&file_path, "ERROR one\nerror two\nok\n")
+            .await
+            .unwrap();
+
+        let edits = vec![
+            EditOperation::regex("^error", "WARN", "gim"),
+            EditOperation::regex("o", "0", "g"),
+        ];
+        service
+            .apply_file_edits(&file_path, &edits, &false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&file_path).await.unwrap(),
+            "WARN 0ne\nWARN tw0\n0k\n"
+        );
+
+        let edits = vec![EditOperation::regex("missing", "x", "g")];
+        let result = service.apply_file_edits(&file_path, &edits, &false).await;
+        assert!(matches!(
+            result,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_directory_tree_empty_directory() {
         let service = FileService::new();
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
 
-        let result = service.directory_tree(temp_dir.path(), &[]).await;
+        let result = service
+            .directory_tree(temp_dir.path(), &[], true, None)
+            .await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
@@ -2456,7 +4578,9 @@ mod tests {
             .await
             .unwrap();
 
-        let result = service.directory_tree(temp_dir.path(), &[]).await;
+        let result = service
+            .directory_tree(temp_dir.path(), &[], true, None)
+            .await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
@@ -2517,7 +4641,7 @@ mod tests {
         // Test excluding by extension
         let exclude_patterns = vec!["*.log".to_string(), "target".to_string()];
         let result = service
-            .directory_tree(temp_dir.path(), &exclude_patterns)
+            .directory_tree(temp_dir.path(), &exclude_patterns, true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2555,7 +4679,7 @@ mod tests {
         // Test excluding all .txt files
         let exclude_patterns = vec!["*.txt".to_string()];
         let result = service
-            .directory_tree(temp_dir.path(), &exclude_patterns)
+            .directory_tree(temp_dir.path(), &exclude_patterns, true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2596,7 +4720,7 @@ mod tests {
         // Test excluding specific nested files - use more specific patterns
         let exclude_patterns = vec!["lib.rs".to_string(), "src/components/*".to_string()];
         let result = service
-            .directory_tree(temp_dir.path(), &exclude_patterns)
+            .directory_tree(temp_dir.path(), &exclude_patterns, true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2630,7 +4754,9 @@ mod tests {
         let service = FileService::new();
         let nonexistent_path = Path::new("/nonexistent/path/that/does/not/exist");
 
-        let result = service.directory_tree(nonexistent_path, &[]).await;
+        let result = service
+            .directory_tree(nonexistent_path, &[], true, None)
+            .await;
         assert!(result.is_err());
 
         if let Err(FileSystemMcpError::IoError { message, path }) = result {
@@ -2654,7 +4780,9 @@ mod tests {
             .await
             .unwrap();
 
-        let result = service.directory_tree(temp_dir.path(), &[]).await;
+        let result = service
+            .directory_tree(temp_dir.path(), &[], true, None)
+            .await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
@@ -2675,6 +4803,42 @@ mod tests {
         assert!(response.message.contains("[DIR]"));
     }
 
+    #[tokio::test]
+    async fn test_directory_tree_max_depth() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+        let deepest = (1..=15).fold(temp_dir.path().to_path_buf(), |path, level| {
+            path.join(format!("level{}", level))
+        });
+        fs::create_dir_all(&deepest).await.unwrap();
+
+        let response = service
+            .directory_tree(temp_dir.path(), &[], true, Some(5))
+            .await
+            .unwrap();
+        let tree: Vec<TreeEntry> = serde_json::from_str(&response.message).unwrap();
+
+        let mut level = &tree;
+        for depth in 1..=5 {
+            assert_eq!(level.len(), 1);
+            assert_eq!(level[0].name, format!("level{}", depth));
+            assert_eq!(level[0].entry_type, "[DIR]");
+            level = level[0].children.as_ref().unwrap();
+        }
+        assert_eq!(level.len(), 1);
+        assert_eq!(level[0].name, "...");
+        assert_eq!(level[0].entry_type, "[TRUNCATED]");
+        assert!(level[0].children.is_none());
+
+        // A depth that covers the whole tree leaves nothing truncated
+        let response = service
+            .directory_tree(temp_dir.path(), &[], true, Some(15))
+            .await
+            .unwrap();
+        assert!(!response.message.contains("[TRUNCATED]"));
+    }
+
     #[tokio::test]
     async fn test_directory_tree_deep_nesting() {
         let service = FileService::new();
@@ -2687,7 +4851,9 @@ mod tests {
             .await
             .unwrap();
 
-        let result = service.directory_tree(temp_dir.path(), &[]).await;
+        let result = service
+            .directory_tree(temp_dir.path(), &[], true, None)
+            .await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
@@ -2717,6 +4883,95 @@ mod tests {
         assert!(deep_file.children.is_none());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_directory_tree_follow_symlinks() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let target = temp_dir.path().join("target");
+        fs::create_dir(&target).await.unwrap();
+        fs::write(target.join("inner.txt"), "inner").await.unwrap();
+        fs::symlink(&target, temp_dir.path().join("link"))
+            .await
+            .unwrap();
+        // A link back to the root must not be walked forever
+        fs::symlink(temp_dir.path(), target.join("loop"))
+            .await
+            .unwrap();
+
+        let find = |tree: &[TreeEntry], name: &str| -> (String, Option<usize>) {
+            let entry = tree.iter().find(|e| e.name == name).unwrap();
+            (
+                entry.entry_type.clone(),
+                entry.children.as_ref().map(Vec::len),
+            )
+        };
+
+        let response = service
+            .directory_tree(temp_dir.path(), &[], false, None)
+            .await
+            .unwrap();
+        let tree: Vec<TreeEntry> = serde_json::from_str(&response.message).unwrap();
+        assert_eq!(find(&tree, "link"), ("[SYMLINK]".to_string(), None));
+        assert_eq!(find(&tree, "target"), ("[DIR]".to_string(), Some(2)));
+
+        let response = service
+            .directory_tree(temp_dir.path(), &[], true, None)
+            .await
+            .unwrap();
+        let tree: Vec<TreeEntry> = serde_json::from_str(&response.message).unwrap();
+        assert_eq!(find(&tree, "link"), ("[DIR]".to_string(), Some(2)));
+        let target_children = tree
+            .iter()
+            .find(|e| e.name == "target")
+            .and_then(|e| e.children.as_ref())
+            .unwrap();
+        assert_eq!(find(target_children, "loop"), ("[DIR]".to_string(), None));
+    }
+
+    #[tokio::test]
+    async fn test_file_tree_markdown_renders_box_drawing_tree() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("src/bin")).await.unwrap();
+        fs::create_dir(root.join("target")).await.unwrap();
+        fs::write(root.join("Cargo.toml"), "").await.unwrap();
+        fs::write(root.join("README.md"), "").await.unwrap();
+        fs::write(root.join("src/main.rs"), "").await.unwrap();
+        fs::write(root.join("src/lib.rs"), "").await.unwrap();
+        fs::write(root.join("src/bin/tool.rs"), "").await.unwrap();
+        fs::write(root.join("target/debug.log"), "").await.unwrap();
+
+        let response = service
+            .file_tree_markdown(root, &["target".to_string()], None)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.message,
+            "├── src/\n\
+             │   ├── bin/\n\
+             │   │   └── tool.rs\n\
+             │   ├── lib.rs\n\
+             │   └── main.rs\n\
+             ├── Cargo.toml\n\
+             └── README.md"
+        );
+
+        let response = service
+            .file_tree_markdown(root, &["target".to_string()], Some(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.message,
+            "├── src/\n\
+             │   └── ...\n\
+             ├── Cargo.toml\n\
+             └── README.md"
+        );
+    }
+
     #[tokio::test]
     async fn test_search_files_basic_pattern() {
         let service = FileService::new();
@@ -2734,7 +4989,7 @@ mod tests {
             .unwrap();
 
         let result = service
-            .search_files(temp_dir.path(), "*.txt", &[], &[])
+            .search_files(temp_dir.path(), "*.txt", &[], &[], true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2767,7 +5022,7 @@ mod tests {
             .unwrap();
 
         let result = service
-            .search_files(temp_dir.path(), "*.rs", &[], &[])
+            .search_files(temp_dir.path(), "*.rs", &[], &[], true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2798,7 +5053,7 @@ mod tests {
 
         let exclude_patterns = vec!["**/lib.rs".to_string()];
         let result = service
-            .search_files(temp_dir.path(), "*.rs", &[], &exclude_patterns)
+            .search_files(temp_dir.path(), "*.rs", &[], &exclude_patterns, true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2831,7 +5086,7 @@ mod tests {
             .unwrap();
 
         let result = service
-            .search_files(temp_dir.path(), "**/main.rs", &[], &[])
+            .search_files(temp_dir.path(), "**/main.rs", &[], &[], true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2856,7 +5111,7 @@ mod tests {
             .unwrap();
 
         let result = service
-            .search_files(temp_dir.path(), "*.rs", &[], &[])
+            .search_files(temp_dir.path(), "*.rs", &[], &[], true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2872,7 +5127,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
 
         let result = service
-            .search_files(temp_dir.path(), "[invalid", &[], &[])
+            .search_files(temp_dir.path(), "[invalid", &[], &[], true, None)
             .await;
         assert!(result.is_err());
 
@@ -2893,7 +5148,7 @@ mod tests {
         let nonexistent_path = Path::new("/nonexistent/path");
 
         let result = service
-            .search_files(nonexistent_path, "*.txt", &[], &[])
+            .search_files(nonexistent_path, "*.txt", &[], &[], true, None)
             .await;
         assert!(result.is_err());
 
@@ -2933,7 +5188,7 @@ mod tests {
 
         let exclude_patterns = vec!["target/**".to_string(), "**/components/*".to_string()];
         let result = service
-            .search_files(temp_dir.path(), "**/*", &[], &exclude_patterns)
+            .search_files(temp_dir.path(), "**/*", &[], &exclude_patterns, true, None)
             .await;
         assert!(result.is_ok());
 
@@ -2960,7 +5215,9 @@ mod tests {
             .unwrap();
 
         // Search for directories
-        let result = service.search_files(temp_dir.path(), "src", &[], &[]).await;
+        let result = service
+            .search_files(temp_dir.path(), "src", &[], &[], true, None)
+            .await;
         assert!(result.is_ok());
 
         let response = result.unwrap();
@@ -2970,6 +5227,730 @@ mod tests {
         assert!(results[0].ends_with("src"));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_search_files_follow_symlinks() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let target = temp_dir.path().join("target");
+        fs::create_dir(&target).await.unwrap();
+        fs::write(target.join("notes.txt"), "notes").await.unwrap();
+        fs::symlink(&target, temp_dir.path().join("linked"))
+            .await
+            .unwrap();
+
+        let search = |follow_symlinks| {
+            let service = &service;
+            let root = temp_dir.path();
+            async move {
+                let response = service
+                    .search_files(root, "**/*", &[], &[], follow_symlinks, None)
+                    .await
+                    .unwrap();
+                let mut results: Vec<String> = serde_json::from_str(&response.message).unwrap();
+                results.sort();
+                results
+                    .into_iter()
+                    .map(|p| {
+                        Path::new(&p)
+                            .strip_prefix(root)
+                            .unwrap()
+                            .display()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // The link itself matches by name, but its contents are only searched when followed
+        assert_eq!(
+            search(false).await,
+            vec!["linked", "target", "target/notes.txt"]
+        );
+        assert_eq!(
+            search(true).await,
+            vec!["linked", "linked/notes.txt", "target", "target/notes.txt"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_files_content_contains() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        fs::create_dir(temp_dir.path().join("src")).await.unwrap();
+        fs::write(
+            temp_dir.path().join("src/main.rs"),
+            "fn main() {\r\n    // TODO: parse args\r\n}\r\n// TODO: tests\r\n",
+        )
+        .await
+        .unwrap();
+        fs::write(temp_dir.path().join("src/lib.rs"), "pub fn done() {}\n")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "TODO: not a .rs file\n")
+            .await
+            .unwrap();
+
+        let response = service
+            .search_files(temp_dir.path(), "**/*.rs", &[], &[], true, Some("TODO"))
+            .await
+            .unwrap();
+        let matches: Vec<ContentSearchMatch> = serde_json::from_str(&response.message).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].path.ends_with("main.rs"));
+        assert_eq!(
+            matches[0].matching_lines,
+            vec![
+                MatchingLine {
+                    line_number: 2,
+                    line: "    // TODO: parse args".to_string(),
+                },
+                MatchingLine {
+                    line_number: 4,
+                    line: "// TODO: tests".to_string(),
+                },
+            ]
+        );
+
+        // Directories matched by the pattern are never content matches
+        let response = service
+            .search_files(temp_dir.path(), "src", &[], &[], true, Some("TODO"))
+            .await
+            .unwrap();
+        let matches: Vec<ContentSearchMatch> = serde_json::from_str(&response.message).unwrap();
+        assert!(matches.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_files_by_date_range() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let day = |n: u64| std::time::UNIX_EPOCH + std::time::Duration::from_secs(n * 86_400);
+
+        fs::create_dir_all(temp_dir.path().join("nested/skip"))
+            .await
+            .unwrap();
+        for (name, modified_day) in [
+            ("old.txt", 100),
+            ("nested/inside.txt", 200),
+            ("nested/skip/excluded.txt", 200),
+            ("recent.txt", 250),
+            ("future.txt", 400),
+        ] {
+            let path = temp_dir.path().join(name);
+            std::fs::write(&path, name).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(day(modified_day))
+                .unwrap();
+        }
+
+        let find = |after, before, exclude: Vec<String>| {
+            let service = &service;
+            let root = temp_dir.path();
+            async move {
+                let response = service
+                    .find_files_by_date_range(root, after, before, &exclude)
+                    .await
+                    .unwrap();
+                let files: Vec<DatedFile> = serde_json::from_str(&response.message).unwrap();
+                files
+                    .into_iter()
+                    .map(|f| {
+                        Path::new(&f.path)
+                            .strip_prefix(root)
+                            .unwrap()
+                            .display()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(
+            find(
+                Some(day(150)),
+                Some(day(300)),
+                vec!["nested/skip/**".to_string()]
+            )
+            .await,
+            ["recent.txt", "nested/inside.txt"]
+        );
+        // Bounds are inclusive and each side is optional
+        assert_eq!(
+            find(Some(day(250)), None, vec![]).await,
+            ["future.txt", "recent.txt"]
+        );
+        assert_eq!(find(None, Some(day(100)), vec![]).await, ["old.txt"]);
+
+        let response = service
+            .find_files_by_date_range(temp_dir.path(), Some(day(250)), Some(day(250)), &[])
+            .await
+            .unwrap();
+        let files: Vec<DatedFile> = serde_json::from_str(&response.message).unwrap();
+        assert_eq!(files[0].modified, "1970-09-08T00:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn test_export_directory_manifest_with_hashes() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        fs::create_dir_all(temp_dir.path().join("src"))
+            .await
+            .unwrap();
+        fs::create_dir_all(temp_dir.path().join("target"))
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("src/main.rs"), "hello")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("empty.txt"), "")
+            .await
+            .unwrap();
+        fs::write(temp_dir.path().join("target/out.bin"), "build")
+            .await
+            .unwrap();
+
+        let response = service
+            .export_directory_manifest(temp_dir.path(), true, &["target/**".to_string()])
+            .await
+            .unwrap();
+        let entries: Vec<ManifestEntry> = serde_json::from_str(&response.message).unwrap();
+
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["empty.txt", "src/main.rs"]);
+        assert_eq!(entries[0].size, 0);
+        assert_eq!(
+            entries[0].sha256.as_deref(),
+            Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+        assert_eq!(entries[1].size, 5);
+        assert_eq!(
+            entries[1].sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        assert!(entries[1].modified > 0);
+
+        let response = service
+            .export_directory_manifest(temp_dir.path(), false, &[])
+            .await
+            .unwrap();
+        let entries: Vec<ManifestEntry> = serde_json::from_str(&response.message).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|e| e.sha256.is_none()));
+        assert!(!response.message.contains("sha256"));
+    }
+
+    #[tokio::test]
+    async fn test_compare_directories() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let dir_a = temp_dir.path().join("a");
+        let dir_b = temp_dir.path().join("b");
+        for dir in [&dir_a, &dir_b] {
+            fs::create_dir_all(dir.join("src")).await.unwrap();
+            fs::write(dir.join("README.md"), "same").await.unwrap();
+            fs::write(dir.join("src/main.rs"), "fn main() {}")
+                .await
+                .unwrap();
+        }
+        fs::write(dir_a.join("Cargo.toml"), "version = 1")
+            .await
+            .unwrap();
+        fs::write(dir_b.join("Cargo.toml"), "version = 22")
+            .await
+            .unwrap();
+        // Same size, different bytes: only told apart by content
+        fs::write(dir_a.join("src/lib.rs"), "aaaa").await.unwrap();
+        fs::write(dir_b.join("src/lib.rs"), "bbbb").await.unwrap();
+        fs::write(dir_a.join("old.txt"), "old").await.unwrap();
+        fs::write(dir_b.join("src/new.rs"), "new").await.unwrap();
+
+        let service = FileService::new();
+        let response = service
+            .compare_directories(&dir_a, &dir_b, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            response,
+            CompareDirectoriesResponse {
+                only_in_a: vec!["old.txt".to_string()],
+                only_in_b: vec!["src/new.rs".to_string()],
+                different: vec!["Cargo.toml".to_string(), "src/lib.rs".to_string()],
+                identical: vec!["README.md".to_string(), "src/main.rs".to_string()],
+            }
+        );
+
+        let response = service
+            .compare_directories(&dir_a, &dir_b, false)
+            .await
+            .unwrap();
+        assert_eq!(response.different, vec!["Cargo.toml"]);
+        assert_eq!(
+            response.identical,
+            vec!["README.md", "src/lib.rs", "src/main.rs"]
+        );
+    }
+
+    async fn read_csv(
+        contents: &str,
+        has_header: bool,
+        delimiter: u8,
+        max_rows: usize,
+    ) -> FileSystemMcpResult<serde_json::Value> {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("data.csv");
+        fs::write(&file_path, contents).await.unwrap();
+
+        let response = FileService::new()
+            .read_csv_file(&file_path, has_header, delimiter, max_rows)
+            .await?;
+        Ok(serde_json::from_str(&response.message).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_read_env_file_returns_variables_and_warnings() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join(".env");
+        fs::write(
+            &file_path,
+            "# App\nexport NAME=\"My App\"\nPORT=8080\nBROKEN\n",
+        )
+        .await
+        .unwrap();
+
+        let service = FileService::new();
+        let response = service.read_env_file(&file_path).await.unwrap();
+        let json: serde_json::Value = serde_json::from_str(&response.message).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "variables": {"NAME": "My App", "PORT": "8080"},
+                "warnings": ["Line 4: skipped 'BROKEN' because it has no '='"]
+            })
+        );
+
+        let missing = service
+            .read_env_file(&temp_dir.path().join("missing.env"))
+            .await;
+        assert!(matches!(
+            missing,
+            Err(FileSystemMcpError::PermissionDenied { .. })
+        ));
+
+        let limited = FileService::new().with_max_read_bytes(Some(10));
+        assert!(matches!(
+            limited.read_env_file(&file_path).await,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_file_as_jsonl_parses_each_line() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("events.jsonl");
+        fs::write(&file_path, "{\"id\": 1}\n\n[1, 2]\r\n\"text\"\n{\"id\": 4}")
+            .await
+            .unwrap();
+
+        let service = FileService::new();
+        let response = service
+            .read_file_as_jsonl(&file_path, None, None)
+            .await
+            .unwrap();
+        let json: serde_json::Value = jsonl_values(&response);
+        assert_eq!(
+            json,
+            serde_json::json!([{"id": 1}, [1, 2], "text", {"id": 4}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_file_as_jsonl_keeps_invalid_lines() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("mixed.jsonl");
+        fs::write(&file_path, "{\"ok\": true}\n{\"ok\": \n{\"ok\": false}\n")
+            .await
+            .unwrap();
+
+        let service = FileService::new();
+        let response = service
+            .read_file_as_jsonl(&file_path, None, None)
+            .await
+            .unwrap();
+        let json: serde_json::Value = jsonl_values(&response);
+        let values = json.as_array().unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0], serde_json::json!({"ok": true}));
+        assert_eq!(values[1]["_raw"], "{\"ok\": ");
+        assert!(!values[1]["_parse_error"].as_str().unwrap().is_empty());
+        assert_eq!(values[2], serde_json::json!({"ok": false}));
+
+        let missing = service
+            .read_file_as_jsonl(&temp_dir.path().join("missing.jsonl"), None, None)
+            .await;
+        assert!(matches!(missing, Err(FileSystemMcpError::IoError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_read_file_as_jsonl_line_range() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("numbers.jsonl");
+        let contents: String = (1..=10).map(|n| format!("{}\n", n)).collect();
+        fs::write(&file_path, contents).await.unwrap();
+
+        let service = FileService::new();
+        let read = |start_line, end_line| {
+            let service = &service;
+            let file_path = &file_path;
+            async move {
+                let response = service
+                    .read_file_as_jsonl(file_path, start_line, end_line)
+                    .await
+                    .unwrap();
+                jsonl_values(&response)
+            }
+        };
+
+        assert_eq!(read(Some(3), Some(5)).await, serde_json::json!([3, 4, 5]));
+        assert_eq!(read(Some(9), None).await, serde_json::json!([9, 10]));
+        assert_eq!(read(None, Some(2)).await, serde_json::json!([1, 2]));
+        assert_eq!(read(Some(20), None).await, serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_read_file_as_jsonl_keeps_invalid_utf8_lines() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("binary.jsonl");
+        fs::write(&file_path, b"{\"id\": 1}\n\"\xff\xfe\"\n{\"id\": 3}\n")
+            .await
+            .unwrap();
+
+        let service = FileService::new();
+        let response = service
+            .read_file_as_jsonl(&file_path, None, None)
+            .await
+            .unwrap();
+        let json = jsonl_values(&response);
+        assert_eq!(json[0], serde_json::json!({"id": 1}));
+        assert_eq!(json[1]["_raw"], "\"��\"");
+        assert!(json[1]["_parse_error"].is_string());
+        assert_eq!(json[2], serde_json::json!({"id": 3}));
+    }
+
+    #[tokio::test]
+    async fn test_read_file_as_jsonl_stops_at_read_limit() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("big.jsonl");
+        // Ten lines of five bytes each
+        let contents: String = (10..20).map(|n| format!("[{}]\n", n)).collect();
+        fs::write(&file_path, &contents).await.unwrap();
+
+        // Two whole lines fit in 12 bytes; the third is cut off and dropped
+        let limited = FileService::new().with_max_read_bytes(Some(12));
+        let response = limited
+            .read_file_as_jsonl(&file_path, None, None)
+            .await
+            .unwrap();
+        assert!(response.truncated);
+        assert_eq!(jsonl_values(&response), serde_json::json!([[10], [11]]));
+
+        // Skipped lines don't count towards the limit
+        let response = limited
+            .read_file_as_jsonl(&file_path, Some(9), None)
+            .await
+            .unwrap();
+        assert!(!response.truncated);
+        assert_eq!(jsonl_values(&response), serde_json::json!([[18], [19]]));
+
+        let exact = FileService::new().with_max_read_bytes(Some(contents.len()));
+        let response = exact
+            .read_file_as_jsonl(&file_path, None, None)
+            .await
+            .unwrap();
+        assert!(!response.truncated);
+        assert_eq!(jsonl_values(&response).as_array().unwrap().len(), 10);
+    }
+
+    fn jsonl_values(response: &ReadFileResponse) -> serde_json::Value {
+        match &response.content {
+            crate::models::responses::FileContent::Text(content) => {
+                serde_json::from_str(content).unwrap()
+            }
+            other => panic!("expected text content, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_split_file_and_join_files_round_trip() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path().canonicalize().unwrap();
+        let file_path = root.join("data.log");
+        let original = "alpha\r\nbeta\ngamma\ndelta\nepsilon\nzeta";
+        fs::write(&file_path, original).await.unwrap();
+        let service = FileService::new();
+
+        let by_bytes = service
+            .split_file(&file_path, &root.join("bytes"), SplitChunk::Bytes(15))
+            .await
+            .unwrap();
+        let sizes: Vec<u64> = by_bytes.chunks.iter().map(|chunk| chunk.size).collect();
+        assert_eq!(sizes, vec![15, 15, 6]);
+        assert_eq!(by_bytes.total_bytes, original.len() as u64);
+        assert_eq!(
+            by_bytes.chunks[0].path,
+            root.join("bytes.001").display().to_string()
+        );
+        assert_eq!(
+            by_bytes.chunks[2].path,
+            root.join("bytes.003").display().to_string()
+        );
+
+        let by_lines = service
+            .split_file(&file_path, &root.join("lines"), SplitChunk::Lines(2))
+            .await
+            .unwrap();
+        assert_eq!(by_lines.chunks.len(), 3);
+        assert_eq!(
+            fs::read_to_string(root.join("lines.001")).await.unwrap(),
+            "alpha\r\nbeta\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("lines.003")).await.unwrap(),
+            "epsilon\nzeta"
+        );
+
+        for (split, output) in [
+            (by_bytes, "joined-bytes.log"),
+            (by_lines, "joined-lines.log"),
+        ] {
+            let inputs: Vec<PathBuf> = split
+                .chunks
+                .iter()
+                .map(|chunk| chunk.path.clone().into())
+                .collect();
+            let output = root.join(output);
+            let response = service.join_files(&inputs, &output).await.unwrap();
+            assert!(response.created);
+            assert_eq!(response.size, Some(original.len() as u64));
+            assert_eq!(fs::read(&output).await.unwrap(), original.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_split_file_rejects_unsafe_targets() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let root = temp_dir.path().canonicalize().unwrap();
+        let file_path = root.join("data.001");
+        fs::write(&file_path, "0123456789").await.unwrap();
+        let service = FileService::new();
+
+        // The first chunk would overwrite the input
+        let result = service
+            .split_file(&file_path, &root.join("data"), SplitChunk::Bytes(4))
+            .await;
+        assert!(matches!(
+            result,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+
+        // A directory prefix would place chunks beside the directory
+        let result = service
+            .split_file(&file_path, &root, SplitChunk::Bytes(4))
+            .await;
+        assert!(matches!(
+            result,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+
+        let big = root.join("big.bin");
+        let file = std::fs::File::create(&big).unwrap();
+        file.set_len(MAX_SPLIT_CHUNKS + 1).unwrap();
+        let result = service
+            .split_file(&big, &root.join("big"), SplitChunk::Bytes(1))
+            .await;
+        assert!(matches!(
+            result,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+
+        // Failed calls wrote nothing
+        assert_eq!(std::fs::read_dir(&root).unwrap().count(), 2);
+
+        let result = service
+            .join_files(std::slice::from_ref(&file_path), &file_path)
+            .await;
+        assert!(matches!(
+            result,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+        assert_eq!(fs::read(&file_path).await.unwrap(), b"0123456789");
+    }
+
+    #[tokio::test]
+    async fn test_parse_yaml_file_as_json_and_toml() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("config.yaml");
+        fs::write(&file_path, "name: demo\nserver:\n  port: 8080\n")
+            .await
+            .unwrap();
+
+        let service = FileService::new();
+        let json = service
+            .parse_yaml_file(&file_path, YamlOutputFormat::Json)
+            .await
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!({"name": "demo", "server": {"port": 8080}})
+        );
+
+        let toml_text = service
+            .parse_yaml_file(&file_path, YamlOutputFormat::Toml)
+            .await
+            .unwrap();
+        let parsed: toml::Value = toml::from_str(&toml_text).unwrap();
+        assert_eq!(parsed["server"]["port"].as_integer(), Some(8080));
+
+        fs::write(&file_path, "key: [unclosed\n").await.unwrap();
+        let invalid = service
+            .parse_yaml_file(&file_path, YamlOutputFormat::Json)
+            .await;
+        assert!(matches!(
+            invalid,
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parse_yaml_file_rejects_files_over_read_limit() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("config.yaml");
+        fs::write(
+            &file_path,
+            "name: demo
+port: 8080
+",
+        )
+        .await
+        .unwrap();
+
+        let limited = FileService::new().with_max_read_bytes(Some(10));
+        let result = limited
+            .parse_yaml_file(&file_path, YamlOutputFormat::Json)
+            .await;
+        assert!(matches!(
+            result,
+            Err(FileSystemMcpError::ValidationError { ref operation, .. })
+                if operation == "parse_yaml_file"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_csv_file_with_header() {
+        let rows = read_csv("name,age\nAda,36\nAlan,41\n", true, b',', 1000)
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            serde_json::json!([
+                {"name": "Ada", "age": "36"},
+                {"name": "Alan", "age": "41"}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_csv_file_without_header() {
+        let rows = read_csv("name,age\nAda,36\n", false, b',', 1000)
+            .await
+            .unwrap();
+        assert_eq!(rows, serde_json::json!([["name", "age"], ["Ada", "36"]]));
+    }
+
+    #[tokio::test]
+    async fn test_read_csv_file_semicolon_delimited() {
+        let rows = read_csv("city;country\nParis;France\nLyon;France\n", true, b';', 1)
+            .await
+            .unwrap();
+        assert_eq!(
+            rows,
+            serde_json::json!([{"city": "Paris", "country": "France"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_csv_file_quoted_fields() {
+        let rows = read_csv(
+            "id,address\n1,\"12 Main St, Springfield\"\n2,\"He said \"\"hi\"\"\"\n",
+            true,
+            b',',
+            1000,
+        )
+        .await
+        .unwrap();
+        assert_eq!(rows[0]["address"], "12 Main St, Springfield");
+        assert_eq!(rows[1]["address"], "He said \"hi\"");
+
+        // Rows with a different number of fields are rejected with their line number
+        let result = read_csv("a,b\n1,2\n3\n", true, b',', 1000).await;
+        match result {
+            Err(FileSystemMcpError::ValidationError { data, .. }) => assert_eq!(data["line"], 3),
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_file_detects_change() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("config.toml");
+        fs::write(&file_path, "version = 1").await.unwrap();
+
+        let writer_path = file_path.clone();
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            fs::write(&writer_path, "version = 2").await.unwrap();
+        });
+
+        let started = std::time::Instant::now();
+        let response = service
+            .watch_file(
+                &file_path,
+                Duration::from_millis(100),
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap();
+        writer.await.unwrap();
+
+        assert!(response.changed);
+        assert!(response.detected_at.is_some());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_watch_file_times_out_without_change() {
+        let service = FileService::new();
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let file_path = temp_dir.path().join("untouched.txt");
+        fs::write(&file_path, "static").await.unwrap();
+
+        let response = service
+            .watch_file(
+                &file_path,
+                Duration::from_millis(100),
+                Duration::from_millis(350),
+            )
+            .await
+            .unwrap();
+
+        assert!(!response.changed);
+        assert_eq!(response.detected_at, None);
+    }
+
     #[tokio::test]
     async fn test_get_file_info_file() {
         let service = FileService::new();
@@ -1,3 +1,3 @@
 pub mod file_service;
 
-pub use file_service::FileService;
+pub use file_service::{DEFAULT_MAX_READ_BYTES, FileService};
@@ -1,8 +1,9 @@
-use std::path::PathBuf;
+use std::{net::IpAddr, path::PathBuf};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
-use crate::config::Config;
+use crate::application::DEFAULT_MAX_READ_BYTES;
+use crate::config::{Config, Transport};
 use crate::errors::FileSystemMcpResult;
 use crate::utils::fs::{resolve_directories, validate_directories};
 
@@ -58,6 +59,37 @@ pub struct Cli {
         long_help = "Specify one or more directories where filesystem operations are allowed. \nAll operations are restricted to these directories and their subdirectories for security."
     )]
     pub directories: Vec<PathBuf>,
+
+    /// Transport to serve the MCP protocol over
+    #[arg(long, value_enum, default_value_t = TransportKind::Stdio)]
+    pub transport: TransportKind,
+
+    /// Address to bind when using the SSE transport
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: IpAddr,
+
+    /// Port to bind when using the SSE transport
+    #[arg(long, default_value_t = 8000)]
+    pub port: u16,
+
+    /// Bytes after which whole-file reads are truncated; 0 disables the limit
+    #[arg(long, default_value_t = DEFAULT_MAX_READ_BYTES)]
+    pub max_read_bytes: usize,
+
+    /// Disable every tool that modifies the filesystem
+    #[arg(long)]
+    pub read_only: bool,
+
+    /// Let the copy_to_clipboard and paste_from_clipboard tools access the system clipboard
+    #[arg(long)]
+    pub enable_clipboard: bool,
+}
+
+/// Transport selectable on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TransportKind {
+    Stdio,
+    Sse,
 }
 
 impl Cli {
@@ -67,10 +99,18 @@ impl Cli {
     /// solely on parsing and configuration creation.
     pub async fn parse_config() -> FileSystemMcpResult<Config> {
         let cli = Self::parse();
+        let transport = match cli.transport {
+            TransportKind::Stdio => Transport::Stdio,
+            TransportKind::Sse => Transport::Sse((cli.host, cli.port).into()),
+        };
         let allowed_directories = resolve_directories(cli.directories).await?;
         validate_directories(&allowed_directories).await?;
         Ok(Config {
             allowed_directories,
+            transport,
+            max_read_bytes: (cli.max_read_bytes > 0).then_some(cli.max_read_bytes),
+            read_only: cli.read_only,
+            enable_clipboard: cli.enable_clipboard,
         })
     }
 }
@@ -1,7 +1,23 @@
-use std::path::PathBuf;
+use std::{net::SocketAddr, path::PathBuf};
 
 /// Configuration derived from CLI arguments
 #[derive(Debug, Clone)]
 pub struct Config {
     pub allowed_directories: Vec<PathBuf>,
+    pub transport: Transport,
+    /// Largest file, in bytes, that whole-file reads accept; `None` means unlimited
+    pub max_read_bytes: Option<usize>,
+    /// Reject every tool that modifies the filesystem
+    pub read_only: bool,
+    /// Allow the clipboard tools to access the system clipboard
+    pub enable_clipboard: bool,
+}
+
+/// Transport the MCP server is served over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// JSON-RPC over stdin/stdout
+    Stdio,
+    /// Server-Sent Events over HTTP, listening on the given address
+    Sse(SocketAddr),
 }
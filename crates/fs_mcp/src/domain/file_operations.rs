@@ -1,11 +1,18 @@
 use async_trait::async_trait;
-use std::path::{Path, PathBuf};
+use regex::Regex;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 use crate::{
     errors::FileSystemMcpResult,
     models::{
-        requests::SortBy,
-        responses::{ReadFileResponse, WriteFileResponse},
+        requests::{FillPattern, SortBy, SplitChunk, YamlOutputFormat},
+        responses::{
+            Base64FileResponse, BulkRenameResponse, CompareDirectoriesResponse, ReadFileResponse,
+            SplitFileResponse, WatchFileResponse, WriteFileResponse,
+        },
     },
 };
 
@@ -65,6 +72,38 @@ pub trait FileOperations: Send + Sync {
     /// * `Err(FileSystemMcpError)` - If the file cannot be read
     async fn read_media_file(&self, path: &Path) -> FileSystemMcpResult<ReadFileResponse>;
 
+    /// Read the raw bytes of any file as base64, without guessing its type
+    ///
+    /// # Arguments
+    /// * `path` - The file path to read
+    ///
+    /// # Returns
+    /// * `Ok(Base64FileResponse)` - The encoded bytes with their size and MD5 digest
+    /// * `Err(FileSystemMcpError)` - If the file cannot be read or exceeds the read limit
+    async fn read_file_base64(&self, path: &Path) -> FileSystemMcpResult<Base64FileResponse>;
+
+    /// Read the lines between the `occurrence`-th line containing `start_marker` and the
+    /// next line containing `end_marker`
+    ///
+    /// # Arguments
+    /// * `path` - The file path to read
+    /// * `start_marker` - Text identifying the opening line
+    /// * `end_marker` - Text identifying the closing line
+    /// * `include_markers` - Whether the marker lines are part of the result
+    /// * `occurrence` - 1-based index of the start marker to use
+    ///
+    /// # Returns
+    /// * `Ok(ReadFileResponse)` - The section joined with newlines
+    /// * `Err(FileSystemMcpError)` - If the file cannot be read or a marker is missing
+    async fn read_file_between_markers(
+        &self,
+        path: &Path,
+        start_marker: &str,
+        end_marker: &str,
+        include_markers: bool,
+        occurrence: usize,
+    ) -> FileSystemMcpResult<ReadFileResponse>;
+
     /// Read files concurrently using futures::join_all for scalability with many files
     ///
     /// # Arguments
@@ -130,6 +169,8 @@ pub trait FileOperations: Send + Sync {
     /// # Arguments
     /// * `path` - The directory path to list
     /// * `exclude_patterns` - Patterns to exclude from the tree
+    /// * `follow_symlinks` - Resolve symlinks to their targets instead of listing them as links
+    /// * `max_depth` - Number of levels to list before truncating; `None` lists everything
     ///
     /// # Returns
     /// * `Ok(ListDirectoryResponse)` - Success response with directory contents
@@ -138,6 +179,25 @@ pub trait FileOperations: Send + Sync {
         &self,
         path: &Path,
         exclude_patterns: &[String],
+        follow_symlinks: bool,
+        max_depth: Option<usize>,
+    ) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Render the contents of a directory as an indented text tree with box-drawing characters
+    ///
+    /// # Arguments
+    /// * `path` - The directory path to list
+    /// * `exclude_patterns` - Patterns to exclude from the tree
+    /// * `max_depth` - Number of levels to list before truncating; `None` lists everything
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - Success response with the rendered tree
+    /// * `Err(FileSystemMcpError)` - If the directory cannot be listed
+    async fn file_tree_markdown(
+        &self,
+        path: &Path,
+        exclude_patterns: &[String],
+        max_depth: Option<usize>,
     ) -> FileSystemMcpResult<WriteFileResponse>;
 
     /// Move/rename a file or directory
@@ -151,6 +211,88 @@ pub trait FileOperations: Send + Sync {
     /// * `Err(FileSystemMcpError)` - If the move operation fails
     async fn move_file(&self, from: &Path, to: &Path) -> FileSystemMcpResult<WriteFileResponse>;
 
+    /// Remove every empty directory below a path, deepest first
+    ///
+    /// A directory counts as empty once all of its subdirectories have been pruned.
+    /// The starting directory itself is kept.
+    ///
+    /// # Arguments
+    /// * `path` - The directory to prune
+    /// * `dry_run` - If true, report what would be removed without deleting anything
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - JSON array of removed (or removable) directory paths
+    /// * `Err(FileSystemMcpError)` - If a directory cannot be read or removed
+    async fn prune_empty_directories(
+        &self,
+        path: &Path,
+        dry_run: bool,
+    ) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Rename the files in a directory whose names match a regex
+    ///
+    /// Only the first match in each file name is replaced, and files keep their
+    /// directory. A new name that is empty, contains a path separator, or is
+    /// already taken is reported in `errors` and that file is left alone.
+    ///
+    /// # Arguments
+    /// * `directory` - The directory containing the files
+    /// * `pattern` - The regex matched against each file name
+    /// * `replacement` - The substitution, with `$1`-style capture group references
+    /// * `recursive` - If true, also rename files in subdirectories
+    /// * `dry_run` - If true, report the renames without performing them
+    ///
+    /// # Returns
+    /// * `Ok(BulkRenameResponse)` - The renames and per-file errors
+    /// * `Err(FileSystemMcpError)` - If the directory cannot be read
+    async fn rename_bulk(
+        &self,
+        directory: &Path,
+        pattern: &Regex,
+        replacement: &str,
+        recursive: bool,
+        dry_run: bool,
+    ) -> FileSystemMcpResult<BulkRenameResponse>;
+
+    /// Split a file into numbered chunk files next to `output_prefix`
+    ///
+    /// Chunks are named `<output_prefix>.001`, `<output_prefix>.002`, ... with more
+    /// digits when there are over 999 of them. Existing chunk files are overwritten.
+    /// An empty file produces no chunks.
+    ///
+    /// # Arguments
+    /// * `path` - The file to split
+    /// * `output_prefix` - Path the chunk numbers are appended to
+    /// * `chunk` - Bytes or lines per chunk
+    ///
+    /// # Returns
+    /// * `Ok(SplitFileResponse)` - The chunk files and their sizes
+    /// * `Err(FileSystemMcpError)` - If more than [`MAX_SPLIT_CHUNKS`] chunks would be
+    ///   written, a chunk path is a directory, a symlink or the input file, or I/O fails
+    ///
+    /// [`MAX_SPLIT_CHUNKS`]: crate::models::requests::MAX_SPLIT_CHUNKS
+    async fn split_file(
+        &self,
+        path: &Path,
+        output_prefix: &Path,
+        chunk: SplitChunk,
+    ) -> FileSystemMcpResult<SplitFileResponse>;
+
+    /// Concatenate files into `output`, creating or overwriting it
+    ///
+    /// # Arguments
+    /// * `inputs` - The files to concatenate, in order
+    /// * `output` - The file to write
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - The output path and its size
+    /// * `Err(FileSystemMcpError)` - If `output` is one of the inputs or I/O fails
+    async fn join_files(
+        &self,
+        inputs: &[PathBuf],
+        output: &Path,
+    ) -> FileSystemMcpResult<WriteFileResponse>;
+
     /// Search for files and directories matching a pattern
     ///
     /// # Arguments
@@ -158,9 +300,12 @@ pub trait FileOperations: Send + Sync {
     /// * `pattern` - The pattern to search for
     /// * `allowed_directories` - List of allowed directories
     /// * `exclude_patterns` - Patterns to exclude from the search
+    /// * `follow_symlinks` - Descend into directories reached through symlinks
+    /// * `content_contains` - Keep only regular files whose content contains this text
     ///
     /// # Returns
-    /// * `Ok(ListDirectoryResponse)` - Success response with directory contents
+    /// * `Ok(WriteFileResponse)` - The matching paths, or with `content_contains`
+    ///   the matching files and lines, as JSON in the message
     /// * `Err(FileSystemMcpError)` - If the directory cannot be listed
     async fn search_files(
         &self,
@@ -168,6 +313,144 @@ pub trait FileOperations: Send + Sync {
         pattern: &str,
         allowed_directories: &[PathBuf],
         exclude_patterns: &[String],
+        follow_symlinks: bool,
+        content_contains: Option<&str>,
+    ) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Find files modified within a time window
+    ///
+    /// # Arguments
+    /// * `path` - The directory path to search
+    /// * `after` - Inclusive lower bound on the modification time
+    /// * `before` - Inclusive upper bound on the modification time
+    /// * `exclude_patterns` - Patterns to exclude from the search
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - JSON array of matching files, newest first
+    /// * `Err(FileSystemMcpError)` - If the directory cannot be read
+    async fn find_files_by_date_range(
+        &self,
+        path: &Path,
+        after: Option<SystemTime>,
+        before: Option<SystemTime>,
+        exclude_patterns: &[String],
+    ) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Export a flat inventory of the files under a directory
+    ///
+    /// # Arguments
+    /// * `path` - The directory to inventory
+    /// * `include_hashes` - Whether to compute the SHA-256 digest of each file
+    /// * `exclude_patterns` - Patterns to exclude from the manifest
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - JSON array of manifest entries sorted by path
+    /// * `Err(FileSystemMcpError)` - If the directory or a file cannot be read
+    async fn export_directory_manifest(
+        &self,
+        path: &Path,
+        include_hashes: bool,
+        exclude_patterns: &[String],
+    ) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Compare the regular files under two directories by relative path
+    ///
+    /// # Arguments
+    /// * `path_a` - The first directory
+    /// * `path_b` - The second directory
+    /// * `compare_content` - Compare SHA-256 digests when sizes match; otherwise
+    ///   files of equal size count as identical
+    ///
+    /// # Returns
+    /// * `Ok(CompareDirectoriesResponse)` - Files only in either directory, and
+    ///   shared files split into different and identical
+    /// * `Err(FileSystemMcpError)` - If a directory or a file cannot be read
+    async fn compare_directories(
+        &self,
+        path_a: &Path,
+        path_b: &Path,
+        compare_content: bool,
+    ) -> FileSystemMcpResult<CompareDirectoriesResponse>;
+
+    /// Parse a CSV file into JSON rows
+    ///
+    /// # Arguments
+    /// * `path` - The CSV file to read
+    /// * `has_header` - Use the first row as column names and return objects instead of arrays
+    /// * `delimiter` - Field delimiter byte
+    /// * `max_rows` - Maximum number of data rows to return
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - JSON array of rows
+    /// * `Err(FileSystemMcpError)` - If the file cannot be read or is not valid CSV
+    async fn read_csv_file(
+        &self,
+        path: &Path,
+        has_header: bool,
+        delimiter: u8,
+        max_rows: usize,
+    ) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Parse a `.env` file into a JSON object of variables
+    ///
+    /// # Arguments
+    /// * `path` - The .env file to read
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - JSON object with `variables` and `warnings` for skipped lines
+    /// * `Err(FileSystemMcpError)` - If the file cannot be read
+    async fn read_env_file(&self, path: &Path) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Parse a newline-delimited JSON file into a JSON array
+    ///
+    /// # Arguments
+    /// * `path` - The JSONL file to read
+    /// * `start_line` - First line to read, 1-based
+    /// * `end_line` - Last line to read, inclusive
+    ///
+    /// # Returns
+    /// * `Ok(ReadFileResponse)` - JSON array with one value per non-empty line; lines that
+    ///   are not valid JSON become `{"_raw": ..., "_parse_error": ...}` objects. Reading
+    ///   stops at the read limit and sets `truncated`
+    /// * `Err(FileSystemMcpError)` - If the file cannot be read
+    async fn read_file_as_jsonl(
+        &self,
+        path: &Path,
+        start_line: Option<usize>,
+        end_line: Option<usize>,
+    ) -> FileSystemMcpResult<ReadFileResponse>;
+
+    /// Parse a YAML file and render it as JSON or TOML
+    ///
+    /// # Arguments
+    /// * `path` - The YAML file to read
+    /// * `format` - Output format
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The converted document; a multi-document stream becomes a JSON array
+    /// * `Err(FileSystemMcpError)` - If the file cannot be read, is not valid YAML or
+    ///   cannot be represented in `format`
+    async fn parse_yaml_file(
+        &self,
+        path: &Path,
+        format: YamlOutputFormat,
+    ) -> FileSystemMcpResult<String>;
+
+    /// Create or overwrite a file with exactly `size_bytes` bytes of generated content
+    ///
+    /// # Arguments
+    /// * `path` - The file to create or overwrite
+    /// * `size_bytes` - The size of the resulting file
+    /// * `pattern` - The content to fill the file with
+    ///
+    /// # Returns
+    /// * `Ok(WriteFileResponse)` - Success response with the size of the file
+    /// * `Err(FileSystemMcpError)` - If the file cannot be written
+    async fn generate_file(
+        &self,
+        path: &Path,
+        size_bytes: u64,
+        pattern: FillPattern,
     ) -> FileSystemMcpResult<WriteFileResponse>;
 
     /// Get file information
@@ -196,4 +479,55 @@ pub trait FileOperations: Send + Sync {
         edits: &[crate::models::requests::EditOperation],
         dry_run: &bool,
     ) -> FileSystemMcpResult<WriteFileResponse>;
+
+    /// Wait for a file's modification time to change
+    ///
+    /// # Arguments
+    /// * `path` - The file to watch; creating or deleting it also counts as a change
+    /// * `poll_interval` - Time between modification time checks
+    /// * `timeout` - How long to wait before reporting no change
+    ///
+    /// # Returns
+    /// * `Ok(WatchFileResponse)` - Whether a change was seen and when
+    /// * `Err(FileSystemMcpError)` - If watching fails
+    async fn watch_file(
+        &self,
+        path: &Path,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> FileSystemMcpResult<WatchFileResponse>;
+
+    /// Create a uniquely named empty file that persists after the call
+    ///
+    /// # Arguments
+    /// * `directory` - The directory to create the file in
+    /// * `prefix` - Optional prefix for the generated name
+    /// * `suffix` - Optional suffix for the generated name
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the created file
+    /// * `Err(FileSystemMcpError)` - If the file cannot be created
+    async fn create_temp_file(
+        &self,
+        directory: &Path,
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+    ) -> FileSystemMcpResult<PathBuf>;
+
+    /// Create a uniquely named empty directory that persists after the call
+    ///
+    /// # Arguments
+    /// * `directory` - The directory to create the new directory in
+    /// * `prefix` - Optional prefix for the generated name
+    /// * `suffix` - Optional suffix for the generated name
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the created directory
+    /// * `Err(FileSystemMcpError)` - If the directory cannot be created
+    async fn create_temp_directory(
+        &self,
+        directory: &Path,
+        prefix: Option<&str>,
+        suffix: Option<&str>,
+    ) -> FileSystemMcpResult<PathBuf>;
 }
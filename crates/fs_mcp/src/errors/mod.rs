@@ -28,6 +28,10 @@ pub enum FileSystemMcpError {
     },
     #[error("Failed to write file: {message}")]
     IoError { message: String, path: String },
+    #[error("Symlink {link} points outside allowed directories: {target}")]
+    SymlinkOutsideAllowedDirectory { link: String, target: String },
+    #[error("Clipboard access is unavailable: {message}")]
+    ClipboardUnavailable { message: String },
 }
 
 impl From<FileSystemMcpError> for McpError {
@@ -63,6 +67,26 @@ impl From<FileSystemMcpError> for McpError {
                     "path": path,
                 })),
             ),
+            FileSystemMcpError::SymlinkOutsideAllowedDirectory { link, target } => {
+                McpError::invalid_request(
+                    format!(
+                        "Permission denied - symlink {} points outside allowed directories",
+                        link
+                    ),
+                    Some(serde_json::json!({
+                        "error": "symlink_outside_allowed_directory",
+                        "link": link,
+                        "target": target,
+                    })),
+                )
+            }
+            FileSystemMcpError::ClipboardUnavailable { message } => McpError::internal_error(
+                format!("Clipboard access is unavailable: {}", message),
+                Some(serde_json::json!({
+                    "error": "clipboard_unavailable",
+                    "message": message,
+                })),
+            ),
         }
     }
 }
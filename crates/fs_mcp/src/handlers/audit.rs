@@ -0,0 +1,118 @@
+use std::{future::Future, path::PathBuf, time::Instant};
+
+use rmcp::model::JsonObject;
+use tracing::Instrument;
+
+use crate::{
+    errors::ToolResult,
+    utils::path::{is_resolved_path_allowed, resolve_relative_path},
+};
+
+/// Argument names that carry the path a tool operates on, in order of preference
+const PATH_ARGUMENTS: [&str; 4] = ["path", "source", "raw_path", "directory"];
+
+/// Run a tool call inside a `tool_call` span, emitting structured start and end events
+///
+/// The start event records the tool name, the path it targets and whether that path
+/// lies inside an allowed directory; the end event adds the duration and outcome.
+pub(crate) async fn audit_tool_call<F>(
+    tool: &str,
+    arguments: Option<&JsonObject>,
+    allowed_directories: &[PathBuf],
+    call: F,
+) -> ToolResult
+where
+    F: Future<Output = ToolResult>,
+{
+    let path = arguments.and_then(target_path).unwrap_or_default();
+    let allowed = path.is_empty()
+        || std::env::current_dir().is_ok_and(|current_dir| {
+            let resolved = resolve_relative_path(path, &current_dir);
+            is_resolved_path_allowed(&resolved, allowed_directories)
+        });
+
+    let span = tracing::info_span!("tool_call", tool = %tool);
+    async move {
+        tracing::info!(tool = %tool, path = %path, allowed = %allowed, "tool call started");
+
+        let started = Instant::now();
+        let result = call.await;
+        let duration_ms = started.elapsed().as_millis();
+        let success = result
+            .as_ref()
+            .is_ok_and(|result| result.is_error != Some(true));
+
+        tracing::info!(
+            tool = %tool,
+            path = %path,
+            allowed = %allowed,
+            duration_ms = %duration_ms,
+            success = %success,
+            "tool call finished"
+        );
+        result
+    }
+    .instrument(span)
+    .await
+}
+
+/// First path-like string argument of a tool call
+fn target_path(arguments: &JsonObject) -> Option<&str> {
+    PATH_ARGUMENTS
+        .iter()
+        .find_map(|name| arguments.get(*name).and_then(|value| value.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rmcp::{ErrorData as McpError, model::CallToolResult};
+    use tracing_test::traced_test;
+
+    use super::*;
+
+    fn arguments(value: serde_json::Value) -> JsonObject {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_audit_logs_successful_call() {
+        let allowed = vec![PathBuf::from("/srv/data")];
+        let arguments = arguments(serde_json::json!({"path": "/srv/data/notes.txt"}));
+
+        let result = audit_tool_call("read_text_file", Some(&arguments), &allowed, async {
+            Ok(CallToolResult::success(vec![]))
+        })
+        .await;
+
+        assert!(result.is_ok());
+        assert!(logs_contain("tool call started"));
+        assert!(logs_contain("tool=read_text_file"));
+        assert!(logs_contain("path=/srv/data/notes.txt"));
+        assert!(logs_contain("allowed=true"));
+        assert!(logs_contain("tool call finished"));
+        assert!(logs_contain("duration_ms="));
+        assert!(logs_contain("success=true"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_audit_logs_rejected_call() {
+        let allowed = vec![PathBuf::from("/srv/data")];
+        let arguments = arguments(serde_json::json!({
+            "source": "/etc/passwd",
+            "destination": "/srv/data/passwd",
+        }));
+
+        let result = audit_tool_call("move_file", Some(&arguments), &allowed, async {
+            Err(McpError::invalid_request("Permission denied", None))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert!(logs_contain("tool=move_file"));
+        assert!(logs_contain("path=/etc/passwd"));
+        assert!(logs_contain("allowed=false"));
+        assert!(logs_contain("success=false"));
+    }
+}
@@ -1,27 +1,68 @@
 use core::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use dashmap::DashMap;
+use mcp_common::Capabilities;
+use percent_encoding::percent_decode_str;
 use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler,
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    handler::server::{router::tool::ToolRouter, tool::ToolCallContext, wrapper::Parameters},
     model::*,
     service::RequestContext,
-    tool, tool_handler, tool_router,
+    tool, tool_router,
 };
 
 use crate::{
     application::FileService,
     domain::FileOperations,
     errors::{FileSystemMcpError, ToolResult},
+    handlers::{audit::audit_tool_call, metrics::ServerMetrics, subscriptions::spawn_file_watcher},
     models::requests::{
-        CreateDirectoryRequest, DirectoryTreeRequest, EditFileRequest, GetFileInfoRequest,
-        ListAllowedDirectoriesRequest, ListDirectoryRequest, ListDirectoryWithSizesRequest,
-        MoveFileRequest, ReadMediaFileRequest, ReadMultipleFilesRequest, ReadTextFileRequest,
-        SearchFilesRequest, WriteFileRequest,
+        CompareDirectoriesRequest, ConvertYamlToJsonRequest, CopyToClipboardRequest,
+        CreateDirectoryRequest, CreateTempDirectoryRequest, CreateTempFileRequest,
+        DirectoryTreeRequest, EditFileRequest, ExportDirectoryManifestRequest,
+        FindFilesByDateRangeRequest, GenerateFileTreeMarkdownRequest, GenerateRandomFileRequest,
+        GetFileInfoRequest, JoinFilesRequest, ListAllowedDirectoriesRequest, ListDirectoryRequest,
+        ListDirectoryWithSizesRequest, MoveFileRequest, NormalizePathRequest, ParseYamlFileRequest,
+        PruneEmptyDirectoriesRequest, ReadCsvFileRequest, ReadEnvFileRequest,
+        ReadFileBetweenMarkersRequest, ReadFileBinaryRequest, ReadJsonlFileRequest,
+        ReadMediaFileRequest, ReadMultipleFilesRequest, ReadTextFileRequest, RenameBulkRequest,
+        SearchFilesRequest, SplitFileRequest, SummarizeDiffRequest, WatchFileRequest,
+        WriteFileRequest, YamlOutputFormat,
+    },
+    models::responses::{
+        AllowedDirectoriesResponse, AllowedDirectoryInfo, FileContent, NormalizedPathResponse,
+        ReadFileResponse, TempPathResponse, WriteFileResponse,
     },
     service::validation::{Validate, validate_path},
+    utils::{
+        clipboard::SystemClipboard,
+        diff::summarize_unified_diff,
+        fs::{describe_directory, disk_space},
+        line_endings::convert_line_endings,
+        path::{is_resolved_path_allowed, resolve_relative_path},
+        yaml::convert_yaml,
+    },
 };
 use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// Static resources as (URI, name), in the order `list_resources` returns them
+const RESOURCES: &[(&str, &str)] = &[
+    ("fs://status", "server-status"),
+    ("fs://help", "help-documentation"),
+    ("fs://allowed-directories", "allowed-directories-list"),
+    ("fs://system-info", "system-info"),
+    ("fs://capabilities", "server-capabilities"),
+];
+/// URI template of the resource serving the last lines of a file
+const TAIL_RESOURCE_TEMPLATE: &str = "fs://tail/{path}?lines={n}";
+const TAIL_RESOURCE_PREFIX: &str = "fs://tail/";
+/// Lines returned by a tail resource read without a `lines` parameter
+const DEFAULT_TAIL_RESOURCE_LINES: usize = 10;
+/// URI template of the resource serving a whole text file, which clients may subscribe to
+const FILE_RESOURCE_TEMPLATE: &str = "fs://file/{path}";
+const FILE_RESOURCE_PREFIX: &str = "fs://file/";
 
 /// Filesystem MCP Service
 ///
@@ -30,32 +71,226 @@ use std::sync::Arc;
 pub struct FileSystemService {
     allowed_directories: Vec<PathBuf>,
     file_operations: Arc<dyn FileOperations>,
+    read_only: bool,
+    /// System clipboard, present only when clipboard access was enabled
+    clipboard: Option<Arc<SystemClipboard>>,
+    metrics: Arc<ServerMetrics>,
+    /// Watcher tasks of the active resource subscriptions, keyed by URI
+    subscriptions: Arc<DashMap<String, JoinHandle<()>>>,
     tool_router: ToolRouter<FileSystemService>,
 }
 
 impl FileSystemService {
-    /// Create a new FileSystemService with the given configuration and file reader
-    pub fn new(allowed_directories: Vec<PathBuf>) -> Self {
+    /// Create a new FileSystemService backed by [`FileService`] with the given read limit
+    pub fn new(allowed_directories: Vec<PathBuf>, max_read_bytes: Option<usize>) -> Self {
+        Self::with_file_operations(
+            allowed_directories,
+            Arc::new(FileService::new().with_max_read_bytes(max_read_bytes)),
+        )
+    }
+
+    /// Create a new FileSystemService that delegates to the given file operations
+    ///
+    /// Allows tests and embedders to inject their own [`FileOperations`] implementation.
+    pub fn with_file_operations(
+        allowed_directories: Vec<PathBuf>,
+        file_operations: Arc<dyn FileOperations>,
+    ) -> Self {
         Self {
             allowed_directories,
-            file_operations: Arc::new(FileService::new()),
+            file_operations,
+            read_only: false,
+            clipboard: None,
+            metrics: Arc::new(ServerMetrics::new()),
+            subscriptions: Arc::new(DashMap::new()),
             tool_router: Self::tool_router(),
         }
     }
 
+    /// Reject all tools that modify the filesystem when `read_only` is set
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Let the clipboard tools access the system clipboard when `enabled` is set
+    pub fn with_clipboard(mut self, enabled: bool) -> Self {
+        self.clipboard = enabled.then(|| Arc::new(SystemClipboard::default()));
+        self
+    }
+
+    /// The system clipboard, or an error when clipboard access was not enabled
+    fn clipboard(&self, operation: &str) -> Result<Arc<SystemClipboard>, McpError> {
+        self.clipboard.clone().ok_or_else(|| {
+            McpError::invalid_request(
+                "Clipboard access is disabled; start the server with --enable-clipboard",
+                Some(serde_json::json!({"operation": operation})),
+            )
+        })
+    }
+
+    /// Fail a modifying tool before it touches the filesystem when running read-only
+    fn ensure_writable(&self, operation: &str) -> Result<(), McpError> {
+        if self.read_only {
+            return Err(McpError::invalid_request(
+                "Server is configured in read-only mode",
+                Some(serde_json::json!({"operation": operation})),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Count one read of the file at `path`, sized by its length on disk
+    ///
+    /// For tools that parse a file and return something other than its content.
+    async fn record_file_read(&self, path: &Path) {
+        let size = tokio::fs::metadata(path)
+            .await
+            .map_or(0, |metadata| metadata.len());
+        self.metrics.record_read_bytes(size);
+    }
+
+    /// Directory to create temporary entries in: the requested one, or the first allowed directory
+    async fn resolve_temp_directory(
+        &self,
+        directory: Option<&str>,
+        operation: &str,
+    ) -> Result<PathBuf, FileSystemMcpError> {
+        match directory {
+            Some(directory) => validate_path(directory, &self.allowed_directories).await,
+            None => self.allowed_directories.first().cloned().ok_or_else(|| {
+                FileSystemMcpError::ValidationError {
+                    message: "No allowed directories configured".to_string(),
+                    path: String::new(),
+                    operation: operation.to_string(),
+                    data: serde_json::json!({"error": "Specify a directory explicitly"}),
+                }
+            }),
+        }
+    }
+
+    /// Read the last lines of the file named by an `fs://tail/{path}?lines={n}` URI
+    ///
+    /// The path is percent-decoded and must lie inside an allowed directory.
+    async fn read_tail_resource(&self, uri: &str) -> Result<String, FileSystemMcpError> {
+        let invalid = |error: &str| FileSystemMcpError::ValidationError {
+            message: "Invalid tail resource URI".to_string(),
+            path: uri.to_string(),
+            operation: "read_resource".to_string(),
+            data: serde_json::json!({
+                "error": error,
+                "uri_template": TAIL_RESOURCE_TEMPLATE
+            }),
+        };
+
+        let rest = uri.strip_prefix(TAIL_RESOURCE_PREFIX).unwrap_or_default();
+        let (encoded_path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let path = percent_decode_str(encoded_path)
+            .decode_utf8()
+            .map_err(|_| invalid("Path is not valid UTF-8"))?;
+        if path.trim().is_empty() {
+            return Err(invalid("Path cannot be empty"));
+        }
+
+        let mut lines = DEFAULT_TAIL_RESOURCE_LINES;
+        for (name, value) in query.split('&').filter_map(|param| param.split_once('=')) {
+            if name == "lines" {
+                lines = value
+                    .parse()
+                    .map_err(|_| invalid("lines must be a non-negative integer"))?;
+            }
+        }
+
+        let valid_path = validate_path(&path, &self.allowed_directories).await?;
+        let response = self
+            .file_operations
+            .read_file_tail(&valid_path, lines)
+            .await?;
+        self.metrics.record_read(&response);
+        match response.content {
+            FileContent::Text(text) => Ok(text),
+            FileContent::Binary(_) => Err(invalid("File is not a text file")),
+        }
+    }
+
+    /// Resolve the file named by an `fs://file/{path}` URI
+    ///
+    /// The path is percent-decoded and must lie inside an allowed directory.
+    async fn file_resource_path(
+        &self,
+        uri: &str,
+        operation: &str,
+    ) -> Result<PathBuf, FileSystemMcpError> {
+        let invalid = |error: &str| FileSystemMcpError::ValidationError {
+            message: "Invalid file resource URI".to_string(),
+            path: uri.to_string(),
+            operation: operation.to_string(),
+            data: serde_json::json!({
+                "error": error,
+                "uri_template": FILE_RESOURCE_TEMPLATE
+            }),
+        };
+
+        let encoded_path = uri
+            .strip_prefix(FILE_RESOURCE_PREFIX)
+            .ok_or_else(|| invalid("Only fs://file/{path} resources are supported"))?;
+        let path = percent_decode_str(encoded_path)
+            .decode_utf8()
+            .map_err(|_| invalid("Path is not valid UTF-8"))?;
+        if path.trim().is_empty() {
+            return Err(invalid("Path cannot be empty"));
+        }
+
+        validate_path(&path, &self.allowed_directories).await
+    }
+
+    /// Read the whole text file named by an `fs://file/{path}` URI
+    async fn read_file_resource(&self, uri: &str) -> Result<String, FileSystemMcpError> {
+        let valid_path = self.file_resource_path(uri, "read_resource").await?;
+        let response = self.file_operations.read_entire_file(&valid_path).await?;
+        self.metrics.record_read(&response);
+        match response.content {
+            FileContent::Text(text) => Ok(text),
+            FileContent::Binary(_) => Err(FileSystemMcpError::ValidationError {
+                message: "Invalid file resource URI".to_string(),
+                path: uri.to_string(),
+                operation: "read_resource".to_string(),
+                data: serde_json::json!({"error": "File is not a text file"}),
+            }),
+        }
+    }
+
     fn create_resource_text(&self, uri: &str, name: &str) -> Resource {
         RawResource::new(uri, name.to_string()).no_annotation()
     }
 
+    /// The static resources, as listed by `list_resources`
+    fn resources(&self) -> Vec<Resource> {
+        RESOURCES
+            .iter()
+            .map(|(uri, name)| self.create_resource_text(uri, name))
+            .collect()
+    }
+
     fn generate_status_content(&self) -> String {
         format!(
             r#"Filesystem MCP Server Status
 
 Server: Running
+Mode: {}
 Allowed Directories: {}
 Total Allowed Paths: {}
-Tools Available: 13
-Resources Available: 3
+Tools Available: {}
+Resources Available: {}
+
+Runtime Statistics:
+- Uptime: {} seconds
+- Files Read: {}
+- Bytes Read: {}
+- Files Written: {}
+- Bytes Written: {}
+- Files Moved: {}
+- Errors: {}
 
 Capabilities:
 - Secure file reading (text and media files)
@@ -72,12 +307,26 @@ Security Model:
 - Path validation and normalization
 - Symlink handling with warnings
 - Input sanitization and validation"#,
+            if self.read_only {
+                "read-only"
+            } else {
+                "read-write"
+            },
             self.allowed_directories
                 .iter()
                 .map(|p| p.display().to_string())
                 .collect::<Vec<_>>()
                 .join(", "),
-            self.allowed_directories.len()
+            self.allowed_directories.len(),
+            self.tool_router.list_all().len(),
+            RESOURCES.len(),
+            self.metrics.uptime_seconds(),
+            self.metrics.files_read(),
+            self.metrics.bytes_read(),
+            self.metrics.files_written(),
+            self.metrics.bytes_written(),
+            self.metrics.files_moved(),
+            self.metrics.errors()
         )
     }
 
@@ -92,12 +341,25 @@ FILE READING:
   - path: File path (required)
   - head: Read first N lines (optional)
   - tail: Read last N lines (optional)
+  - Returns line_count and size_bytes of the whole file alongside the content
   - Example: {{"path": "/project/README.md", "head": 10}}
 
 - read_media_file: Read image/audio files as base64 with MIME type
   - path: Media file path (required)
   - Example: {{"path": "/images/photo.jpg"}}
 
+- read_file_as_base64: Read any file as base64 with its size and MD5 digest, without MIME detection
+  - path: File path (required)
+  - Example: {{"path": "/project/target/release/app"}}
+
+- read_file_between_markers: Read the lines between two marker lines
+  - path: File path (required)
+  - start_marker: Text on the opening line (required)
+  - end_marker: Text on the closing line (required)
+  - include_markers: Keep the marker lines (optional, default false)
+  - occurrence: Which section to read, 1-based (optional, default 1)
+  - Example: {{"path": "/certs/chain.pem", "start_marker": "BEGIN CERTIFICATE", "end_marker": "END CERTIFICATE", "occurrence": 2}}
+
 - read_multiple_files: Read multiple files simultaneously
   - paths: Array of file paths (required)
   - Example: {{"paths": ["/config.json", "/settings.yaml"]}}
@@ -106,6 +368,7 @@ FILE WRITING:
 - write_file: Create or overwrite file with content
   - path: File path (required)
   - content: File content (required)
+  - line_ending: "lf", "crlf" or "preserve" (optional, default: "lf")
   - Example: {{"path": "/project/new_file.txt", "content": "Hello World"}}
 
 - edit_file: Make line-based edits with git-style diff
@@ -113,6 +376,8 @@ FILE WRITING:
   - edits: Array of edit operations (required)
   - dry_run: Preview changes without applying (optional)
   - Example: {{"path": "/config.py", "edits": [{{"old_text": "DEBUG = False", "new_text": "DEBUG = True"}}]}}
+  - Regex edits set kind to "regex" and use pattern, replacement ($1 for capture groups) and flags ("g" replaces all matches, "i", "m", "s")
+  - Example: {{"path": "/app.py", "edits": [{{"kind": "regex", "pattern": "print\\((.*)\\)", "replacement": "log($1)", "flags": "g"}}]}}
 
 DIRECTORY OPERATIONS:
 - create_directory: Create directory and parent directories
@@ -125,13 +390,20 @@ DIRECTORY OPERATIONS:
 
 - list_directory_with_sizes: List directory with file sizes and sorting
   - path: Directory path (required)
-  - sort_by: Sort criteria - "name", "size", "modified" (optional)
+  - sort_by: Sort criteria - "name", "size", "modified" (newest first), "modified_ascending" (optional)
   - Example: {{"path": "/project", "sort_by": "size"}}
 
 - directory_tree: Get recursive directory tree as JSON
   - path: Root directory path (required)
   - exclude_patterns: Glob patterns to exclude (optional)
-  - Example: {{"path": "/project", "exclude_patterns": ["*.log", "node_modules/**"]}}
+  - max_depth: Levels to list before truncating (optional, default: unlimited)
+  - Example: {{"path": "/project", "exclude_patterns": ["*.log", "node_modules/**"], "max_depth": 3}}
+
+- generate_file_tree_markdown: Render a directory as a text tree with box-drawing characters
+  - path: Root directory path (required)
+  - max_depth: Levels to list before truncating (optional, default: unlimited)
+  - exclude_patterns: Glob patterns to exclude (optional)
+  - Example: {{"path": "/project", "max_depth": 2, "exclude_patterns": ["target", ".git"]}}
 
 FILE MANAGEMENT:
 - move_file: Move or rename files and directories
@@ -139,24 +411,142 @@ FILE MANAGEMENT:
   - destination: Destination path (required)
   - Example: {{"source": "/old_name.txt", "destination": "/new_name.txt"}}
 
+- prune_empty_directories: Remove empty directories below a path, deepest first
+  - path: Directory to prune, kept itself (required)
+  - dry_run: List what would be removed without deleting (optional, default: false)
+  - Example: {{"path": "/project/build", "dry_run": true}}
+
+- rename_bulk: Rename files whose names match a regex, e.g. to change extensions
+  - directory: Directory containing the files (required)
+  - match_pattern: Regex matched against each file name (required)
+  - replacement: Replacement for the first match, $1/$2 insert capture groups (required)
+  - dry_run: Report the renames without performing them (optional, default: false)
+  - recursive: Include files in subdirectories (optional, default: false)
+  - Example: {{"directory": "/photos", "match_pattern": "^IMG_(\\d+)\\.jpeg$", "replacement": "photo-$1.jpg", "dry_run": true}}
+
+- split_file: Split a large file into numbered chunk files
+  - path: File to split (required)
+  - chunk_size_bytes: Bytes per chunk (set this or chunk_lines)
+  - chunk_lines: Lines per chunk (set this or chunk_size_bytes)
+  - output_prefix: Chunks are written as <output_prefix>.001, .002, ... (required)
+  - Example: {{"path": "/logs/app.log", "chunk_lines": 100000, "output_prefix": "/logs/parts/app.log"}}
+
+- join_files: Concatenate files in order into one file
+  - input_paths: Files to concatenate (required)
+  - output_path: File to create or overwrite (required)
+  - Example: {{"input_paths": ["/logs/parts/app.log.001", "/logs/parts/app.log.002"], "output_path": "/logs/app.log"}}
+
 - search_files: Search for files matching patterns
   - path: Search directory (required)
   - pattern: Glob pattern (required)
   - exclude_patterns: Patterns to exclude (optional)
+  - content_contains: Only return files containing this text, with their matching lines (optional)
   - Example: {{"path": "/project", "pattern": "*.rs", "exclude_patterns": ["target/**"]}}
+  - Example: {{"path": "/project", "pattern": "**/*.rs", "content_contains": "TODO"}}
+
+- find_files_by_date_range: Find files modified within a time window
+  - path: Search directory (required)
+  - after, before: Inclusive RFC 3339 bounds (optional)
+  - exclude_patterns: Patterns to exclude (optional)
+  - Example: {{"path": "/project", "after": "2024-01-01T00:00:00Z", "exclude_patterns": ["target/**"]}}
+
+- export_directory_manifest: Flat JSON inventory of all files, sorted by path
+  - path: Directory to inventory (required)
+  - include_hashes: Compute SHA-256 digests (optional, default: false)
+  - exclude_patterns: Patterns to exclude (optional)
+  - Example: {{"path": "/project", "include_hashes": true, "exclude_patterns": ["target/**"]}}
+
+- compare_directories: Files only in either directory, and shared files that differ or match
+  - path_a, path_b: Directories to compare (required)
+  - compare_content: Compare SHA-256 digests of same-sized files (optional, default: false)
+  - Example: {{"path_a": "/backup/site", "path_b": "/srv/site", "compare_content": true}}
+
+- read_csv_file: Parse a CSV file into JSON rows
+  - path: CSV file path (required)
+  - has_header: Use the first row as column names and return objects (optional, default: true)
+  - delimiter: Single ASCII field delimiter (optional, default: ',')
+  - max_rows: Maximum number of data rows (optional, default: 1000)
+  - Example: {{"path": "/project/data/sales.csv", "delimiter": ";", "max_rows": 100}}
+
+- read_env_file: Parse a .env file into a JSON object of variables
+  - path: .env file path (required)
+  - Example: {{"path": "/project/.env"}}
+
+- read_file_as_jsonl: Parse a newline-delimited JSON file into a JSON array
+  - path: JSONL file path (required)
+  - start_line: First line to read, 1-based (optional, default: 1)
+  - end_line: Last line to read, inclusive (optional, default: end of file)
+  - Example: {{"path": "/data/events.jsonl", "start_line": 100, "end_line": 199}}
+
+- parse_yaml_file: Parse a YAML file into JSON or TOML
+  - path: YAML file path (required)
+  - output_format: "json" or "toml" (optional, default: "json"); multi-document files become a JSON array
+  - Example: {{"path": "/project/docker-compose.yml", "output_format": "toml"}}
+
+- watch_file: Wait for a file's modification time to change
+  - path: File path (required)
+  - poll_interval_ms: Milliseconds between checks, minimum 100 (optional, default: 500)
+  - timeout_ms: Milliseconds to wait, maximum 300000 (optional, default: 30000)
+  - Example: {{"path": "/project/build/output.log", "timeout_ms": 60000}}
 
 - get_file_info: Get detailed file/directory metadata
   - path: File or directory path (required)
   - Example: {{"path": "/project/config.json"}}
 
+- create_temp_file: Create a uniquely named empty file
+  - directory: Target directory, defaults to the first allowed directory (optional)
+  - prefix, suffix: Name prefix and suffix (optional)
+  - Example: {{"prefix": "draft-", "suffix": ".md"}}
+
+- create_temp_directory: Create a uniquely named empty directory
+  - directory: Target directory, defaults to the first allowed directory (optional)
+  - prefix, suffix: Name prefix and suffix (optional)
+  - Example: {{"directory": "/project/tmp", "prefix": "build-"}}
+
+- generate_random_file: Create or overwrite a file of an exact size for testing
+  - path: File path (required)
+  - size_bytes: Size of the file in bytes, at most 104857600 (100 MB) (required)
+  - pattern: zeros, random, lorem or ascii (optional, default: random)
+  - Example: {{"path": "/project/fixtures/1mb.bin", "size_bytes": 1048576, "pattern": "zeros"}}
+
 UTILITY:
-- list_allowed_directories: Show allowed directory paths
-  - No parameters required
+- list_allowed_directories: Show allowed directory paths as JSON with total and items
+  - extended_info: Also report canonical_path, exists, readable, writable and available_bytes (optional, default: false)
+  - Example: {{"extended_info": true}}
+
+- normalize_path: Explain how a path resolves and whether it is allowed
+  - raw_path: Path to resolve (required)
+  - base_directory: Directory relative paths are resolved against (optional)
+  - Example: {{"raw_path": "../lib/mod.rs", "base_directory": "/project/src"}}
+
+- summarize_diff: Count added and removed lines per file in a unified diff
+  - diff_text: Output of git diff or diff -u (required)
+  - Example: {{"diff_text": "--- a/app.py\n+++ b/app.py\n@@ -1 +1 @@\n-x = 1\n+x = 2\n"}}
+
+- convert_yaml_to_json: Convert YAML text to JSON
+  - yaml_content: YAML document(s) (required); multiple documents become a JSON array
+  - Example: {{"yaml_content": "name: api\nports: [80, 443]"}}
+
+- copy_to_clipboard: Copy text to the system clipboard (requires --enable-clipboard)
+  - content: Text to copy (required)
+  - Example: {{"content": "cargo test --workspace"}}
+
+- paste_from_clipboard: Read the text on the system clipboard (requires --enable-clipboard)
+  - Example: {{}}
 
 RESOURCES:
 - fs://status: Current server status and configuration
 - fs://help: This help documentation
 - fs://allowed-directories: List of allowed directory paths
+- fs://system-info: Total, free and available disk space per allowed directory
+- fs://capabilities: Tool names and parameter schemas as JSON
+
+RESOURCE TEMPLATES:
+- fs://file/{{path}}: Full content of a text file
+  - Subscribe (resources/subscribe) to receive notifications/resources/updated when the file changes
+  - path: Percent-encoded file path, e.g. fs://file/%2Fhome%2Fuser%2Fnotes.md
+- fs://tail/{{path}}?lines={{n}}: Last n lines of a file (default 10), re-read on every request
+  - path: Percent-encoded file path, e.g. fs://tail/%2Fvar%2Flog%2Fapp.log?lines=50
 
 ALLOWED DIRECTORIES:
 {}
@@ -196,6 +586,27 @@ EXAMPLE WORKFLOWS:
         )
     }
 
+    /// Machine-readable list of the registered tools and their parameter schemas
+    fn generate_capabilities_content(&self) -> String {
+        Capabilities::new(
+            self.get_info().protocol_version,
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+        )
+        .with_tools(self.tool_router.list_all())
+        .with_resources(self.resources())
+        .to_json_string()
+    }
+
+    fn generate_system_info_content(&self) -> Result<String, FileSystemMcpError> {
+        let disks = self
+            .allowed_directories
+            .iter()
+            .map(|dir| disk_space(dir))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(serde_json::to_string_pretty(&disks).unwrap_or_default())
+    }
+
     fn generate_allowed_directories_content(&self) -> String {
         format!(
             r#"Allowed Directories Configuration
@@ -278,6 +689,7 @@ impl FileSystemService {
                 .into());
             }
         };
+        self.metrics.record_read(&content);
 
         Ok(CallToolResult::success(vec![content.into()]))
     }
@@ -293,6 +705,48 @@ impl FileSystemService {
         let path = validate_path(req.path(), &self.allowed_directories).await?;
 
         let content = self.file_operations.read_media_file(&path).await?;
+        self.metrics.record_read(&content);
+
+        Ok(CallToolResult::success(vec![content.into()]))
+    }
+
+    #[tool(
+        description = "Read any file, e.g. a compiled artifact or database dump, as base64 without interpreting its content. Returns JSON with base64, size_bytes and md5 (hex digest of the bytes, to verify the transfer). Files larger than the server's read limit are rejected. Only works within allowed directories."
+    )]
+    async fn read_file_as_base64(
+        &self,
+        Parameters(req): Parameters<ReadFileBinaryRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let path = validate_path(req.path(), &self.allowed_directories).await?;
+
+        let response = self.file_operations.read_file_base64(&path).await?;
+        self.metrics.record_read_bytes(response.size_bytes);
+
+        Ok(CallToolResult::success(vec![response.into()]))
+    }
+
+    #[tool(
+        description = "Read the section of a text file between a line containing start_marker and the next line containing end_marker, e.g. a certificate between BEGIN CERTIFICATE and END CERTIFICATE. Use occurrence (1-based) to pick a later section and include_markers to keep the marker lines. Only works within allowed directories."
+    )]
+    async fn read_file_between_markers(
+        &self,
+        Parameters(req): Parameters<ReadFileBetweenMarkersRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let path = validate_path(req.path(), &self.allowed_directories).await?;
+
+        let content = self
+            .file_operations
+            .read_file_between_markers(
+                &path,
+                req.start_marker(),
+                req.end_marker(),
+                *req.include_markers(),
+                *req.occurrence(),
+            )
+            .await?;
+        self.metrics.record_read(&content);
 
         Ok(CallToolResult::success(vec![content.into()]))
     }
@@ -322,7 +776,10 @@ impl FileSystemService {
 
         for (result, path) in results.into_iter().zip(validated_paths.iter()) {
             match result {
-                Ok(content) => contents.push(format!("{}:\n{}\n", path.display(), content)),
+                Ok(content) => {
+                    self.metrics.record_read(&content);
+                    contents.push(format!("{}:\n{}\n", path.display(), content));
+                }
                 Err(e) => errors.push(format!("Error reading {}: {}", path.display(), e)),
             }
         }
@@ -338,28 +795,42 @@ impl FileSystemService {
     }
 
     #[tool(
-        description = "Create a new file or completely overwrite an existing file with new content. Use with caution as it will overwrite existing files without warning. Handles text content with proper encoding. Only works within allowed directories."
+        description = "Create a new file or completely overwrite an existing file with new content. Use with caution as it will overwrite existing files without warning. Line breaks are written as LF by default; set line_ending to 'crlf' for Windows-style CRLF or 'preserve' to write the content unchanged. Only works within allowed directories."
     )]
     async fn write_file(&self, Parameters(req): Parameters<WriteFileRequest>) -> ToolResult {
+        self.ensure_writable("write_file")?;
         req.validate()?;
         let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let content = convert_line_endings(req.content(), req.line_ending().unwrap_or_default());
         let result = self
             .file_operations
-            .write_file(&valid_path, req.content())
+            .write_file(&valid_path, &content)
             .await?;
+        self.metrics.record_write(content.len() as u64);
         Ok(CallToolResult::success(vec![result.into()]))
     }
 
     #[tool(
-        description = "Make line-based edits to a text file. Each edit replaces exact line sequences with new content. Returns a git-style diff showing the changes made. Only works within allowed directories."
+        description = "Make line-based edits to a text file. Each edit replaces exact line sequences with new content, or, when it sets kind to 'regex' and gives pattern instead of old_text, the first match of a regular expression (every match with flags 'g'; $1 in replacement inserts a capture group). Returns a git-style diff showing the changes made. Only works within allowed directories."
     )]
     async fn edit_file(&self, Parameters(req): Parameters<EditFileRequest>) -> ToolResult {
+        // Dry runs only preview the diff, so they stay available in read-only mode
+        if !*req.dry_run() {
+            self.ensure_writable("edit_file")?;
+        }
         req.validate()?;
         let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
         let result = self
             .file_operations
             .apply_file_edits(&valid_path, req.edits(), req.dry_run())
             .await?;
+        if !*req.dry_run() {
+            // Edits rewrite the whole file, so its new size is what was written
+            let written = tokio::fs::metadata(&valid_path)
+                .await
+                .map_or(0, |metadata| metadata.len());
+            self.metrics.record_write(written);
+        }
         Ok(CallToolResult::success(vec![result.into()]))
     }
 
@@ -370,6 +841,7 @@ impl FileSystemService {
         &self,
         Parameters(req): Parameters<CreateDirectoryRequest>,
     ) -> ToolResult {
+        self.ensure_writable("create_directory")?;
         req.validate()?;
         let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
         let result = self.file_operations.create_directory(&valid_path).await?;
@@ -410,13 +882,35 @@ impl FileSystemService {
         let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
         let result = self
             .file_operations
-            .directory_tree(&valid_path, req.exclude_patterns())
+            .directory_tree(
+                &valid_path,
+                req.exclude_patterns(),
+                *req.follow_symlinks(),
+                *req.max_depth(),
+            )
+            .await?;
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Render a directory as an indented text tree using box-drawing characters, e.g. for a README or a quick overview. Directories are listed first and end with '/'. Use max_depth to limit the levels shown and exclude_patterns to skip entries such as 'target' or 'node_modules'. Only works within allowed directories."
+    )]
+    async fn generate_file_tree_markdown(
+        &self,
+        Parameters(req): Parameters<GenerateFileTreeMarkdownRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .file_tree_markdown(&valid_path, req.exclude_patterns(), *req.max_depth())
             .await?;
         Ok(CallToolResult::success(vec![result.into()]))
     }
 
     #[tool(description = "Move or rename files and directories")]
     async fn move_file(&self, Parameters(req): Parameters<MoveFileRequest>) -> ToolResult {
+        self.ensure_writable("move_file")?;
         req.validate()?;
         let valid_from = validate_path(req.source(), &self.allowed_directories).await?;
         let valid_to = validate_path(req.destination(), &self.allowed_directories).await?;
@@ -424,10 +918,98 @@ impl FileSystemService {
             .file_operations
             .move_file(&valid_from, &valid_to)
             .await?;
+        self.metrics.record_moves(1);
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Remove every empty directory below a path, including directories that only contain empty directories. The path itself is kept and symlinks are not followed. With dry_run, nothing is deleted. Returns a JSON array of the removed (or removable) directories, deepest first. Only works within allowed directories."
+    )]
+    async fn prune_empty_directories(
+        &self,
+        Parameters(req): Parameters<PruneEmptyDirectoriesRequest>,
+    ) -> ToolResult {
+        // Dry runs only report, so they stay available in read-only mode
+        if !*req.dry_run() {
+            self.ensure_writable("prune_empty_directories")?;
+        }
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .prune_empty_directories(&valid_path, *req.dry_run())
+            .await?;
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Rename every file in a directory whose name matches a regular expression. Only the first match in each file name is replaced; use $1, $2 or ${name} in replacement for capture groups (e.g. match_pattern '^(.*)\\.jpeg$' with replacement '$1.jpg'). Files keep their directory; with recursive, files in subdirectories are included. A file is skipped and reported in errors when its new name is empty, contains a path separator, or already exists. With dry_run, nothing is renamed. Returns JSON {renamed: [{from, to}], errors}. Only works within allowed directories."
+    )]
+    async fn rename_bulk(&self, Parameters(req): Parameters<RenameBulkRequest>) -> ToolResult {
+        // Dry runs only report, so they stay available in read-only mode
+        if !*req.dry_run() {
+            self.ensure_writable("rename_bulk")?;
+        }
+        req.validate()?;
+        let pattern = req.pattern()?;
+        let valid_path = validate_path(req.directory(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .rename_bulk(
+                &valid_path,
+                &pattern,
+                req.replacement(),
+                *req.recursive(),
+                *req.dry_run(),
+            )
+            .await?;
+        if !*req.dry_run() {
+            self.metrics.record_moves(result.renamed.len() as u64);
+        }
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Split a large file into numbered chunk files <output_prefix>.001, <output_prefix>.002, ... Set exactly one of chunk_size_bytes (every chunk has exactly that many bytes) or chunk_lines (every chunk has exactly that many lines, line breaks included); only the last chunk may be shorter. Chunk files are overwritten if they exist, and at most 10000 are written. Joining the chunks in order with join_files restores the original byte for byte. Returns JSON {chunks: [{path, size}], total_bytes}. Only works within allowed directories."
+    )]
+    async fn split_file(&self, Parameters(req): Parameters<SplitFileRequest>) -> ToolResult {
+        self.ensure_writable("split_file")?;
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let valid_prefix = validate_path(req.output_prefix(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .split_file(&valid_path, &valid_prefix, req.chunk())
+            .await?;
+        self.metrics.record_read_bytes(result.total_bytes);
+        self.metrics.record_write(result.total_bytes);
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Concatenate files in the given order into output_path, creating or overwriting it; the reverse of split_file. output_path cannot be one of the inputs. Only works within allowed directories."
+    )]
+    async fn join_files(&self, Parameters(req): Parameters<JoinFilesRequest>) -> ToolResult {
+        self.ensure_writable("join_files")?;
+        req.validate()?;
+        let mut valid_inputs = Vec::with_capacity(req.input_paths().len());
+        for input in req.input_paths() {
+            valid_inputs.push(validate_path(input, &self.allowed_directories).await?);
+        }
+        let valid_output = validate_path(req.output_path(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .join_files(&valid_inputs, &valid_output)
+            .await?;
+        let size = result.size.unwrap_or_default();
+        self.metrics.record_read_bytes(size);
+        self.metrics.record_write(size);
         Ok(CallToolResult::success(vec![result.into()]))
     }
 
-    #[tool(description = "Search for files and directories matching a pattern")]
+    #[tool(
+        description = "Search for files and directories matching a glob pattern. Returns a JSON array of paths; with content_contains, only regular files containing that text are kept and each result is {path, matching_lines: [{line_number, line}]}."
+    )]
     async fn search_files(&self, Parameters(req): Parameters<SearchFilesRequest>) -> ToolResult {
         req.validate()?;
         let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
@@ -438,68 +1020,410 @@ impl FileSystemService {
                 req.pattern(),
                 &self.allowed_directories,
                 req.exclude_patterns(),
+                *req.follow_symlinks(),
+                req.content_contains().as_deref(),
             )
             .await?;
         Ok(CallToolResult::success(vec![result.into()]))
     }
 
-    #[tool(description = "Retrieve detailed metadata about a file or directory")]
-    async fn get_file_info(&self, Parameters(req): Parameters<GetFileInfoRequest>) -> ToolResult {
+    #[tool(
+        description = "Find files modified within a time window. after and before are optional RFC 3339 timestamps (e.g. 2024-01-31T09:00:00Z) and both bounds are inclusive. Searches recursively and returns a JSON array of {path, modified}, newest first. Only works within allowed directories."
+    )]
+    async fn find_files_by_date_range(
+        &self,
+        Parameters(req): Parameters<FindFilesByDateRangeRequest>,
+    ) -> ToolResult {
         req.validate()?;
+        let (after, before) = req.time_range()?;
         let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
-        let result = self.file_operations.get_file_info(&valid_path).await?;
+        let result = self
+            .file_operations
+            .find_files_by_date_range(&valid_path, after, before, req.exclude_patterns())
+            .await?;
         Ok(CallToolResult::success(vec![result.into()]))
     }
 
-    #[tool(description = "Returns the list of directories that this server is allowed to access")]
-    async fn list_allowed_directories(
+    #[tool(
+        description = "Export a flat JSON inventory of every file under a directory, for snapshots and diffing. Returns an array of {path, size, modified, sha256} sorted by path, where path is relative to the directory and modified is in Unix seconds. SHA-256 digests are computed only when include_hashes is true. Only works within allowed directories."
+    )]
+    async fn export_directory_manifest(
         &self,
-        Parameters(_req): Parameters<ListAllowedDirectoriesRequest>,
+        Parameters(req): Parameters<ExportDirectoryManifestRequest>,
     ) -> ToolResult {
-        let directories: Vec<String> = self
-            .allowed_directories
-            .iter()
-            .map(|p| p.to_string_lossy().to_string())
-            .collect();
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .export_directory_manifest(&valid_path, *req.include_hashes(), req.exclude_patterns())
+            .await?;
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
 
-        let result = format!("Allowed directories:\n{}", directories.join("\n"));
-        Ok(CallToolResult::success(vec![Content::text(result)]))
+    #[tool(
+        description = "Compare the files under two directories by relative path. Returns JSON with only_in_a, only_in_b, different and identical path lists, sorted and relative to each directory. Files present in both are compared by size, and by SHA-256 digest as well when compare_content is true. Only works within allowed directories."
+    )]
+    async fn compare_directories(
+        &self,
+        Parameters(req): Parameters<CompareDirectoriesRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let path_a = validate_path(req.path_a(), &self.allowed_directories).await?;
+        let path_b = validate_path(req.path_b(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .compare_directories(&path_a, &path_b, *req.compare_content())
+            .await?;
+        Ok(CallToolResult::success(vec![result.into()]))
     }
-}
 
-#[tool_handler]
-impl ServerHandler for FileSystemService {
-    fn get_info(&self) -> ServerInfo {
-        ServerInfo {
-            protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder()
-                .enable_tools()
-                .enable_resources()
-                .build(),
-            server_info: Implementation::from_build_env(),
-            instructions: Some("FileSystem MCP Server for secure file operations. Tools: read_text_file, read_media_file, read_multiple_files, write_file, edit_file, create_directory, list_directory, list_directory_with_sizes, directory_tree, move_file, search_files, get_file_info, list_allowed_directories. All operations are restricted to allowed directories for security. Resources: fs://status, fs://help, fs://allowed-directories.".to_string()),
-        }
+    #[tool(
+        description = "Parse a CSV file into JSON. With has_header (default true) the first row names the columns and each row becomes an object; otherwise each row is an array of strings. Quoted fields may contain delimiters and newlines. Returns at most max_rows rows (default 1000). Only works within allowed directories."
+    )]
+    async fn read_csv_file(&self, Parameters(req): Parameters<ReadCsvFileRequest>) -> ToolResult {
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .read_csv_file(
+                &valid_path,
+                *req.has_header(),
+                req.delimiter_byte(),
+                req.row_limit(),
+            )
+            .await?;
+        self.record_file_read(&valid_path).await;
+        Ok(CallToolResult::success(vec![result.into()]))
     }
 
-    async fn list_resources(
+    #[tool(
+        description = "Parse a .env file into JSON. Handles # comments, blank lines, the export prefix, single- and double-quoted values, multi-line values and ${VAR} references to variables defined earlier in the file. Returns an object with variables (name to value) and warnings for lines that were skipped, such as lines without '='. Files larger than the server's read limit are rejected. Only works within allowed directories."
+    )]
+    async fn read_env_file(&self, Parameters(req): Parameters<ReadEnvFileRequest>) -> ToolResult {
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self.file_operations.read_env_file(&valid_path).await?;
+        self.record_file_read(&valid_path).await;
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Parse a newline-delimited JSON (.jsonl) file into a JSON array with one value per non-empty line. Lines that are not valid JSON (including invalid UTF-8) appear as {\"_raw\": line, \"_parse_error\": message} objects instead of failing the read. Reading stops at the server's read limit and sets truncated. Use start_line and end_line (1-based, inclusive) to read part of a large file. Only works within allowed directories."
+    )]
+    async fn read_file_as_jsonl(
         &self,
-        _request: Option<PaginatedRequestParam>,
-        _: RequestContext<RoleServer>,
-    ) -> Result<ListResourcesResult, McpError> {
-        Ok(ListResourcesResult {
-            resources: vec![
-                self.create_resource_text("fs://status", "server-status"),
-                self.create_resource_text("fs://help", "help-documentation"),
-                self.create_resource_text("fs://allowed-directories", "allowed-directories-list"),
-            ],
-            next_cursor: None,
-        })
+        Parameters(req): Parameters<ReadJsonlFileRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .read_file_as_jsonl(&valid_path, *req.start_line(), *req.end_line())
+            .await?;
+        self.metrics.record_read(&result);
+        Ok(CallToolResult::success(vec![result.into()]))
     }
 
-    async fn read_resource(
+    #[tool(
+        description = "Parse a YAML file and return it as pretty-printed JSON (default) or TOML (output_format: 'toml'). A file with several '---' separated documents becomes a JSON array. Merge keys are applied and non-string keys become strings. TOML output needs a single document whose top level is a mapping without null values. Files larger than the server's read limit are rejected. Only works within allowed directories."
+    )]
+    async fn parse_yaml_file(
         &self,
-        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
-        _: RequestContext<RoleServer>,
+        Parameters(req): Parameters<ParseYamlFileRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let output = self
+            .file_operations
+            .parse_yaml_file(&valid_path, req.output_format().unwrap_or_default())
+            .await?;
+        self.record_file_read(&valid_path).await;
+        Ok(CallToolResult::success(vec![Content::text(output)]))
+    }
+
+    #[tool(
+        description = "Convert YAML text to pretty-printed JSON without touching the filesystem. Several '---' separated documents become a JSON array. Merge keys are applied and non-string keys become strings."
+    )]
+    async fn convert_yaml_to_json(
+        &self,
+        Parameters(req): Parameters<ConvertYamlToJsonRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let json = convert_yaml(
+            req.yaml_content(),
+            YamlOutputFormat::Json,
+            "",
+            "convert_yaml_to_json",
+        )?;
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Wait for a file to change. Polls the file's modification time every poll_interval_ms (minimum 100) for up to timeout_ms (maximum 300000) and returns as soon as it changes; creating or deleting the file also counts. Returns JSON with changed and detected_at (RFC 3339). Only works within allowed directories."
+    )]
+    async fn watch_file(&self, Parameters(req): Parameters<WatchFileRequest>) -> ToolResult {
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let response = self
+            .file_operations
+            .watch_file(&valid_path, req.poll_interval(), req.timeout())
+            .await?;
+        Ok(CallToolResult::success(vec![response.into()]))
+    }
+
+    #[tool(description = "Retrieve detailed metadata about a file or directory")]
+    async fn get_file_info(&self, Parameters(req): Parameters<GetFileInfoRequest>) -> ToolResult {
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self.file_operations.get_file_info(&valid_path).await?;
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Create a new, uniquely named empty file for scratch use. Defaults to the first allowed directory. Returns the created path. Only works within allowed directories."
+    )]
+    async fn create_temp_file(
+        &self,
+        Parameters(req): Parameters<CreateTempFileRequest>,
+    ) -> ToolResult {
+        self.ensure_writable("create_temp_file")?;
+        req.validate()?;
+        let directory = self
+            .resolve_temp_directory(req.directory().as_deref(), "create_temp_file")
+            .await?;
+        let path = self
+            .file_operations
+            .create_temp_file(&directory, req.prefix().as_deref(), req.suffix().as_deref())
+            .await?;
+        let path = validate_path(&path.to_string_lossy(), &self.allowed_directories).await?;
+        Ok(CallToolResult::success(vec![
+            TempPathResponse::created(&path).into(),
+        ]))
+    }
+
+    #[tool(
+        description = "Create a new, uniquely named empty directory for scratch use. Defaults to the first allowed directory. Returns the created path. Only works within allowed directories."
+    )]
+    async fn create_temp_directory(
+        &self,
+        Parameters(req): Parameters<CreateTempDirectoryRequest>,
+    ) -> ToolResult {
+        self.ensure_writable("create_temp_directory")?;
+        req.validate()?;
+        let directory = self
+            .resolve_temp_directory(req.directory().as_deref(), "create_temp_directory")
+            .await?;
+        let path = self
+            .file_operations
+            .create_temp_directory(&directory, req.prefix().as_deref(), req.suffix().as_deref())
+            .await?;
+        let path = validate_path(&path.to_string_lossy(), &self.allowed_directories).await?;
+        Ok(CallToolResult::success(vec![
+            TempPathResponse::created(&path).into(),
+        ]))
+    }
+
+    #[tool(
+        description = "Create or overwrite a file with exactly size_bytes bytes (at most 100 MB) for testing. pattern selects the content: 'zeros', 'random' (default), 'lorem' (repeated Lorem Ipsum text) or 'ascii' (random printable characters). Only works within allowed directories."
+    )]
+    async fn generate_random_file(
+        &self,
+        Parameters(req): Parameters<GenerateRandomFileRequest>,
+    ) -> ToolResult {
+        self.ensure_writable("generate_random_file")?;
+        req.validate()?;
+        let valid_path = validate_path(req.path(), &self.allowed_directories).await?;
+        let result = self
+            .file_operations
+            .generate_file(
+                &valid_path,
+                *req.size_bytes(),
+                req.pattern().unwrap_or_default(),
+            )
+            .await?;
+        self.metrics.record_write(*req.size_bytes());
+        Ok(CallToolResult::success(vec![result.into()]))
+    }
+
+    #[tool(
+        description = "Returns the directories this server is allowed to access as JSON with total and items. With extended_info, each item also reports canonical_path, exists, readable, writable and available_bytes (free space on its filesystem)."
+    )]
+    async fn list_allowed_directories(
+        &self,
+        Parameters(req): Parameters<ListAllowedDirectoriesRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let items = if *req.extended_info() {
+            let described = self
+                .allowed_directories
+                .iter()
+                .map(|dir| describe_directory(dir, !self.read_only));
+            futures::future::join_all(described).await
+        } else {
+            self.allowed_directories
+                .iter()
+                .map(|dir| AllowedDirectoryInfo {
+                    path: dir.display().to_string(),
+                    ..Default::default()
+                })
+                .collect()
+        };
+
+        Ok(CallToolResult::success(vec![
+            AllowedDirectoriesResponse {
+                total: items.len(),
+                items,
+            }
+            .into(),
+        ]))
+    }
+
+    #[tool(
+        description = "Explain how a path resolves without accessing it. Expands ~, resolves relative paths against base_directory (or the server's current directory) and removes . and .. components. Returns JSON with the resolved path, whether it lies inside an allowed directory, and its components."
+    )]
+    async fn normalize_path(
+        &self,
+        Parameters(req): Parameters<NormalizePathRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let current_dir = std::env::current_dir().map_err(|e| FileSystemMcpError::IoError {
+            message: format!("Failed to get current directory: {}", e),
+            path: req.raw_path().clone(),
+        })?;
+        let base_directory = match req.base_directory() {
+            Some(base) => resolve_relative_path(base, &current_dir),
+            None => current_dir,
+        };
+
+        let resolved = resolve_relative_path(req.raw_path(), &base_directory);
+        let is_allowed = is_resolved_path_allowed(&resolved, &self.allowed_directories);
+
+        let response = NormalizedPathResponse {
+            resolved: resolved.display().to_string(),
+            is_allowed,
+            components: resolved
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect(),
+        };
+        Ok(CallToolResult::success(vec![response.into()]))
+    }
+
+    #[tool(
+        description = "Summarize a unified diff (git diff or diff -u output) without touching the filesystem. Counts added and removed lines per file and returns JSON with files_changed [{file, additions, deletions}], total_additions, total_deletions and a one-sentence summary."
+    )]
+    async fn summarize_diff(
+        &self,
+        Parameters(req): Parameters<SummarizeDiffRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let response = summarize_unified_diff(req.diff_text());
+        Ok(CallToolResult::success(vec![response.into()]))
+    }
+
+    #[tool(
+        description = "Copy text to the system clipboard, replacing its contents. Only available when the server runs with --enable-clipboard; fails when no clipboard is available, e.g. on a headless machine."
+    )]
+    async fn copy_to_clipboard(
+        &self,
+        Parameters(req): Parameters<CopyToClipboardRequest>,
+    ) -> ToolResult {
+        req.validate()?;
+        let clipboard = self.clipboard("copy_to_clipboard")?;
+        let content = req.content().clone();
+        let size = content.len() as u64;
+        tokio::task::spawn_blocking(move || clipboard.set_text(&content))
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))??;
+        self.metrics.record_write(size);
+
+        let response = WriteFileResponse::new(
+            format!("Copied {} bytes to the clipboard", size),
+            "clipboard".to_string(),
+            Some(size),
+            false,
+        );
+        Ok(CallToolResult::success(vec![response.into()]))
+    }
+
+    #[tool(
+        description = "Read the text on the system clipboard. Returns empty text when the clipboard holds nothing or no text. Only available when the server runs with --enable-clipboard; fails when no clipboard is available, e.g. on a headless machine."
+    )]
+    async fn paste_from_clipboard(&self) -> ToolResult {
+        let clipboard = self.clipboard("paste_from_clipboard")?;
+        let text = tokio::task::spawn_blocking(move || clipboard.get_text())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))??;
+        let response = ReadFileResponse::text(text);
+        self.metrics.record_read(&response);
+
+        Ok(CallToolResult::success(vec![response.into()]))
+    }
+}
+
+// `call_tool` is written out instead of generated by `#[tool_handler]` so every
+// invocation goes through the audit log
+impl ServerHandler for FileSystemService {
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let tool = request.name.clone();
+        let arguments = request.arguments.clone();
+        let call = ToolCallContext::new(self, request, context);
+        let result = audit_tool_call(
+            &tool,
+            arguments.as_ref(),
+            &self.allowed_directories,
+            self.tool_router.call(call),
+        )
+        .await;
+        if !result
+            .as_ref()
+            .is_ok_and(|result| result.is_error != Some(true))
+        {
+            self.metrics.record_error();
+        }
+        result
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some("FileSystem MCP Server for secure file operations. Tools: read_text_file, read_media_file, read_file_as_base64, read_file_between_markers, read_multiple_files, write_file, edit_file, generate_random_file, create_directory, list_directory, list_directory_with_sizes, directory_tree, generate_file_tree_markdown, move_file, prune_empty_directories, rename_bulk, split_file, join_files, search_files, find_files_by_date_range, export_directory_manifest, compare_directories, read_csv_file, read_env_file, read_file_as_jsonl, parse_yaml_file, watch_file, get_file_info, list_allowed_directories, normalize_path, summarize_diff, convert_yaml_to_json, copy_to_clipboard, paste_from_clipboard. All operations are restricted to allowed directories for security; tools that modify files are rejected when the server runs with --read-only, and the clipboard tools only work with --enable-clipboard. Resources: fs://status, fs://help, fs://allowed-directories, fs://system-info, fs://capabilities. Resource templates: fs://file/{path} (full text file; subscribe for change notifications), fs://tail/{path}?lines={n} (last n lines of a file).".to_string()),
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: self.resources(),
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
     ) -> Result<ReadResourceResult, McpError> {
         match uri.as_str() {
             "fs://status" => {
@@ -520,12 +1444,37 @@ impl ServerHandler for FileSystemService {
                     contents: vec![ResourceContents::text(directories, uri)],
                 })
             }
+            "fs://system-info" => {
+                let system_info = self.generate_system_info_content()?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(system_info, uri)],
+                })
+            }
+            "fs://capabilities" => {
+                let capabilities = self.generate_capabilities_content();
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(capabilities, uri)],
+                })
+            }
+            file_uri if file_uri.starts_with(FILE_RESOURCE_PREFIX) => {
+                let text = self.read_file_resource(file_uri).await?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(text, uri)],
+                })
+            }
+            tail_uri if tail_uri.starts_with(TAIL_RESOURCE_PREFIX) => {
+                let tail = self.read_tail_resource(tail_uri).await?;
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(tail, uri)],
+                })
+            }
             _ => Err(FileSystemMcpError::ValidationError {
                 message: format!("Resource not found: {}", uri),
                 path: uri.to_string(),
                 operation: "read_resource".to_string(),
                 data: serde_json::json!({
-                    "available_resources": ["fs://status", "fs://help", "fs://allowed-directories"]
+                    "available_resources": RESOURCES.iter().map(|(uri, _)| *uri).collect::<Vec<_>>(),
+                    "resource_templates": [FILE_RESOURCE_TEMPLATE, TAIL_RESOURCE_TEMPLATE]
                 }),
             }
             .into()),
@@ -539,10 +1488,60 @@ impl ServerHandler for FileSystemService {
     ) -> Result<ListResourceTemplatesResult, McpError> {
         Ok(ListResourceTemplatesResult {
             next_cursor: None,
-            resource_templates: Vec::new(),
+            resource_templates: vec![
+                RawResourceTemplate {
+                    uri_template: FILE_RESOURCE_TEMPLATE.to_string(),
+                    name: "file".to_string(),
+                    title: Some("File".to_string()),
+                    description: Some(
+                        "Full content of a text file. Subscribe to receive \
+                         notifications/resources/updated whenever the file changes. \
+                         path is the percent-encoded file path and must be inside an allowed directory."
+                            .to_string(),
+                    ),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+                RawResourceTemplate {
+                    uri_template: TAIL_RESOURCE_TEMPLATE.to_string(),
+                    name: "file-tail".to_string(),
+                    title: Some("File tail".to_string()),
+                    description: Some(
+                        "Last n lines of a text file (default 10), read fresh on every request. \
+                         path is the percent-encoded file path and must be inside an allowed directory."
+                            .to_string(),
+                    ),
+                    mime_type: Some("text/plain".to_string()),
+                }
+                .no_annotation(),
+            ],
         })
     }
 
+    async fn subscribe(
+        &self,
+        SubscribeRequestParam { uri }: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        let path = self.file_resource_path(&uri, "subscribe").await?;
+        let watcher = spawn_file_watcher(uri.clone(), path, context.peer);
+        if let Some(previous) = self.subscriptions.insert(uri, watcher) {
+            previous.abort();
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParam { uri }: UnsubscribeRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        if let Some((_, watcher)) = self.subscriptions.remove(&uri) {
+            watcher.abort();
+        }
+        Ok(())
+    }
+
     async fn initialize(
         &self,
         _request: InitializeRequestParam,
@@ -560,3 +1559,1493 @@ impl fmt::Debug for FileSystemService {
             .finish()
     }
 }
+
+impl Drop for FileSystemService {
+    /// Stop watching subscribed files once the session's service goes away
+    fn drop(&mut self) {
+        for subscription in self.subscriptions.iter() {
+            subscription.value().abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use regex::Regex;
+    use rmcp::{handler::server::wrapper::Parameters, model::RawContent};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{
+        errors::FileSystemMcpResult,
+        models::{
+            requests::{EditOperation, FillPattern, MAX_GENERATED_FILE_BYTES, SortBy, SplitChunk},
+            responses::{
+                Base64FileResponse, BulkRenameResponse, CompareDirectoriesResponse,
+                ContentSearchMatch, FileContent, ReadFileResponse, SplitFileResponse,
+                WatchFileResponse, WriteFileResponse,
+            },
+        },
+    };
+
+    /// File operations that serve canned content for whole-file reads and use the disk otherwise
+    struct MockFileOperations {
+        inner: FileService,
+    }
+
+    #[async_trait]
+    impl FileOperations for MockFileOperations {
+        async fn read_entire_file(&self, _path: &Path) -> FileSystemMcpResult<ReadFileResponse> {
+            Ok(ReadFileResponse {
+                content: FileContent::Text("from the mock".to_string()),
+                mime_type: "text/plain".to_string(),
+                truncated: false,
+                line_count: 1,
+                size_bytes: 13,
+            })
+        }
+
+        async fn read_file_head(
+            &self,
+            path: &Path,
+            lines: usize,
+        ) -> FileSystemMcpResult<ReadFileResponse> {
+            self.inner.read_file_head(path, lines).await
+        }
+
+        async fn read_file_tail(
+            &self,
+            path: &Path,
+            lines: usize,
+        ) -> FileSystemMcpResult<ReadFileResponse> {
+            self.inner.read_file_tail(path, lines).await
+        }
+
+        async fn read_media_file(&self, path: &Path) -> FileSystemMcpResult<ReadFileResponse> {
+            self.inner.read_media_file(path).await
+        }
+
+        async fn read_file_base64(&self, path: &Path) -> FileSystemMcpResult<Base64FileResponse> {
+            self.inner.read_file_base64(path).await
+        }
+
+        async fn read_file_between_markers(
+            &self,
+            path: &Path,
+            start_marker: &str,
+            end_marker: &str,
+            include_markers: bool,
+            occurrence: usize,
+        ) -> FileSystemMcpResult<ReadFileResponse> {
+            self.inner
+                .read_file_between_markers(
+                    path,
+                    start_marker,
+                    end_marker,
+                    include_markers,
+                    occurrence,
+                )
+                .await
+        }
+
+        async fn read_files(
+            &self,
+            paths: &[PathBuf],
+        ) -> Vec<FileSystemMcpResult<ReadFileResponse>> {
+            self.inner.read_files(paths).await
+        }
+
+        async fn write_file(
+            &self,
+            path: &Path,
+            content: &str,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.write_file(path, content).await
+        }
+
+        async fn create_directory(&self, path: &Path) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.create_directory(path).await
+        }
+
+        async fn list_directory(&self, path: &Path) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.list_directory(path).await
+        }
+
+        async fn list_directory_with_sizes(
+            &self,
+            path: &Path,
+            sort_by: &SortBy,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.list_directory_with_sizes(path, sort_by).await
+        }
+
+        async fn directory_tree(
+            &self,
+            path: &Path,
+            exclude_patterns: &[String],
+            follow_symlinks: bool,
+            max_depth: Option<usize>,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner
+                .directory_tree(path, exclude_patterns, follow_symlinks, max_depth)
+                .await
+        }
+
+        async fn file_tree_markdown(
+            &self,
+            path: &Path,
+            exclude_patterns: &[String],
+            max_depth: Option<usize>,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner
+                .file_tree_markdown(path, exclude_patterns, max_depth)
+                .await
+        }
+
+        async fn move_file(
+            &self,
+            from: &Path,
+            to: &Path,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.move_file(from, to).await
+        }
+
+        async fn prune_empty_directories(
+            &self,
+            path: &Path,
+            dry_run: bool,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.prune_empty_directories(path, dry_run).await
+        }
+
+        async fn rename_bulk(
+            &self,
+            directory: &Path,
+            pattern: &Regex,
+            replacement: &str,
+            recursive: bool,
+            dry_run: bool,
+        ) -> FileSystemMcpResult<BulkRenameResponse> {
+            self.inner
+                .rename_bulk(directory, pattern, replacement, recursive, dry_run)
+                .await
+        }
+
+        async fn search_files(
+            &self,
+            path: &Path,
+            pattern: &str,
+            allowed_directories: &[PathBuf],
+            exclude_patterns: &[String],
+            follow_symlinks: bool,
+            content_contains: Option<&str>,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner
+                .search_files(
+                    path,
+                    pattern,
+                    allowed_directories,
+                    exclude_patterns,
+                    follow_symlinks,
+                    content_contains,
+                )
+                .await
+        }
+
+        async fn find_files_by_date_range(
+            &self,
+            path: &Path,
+            after: Option<std::time::SystemTime>,
+            before: Option<std::time::SystemTime>,
+            exclude_patterns: &[String],
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner
+                .find_files_by_date_range(path, after, before, exclude_patterns)
+                .await
+        }
+
+        async fn read_csv_file(
+            &self,
+            path: &Path,
+            has_header: bool,
+            delimiter: u8,
+            max_rows: usize,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner
+                .read_csv_file(path, has_header, delimiter, max_rows)
+                .await
+        }
+
+        async fn read_env_file(&self, path: &Path) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.read_env_file(path).await
+        }
+
+        async fn read_file_as_jsonl(
+            &self,
+            path: &Path,
+            start_line: Option<usize>,
+            end_line: Option<usize>,
+        ) -> FileSystemMcpResult<ReadFileResponse> {
+            self.inner
+                .read_file_as_jsonl(path, start_line, end_line)
+                .await
+        }
+
+        async fn parse_yaml_file(
+            &self,
+            path: &Path,
+            format: YamlOutputFormat,
+        ) -> FileSystemMcpResult<String> {
+            self.inner.parse_yaml_file(path, format).await
+        }
+
+        async fn split_file(
+            &self,
+            path: &Path,
+            output_prefix: &Path,
+            chunk: SplitChunk,
+        ) -> FileSystemMcpResult<SplitFileResponse> {
+            self.inner.split_file(path, output_prefix, chunk).await
+        }
+
+        async fn join_files(
+            &self,
+            inputs: &[PathBuf],
+            output: &Path,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.join_files(inputs, output).await
+        }
+
+        async fn generate_file(
+            &self,
+            path: &Path,
+            size_bytes: u64,
+            pattern: FillPattern,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.generate_file(path, size_bytes, pattern).await
+        }
+
+        async fn export_directory_manifest(
+            &self,
+            path: &Path,
+            include_hashes: bool,
+            exclude_patterns: &[String],
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner
+                .export_directory_manifest(path, include_hashes, exclude_patterns)
+                .await
+        }
+
+        async fn compare_directories(
+            &self,
+            path_a: &Path,
+            path_b: &Path,
+            compare_content: bool,
+        ) -> FileSystemMcpResult<CompareDirectoriesResponse> {
+            self.inner
+                .compare_directories(path_a, path_b, compare_content)
+                .await
+        }
+
+        async fn watch_file(
+            &self,
+            path: &Path,
+            poll_interval: Duration,
+            timeout: Duration,
+        ) -> FileSystemMcpResult<WatchFileResponse> {
+            self.inner.watch_file(path, poll_interval, timeout).await
+        }
+
+        async fn get_file_info(&self, path: &Path) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.get_file_info(path).await
+        }
+
+        async fn apply_file_edits(
+            &self,
+            path: &Path,
+            edits: &[EditOperation],
+            dry_run: &bool,
+        ) -> FileSystemMcpResult<WriteFileResponse> {
+            self.inner.apply_file_edits(path, edits, dry_run).await
+        }
+
+        async fn create_temp_file(
+            &self,
+            directory: &Path,
+            prefix: Option<&str>,
+            suffix: Option<&str>,
+        ) -> FileSystemMcpResult<PathBuf> {
+            self.inner.create_temp_file(directory, prefix, suffix).await
+        }
+
+        async fn create_temp_directory(
+            &self,
+            directory: &Path,
+            prefix: Option<&str>,
+            suffix: Option<&str>,
+        ) -> FileSystemMcpResult<PathBuf> {
+            self.inner
+                .create_temp_directory(directory, prefix, suffix)
+                .await
+        }
+    }
+
+    fn text_of(result: &CallToolResult) -> String {
+        match &result.content[0].raw {
+            RawContent::Text(text) => text.text.clone(),
+            other => panic!("unexpected content: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_injected_file_operations_are_used() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("real.txt");
+        tokio::fs::write(&file, "from disk").await.unwrap();
+
+        let request: ReadTextFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file})).unwrap();
+
+        let service = FileSystemService::with_file_operations(
+            vec![allowed.clone()],
+            Arc::new(MockFileOperations {
+                inner: FileService::new(),
+            }),
+        );
+        let result = service.read_text_file(Parameters(request)).await.unwrap();
+        let response: ReadFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(
+            response.content,
+            FileContent::Text("from the mock".to_string())
+        );
+
+        // Methods the mock doesn't override still reach the disk
+        let request: ReadTextFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file, "head": 1})).unwrap();
+        let result = service.read_text_file(Parameters(request)).await.unwrap();
+        let response: ReadFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.content, FileContent::Text("from disk".to_string()));
+
+        let request: ReadTextFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file})).unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+        let result = service.read_text_file(Parameters(request)).await.unwrap();
+        let response: ReadFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.content, FileContent::Text("from disk".to_string()));
+        assert_eq!(response.mime_type, "text/plain");
+        assert!(!response.truncated);
+        assert_eq!((response.line_count, response.size_bytes), (1, 9));
+    }
+
+    #[tokio::test]
+    async fn test_write_file_returns_json_response() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("out.txt");
+        tokio::fs::write(&file, "old").await.unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+
+        let request: WriteFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file, "content": "hello"})).unwrap();
+        let result = service.write_file(Parameters(request)).await.unwrap();
+
+        let response: WriteFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.path, file.display().to_string());
+        assert_eq!(response.size, Some(5));
+        assert!(!response.created);
+    }
+
+    #[tokio::test]
+    async fn test_edit_file_regex_edits() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("main.rs");
+        tokio::fs::write(&file, "let a = foo(1);\nlet b = foo(2);\n")
+            .await
+            .unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+
+        let request: EditFileRequest = serde_json::from_value(serde_json::json!({
+            "path": file,
+            "edits": [
+                {"kind": "regex", "pattern": r"foo\((\d)\)", "replacement": "bar($1, true)", "flags": "g"},
+                {"old_text": "let b", "new_text": "let c"}
+            ]
+        }))
+        .unwrap();
+        service.edit_file(Parameters(request)).await.unwrap();
+        assert_eq!(
+            tokio::fs::read_to_string(&file).await.unwrap(),
+            "let a = bar(1, true);\nlet c = bar(2, true);\n"
+        );
+
+        for (pattern, flags) in [("foo(", "g"), ("foo", "x")] {
+            let request: EditFileRequest = serde_json::from_value(serde_json::json!({
+                "path": file,
+                "edits": [{"kind": "regex", "pattern": pattern, "replacement": "bar", "flags": flags}]
+            }))
+            .unwrap();
+            let error = service.edit_file(Parameters(request)).await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+            assert_eq!(error.data.unwrap()["data"]["edit_index"], 0);
+        }
+
+        // A regex edit must say so; kind falls back to text, which needs old_text
+        let untagged = serde_json::from_value::<EditFileRequest>(serde_json::json!({
+            "path": file,
+            "edits": [{"pattern": "foo", "replacement": "bar"}]
+        }))
+        .unwrap_err();
+        assert!(untagged.to_string().contains("old_text"), "{untagged}");
+        let unknown = serde_json::from_value::<EditFileRequest>(serde_json::json!({
+            "path": file,
+            "edits": [{"kind": "glob", "pattern": "foo", "replacement": "bar"}]
+        }))
+        .unwrap_err();
+        assert!(unknown.to_string().contains("unknown variant"), "{unknown}");
+    }
+
+    #[tokio::test]
+    async fn test_search_files_content_contains() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        tokio::fs::write(allowed.join("with.txt"), "alpha\nneedle here\n")
+            .await
+            .unwrap();
+        tokio::fs::write(allowed.join("without.txt"), "alpha\nbeta\n")
+            .await
+            .unwrap();
+        let service = FileSystemService::new(vec![allowed.clone()], None);
+
+        let request: SearchFilesRequest = serde_json::from_value(serde_json::json!({
+            "path": allowed,
+            "pattern": "*.txt",
+            "content_contains": "needle"
+        }))
+        .unwrap();
+        let result = service.search_files(Parameters(request)).await.unwrap();
+        let response: WriteFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        let matches: Vec<ContentSearchMatch> = serde_json::from_str(&response.message).unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            matches[0].path,
+            allowed.join("with.txt").display().to_string()
+        );
+        assert_eq!(matches[0].matching_lines[0].line_number, 2);
+        assert_eq!(matches[0].matching_lines[0].line, "needle here");
+
+        let request: SearchFilesRequest = serde_json::from_value(serde_json::json!({
+            "path": allowed,
+            "pattern": "*.txt",
+            "content_contains": ""
+        }))
+        .unwrap();
+        let error = service.search_files(Parameters(request)).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_compare_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let outside = TempDir::new().unwrap();
+        for dir in ["a", "b"] {
+            tokio::fs::create_dir(allowed.join(dir)).await.unwrap();
+            tokio::fs::write(allowed.join(dir).join("shared.txt"), dir)
+                .await
+                .unwrap();
+        }
+        tokio::fs::write(allowed.join("a/only.txt"), "a")
+            .await
+            .unwrap();
+        let service = FileSystemService::new(vec![allowed.clone()], None);
+
+        let request: CompareDirectoriesRequest = serde_json::from_value(serde_json::json!({
+            "path_a": allowed.join("a"),
+            "path_b": allowed.join("b"),
+            "compare_content": true
+        }))
+        .unwrap();
+        let result = service
+            .compare_directories(Parameters(request))
+            .await
+            .unwrap();
+        let response: CompareDirectoriesResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.only_in_a, vec!["only.txt"]);
+        assert!(response.only_in_b.is_empty());
+        assert_eq!(response.different, vec!["shared.txt"]);
+        assert!(response.identical.is_empty());
+
+        let request: CompareDirectoriesRequest = serde_json::from_value(serde_json::json!({
+            "path_a": allowed.join("a"),
+            "path_b": outside.path()
+        }))
+        .unwrap();
+        assert!(
+            service
+                .compare_directories(Parameters(request))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_yaml_tools_return_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("stream.yaml");
+        tokio::fs::write(&file, "---\nkind: a\n---\nkind: b\n")
+            .await
+            .unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+
+        let request: ParseYamlFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file})).unwrap();
+        let result = service.parse_yaml_file(Parameters(request)).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(parsed, serde_json::json!([{"kind": "a"}, {"kind": "b"}]));
+
+        let request: ConvertYamlToJsonRequest =
+            serde_json::from_value(serde_json::json!({"yaml_content": "list: [1, two]"})).unwrap();
+        let result = service
+            .convert_yaml_to_json(Parameters(request))
+            .await
+            .unwrap();
+        let converted: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(converted, serde_json::json!({"list": [1, "two"]}));
+
+        let request: ConvertYamlToJsonRequest =
+            serde_json::from_value(serde_json::json!({"yaml_content": "a: [b"})).unwrap();
+        assert!(
+            service
+                .convert_yaml_to_json(Parameters(request))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_file_line_endings() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("mixed.txt");
+        tokio::fs::write(&file, "").await.unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+        let mixed = "first\nsecond\r\nthird\n";
+
+        let write = |line_ending: Option<&str>| {
+            let mut request = serde_json::json!({"path": file, "content": mixed});
+            if let Some(line_ending) = line_ending {
+                request["line_ending"] = serde_json::json!(line_ending);
+            }
+            serde_json::from_value::<WriteFileRequest>(request).unwrap()
+        };
+
+        let result = service
+            .write_file(Parameters(write(Some("crlf"))))
+            .await
+            .unwrap();
+        let bytes = tokio::fs::read(&file).await.unwrap();
+        assert_eq!(bytes, b"first\r\nsecond\r\nthird\r\n");
+        // Every line feed on disk is part of a CRLF pair
+        assert!(
+            bytes
+                .iter()
+                .enumerate()
+                .all(|(i, &b)| b != b'\n' || (i > 0 && bytes[i - 1] == b'\r'))
+        );
+        let response: WriteFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.size, Some(bytes.len() as u64));
+
+        service.write_file(Parameters(write(None))).await.unwrap();
+        assert_eq!(
+            tokio::fs::read(&file).await.unwrap(),
+            b"first\nsecond\nthird\n"
+        );
+
+        service
+            .write_file(Parameters(write(Some("preserve"))))
+            .await
+            .unwrap();
+        assert_eq!(tokio::fs::read(&file).await.unwrap(), mixed.as_bytes());
+
+        let invalid = serde_json::json!({"path": "x", "content": "", "line_ending": "CRLF"});
+        assert!(serde_json::from_value::<WriteFileRequest>(invalid).is_err());
+    }
+
+    fn temp_path_of(result: &CallToolResult) -> PathBuf {
+        let response: serde_json::Value = serde_json::from_str(&text_of(result)).unwrap();
+        assert_eq!(response["created"], true);
+        PathBuf::from(response["path"].as_str().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_create_temp_file_in_default_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let service = FileSystemService::new(vec![allowed.clone()], None);
+
+        let request: CreateTempFileRequest =
+            serde_json::from_value(serde_json::json!({"prefix": "draft-", "suffix": ".md"}))
+                .unwrap();
+        let result = service.create_temp_file(Parameters(request)).await.unwrap();
+        let path = temp_path_of(&result);
+
+        assert!(path.is_file());
+        assert_eq!(path.parent(), Some(allowed.as_path()));
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("draft-") && name.ends_with(".md"));
+    }
+
+    #[tokio::test]
+    async fn test_create_temp_directory_in_requested_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let nested = allowed.join("scratch");
+        tokio::fs::create_dir(&nested).await.unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+
+        let request: CreateTempDirectoryRequest =
+            serde_json::from_value(serde_json::json!({"directory": nested})).unwrap();
+        let result = service
+            .create_temp_directory(Parameters(request))
+            .await
+            .unwrap();
+        let path = temp_path_of(&result);
+
+        assert!(path.is_dir());
+        assert_eq!(path.parent(), Some(nested.as_path()));
+    }
+
+    #[tokio::test]
+    async fn test_create_temp_entries_reject_outside_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let service = FileSystemService::new(vec![temp_dir.path().canonicalize().unwrap()], None);
+
+        let request: CreateTempFileRequest =
+            serde_json::from_value(serde_json::json!({"directory": outside.path()})).unwrap();
+        assert!(service.create_temp_file(Parameters(request)).await.is_err());
+
+        let request: CreateTempDirectoryRequest =
+            serde_json::from_value(serde_json::json!({"prefix": "../escape"})).unwrap();
+        assert!(
+            service
+                .create_temp_directory(Parameters(request))
+                .await
+                .is_err()
+        );
+        assert_eq!(std::fs::read_dir(outside.path()).unwrap().count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_system_info_resource_listed_and_readable() {
+        use rmcp::ServiceExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let service = FileSystemService::new(vec![temp_dir.path().canonicalize().unwrap()], None);
+
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            if let Ok(running) = service.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let resources = client.list_all_resources().await.unwrap();
+        assert!(resources.iter().any(|r| r.uri == "fs://system-info"));
+
+        let result = client
+            .read_resource(ReadResourceRequestParam {
+                uri: "fs://system-info".to_string(),
+            })
+            .await
+            .unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("Expected text resource contents");
+        };
+        let disks: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(disks.as_array().unwrap().len(), 1);
+        assert!(disks[0]["total_bytes"].as_u64().unwrap() > 0);
+        assert!(disks[0]["available_bytes"].is_u64());
+
+        client.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_status_resource_reports_runtime_counters() {
+        use rmcp::ServiceExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let service = FileSystemService::new(vec![allowed.clone()], None);
+
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            if let Ok(running) = service.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let file = allowed.join("notes.txt");
+        tokio::fs::write(&file, "").await.unwrap();
+        let call = |name: &'static str, arguments: serde_json::Value| CallToolRequestParam {
+            name: name.into(),
+            arguments: arguments.as_object().cloned(),
+        };
+        client
+            .call_tool(call(
+                "write_file",
+                serde_json::json!({"path": file, "content": "hello world"}),
+            ))
+            .await
+            .unwrap();
+        client
+            .call_tool(call("read_text_file", serde_json::json!({"path": file})))
+            .await
+            .unwrap();
+        let missing = allowed.join("missing.txt");
+        assert!(
+            client
+                .call_tool(call("read_text_file", serde_json::json!({"path": missing})))
+                .await
+                .is_err()
+        );
+        client
+            .call_tool(call(
+                "rename_bulk",
+                serde_json::json!({
+                    "directory": allowed,
+                    "match_pattern": "^notes",
+                    "replacement": "moved"
+                }),
+            ))
+            .await
+            .unwrap();
+
+        let result = client
+            .read_resource(ReadResourceRequestParam {
+                uri: "fs://status".to_string(),
+            })
+            .await
+            .unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("Expected text resource contents");
+        };
+        assert!(text.contains("- Files Read: 1\n"));
+        assert!(text.contains("- Bytes Read: 11\n"));
+        assert!(text.contains("- Files Written: 1\n"));
+        assert!(text.contains("- Files Moved: 1\n"));
+        assert!(text.contains("- Bytes Written: 11\n"));
+        assert!(text.contains("- Errors: 1\n"));
+        assert!(text.contains("- Uptime: "));
+
+        client.cancel().await.unwrap();
+    }
+
+    #[test]
+    fn test_status_counts_registered_tools_and_resources() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = FileSystemService::new(vec![temp_dir.path().to_path_buf()], None);
+        let status = service.generate_status_content();
+
+        let tools = FileSystemService::tool_router().list_all().len();
+        assert!(status.contains(&format!("Tools Available: {}\n", tools)));
+        assert!(status.contains(&format!("Resources Available: {}\n", RESOURCES.len())));
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_resource_matches_registered_tools() {
+        use rmcp::ServiceExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let service = FileSystemService::new(vec![temp_dir.path().canonicalize().unwrap()], None);
+
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            if let Ok(running) = service.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let result = client
+            .read_resource(ReadResourceRequestParam {
+                uri: "fs://capabilities".to_string(),
+            })
+            .await
+            .unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("Expected text resource contents");
+        };
+        let capabilities: serde_json::Value = serde_json::from_str(text).unwrap();
+        let tools = capabilities["tools"].as_array().unwrap();
+
+        assert_eq!(capabilities["protocol_version"], "2024-11-05");
+        assert_eq!(tools.len(), client.list_all_tools().await.unwrap().len());
+        assert!(tools.iter().all(|tool| tool["input_schema"].is_object()));
+
+        client.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tail_resource_template_reads_last_lines() {
+        use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+        use rmcp::ServiceExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let log = allowed.join("app log.txt");
+        let content: Vec<String> = (1..=15).map(|i| format!("line {i}")).collect();
+        tokio::fs::write(&log, content.join("\n")).await.unwrap();
+        let outside = TempDir::new().unwrap();
+        let secret = outside.path().join("secret.txt");
+        tokio::fs::write(&secret, "secret").await.unwrap();
+
+        let service = FileSystemService::new(vec![allowed], None);
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            if let Ok(running) = service.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let templates = client.list_all_resource_templates().await.unwrap();
+        assert!(
+            templates
+                .iter()
+                .any(|t| t.uri_template == TAIL_RESOURCE_TEMPLATE)
+        );
+
+        let encoded = |path: &Path| {
+            utf8_percent_encode(&path.to_string_lossy(), NON_ALPHANUMERIC).to_string()
+        };
+        let read = |uri: String| {
+            let client = &client;
+            async move {
+                let result = client
+                    .read_resource(ReadResourceRequestParam { uri })
+                    .await?;
+                let ResourceContents::TextResourceContents { text, .. } = &result.contents[0]
+                else {
+                    panic!("Expected text resource contents");
+                };
+                Ok::<_, rmcp::ServiceError>(text.clone())
+            }
+        };
+
+        let tail = read(format!("fs://tail/{}?lines=3", encoded(&log)))
+            .await
+            .unwrap();
+        assert_eq!(tail, "line 13\nline 14\nline 15");
+
+        // Without lines the default applies, and every read sees the current content
+        tokio::fs::write(&log, format!("{}\nline 16", content.join("\n")))
+            .await
+            .unwrap();
+        let tail = read(format!("fs://tail/{}", encoded(&log))).await.unwrap();
+        assert_eq!(tail.lines().count(), DEFAULT_TAIL_RESOURCE_LINES);
+        assert!(tail.ends_with("line 16"));
+
+        assert!(
+            read(format!("fs://tail/{}?lines=1", encoded(&secret)))
+                .await
+                .is_err()
+        );
+        assert!(
+            read(format!("fs://tail/{}?lines=many", encoded(&log)))
+                .await
+                .is_err()
+        );
+
+        client.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_subscription_notifies_on_change() {
+        use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+        use rmcp::{ClientHandler, RoleClient, ServiceExt, service::NotificationContext};
+        use tokio::sync::mpsc;
+
+        /// Client that forwards the URIs of resource updates to a channel
+        #[derive(Clone)]
+        struct UpdateListener(mpsc::UnboundedSender<String>);
+
+        impl ClientHandler for UpdateListener {
+            async fn on_resource_updated(
+                &self,
+                params: ResourceUpdatedNotificationParam,
+                _context: NotificationContext<RoleClient>,
+            ) {
+                let _ = self.0.send(params.uri);
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let notes = allowed.join("notes.md");
+        tokio::fs::write(&notes, "first draft").await.unwrap();
+
+        let service = FileSystemService::new(vec![allowed.clone()], None);
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            if let Ok(running) = service.serve(server_io).await {
+                let _ = running.waiting().await;
+            }
+        });
+        let (tx, mut updates) = mpsc::unbounded_channel();
+        let client = UpdateListener(tx).serve(client_io).await.unwrap();
+        assert!(
+            client
+                .peer_info()
+                .unwrap()
+                .capabilities
+                .resources
+                .as_ref()
+                .unwrap()
+                .subscribe
+                == Some(true)
+        );
+
+        let uri = format!(
+            "fs://file/{}",
+            utf8_percent_encode(&notes.to_string_lossy(), NON_ALPHANUMERIC)
+        );
+        client
+            .subscribe(SubscribeRequestParam { uri: uri.clone() })
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        tokio::fs::write(&notes, "second draft").await.unwrap();
+        let updated = tokio::time::timeout(Duration::from_secs(5), updates.recv())
+            .await
+            .expect("no resource update within 5 seconds")
+            .unwrap();
+        assert_eq!(updated, uri);
+
+        let result = client
+            .read_resource(ReadResourceRequestParam { uri: uri.clone() })
+            .await
+            .unwrap();
+        let ResourceContents::TextResourceContents { text, .. } = &result.contents[0] else {
+            panic!("Expected text resource contents");
+        };
+        assert_eq!(text, "second draft");
+
+        // After unsubscribing further changes are not reported
+        client
+            .unsubscribe(UnsubscribeRequestParam { uri })
+            .await
+            .unwrap();
+        tokio::fs::write(&notes, "third draft").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(updates.try_recv().is_err());
+
+        // Only files inside the allowed directories can be subscribed to
+        let outside = TempDir::new().unwrap();
+        let secret = outside.path().join("secret.txt");
+        tokio::fs::write(&secret, "secret").await.unwrap();
+        let outside_uri = format!(
+            "fs://file/{}",
+            utf8_percent_encode(&secret.to_string_lossy(), NON_ALPHANUMERIC)
+        );
+        assert!(
+            client
+                .subscribe(SubscribeRequestParam { uri: outside_uri })
+                .await
+                .is_err()
+        );
+        assert!(
+            client
+                .subscribe(SubscribeRequestParam {
+                    uri: "fs://status".to_string()
+                })
+                .await
+                .is_err()
+        );
+
+        client.cancel().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_find_files_by_date_range_rejects_bad_timestamps() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = FileSystemService::new(vec![temp_dir.path().canonicalize().unwrap()], None);
+        let path = temp_dir.path().display().to_string();
+
+        for arguments in [
+            serde_json::json!({"path": path, "after": "yesterday"}),
+            serde_json::json!({
+                "path": path,
+                "after": "2024-02-01T00:00:00Z",
+                "before": "2024-01-01T00:00:00Z"
+            }),
+        ] {
+            let request: FindFilesByDateRangeRequest = serde_json::from_value(arguments).unwrap();
+            assert!(
+                service
+                    .find_files_by_date_range(Parameters(request))
+                    .await
+                    .is_err()
+            );
+        }
+
+        let request: FindFilesByDateRangeRequest = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "after": "2024-01-01T00:00:00+02:00"
+        }))
+        .unwrap();
+        assert!(
+            service
+                .find_files_by_date_range(Parameters(request))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_normalize_path_reports_resolution() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        tokio::fs::create_dir(allowed.join("src")).await.unwrap();
+        let service = FileSystemService::new(vec![allowed.clone()], None);
+
+        let normalize = |arguments: serde_json::Value| {
+            let request: NormalizePathRequest = serde_json::from_value(arguments).unwrap();
+            let service = &service;
+            async move {
+                let result = service.normalize_path(Parameters(request)).await.unwrap();
+                serde_json::from_str::<serde_json::Value>(&text_of(&result)).unwrap()
+            }
+        };
+
+        // Absolute path inside the allowlist, including a file that does not exist yet
+        let absolute = allowed.join("src/./new.rs");
+        let response = normalize(serde_json::json!({"raw_path": absolute})).await;
+        assert_eq!(
+            response["resolved"],
+            allowed.join("src/new.rs").display().to_string()
+        );
+        assert_eq!(response["is_allowed"], true);
+        let components = response["components"].as_array().unwrap();
+        assert_eq!(components.last().unwrap(), "new.rs");
+
+        // Relative path resolved against the base directory
+        let response = normalize(serde_json::json!({
+            "raw_path": "./src/../lib",
+            "base_directory": allowed
+        }))
+        .await;
+        assert_eq!(
+            response["resolved"],
+            allowed.join("lib").display().to_string()
+        );
+        assert_eq!(response["is_allowed"], true);
+
+        // Traversal that stays inside the allowlist
+        let response = normalize(serde_json::json!({
+            "raw_path": "../src/main.rs",
+            "base_directory": allowed.join("src")
+        }))
+        .await;
+        assert_eq!(
+            response["resolved"],
+            allowed.join("src/main.rs").display().to_string()
+        );
+        assert_eq!(response["is_allowed"], true);
+
+        // Traversal that escapes the allowlist
+        let response = normalize(serde_json::json!({
+            "raw_path": "../../../../../../etc/passwd",
+            "base_directory": allowed.join("src")
+        }))
+        .await;
+        assert_eq!(response["is_allowed"], false);
+        assert!(response["resolved"].as_str().unwrap().ends_with("passwd"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_random_file_writes_requested_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("fixture.txt");
+        tokio::fs::write(&file, "placeholder").await.unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+
+        let request: GenerateRandomFileRequest = serde_json::from_value(serde_json::json!({
+            "path": file,
+            "size_bytes": 100_000,
+            "pattern": "lorem"
+        }))
+        .unwrap();
+        let result = service
+            .generate_random_file(Parameters(request))
+            .await
+            .unwrap();
+        let response: WriteFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.size, Some(100_000));
+        assert!(!response.created);
+        let content = tokio::fs::read_to_string(&file).await.unwrap();
+        assert_eq!(content.len(), 100_000);
+        assert!(content.starts_with("Lorem ipsum"));
+
+        let oversized: GenerateRandomFileRequest = serde_json::from_value(serde_json::json!({
+            "path": file,
+            "size_bytes": MAX_GENERATED_FILE_BYTES + 1
+        }))
+        .unwrap();
+        assert!(
+            service
+                .generate_random_file(Parameters(oversized))
+                .await
+                .is_err()
+        );
+        assert!(
+            serde_json::from_value::<GenerateRandomFileRequest>(serde_json::json!({
+                "path": file,
+                "size_bytes": 1,
+                "pattern": "ones"
+            }))
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_allowed_directories_extended_info() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let missing = allowed.join("removed");
+        let service = FileSystemService::new(vec![allowed.clone(), missing.clone()], None);
+        let list = |extended_info: bool| {
+            let service = &service;
+            async move {
+                let request: ListAllowedDirectoriesRequest =
+                    serde_json::from_value(serde_json::json!({"extended_info": extended_info}))
+                        .unwrap();
+                let result = service
+                    .list_allowed_directories(Parameters(request))
+                    .await
+                    .unwrap();
+                serde_json::from_str::<serde_json::Value>(&text_of(&result)).unwrap()
+            }
+        };
+
+        let plain = list(false).await;
+        assert_eq!(
+            plain,
+            serde_json::json!({
+                "total": 2,
+                "items": [{"path": allowed.display().to_string()}, {"path": missing.display().to_string()}]
+            })
+        );
+
+        let extended = list(true).await;
+        assert_eq!(extended["total"], 2);
+        let existing = &extended["items"][0];
+        assert_eq!(existing["canonical_path"], allowed.display().to_string());
+        assert_eq!(existing["exists"], true);
+        assert_eq!(existing["readable"], true);
+        assert_eq!(existing["writable"], true);
+        assert!(existing["available_bytes"].as_u64().unwrap() > 0);
+        // Checking writability leaves nothing behind
+        assert_eq!(std::fs::read_dir(&allowed).unwrap().count(), 0);
+
+        let removed = &extended["items"][1];
+        assert_eq!(removed["exists"], false);
+        assert_eq!(removed["readable"], false);
+        assert_eq!(removed["writable"], false);
+        assert!(removed.get("canonical_path").is_none());
+
+        let read_only = FileSystemService::new(vec![allowed], None).with_read_only(true);
+        let request: ListAllowedDirectoriesRequest =
+            serde_json::from_value(serde_json::json!({"extended_info": true})).unwrap();
+        let result = read_only
+            .list_allowed_directories(Parameters(request))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response["items"][0]["readable"], true);
+        assert_eq!(response["items"][0]["writable"], false);
+    }
+
+    #[tokio::test]
+    async fn test_read_file_as_base64_round_trips_bytes() {
+        use base64::{Engine, engine::general_purpose};
+
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let blob = allowed.join("artifact.bin");
+        let bytes: Vec<u8> = (0..=255u8).chain([0, 0xff, 0x0a, 0x0d]).collect();
+        tokio::fs::write(&blob, &bytes).await.unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+
+        let request: ReadFileBinaryRequest =
+            serde_json::from_value(serde_json::json!({"path": blob})).unwrap();
+        let result = service
+            .read_file_as_base64(Parameters(request))
+            .await
+            .unwrap();
+        let response: Base64FileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+
+        assert_eq!(response.size_bytes, bytes.len() as u64);
+        assert_eq!(
+            general_purpose::STANDARD.decode(&response.base64).unwrap(),
+            bytes
+        );
+        assert_eq!(response.md5.len(), 32);
+
+        // MD5 of the empty input, a well-known digest
+        let empty = blob.with_file_name("empty.bin");
+        tokio::fs::write(&empty, b"").await.unwrap();
+        let request: ReadFileBinaryRequest =
+            serde_json::from_value(serde_json::json!({"path": empty})).unwrap();
+        let result = service
+            .read_file_as_base64(Parameters(request))
+            .await
+            .unwrap();
+        let response: Base64FileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.md5, "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!((response.base64.as_str(), response.size_bytes), ("", 0));
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_tools_require_enable_flag() {
+        let temp_dir = TempDir::new().unwrap();
+        let service = FileSystemService::new(vec![temp_dir.path().to_path_buf()], None);
+
+        let request: CopyToClipboardRequest =
+            serde_json::from_value(serde_json::json!({"content": "text"})).unwrap();
+        let error = service
+            .copy_to_clipboard(Parameters(request))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_REQUEST);
+        assert!(error.message.contains("--enable-clipboard"));
+
+        let error = service.paste_from_clipboard().await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INVALID_REQUEST);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_clipboard_unavailable_without_display() {
+        if ["DISPLAY", "WAYLAND_DISPLAY"]
+            .iter()
+            .any(|name| std::env::var_os(name).is_some())
+        {
+            return;
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let service =
+            FileSystemService::new(vec![temp_dir.path().to_path_buf()], None).with_clipboard(true);
+
+        let error = service.paste_from_clipboard().await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+        assert!(error.message.contains("Clipboard access is unavailable"));
+    }
+
+    #[tokio::test]
+    #[cfg_attr(not(feature = "clipboard_tests"), ignore)]
+    async fn test_clipboard_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let service =
+            FileSystemService::new(vec![temp_dir.path().to_path_buf()], None).with_clipboard(true);
+        let content = format!("clipboard round trip {}", std::process::id());
+
+        let request: CopyToClipboardRequest =
+            serde_json::from_value(serde_json::json!({"content": content})).unwrap();
+        service
+            .copy_to_clipboard(Parameters(request))
+            .await
+            .unwrap();
+
+        let result = service.paste_from_clipboard().await.unwrap();
+        let response: ReadFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        let size = content.len() as u64;
+        assert_eq!(response.content, FileContent::Text(content));
+        assert_eq!(
+            (
+                service.metrics.files_written(),
+                service.metrics.bytes_written()
+            ),
+            (1, size)
+        );
+        assert_eq!(
+            (service.metrics.files_read(), service.metrics.bytes_read()),
+            (1, size)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parsing_tools_count_file_size_as_bytes_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let csv = allowed.join("data.csv");
+        let rows: String = (0..50).map(|i| format!("{i},row {i}\n")).collect();
+        tokio::fs::write(&csv, format!("id,name\n{rows}"))
+            .await
+            .unwrap();
+        let env = allowed.join(".env");
+        tokio::fs::write(
+            &env,
+            "# settings\nexport NAME=\"demo\"\nURL=http://${NAME}\n",
+        )
+        .await
+        .unwrap();
+        let yaml = allowed.join("config.yaml");
+        tokio::fs::write(&yaml, "name: demo\nports: [80, 443]\n")
+            .await
+            .unwrap();
+        let service = FileSystemService::new(vec![allowed], None);
+        let size_on_disk = |path: &PathBuf| std::fs::metadata(path).unwrap().len();
+
+        // max_rows limits the output, not the bytes read from disk
+        let request: ReadCsvFileRequest =
+            serde_json::from_value(serde_json::json!({"path": csv, "max_rows": 2})).unwrap();
+        service.read_csv_file(Parameters(request)).await.unwrap();
+        assert_eq!(service.metrics.bytes_read(), size_on_disk(&csv));
+
+        let request: ReadEnvFileRequest =
+            serde_json::from_value(serde_json::json!({"path": env})).unwrap();
+        service.read_env_file(Parameters(request)).await.unwrap();
+        assert_eq!(
+            service.metrics.bytes_read(),
+            size_on_disk(&csv) + size_on_disk(&env)
+        );
+
+        let request: ParseYamlFileRequest =
+            serde_json::from_value(serde_json::json!({"path": yaml})).unwrap();
+        service.parse_yaml_file(Parameters(request)).await.unwrap();
+        assert_eq!(
+            (service.metrics.files_read(), service.metrics.bytes_read()),
+            (
+                3,
+                size_on_disk(&csv) + size_on_disk(&env) + size_on_disk(&yaml)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_only_mode_rejects_write_tools() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("notes.txt");
+        tokio::fs::write(&file, "original").await.unwrap();
+        let service = FileSystemService::new(vec![allowed.clone()], None).with_read_only(true);
+
+        let assert_rejected = |result: ToolResult| {
+            let error = result.unwrap_err();
+            assert_eq!(error.code, ErrorCode::INVALID_REQUEST);
+            assert_eq!(error.message, "Server is configured in read-only mode");
+        };
+
+        let request: WriteFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file, "content": "changed"}))
+                .unwrap();
+        assert_rejected(service.write_file(Parameters(request)).await);
+
+        let edit = serde_json::json!({
+            "path": file,
+            "edits": [{"old_text": "original", "new_text": "changed"}],
+        });
+        let request: EditFileRequest = serde_json::from_value(edit.clone()).unwrap();
+        assert_rejected(service.edit_file(Parameters(request)).await);
+
+        let request: CreateDirectoryRequest =
+            serde_json::from_value(serde_json::json!({"path": allowed.join("new")})).unwrap();
+        assert_rejected(service.create_directory(Parameters(request)).await);
+
+        let request: MoveFileRequest = serde_json::from_value(serde_json::json!({
+            "source": file,
+            "destination": allowed.join("moved.txt"),
+        }))
+        .unwrap();
+        assert_rejected(service.move_file(Parameters(request)).await);
+
+        let request: CreateTempFileRequest = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_rejected(service.create_temp_file(Parameters(request)).await);
+
+        let request: PruneEmptyDirectoriesRequest =
+            serde_json::from_value(serde_json::json!({"path": allowed})).unwrap();
+        assert_rejected(service.prune_empty_directories(Parameters(request)).await);
+
+        let request: GenerateRandomFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file, "size_bytes": 16})).unwrap();
+        assert_rejected(service.generate_random_file(Parameters(request)).await);
+
+        let rename = serde_json::json!({
+            "directory": allowed,
+            "match_pattern": "notes",
+            "replacement": "renamed",
+        });
+        let request: RenameBulkRequest = serde_json::from_value(rename.clone()).unwrap();
+        assert_rejected(service.rename_bulk(Parameters(request)).await);
+
+        let request: SplitFileRequest = serde_json::from_value(serde_json::json!({
+            "path": file,
+            "chunk_size_bytes": 2,
+            "output_prefix": allowed.join("part"),
+        }))
+        .unwrap();
+        assert_rejected(service.split_file(Parameters(request)).await);
+
+        let request: JoinFilesRequest = serde_json::from_value(serde_json::json!({
+            "input_paths": [file],
+            "output_path": allowed.join("joined.txt"),
+        }))
+        .unwrap();
+        assert_rejected(service.join_files(Parameters(request)).await);
+
+        // Nothing on disk changed
+        assert_eq!(tokio::fs::read_to_string(&file).await.unwrap(), "original");
+        assert!(!allowed.join("new").exists());
+        assert!(!allowed.join("moved.txt").exists());
+        assert_eq!(std::fs::read_dir(&allowed).unwrap().count(), 1);
+
+        // Read tools and dry-run edits are unaffected
+        let request: ReadTextFileRequest =
+            serde_json::from_value(serde_json::json!({"path": file})).unwrap();
+        let result = service.read_text_file(Parameters(request)).await.unwrap();
+        let response: ReadFileResponse = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(response.content, FileContent::Text("original".to_string()));
+
+        let request: ListDirectoryRequest =
+            serde_json::from_value(serde_json::json!({"path": allowed})).unwrap();
+        assert!(service.list_directory(Parameters(request)).await.is_ok());
+
+        let request: SearchFilesRequest =
+            serde_json::from_value(serde_json::json!({"path": allowed, "pattern": "notes"}))
+                .unwrap();
+        assert!(service.search_files(Parameters(request)).await.is_ok());
+
+        let request: GetFileInfoRequest =
+            serde_json::from_value(serde_json::json!({"path": file})).unwrap();
+        assert!(service.get_file_info(Parameters(request)).await.is_ok());
+
+        let mut dry_run = edit;
+        dry_run["dry_run"] = serde_json::json!(true);
+        let request: EditFileRequest = serde_json::from_value(dry_run).unwrap();
+        assert!(service.edit_file(Parameters(request)).await.is_ok());
+        assert_eq!(tokio::fs::read_to_string(&file).await.unwrap(), "original");
+
+        let mut dry_run = rename;
+        dry_run["dry_run"] = serde_json::json!(true);
+        let request: RenameBulkRequest = serde_json::from_value(dry_run).unwrap();
+        assert!(service.rename_bulk(Parameters(request)).await.is_ok());
+        assert!(file.exists());
+    }
+}
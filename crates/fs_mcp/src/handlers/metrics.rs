@@ -0,0 +1,120 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
+};
+
+use crate::models::responses::{FileContent, ReadFileResponse};
+
+/// Runtime counters reported by the `fs://status` resource
+#[derive(Debug)]
+pub(crate) struct ServerMetrics {
+    started: Instant,
+    files_read: AtomicU64,
+    bytes_read: AtomicU64,
+    files_written: AtomicU64,
+    bytes_written: AtomicU64,
+    files_moved: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl ServerMetrics {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            files_read: AtomicU64::new(0),
+            bytes_read: AtomicU64::new(0),
+            files_written: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+            files_moved: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+        }
+    }
+
+    /// Count one file read, sized by its decoded content
+    pub(crate) fn record_read(&self, response: &ReadFileResponse) {
+        self.record_read_bytes(content_bytes(&response.content));
+    }
+
+    /// Count one file read of `bytes` bytes
+    pub(crate) fn record_read_bytes(&self, bytes: u64) {
+        self.files_read.fetch_add(1, Ordering::Relaxed);
+        self.bytes_read.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Count one file written with `bytes` of new content
+    pub(crate) fn record_write(&self, bytes: u64) {
+        self.files_written.fetch_add(1, Ordering::Relaxed);
+        self.bytes_written.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Count `files` files or directories moved or renamed
+    pub(crate) fn record_moves(&self, files: u64) {
+        self.files_moved.fetch_add(files, Ordering::Relaxed);
+    }
+
+    /// Count one failed tool call
+    pub(crate) fn record_error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn files_read(&self) -> u64 {
+        self.files_read.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn files_written(&self) -> u64 {
+        self.files_written.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn bytes_written(&self) -> u64 {
+        self.bytes_written.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn files_moved(&self) -> u64 {
+        self.files_moved.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn uptime_seconds(&self) -> u64 {
+        self.started.elapsed().as_secs()
+    }
+}
+
+impl Default for ServerMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Size of the file data behind a read, undoing base64 for binary content
+fn content_bytes(content: &FileContent) -> u64 {
+    match content {
+        FileContent::Text(text) => text.len() as u64,
+        FileContent::Binary(encoded) => {
+            let padding = encoded.bytes().rev().take_while(|&b| b == b'=').count();
+            (encoded.len() / 4 * 3).saturating_sub(padding) as u64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_bytes_decodes_base64_length() {
+        assert_eq!(content_bytes(&FileContent::Text("héllo".to_string())), 6);
+        // "abcd" -> "YWJjZA=="
+        assert_eq!(
+            content_bytes(&FileContent::Binary("YWJjZA==".to_string())),
+            4
+        );
+        assert_eq!(content_bytes(&FileContent::Binary("YWJj".to_string())), 3);
+    }
+}
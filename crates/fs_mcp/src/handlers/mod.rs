@@ -1,19 +1,104 @@
+mod audit;
 pub mod fs_tools;
+mod metrics;
+mod subscriptions;
 
 pub use fs_tools::FileSystemService;
 
-use crate::config::Config;
+use std::net::SocketAddr;
+
+use rmcp::transport::SseServer;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{Config, Transport};
+
+/// Build a `FileSystemService` honouring the configured directories, read limit,
+/// read-only mode and clipboard access
+fn build_service(config: &Config) -> FileSystemService {
+    FileSystemService::new(config.allowed_directories.clone(), config.max_read_bytes)
+        .with_read_only(config.read_only)
+        .with_clipboard(config.enable_clipboard)
+}
+
+/// Start serving over SSE, with one `FileSystemService` per client session
+///
+/// Returns the token that shuts the HTTP server down when cancelled.
+pub(crate) async fn serve_sse(
+    bind: SocketAddr,
+    config: Config,
+) -> std::io::Result<CancellationToken> {
+    let server = SseServer::serve(bind).await?;
+    Ok(server.with_service(move || build_service(&config)))
+}
 
 pub async fn run(config: Config) -> Result<(), Box<dyn std::error::Error>> {
     use rmcp::{ServiceExt, transport::stdio};
 
-    let service = FileSystemService::new(config.allowed_directories)
-        .serve(stdio())
-        .await
-        .inspect_err(|e| {
-            tracing::error!("serving error: {:?}", e);
-        })?;
+    match config.transport {
+        Transport::Stdio => {
+            let service = build_service(&config)
+                .serve(stdio())
+                .await
+                .inspect_err(|e| {
+                    tracing::error!("serving error: {:?}", e);
+                })?;
 
-    service.waiting().await?;
+            service.waiting().await?;
+        }
+        Transport::Sse(bind) => {
+            let ct = serve_sse(bind, config).await?;
+            tracing::info!("Serving MCP over SSE at http://{}/sse", bind);
+
+            tokio::signal::ctrl_c().await?;
+            ct.cancel();
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use rmcp::{ServiceExt, model::CallToolRequestParam, transport::SseClientTransport};
+    use tempfile::TempDir;
+
+    use super::serve_sse;
+    use crate::config::{Config, Transport};
+
+    #[tokio::test]
+    async fn test_sse_transport_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed = temp_dir.path().canonicalize().unwrap();
+        let file = allowed.join("hello.txt");
+        tokio::fs::write(&file, "served over sse").await.unwrap();
+
+        let bind = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .unwrap();
+        let config = Config {
+            allowed_directories: vec![allowed],
+            transport: Transport::Sse(bind),
+            max_read_bytes: None,
+            read_only: false,
+            enable_clipboard: false,
+        };
+        let ct = serve_sse(bind, config).await.unwrap();
+
+        let transport = SseClientTransport::start(format!("http://{}/sse", bind))
+            .await
+            .unwrap();
+        let client = ().serve(transport).await.unwrap();
+
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "read_text_file".into(),
+                arguments: serde_json::json!({"path": file}).as_object().cloned(),
+            })
+            .await
+            .unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert!(text.contains("served over sse"));
+
+        client.cancel().await.unwrap();
+        ct.cancel();
+    }
+}
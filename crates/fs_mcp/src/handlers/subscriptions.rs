@@ -0,0 +1,48 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use rmcp::{Peer, RoleServer, model::ResourceUpdatedNotificationParam};
+use tokio::task::JoinHandle;
+
+/// How often a subscribed file's modification time is checked
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watch `path` and send `notifications/resources/updated` for `uri` whenever it changes
+///
+/// A change is a different modification time, including the file appearing or
+/// disappearing. The task ends on its own once the client can no longer be notified.
+pub(crate) fn spawn_file_watcher(
+    uri: String,
+    path: PathBuf,
+    peer: Peer<RoleServer>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_modified = modified_time(&path).await;
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            let modified = modified_time(&path).await;
+            if modified == last_modified {
+                continue;
+            }
+            last_modified = modified;
+
+            let notification = ResourceUpdatedNotificationParam { uri: uri.clone() };
+            if let Err(e) = peer.notify_resource_updated(notification).await {
+                tracing::debug!("Stopping subscription to {}: {}", uri, e);
+                break;
+            }
+        }
+    })
+}
+
+async fn modified_time(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -1,6 +1,14 @@
+use core::fmt;
+use std::{
+    borrow::Cow,
+    time::{Duration, SystemTime},
+};
+
+use chrono::DateTime;
 use derive_getters::Getters;
+use regex::{Regex, RegexBuilder};
 use rmcp::schemars;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, de};
 
 use crate::{
     errors::{FileSystemMcpError, FileSystemMcpResult},
@@ -65,6 +73,82 @@ impl Validate for ReadMediaFileRequest {
     }
 }
 
+/// Request to read any file as base64, without interpreting its content
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ReadFileBinaryRequest {
+    /// Path to the file to read
+    path: String,
+}
+
+impl Validate for ReadFileBinaryRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "read_file_as_base64".to_string(),
+                data: serde_json::json!({"error": "Path is empty"}),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Request to read the section of a text file between two marker lines
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ReadFileBetweenMarkersRequest {
+    /// Path to the file to read
+    path: String,
+    /// Text identifying the line that opens the section
+    start_marker: String,
+    /// Text identifying the line that closes the section
+    end_marker: String,
+    /// Whether to include the marker lines themselves in the result
+    #[serde(default)]
+    include_markers: bool,
+    /// Which start marker to use when the file contains several, starting at 1
+    #[serde(default = "default_occurrence")]
+    occurrence: usize,
+}
+
+fn default_occurrence() -> usize {
+    1
+}
+
+impl Validate for ReadFileBetweenMarkersRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "validate".to_string(),
+                data: serde_json::json!({"error": "Path is empty"}),
+            });
+        }
+
+        if self.start_marker.is_empty() || self.end_marker.is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid markers".to_string(),
+                path: self.path.clone(),
+                operation: "validate".to_string(),
+                data: serde_json::json!({"error": "Markers must not be empty"}),
+            });
+        }
+
+        if self.occurrence == 0 {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid occurrence".to_string(),
+                path: self.path.clone(),
+                operation: "validate".to_string(),
+                data: serde_json::json!({"error": "Occurrence is 1-based and must be at least 1"}),
+            });
+        }
+
+        Ok(())
+    }
+}
+
 /// Request to read multiple files
 #[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
 pub struct ReadMultipleFilesRequest {
@@ -105,6 +189,22 @@ pub struct WriteFileRequest {
     path: String,
     /// Content to write to the file
     content: String,
+    /// Line breaks to write: 'lf' (default), 'crlf' or 'preserve' to keep the content as is
+    #[serde(default)]
+    line_ending: Option<LineEnding>,
+}
+
+/// Line break style `write_file` writes
+#[derive(Debug, Deserialize, schemars::JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// Unix style `\n`; `\r\n` is normalized to `\n`
+    #[default]
+    Lf,
+    /// Windows style `\r\n`; every bare `\n` becomes `\r\n`
+    CrLf,
+    /// Write the content unchanged
+    Preserve,
 }
 
 impl Validate for WriteFileRequest {
@@ -138,19 +238,178 @@ impl Validate for WriteFileRequest {
 }
 
 /// Edit operation for file editing
-#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+#[derive(Debug, Deserialize, Getters)]
 pub struct EditOperation {
+    /// Exact text edit, or regex edit when `kind` is "regex"
+    #[serde(flatten, deserialize_with = "deserialize_edit_kind")]
+    kind: EditKind,
+}
+
+/// How an edit finds the text it replaces, selected by its `kind` field
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EditKind {
+    /// Replace old_text, matched exactly or line by line ignoring indentation
+    Text(TextEditOperation),
+    /// Replace the matches of a regular expression
+    Regex(RegexEditOperation),
+}
+
+/// Deserialize an [`EditKind`], reading an edit without `kind` as a text edit
+///
+/// Edits written before regex edits existed have only `old_text` and `new_text`.
+fn deserialize_edit_kind<'de, D>(deserializer: D) -> Result<EditKind, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut fields = serde_json::Map::deserialize(deserializer)?;
+    fields.entry("kind").or_insert_with(|| "text".into());
+    EditKind::deserialize(serde_json::Value::Object(fields)).map_err(de::Error::custom)
+}
+
+/// One schema per edit kind; like [`deserialize_edit_kind`], the text edit
+/// schema leaves `kind` optional with a default of "text"
+impl schemars::JsonSchema for EditOperation {
+    fn schema_name() -> Cow<'static, str> {
+        "EditOperation".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let with_kind = |mut schema: schemars::Schema, kind: &str, required: bool| {
+            let object = schema.ensure_object();
+            let mut kind_schema = serde_json::json!({"type": "string", "const": kind});
+            if !required {
+                kind_schema["default"] = kind.into();
+            }
+            if let Some(properties) = object
+                .entry("properties")
+                .or_insert_with(|| serde_json::json!({}))
+                .as_object_mut()
+            {
+                properties.insert("kind".to_string(), kind_schema);
+            }
+            if required
+                && let Some(fields) = object
+                    .entry("required")
+                    .or_insert_with(|| serde_json::json!([]))
+                    .as_array_mut()
+            {
+                fields.insert(0, "kind".into());
+            }
+            schema
+        };
+
+        schemars::json_schema!({
+            "description": "Exact text edit, or regex edit when `kind` is \"regex\"",
+            "oneOf": [
+                with_kind(TextEditOperation::json_schema(generator), "text", false),
+                with_kind(RegexEditOperation::json_schema(generator), "regex", true),
+            ]
+        })
+    }
+}
+
+/// Edit that replaces exact text
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct TextEditOperation {
     /// Text to search for - must match exactly
     old_text: String,
     /// Text to replace with
     new_text: String,
+    /// Replace every exact occurrence of old_text instead of rejecting ambiguous matches
+    #[serde(default)]
+    allow_multiple_matches: bool,
+}
+
+/// Edit that replaces the matches of a regular expression
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct RegexEditOperation {
+    /// Regular expression to search for
+    pattern: String,
+    /// Replacement; `$1`, `$2` or `${name}` insert capture groups
+    replacement: String,
+    /// 'g' replaces every match instead of the first; 'i' ignores case, 'm' makes ^ and $
+    /// match at line breaks and 's' lets . match line breaks
+    #[serde(default)]
+    flags: String,
+}
+
+impl RegexEditOperation {
+    /// Compile `pattern` with the `i`, `m` and `s` flags applied
+    pub fn regex(&self) -> FileSystemMcpResult<Regex> {
+        let invalid = |error: String| FileSystemMcpError::ValidationError {
+            message: "Invalid regex edit".to_string(),
+            path: "edit_operation".to_string(),
+            operation: "validate".to_string(),
+            data: serde_json::json!({
+                "error": error,
+                "pattern": self.pattern,
+                "flags": self.flags
+            }),
+        };
+
+        let mut builder = RegexBuilder::new(&self.pattern);
+        for flag in self.flags.chars() {
+            match flag {
+                'g' => {}
+                'i' => {
+                    builder.case_insensitive(true);
+                }
+                'm' => {
+                    builder.multi_line(true);
+                }
+                's' => {
+                    builder.dot_matches_new_line(true);
+                }
+                other => {
+                    return Err(invalid(format!(
+                        "Unknown flag '{}'; supported flags are g, i, m and s",
+                        other
+                    )));
+                }
+            }
+        }
+        builder.build().map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Whether every match is replaced rather than only the first
+    pub fn is_global(&self) -> bool {
+        self.flags.contains('g')
+    }
 }
 
 impl EditOperation {
     /// Create a new EditOperation instance
     #[cfg(test)]
     pub fn new(old_text: String, new_text: String) -> Self {
-        Self { old_text, new_text }
+        Self {
+            kind: EditKind::Text(TextEditOperation {
+                old_text,
+                new_text,
+                allow_multiple_matches: false,
+            }),
+        }
+    }
+
+    /// Create a regex edit
+    #[cfg(test)]
+    pub fn regex(pattern: &str, replacement: &str, flags: &str) -> Self {
+        Self {
+            kind: EditKind::Regex(RegexEditOperation {
+                pattern: pattern.to_string(),
+                replacement: replacement.to_string(),
+                flags: flags.to_string(),
+            }),
+        }
+    }
+
+    /// Allow this edit to replace every exact occurrence of old_text
+    #[cfg(test)]
+    pub fn with_allow_multiple_matches(mut self, allow_multiple_matches: bool) -> Self {
+        if let EditKind::Text(edit) = &mut self.kind {
+            edit.allow_multiple_matches = allow_multiple_matches;
+        }
+        self
     }
 }
 
@@ -167,6 +426,30 @@ pub struct EditFileRequest {
 }
 
 impl Validate for EditOperation {
+    fn validate(&self) -> Result<(), FileSystemMcpError> {
+        match &self.kind {
+            EditKind::Text(edit) => edit.validate(),
+            EditKind::Regex(edit) => edit.validate(),
+        }
+    }
+}
+
+impl Validate for RegexEditOperation {
+    fn validate(&self) -> Result<(), FileSystemMcpError> {
+        if self.pattern.is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid regex edit".to_string(),
+                path: "edit_operation".to_string(),
+                operation: "validate".to_string(),
+                data: serde_json::json!({"error": "pattern cannot be empty"}),
+            });
+        }
+
+        self.regex().map(|_| ())
+    }
+}
+
+impl Validate for TextEditOperation {
     fn validate(&self) -> Result<(), FileSystemMcpError> {
         if self.old_text.is_empty() {
             return Err(FileSystemMcpError::ValidationError {
@@ -254,6 +537,33 @@ impl Validate for CreateDirectoryRequest {
     }
 }
 
+/// Request to remove the empty directories below a path
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct PruneEmptyDirectoriesRequest {
+    /// Directory to prune; it is kept even if it ends up empty
+    path: String,
+    /// List the directories that would be removed without deleting them
+    #[serde(default)]
+    dry_run: bool,
+}
+
+impl Validate for PruneEmptyDirectoriesRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "prune_empty_directories".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
 /// Request to list directory contents
 #[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
 pub struct ListDirectoryRequest {
@@ -289,6 +599,20 @@ pub enum SortBy {
     Size,
     /// Sort by modification time (newest first)
     Modified,
+    /// Sort by modification time (oldest first)
+    #[serde(rename = "modified_ascending")]
+    ModifiedAscending,
+}
+
+impl fmt::Display for SortBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortBy::Name => write!(f, "name"),
+            SortBy::Size => write!(f, "size"),
+            SortBy::Modified => write!(f, "modified (descending)"),
+            SortBy::ModifiedAscending => write!(f, "modified (ascending)"),
+        }
+    }
 }
 
 /// Request to list directory contents with sizes
@@ -296,7 +620,7 @@ pub enum SortBy {
 pub struct ListDirectoryWithSizesRequest {
     /// Path to the directory to list
     path: String,
-    /// Sort entries by name or size
+    /// Sort entries by name, size, modified (newest first) or modified_ascending (oldest first)
     #[serde(rename = "sortBy", default)]
     sort_by: SortBy,
 }
@@ -326,6 +650,16 @@ pub struct DirectoryTreeRequest {
     /// Patterns to exclude from the tree
     #[serde(default)]
     exclude_patterns: Vec<String>,
+    /// Resolve symlinks to their targets; when false they are listed as "[SYMLINK]"
+    #[serde(default = "default_follow_symlinks")]
+    follow_symlinks: bool,
+    /// Number of levels to list; deeper contents are replaced by a "[TRUNCATED]" entry
+    #[serde(default)]
+    max_depth: Option<usize>,
+}
+
+fn default_follow_symlinks() -> bool {
+    true
 }
 
 impl Validate for DirectoryTreeRequest {
@@ -341,6 +675,58 @@ impl Validate for DirectoryTreeRequest {
                 }),
             });
         }
+        if self.max_depth == Some(0) {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid max_depth".to_string(),
+                path: self.path.clone(),
+                operation: "directory_tree".to_string(),
+                data: serde_json::json!({
+                    "error": "max_depth must be at least 1",
+                    "provided_max_depth": self.max_depth
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to render a directory tree as text
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct GenerateFileTreeMarkdownRequest {
+    /// Path to the directory
+    path: String,
+    /// Number of levels to list; deeper contents are replaced by a "..." line
+    #[serde(default)]
+    max_depth: Option<usize>,
+    /// Patterns to exclude from the tree
+    #[serde(default)]
+    exclude_patterns: Vec<String>,
+}
+
+impl Validate for GenerateFileTreeMarkdownRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "generate_file_tree_markdown".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+        if self.max_depth == Some(0) {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid max_depth".to_string(),
+                path: self.path.clone(),
+                operation: "generate_file_tree_markdown".to_string(),
+                data: serde_json::json!({
+                    "error": "max_depth must be at least 1",
+                    "provided_max_depth": self.max_depth
+                }),
+            });
+        }
         Ok(())
     }
 }
@@ -397,25 +783,100 @@ impl Validate for MoveFileRequest {
     }
 }
 
-/// Request to search for files
+/// Request to rename every file in a directory whose name matches a regex
 #[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
-pub struct SearchFilesRequest {
-    /// Base path to search from
+pub struct RenameBulkRequest {
+    /// Directory containing the files to rename
+    directory: String,
+    /// Regular expression matched against each file name (not the full path)
+    match_pattern: String,
+    /// Replacement for the first match; `$1`, `$2` or `${name}` insert capture groups
+    replacement: String,
+    /// Report the renames that would happen without renaming anything
+    #[serde(default)]
+    dry_run: bool,
+    /// Also rename files in subdirectories; each file stays in its own directory
+    #[serde(default)]
+    recursive: bool,
+}
+
+impl RenameBulkRequest {
+    /// Compile `match_pattern`
+    pub fn pattern(&self) -> FileSystemMcpResult<Regex> {
+        Regex::new(&self.match_pattern).map_err(|e| FileSystemMcpError::ValidationError {
+            message: "Invalid match pattern".to_string(),
+            path: self.directory.clone(),
+            operation: "rename_bulk".to_string(),
+            data: serde_json::json!({
+                "error": e.to_string(),
+                "match_pattern": self.match_pattern
+            }),
+        })
+    }
+}
+
+impl Validate for RenameBulkRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.directory.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid directory".to_string(),
+                path: self.directory.clone(),
+                operation: "rename_bulk".to_string(),
+                data: serde_json::json!({
+                    "error": "Directory cannot be empty",
+                    "provided_directory": self.directory
+                }),
+            });
+        }
+
+        self.pattern().map(|_| ())
+    }
+}
+
+/// Most chunk files a single `split_file` call writes
+pub const MAX_SPLIT_CHUNKS: u64 = 10_000;
+
+/// Request to split a file into numbered chunk files
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct SplitFileRequest {
+    /// Path of the file to split
     path: String,
-    /// Glob pattern to match
-    pattern: String,
-    /// Patterns to exclude from results
+    /// Bytes per chunk; set this or chunk_lines
     #[serde(default)]
-    exclude_patterns: Vec<String>,
+    chunk_size_bytes: Option<u64>,
+    /// Lines per chunk; set this or chunk_size_bytes
+    #[serde(default)]
+    chunk_lines: Option<usize>,
+    /// Chunk files are written as `<output_prefix>.001`, `<output_prefix>.002`, ...
+    output_prefix: String,
 }
 
-impl Validate for SearchFilesRequest {
+/// How `split_file` sizes each chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitChunk {
+    /// Exactly this many bytes; the last chunk may be shorter
+    Bytes(u64),
+    /// Exactly this many lines, line breaks included; the last chunk may be shorter
+    Lines(usize),
+}
+
+impl SplitFileRequest {
+    /// Chunk size; `validate` ensures exactly one of the two sizes is set
+    pub fn chunk(&self) -> SplitChunk {
+        match self.chunk_size_bytes {
+            Some(bytes) => SplitChunk::Bytes(bytes),
+            None => SplitChunk::Lines(self.chunk_lines.unwrap_or_default()),
+        }
+    }
+}
+
+impl Validate for SplitFileRequest {
     fn validate(&self) -> FileSystemMcpResult<()> {
         if self.path.trim().is_empty() {
             return Err(FileSystemMcpError::ValidationError {
                 message: "Invalid path".to_string(),
                 path: self.path.clone(),
-                operation: "search_files".to_string(),
+                operation: "split_file".to_string(),
                 data: serde_json::json!({
                     "error": "Path cannot be empty",
                     "provided_path": self.path
@@ -423,55 +884,817 @@ impl Validate for SearchFilesRequest {
             });
         }
 
-        if self.pattern.trim().is_empty() {
+        if self.output_prefix.trim().is_empty() {
             return Err(FileSystemMcpError::ValidationError {
-                message: "Invalid pattern".to_string(),
+                message: "Invalid output_prefix".to_string(),
                 path: self.path.clone(),
-                operation: "search_files".to_string(),
-                data: serde_json::json!({
-                    "error": "Search pattern cannot be empty",
-                    "provided_pattern": self.pattern
-                }),
+                operation: "split_file".to_string(),
+                data: serde_json::json!({"error": "output_prefix cannot be empty"}),
             });
         }
 
-        Ok(())
+        match (self.chunk_size_bytes, self.chunk_lines) {
+            (Some(0), _) | (_, Some(0)) => Err(FileSystemMcpError::ValidationError {
+                message: "Invalid chunk size".to_string(),
+                path: self.path.clone(),
+                operation: "split_file".to_string(),
+                data: serde_json::json!({
+                    "error": "chunk_size_bytes and chunk_lines must be at least 1",
+                    "chunk_size_bytes": self.chunk_size_bytes,
+                    "chunk_lines": self.chunk_lines
+                }),
+            }),
+            (Some(_), None) | (None, Some(_)) => Ok(()),
+            _ => Err(FileSystemMcpError::ValidationError {
+                message: "Invalid chunk size".to_string(),
+                path: self.path.clone(),
+                operation: "split_file".to_string(),
+                data: serde_json::json!({
+                    "error": "Set exactly one of chunk_size_bytes and chunk_lines",
+                    "chunk_size_bytes": self.chunk_size_bytes,
+                    "chunk_lines": self.chunk_lines
+                }),
+            }),
+        }
     }
 }
 
-/// Request to get file information
+/// Request to concatenate files into one
 #[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
-pub struct GetFileInfoRequest {
-    /// Path to the file or directory
-    path: String,
+pub struct JoinFilesRequest {
+    /// Files to concatenate, in order
+    input_paths: Vec<String>,
+    /// File to write; created or overwritten
+    output_path: String,
 }
 
-impl Validate for GetFileInfoRequest {
+impl Validate for JoinFilesRequest {
     fn validate(&self) -> FileSystemMcpResult<()> {
-        if self.path.trim().is_empty() {
+        if self.input_paths.is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "No input files".to_string(),
+                path: self.output_path.clone(),
+                operation: "join_files".to_string(),
+                data: serde_json::json!({"error": "input_paths cannot be empty"}),
+            });
+        }
+
+        if let Some(path) = self
+            .input_paths
+            .iter()
+            .chain([&self.output_path])
+            .find(|path| path.trim().is_empty())
+        {
             return Err(FileSystemMcpError::ValidationError {
                 message: "Invalid path".to_string(),
-                path: self.path.clone(),
-                operation: "get_file_info".to_string(),
-                data: serde_json::json!({
-                    "error": "Path cannot be empty",
-                    "provided_path": self.path
-                }),
+                path: path.clone(),
+                operation: "join_files".to_string(),
+                data: serde_json::json!({"error": "Paths cannot be empty"}),
             });
         }
+
         Ok(())
     }
 }
 
-/// Request to list allowed directories (no parameters needed)
+/// Request to find files modified within a time window
 #[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
-pub struct ListAllowedDirectoriesRequest {
-    // Empty struct - no parameters needed
-}
-
-impl Validate for ListAllowedDirectoriesRequest {
+pub struct FindFilesByDateRangeRequest {
+    /// Base path to search from
+    path: String,
+    /// Only include files modified at or after this RFC 3339 timestamp
+    #[serde(default)]
+    after: Option<String>,
+    /// Only include files modified at or before this RFC 3339 timestamp
+    #[serde(default)]
+    before: Option<String>,
+    /// Patterns to exclude from results
+    #[serde(default)]
+    exclude_patterns: Vec<String>,
+}
+
+impl FindFilesByDateRangeRequest {
+    /// The `after` and `before` bounds parsed as system times
+    pub fn time_range(&self) -> FileSystemMcpResult<(Option<SystemTime>, Option<SystemTime>)> {
+        let parse = |field: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|timestamp| {
+                    DateTime::parse_from_rfc3339(timestamp)
+                        .map(SystemTime::from)
+                        .map_err(|e| FileSystemMcpError::ValidationError {
+                            message: format!("Invalid {} timestamp", field),
+                            path: self.path.clone(),
+                            operation: "find_files_by_date_range".to_string(),
+                            data: serde_json::json!({
+                                "error": e.to_string(),
+                                "field": field,
+                                "provided_value": timestamp,
+                                "expected_format": "RFC 3339, e.g. 2024-01-31T09:00:00Z"
+                            }),
+                        })
+                })
+                .transpose()
+        };
+
+        Ok((parse("after", &self.after)?, parse("before", &self.before)?))
+    }
+}
+
+impl Validate for FindFilesByDateRangeRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "find_files_by_date_range".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+
+        if let (Some(after), Some(before)) = self.time_range()?
+            && after > before
+        {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid time range".to_string(),
+                path: self.path.clone(),
+                operation: "find_files_by_date_range".to_string(),
+                data: serde_json::json!({
+                    "error": "after must not be later than before",
+                    "after": self.after,
+                    "before": self.before
+                }),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Request to export a flat inventory of the files under a directory
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ExportDirectoryManifestRequest {
+    /// Directory to inventory
+    path: String,
+    /// Compute the SHA-256 digest of every file
+    #[serde(default)]
+    include_hashes: bool,
+    /// Patterns to exclude from the manifest
+    #[serde(default)]
+    exclude_patterns: Vec<String>,
+}
+
+impl Validate for ExportDirectoryManifestRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "export_directory_manifest".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to compare the files under two directories
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct CompareDirectoriesRequest {
+    /// First directory
+    path_a: String,
+    /// Second directory
+    path_b: String,
+    /// Compare SHA-256 digests of files with equal sizes instead of trusting the size
+    #[serde(default)]
+    compare_content: bool,
+}
+
+impl Validate for CompareDirectoriesRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        for (field, path) in [("path_a", &self.path_a), ("path_b", &self.path_b)] {
+            if path.trim().is_empty() {
+                return Err(FileSystemMcpError::ValidationError {
+                    message: "Invalid path".to_string(),
+                    path: path.clone(),
+                    operation: "compare_directories".to_string(),
+                    data: serde_json::json!({
+                        "error": format!("{} cannot be empty", field)
+                    }),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Request to search for files
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct SearchFilesRequest {
+    /// Base path to search from
+    path: String,
+    /// Glob pattern to match
+    pattern: String,
+    /// Patterns to exclude from results
+    #[serde(default)]
+    exclude_patterns: Vec<String>,
+    /// Descend into directories reached through symlinks
+    #[serde(default = "default_follow_symlinks")]
+    follow_symlinks: bool,
+    /// Only return files whose content contains this text
+    content_contains: Option<String>,
+}
+
+impl Validate for SearchFilesRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "search_files".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+
+        if self.pattern.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid pattern".to_string(),
+                path: self.path.clone(),
+                operation: "search_files".to_string(),
+                data: serde_json::json!({
+                    "error": "Search pattern cannot be empty",
+                    "provided_pattern": self.pattern
+                }),
+            });
+        }
+
+        if self.content_contains.as_ref().is_some_and(String::is_empty) {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid content filter".to_string(),
+                path: self.path.clone(),
+                operation: "search_files".to_string(),
+                data: serde_json::json!({
+                    "error": "content_contains cannot be empty"
+                }),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Request to get file information
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct GetFileInfoRequest {
+    /// Path to the file or directory
+    path: String,
+}
+
+impl Validate for GetFileInfoRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "get_file_info".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to summarize a unified diff
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct SummarizeDiffRequest {
+    /// Unified diff text, e.g. the output of `git diff` or `diff -u`
+    diff_text: String,
+}
+
+impl Validate for SummarizeDiffRequest {
     fn validate(&self) -> FileSystemMcpResult<()> {
-        // No validation needed for empty request
+        if self.diff_text.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid diff".to_string(),
+                path: String::new(),
+                operation: "summarize_diff".to_string(),
+                data: serde_json::json!({"error": "Diff text cannot be empty"}),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to explain how a path resolves
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct NormalizePathRequest {
+    /// Path to resolve; may be relative and contain `.`, `..` or `~`
+    raw_path: String,
+    /// Directory relative paths are resolved against (defaults to the current directory)
+    #[serde(default)]
+    base_directory: Option<String>,
+}
+
+impl Validate for NormalizePathRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.raw_path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.raw_path.clone(),
+                operation: "normalize_path".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.raw_path
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Rows `read_csv_file` returns when `max_rows` is not given
+const DEFAULT_CSV_MAX_ROWS: usize = 1000;
+
+fn default_has_header() -> bool {
+    true
+}
+
+/// Request to read a CSV file as JSON
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ReadCsvFileRequest {
+    /// Path of the CSV file
+    path: String,
+    /// Treat the first row as column names (default: true)
+    #[serde(default = "default_has_header")]
+    has_header: bool,
+    /// Single ASCII field delimiter (default: ',')
+    #[serde(default)]
+    delimiter: Option<char>,
+    /// Maximum number of data rows to return (default: 1000)
+    #[serde(default)]
+    max_rows: Option<usize>,
+}
+
+impl ReadCsvFileRequest {
+    /// Field delimiter as the byte the CSV reader expects
+    pub fn delimiter_byte(&self) -> u8 {
+        self.delimiter.map_or(b',', |delimiter| delimiter as u8)
+    }
+
+    /// Number of data rows to return, applying the default
+    pub fn row_limit(&self) -> usize {
+        self.max_rows.unwrap_or(DEFAULT_CSV_MAX_ROWS)
+    }
+}
+
+impl Validate for ReadCsvFileRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "read_csv_file".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+
+        if let Some(delimiter) = self.delimiter
+            && (!delimiter.is_ascii() || delimiter == '"' || delimiter == '\n')
+        {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid delimiter".to_string(),
+                path: self.path.clone(),
+                operation: "read_csv_file".to_string(),
+                data: serde_json::json!({
+                    "error": "Delimiter must be a single ASCII character other than a quote or newline",
+                    "delimiter": delimiter
+                }),
+            });
+        }
+
+        if self.max_rows == Some(0) {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid max_rows".to_string(),
+                path: self.path.clone(),
+                operation: "read_csv_file".to_string(),
+                data: serde_json::json!({
+                    "error": "max_rows must be at least 1",
+                    "max_rows": self.max_rows
+                }),
+            });
+        }
+
         Ok(())
     }
 }
+
+/// Request to parse a `.env` file
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ReadEnvFileRequest {
+    /// Path to the .env file
+    path: String,
+}
+
+impl Validate for ReadEnvFileRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "read_env_file".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to read a newline-delimited JSON file
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ReadJsonlFileRequest {
+    /// Path of the JSONL file
+    path: String,
+    /// First line to read, 1-based (default: 1)
+    #[serde(default)]
+    start_line: Option<usize>,
+    /// Last line to read, inclusive (default: end of file)
+    #[serde(default)]
+    end_line: Option<usize>,
+}
+
+impl Validate for ReadJsonlFileRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "read_file_as_jsonl".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+
+        if self.start_line == Some(0) || self.end_line == Some(0) {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid line range".to_string(),
+                path: self.path.clone(),
+                operation: "read_file_as_jsonl".to_string(),
+                data: serde_json::json!({
+                    "error": "Line numbers start at 1",
+                    "start_line": self.start_line,
+                    "end_line": self.end_line
+                }),
+            });
+        }
+
+        if let (Some(start_line), Some(end_line)) = (self.start_line, self.end_line)
+            && start_line > end_line
+        {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid line range".to_string(),
+                path: self.path.clone(),
+                operation: "read_file_as_jsonl".to_string(),
+                data: serde_json::json!({
+                    "error": "start_line must not be after end_line",
+                    "start_line": start_line,
+                    "end_line": end_line
+                }),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Request to parse a YAML file
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ParseYamlFileRequest {
+    /// Path to the YAML file
+    path: String,
+    /// Output format: 'json' (default) or 'toml'
+    #[serde(default)]
+    output_format: Option<YamlOutputFormat>,
+}
+
+/// Format `parse_yaml_file` renders YAML as
+#[derive(Debug, Deserialize, schemars::JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlOutputFormat {
+    /// Pretty-printed JSON
+    #[default]
+    Json,
+    /// TOML; the YAML must be a single document whose top level is a mapping
+    Toml,
+}
+
+impl Validate for ParseYamlFileRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "parse_yaml_file".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to convert YAML text to JSON
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ConvertYamlToJsonRequest {
+    /// YAML document(s) to convert
+    yaml_content: String,
+}
+
+impl Validate for ConvertYamlToJsonRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.yaml_content.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid YAML".to_string(),
+                path: String::new(),
+                operation: "convert_yaml_to_json".to_string(),
+                data: serde_json::json!({"error": "YAML content cannot be empty"}),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Largest file `generate_random_file` creates (100 MB)
+pub const MAX_GENERATED_FILE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Content `generate_random_file` fills a file with
+#[derive(Debug, Deserialize, schemars::JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FillPattern {
+    /// Every byte is 0x00
+    Zeros,
+    /// Random bytes
+    #[default]
+    Random,
+    /// A Lorem Ipsum paragraph, repeated
+    Lorem,
+    /// Random printable ASCII characters
+    Ascii,
+}
+
+impl fmt::Display for FillPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FillPattern::Zeros => write!(f, "zeros"),
+            FillPattern::Random => write!(f, "random"),
+            FillPattern::Lorem => write!(f, "lorem"),
+            FillPattern::Ascii => write!(f, "ascii"),
+        }
+    }
+}
+
+/// Request to create a file of a given size for testing
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct GenerateRandomFileRequest {
+    /// Path of the file to create or overwrite
+    path: String,
+    /// Exact size of the file in bytes (at most 100 MB)
+    size_bytes: u64,
+    /// Content to fill the file with: 'zeros', 'random' (default), 'lorem' or 'ascii'
+    #[serde(default)]
+    pattern: Option<FillPattern>,
+}
+
+impl Validate for GenerateRandomFileRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "generate_random_file".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+
+        if self.size_bytes > MAX_GENERATED_FILE_BYTES {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "File size too large".to_string(),
+                path: self.path.clone(),
+                operation: "generate_random_file".to_string(),
+                data: serde_json::json!({
+                    "error": format!("size_bytes cannot exceed {}", MAX_GENERATED_FILE_BYTES),
+                    "provided_size_bytes": self.size_bytes
+                }),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Shortest interval `watch_file` polls at
+const MIN_WATCH_POLL_INTERVAL_MS: u64 = 100;
+
+/// Longest time `watch_file` waits for a change (5 minutes)
+const MAX_WATCH_TIMEOUT_MS: u64 = 300_000;
+
+fn default_watch_poll_interval_ms() -> u64 {
+    500
+}
+
+fn default_watch_timeout_ms() -> u64 {
+    30_000
+}
+
+/// Request to wait for a file to change
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct WatchFileRequest {
+    /// Path of the file to watch
+    path: String,
+    /// Milliseconds between modification time checks (minimum 100, default 500)
+    #[serde(default = "default_watch_poll_interval_ms")]
+    poll_interval_ms: u64,
+    /// Milliseconds to wait for a change before giving up (maximum 300000, default 30000)
+    #[serde(default = "default_watch_timeout_ms")]
+    timeout_ms: u64,
+}
+
+impl WatchFileRequest {
+    /// Poll interval, raised to the 100 ms minimum
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms.max(MIN_WATCH_POLL_INTERVAL_MS))
+    }
+
+    /// Watch timeout, capped at 5 minutes
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.min(MAX_WATCH_TIMEOUT_MS))
+    }
+}
+
+impl Validate for WatchFileRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        if self.path.trim().is_empty() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid path".to_string(),
+                path: self.path.clone(),
+                operation: "watch_file".to_string(),
+                data: serde_json::json!({
+                    "error": "Path cannot be empty",
+                    "provided_path": self.path
+                }),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Request to list allowed directories (no parameters needed)
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct ListAllowedDirectoriesRequest {
+    /// Also report each directory's canonical path, existence, access and free space
+    #[serde(default)]
+    extended_info: bool,
+}
+
+impl Validate for ListAllowedDirectoriesRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        // No validation needed, extended_info is a plain flag
+        Ok(())
+    }
+}
+
+/// Request to copy text to the system clipboard
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct CopyToClipboardRequest {
+    /// Text to place on the clipboard
+    content: String,
+}
+
+impl Validate for CopyToClipboardRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        // Any text, including an empty string that clears the clipboard, is accepted
+        Ok(())
+    }
+}
+
+/// Request to create a uniquely named temporary file
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct CreateTempFileRequest {
+    /// Directory to create the file in; defaults to the first allowed directory
+    #[serde(default)]
+    directory: Option<String>,
+    /// Prefix for the generated file name
+    #[serde(default)]
+    prefix: Option<String>,
+    /// Suffix for the generated file name, e.g. '.txt'
+    #[serde(default)]
+    suffix: Option<String>,
+}
+
+impl Validate for CreateTempFileRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        validate_temp_name_parts(&self.prefix, &self.suffix, "create_temp_file")
+    }
+}
+
+/// Request to create a uniquely named temporary directory
+#[derive(Debug, Deserialize, schemars::JsonSchema, Getters)]
+pub struct CreateTempDirectoryRequest {
+    /// Directory to create the new directory in; defaults to the first allowed directory
+    #[serde(default)]
+    directory: Option<String>,
+    /// Prefix for the generated directory name
+    #[serde(default)]
+    prefix: Option<String>,
+    /// Suffix for the generated directory name
+    #[serde(default)]
+    suffix: Option<String>,
+}
+
+impl Validate for CreateTempDirectoryRequest {
+    fn validate(&self) -> FileSystemMcpResult<()> {
+        validate_temp_name_parts(&self.prefix, &self.suffix, "create_temp_directory")
+    }
+}
+
+/// Reject name prefixes and suffixes that would place the entry in another directory
+fn validate_temp_name_parts(
+    prefix: &Option<String>,
+    suffix: &Option<String>,
+    operation: &str,
+) -> FileSystemMcpResult<()> {
+    for part in [prefix, suffix].into_iter().flatten() {
+        if part.contains(['/', '\\']) || part.contains("..") {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Invalid name part".to_string(),
+                path: part.clone(),
+                operation: operation.to_string(),
+                data: serde_json::json!({
+                    "error": "Prefix and suffix cannot contain path separators or '..'",
+                    "provided_value": part
+                }),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rmcp::handler::server::tool::schema_for_type;
+
+    use super::*;
+
+    #[test]
+    fn test_edit_operation_schema_matches_deserialization() {
+        // The input schema clients see for edit_file
+        let schema = serde_json::Value::Object(schema_for_type::<EditFileRequest>());
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        let edits = [
+            serde_json::json!({"old_text": "a", "new_text": "b"}),
+            serde_json::json!({"kind": "text", "old_text": "a", "new_text": "b"}),
+            serde_json::json!({"old_text": "a", "new_text": "b", "allow_multiple_matches": true}),
+            serde_json::json!({"kind": "regex", "pattern": "a+", "replacement": "b"}),
+            serde_json::json!({"kind": "regex", "pattern": "a+", "replacement": "b", "flags": "g"}),
+            serde_json::json!({"pattern": "a+", "replacement": "b"}),
+            serde_json::json!({"kind": "text", "pattern": "a+", "replacement": "b"}),
+            serde_json::json!({"kind": "regex", "old_text": "a", "new_text": "b"}),
+            serde_json::json!({"kind": "glob", "old_text": "a", "new_text": "b"}),
+            serde_json::json!({"old_text": "a"}),
+        ];
+        for edit in edits {
+            let request = serde_json::json!({"path": "/file.txt", "edits": [edit]});
+            let deserialized = serde_json::from_value::<EditFileRequest>(request.clone());
+            assert_eq!(
+                validator.is_valid(&request),
+                deserialized.is_ok(),
+                "{request}: {deserialized:?}"
+            );
+        }
+    }
+}
@@ -1,68 +1,140 @@
 use base64::{Engine, engine::general_purpose};
-use rmcp::model::Content;
-use std::{fmt, path::Path};
+use rmcp::{model::Content, schemars};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use crate::utils::mime::mime_type_for_path;
 
 /// File content types for different file formats
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Serialized as `{"encoding": "text" | "base64", "content": "..."}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "encoding", content = "content")]
 pub enum FileContent {
     /// Plain text content
+    #[serde(rename = "text")]
     Text(String),
     /// Base64 encoded binary content
+    #[serde(rename = "base64")]
     Binary(String),
 }
 
 /// Response for read_text_file, read_media_file tools
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ReadFileResponse {
     /// Content of the file as either text or base64-encoded binary
+    #[serde(flatten)]
     pub content: FileContent,
     /// MIME type of the file
     pub mime_type: String,
+    /// Whether the read stopped at the configured byte limit before the end of the file
+    pub truncated: bool,
+    /// Number of lines in the file; head and tail reads report the whole file, not just the returned lines
+    pub line_count: usize,
+    /// Size of the file in bytes; head and tail reads report the whole file, not just the returned lines
+    pub size_bytes: u64,
 }
 
+/// Appended to text output when a read was cut short by the byte limit
+const TRUNCATION_NOTICE: &str = "[Content truncated: file exceeds the server's read limit]";
+
 impl ReadFileResponse {
     /// Create a new ReadFileResponse from raw bytes, automatically determining content type
     pub fn new(bytes: Vec<u8>, path: &Path) -> Self {
-        let mime_type = mime_guess::from_path(path)
-            .first_or_octet_stream()
-            .to_string();
+        let mime_type = mime_type_for_path(path).to_string();
 
-        let content = if mime_type.starts_with("text/") {
+        let size_bytes = bytes.len() as u64;
+        let (content, line_count) = if mime_type.starts_with("text/") {
             // For text files, convert bytes to UTF-8 string
-            FileContent::Text(String::from_utf8_lossy(&bytes).to_string())
+            let text = String::from_utf8_lossy(&bytes).to_string();
+            let line_count = count_lines(&text);
+            (FileContent::Text(text), line_count)
         } else {
             // For binary files, encode as base64
             let base64_content = general_purpose::STANDARD.encode(&bytes);
-            FileContent::Binary(base64_content)
+            (FileContent::Binary(base64_content), 0)
         };
 
-        Self { content, mime_type }
+        Self {
+            content,
+            mime_type,
+            truncated: false,
+            line_count,
+            size_bytes,
+        }
     }
 
-    /// Create a text file response
+    /// Create a text file response, counting lines and bytes from the content
     pub fn text(content: String) -> Self {
         Self {
+            line_count: count_lines(&content),
+            size_bytes: content.len() as u64,
             content: FileContent::Text(content),
             mime_type: "text/plain".to_string(),
+            truncated: false,
         }
     }
 
     /// Create a binary file response
     pub fn binary(base64_content: String, mime_type: String) -> Self {
+        let padding = base64_content
+            .bytes()
+            .rev()
+            .take_while(|&b| b == b'=')
+            .count();
         Self {
+            size_bytes: (base64_content.len() / 4 * 3).saturating_sub(padding) as u64,
             content: FileContent::Binary(base64_content),
             mime_type,
+            truncated: false,
+            line_count: 0,
+        }
+    }
+
+    /// Report the line count and size of the whole file rather than of the returned content
+    pub fn with_file_totals(mut self, line_count: usize, size_bytes: u64) -> Self {
+        self.line_count = line_count;
+        self.size_bytes = size_bytes;
+        self
+    }
+
+    /// Mark whether the content was cut short by the read limit
+    pub fn with_truncated(mut self, truncated: bool) -> Self {
+        self.truncated = truncated;
+        self
+    }
+
+    /// Notice to append to textual output, if the content was truncated
+    fn truncation_suffix(&self) -> String {
+        if self.truncated {
+            format!("\n\n{}", TRUNCATION_NOTICE)
+        } else {
+            String::new()
         }
     }
 }
 
+/// Number of lines in `text`: one per `\n`, plus a final line without a terminator
+fn count_lines(text: &str) -> usize {
+    let newlines = text.bytes().filter(|&b| b == b'\n').count();
+    if text.is_empty() || text.ends_with('\n') {
+        newlines
+    } else {
+        newlines + 1
+    }
+}
+
 impl fmt::Display for ReadFileResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.content {
-            FileContent::Text(text) => write!(f, "{}", text),
-            FileContent::Binary(base64) => {
-                write!(f, "Binary file ({}): {}", self.mime_type, base64)
-            }
+            FileContent::Text(text) => write!(f, "{}{}", text, self.truncation_suffix()),
+            FileContent::Binary(base64) => write!(
+                f,
+                "Binary file ({}): {}{}",
+                self.mime_type,
+                base64,
+                self.truncation_suffix()
+            ),
         }
     }
 }
@@ -70,23 +142,34 @@ impl fmt::Display for ReadFileResponse {
 impl From<ReadFileResponse> for Content {
     fn from(value: ReadFileResponse) -> Self {
         match value.content {
-            FileContent::Text(text) => Content::text(text),
-            FileContent::Binary(base64_data) => {
-                if value.mime_type.starts_with("image/") {
-                    Content::image(base64_data, value.mime_type)
-                } else {
-                    Content::text(format!(
-                        "Binary file ({}): {}",
-                        value.mime_type, base64_data
-                    ))
-                }
+            // Images stay image content so clients can render them
+            FileContent::Binary(base64_data) if value.mime_type.starts_with("image/") => {
+                Content::image(base64_data, value.mime_type)
             }
+            _ => Content::text(serde_json::to_string_pretty(&value).unwrap_or_default()),
         }
     }
 }
 
+/// Response for read_file_as_base64
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Base64FileResponse {
+    /// The file's bytes, base64 encoded with the standard alphabet and padding
+    pub base64: String,
+    /// Size of the file in bytes
+    pub size_bytes: u64,
+    /// Hex-encoded MD5 digest of the file's bytes, for checking the transfer
+    pub md5: String,
+}
+
+impl From<Base64FileResponse> for Content {
+    fn from(value: Base64FileResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
 /// Response for file write operations
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct WriteFileResponse {
     /// Success message describing the operation
     pub message: String,
@@ -96,6 +179,9 @@ pub struct WriteFileResponse {
     pub size: Option<u64>,
     /// Whether the operation created a new file/directory
     pub created: bool,
+    /// Full content the file would have, set only by dry-run edits that changed nothing on disk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<String>,
 }
 
 impl WriteFileResponse {
@@ -106,6 +192,18 @@ impl WriteFileResponse {
             path,
             size,
             created,
+            preview: None,
+        }
+    }
+
+    /// Create a response for a dry-run edit, carrying the content the file would have
+    pub fn dry_run(path: &Path, edit_count: usize, modified_content: String) -> Self {
+        Self {
+            message: format!("Dry run completed. {} edits would be applied.", edit_count),
+            path: path.display().to_string(),
+            size: Some(modified_content.len() as u64),
+            created: false,
+            preview: Some(modified_content),
         }
     }
 
@@ -117,6 +215,7 @@ impl WriteFileResponse {
             path: path.display().to_string(),
             size: Some(size),
             created,
+            preview: None,
         }
     }
 
@@ -127,6 +226,7 @@ impl WriteFileResponse {
             path: path.display().to_string(),
             size: None,
             created: true,
+            preview: None,
         }
     }
 
@@ -138,6 +238,7 @@ impl WriteFileResponse {
             path: path.display().to_string(),
             size: None,
             created: false,
+            preview: None,
         }
     }
 
@@ -148,6 +249,7 @@ impl WriteFileResponse {
             path: format!("{} -> {}", from.display(), to.display()),
             size: None,
             created: false,
+            preview: None,
         }
     }
 
@@ -158,22 +260,230 @@ impl WriteFileResponse {
             path: format!("{} -> {}", from.display(), to.display()),
             size: Some(size),
             created: true,
+            preview: None,
         }
     }
 }
 
-impl fmt::Display for WriteFileResponse {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {}", self.message, self.path)?;
-        if let Some(size) = self.size {
-            write!(f, " ({} bytes)", size)?;
+impl From<WriteFileResponse> for Content {
+    fn from(value: WriteFileResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// Response for list_allowed_directories
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AllowedDirectoriesResponse {
+    /// Number of allowed directories
+    pub total: usize,
+    /// The allowed directories, in configuration order
+    pub items: Vec<AllowedDirectoryInfo>,
+}
+
+impl From<AllowedDirectoriesResponse> for Content {
+    fn from(value: AllowedDirectoriesResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// Outcome of a `rename_bulk` call
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BulkRenameResponse {
+    /// Files renamed, or that would be renamed in a dry run, in path order
+    pub renamed: Vec<RenamedFile>,
+    /// Files whose new name was rejected or could not be applied
+    pub errors: Vec<String>,
+}
+
+impl From<BulkRenameResponse> for Content {
+    fn from(value: BulkRenameResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// Chunk files written by `split_file`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SplitFileResponse {
+    /// Chunk files in order
+    pub chunks: Vec<FileChunk>,
+    /// Bytes written across all chunks
+    pub total_bytes: u64,
+}
+
+impl From<SplitFileResponse> for Content {
+    fn from(value: SplitFileResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// A file found by `search_files` whose content contains the searched text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentSearchMatch {
+    pub path: String,
+    pub matching_lines: Vec<MatchingLine>,
+}
+
+/// A line containing the searched text, numbered from 1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchingLine {
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// A single chunk file written by `split_file`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChunk {
+    pub path: String,
+    pub size: u64,
+}
+
+/// A single rename performed by `rename_bulk`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenamedFile {
+    pub from: String,
+    pub to: String,
+}
+
+/// An allowed directory; everything but `path` is only reported with extended_info
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AllowedDirectoryInfo {
+    /// Directory as configured
+    pub path: String,
+    /// Directory with symlinks resolved, if it can be resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_path: Option<String>,
+    /// Whether the directory exists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exists: Option<bool>,
+    /// Whether the directory's entries can be listed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readable: Option<bool>,
+    /// Whether files can be created in the directory; always false in read-only mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub writable: Option<bool>,
+    /// Free bytes available to unprivileged users on the directory's filesystem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_bytes: Option<u64>,
+}
+
+/// Response for temporary file and directory creation
+#[derive(Debug, Serialize)]
+pub struct TempPathResponse {
+    /// Path of the created file or directory
+    pub path: String,
+    /// Always true; the entry is created before the response is returned
+    pub created: bool,
+}
+
+impl TempPathResponse {
+    /// Create a response for a newly created temporary path
+    pub fn created(path: &Path) -> Self {
+        Self {
+            path: path.display().to_string(),
+            created: true,
         }
-        Ok(())
     }
 }
 
-impl From<WriteFileResponse> for Content {
-    fn from(value: WriteFileResponse) -> Self {
-        Content::text(value.to_string())
+impl From<TempPathResponse> for Content {
+    fn from(value: TempPathResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
     }
 }
+
+/// Response describing how a path resolves
+#[derive(Debug, Serialize)]
+pub struct NormalizedPathResponse {
+    /// Absolute path with `.` and `..` components removed
+    pub resolved: String,
+    /// Whether the resolved path lies inside an allowed directory
+    pub is_allowed: bool,
+    /// Components of the resolved path, starting with the root
+    pub components: Vec<String>,
+}
+
+impl From<NormalizedPathResponse> for Content {
+    fn from(value: NormalizedPathResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// Response for watching a file for changes
+#[derive(Debug, Serialize)]
+pub struct WatchFileResponse {
+    /// Whether the file's modification time changed before the timeout
+    pub changed: bool,
+    /// RFC 3339 time the change was noticed
+    pub detected_at: Option<String>,
+}
+
+impl From<WatchFileResponse> for Content {
+    fn from(value: WatchFileResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// Files of two directories grouped by how they compare
+///
+/// Paths are relative to the compared directories, use `/` separators and are sorted.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompareDirectoriesResponse {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    pub different: Vec<String>,
+    pub identical: Vec<String>,
+}
+
+impl From<CompareDirectoriesResponse> for Content {
+    fn from(value: CompareDirectoriesResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// Line counts for one file in a unified diff
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileDiffStat {
+    /// Path of the file, without the `a/` or `b/` prefix
+    pub file: String,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// Response for summarizing a unified diff
+#[derive(Debug, Default, Serialize)]
+pub struct DiffSummaryResponse {
+    /// Files in the order they appear in the diff
+    pub files_changed: Vec<FileDiffStat>,
+    pub total_additions: u64,
+    pub total_deletions: u64,
+    /// One-sentence description, e.g. "Added 12 lines, removed 3 lines in 2 files: a.rs, b.rs."
+    pub summary: String,
+}
+
+impl From<DiffSummaryResponse> for Content {
+    fn from(value: DiffSummaryResponse) -> Self {
+        Content::text(serde_json::to_string_pretty(&value).unwrap_or_default())
+    }
+}
+
+/// Variables parsed from a `.env` file
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct EnvFileResponse {
+    /// Variable names mapped to their values, sorted by name
+    pub variables: BTreeMap<String, String>,
+    /// Lines that were skipped or only partly understood, e.g. "Line 3: skipped 'FOO' because it has no '='"
+    pub warnings: Vec<String>,
+}
+
+/// Disk space of the filesystem holding an allowed directory, reported by fs://system-info
+#[derive(Debug, Serialize)]
+pub struct DiskSpaceInfo {
+    /// Allowed directory the figures were measured for
+    pub path: String,
+    /// Total size of the filesystem in bytes
+    pub total_bytes: u64,
+    /// Free bytes, including those reserved for the superuser
+    pub free_bytes: u64,
+    /// Free bytes available to unprivileged users
+    pub available_bytes: u64,
+}
@@ -16,7 +16,8 @@ pub trait Validate {
 /// - Expands home directory (~) references
 /// - Converts to absolute path
 /// - Checks path boundaries before file operations
-/// - Handles symlinks by checking their real path to prevent symlink attacks
+/// - Handles symlinks by checking their real path to prevent symlink attacks,
+///   reporting links that resolve outside the allowed directories explicitly
 /// - For non-existent files, validates parent directory permissions
 ///
 /// # Arguments
@@ -75,7 +76,26 @@ pub async fn validate_path(
     // Step 3: Normalize the path
     let normalized_requested = normalize_path(&absolute_path);
 
-    // Step 4: Security check - verify path is within allowed directories before file operations
+    // Step 4: Report symlinks inside an allowed directory whose target resolves outside of it
+    if let Ok(metadata) = fs::symlink_metadata(&normalized_requested).await
+        && metadata.file_type().is_symlink()
+        && let Some(link_parent) = normalized_requested.parent()
+        && is_path_within_allowed_directories(link_parent, allowed_directories)
+        && let Ok(target) = fs::canonicalize(&normalized_requested).await
+        && !is_path_within_allowed_directories(&target, allowed_directories)
+    {
+        tracing::warn!(
+            link = %normalized_requested.display(),
+            target = %target.display(),
+            "Rejected symlink pointing outside allowed directories"
+        );
+        return Err(FileSystemMcpError::SymlinkOutsideAllowedDirectory {
+            link: normalized_requested.display().to_string(),
+            target: target.display().to_string(),
+        });
+    }
+
+    // Step 5: Security check - verify path is within allowed directories before file operations
     if !is_path_within_allowed_directories(&normalized_requested, allowed_directories) {
         return Err(FileSystemMcpError::PermissionDenied {
             path: format!(
@@ -90,7 +110,7 @@ pub async fn validate_path(
         });
     }
 
-    // Step 5: Handle symlinks by checking their real path to prevent symlink attacks
+    // Step 6: Handle symlinks by checking their real path to prevent symlink attacks
     match fs::canonicalize(&absolute_path).await {
         Ok(real_path) => {
             let normalized_real = normalize_path(&real_path);
@@ -110,7 +130,7 @@ pub async fn validate_path(
             Ok(real_path)
         }
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            // Step 6: For new files that don't exist yet, verify parent directory
+            // Step 7: For new files that don't exist yet, verify parent directory
             let parent_dir =
                 absolute_path
                     .parent()
@@ -193,6 +213,44 @@ mod tests {
         ));
     }
 
+    /// Test that symlinks escaping the allowlist are reported with their target
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlink_outside_allowed_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let allowed_path = temp_dir.path().canonicalize().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let outside_path = outside_dir.path().canonicalize().unwrap();
+
+        let link = allowed_path.join("escape");
+        tokio::fs::symlink(&outside_path, &link).await.unwrap();
+
+        let result = validate_path(
+            &link.display().to_string(),
+            std::slice::from_ref(&allowed_path),
+        )
+        .await;
+        match result {
+            Err(FileSystemMcpError::SymlinkOutsideAllowedDirectory {
+                link: reported_link,
+                target,
+            }) => {
+                assert_eq!(reported_link, link.display().to_string());
+                assert_eq!(target, outside_path.display().to_string());
+            }
+            other => panic!("expected SymlinkOutsideAllowedDirectory, got {other:?}"),
+        }
+
+        // Symlinks that stay inside the allowlist resolve to their target
+        let inside_dir = allowed_path.join("inside");
+        tokio::fs::create_dir(&inside_dir).await.unwrap();
+        let inside_link = allowed_path.join("inside_link");
+        tokio::fs::symlink(&inside_dir, &inside_link).await.unwrap();
+
+        let result = validate_path(&inside_link.display().to_string(), &[allowed_path]).await;
+        assert_eq!(result.unwrap(), inside_dir);
+    }
+
     /// Test path traversal security
     #[tokio::test]
     async fn test_path_traversal_security() {
@@ -0,0 +1,45 @@
+use std::sync::Mutex;
+
+use crate::errors::{FileSystemMcpError, FileSystemMcpResult};
+
+/// System clipboard used by the clipboard tools
+///
+/// The platform handle is opened on first use and kept for the life of the server:
+/// on X11 copied text is only served to other applications while the process that
+/// copied it still holds the clipboard. Calls block, so async callers should run
+/// them on a blocking thread.
+#[derive(Default)]
+pub struct SystemClipboard {
+    handle: Mutex<Option<arboard::Clipboard>>,
+}
+
+impl SystemClipboard {
+    /// Replace the clipboard contents with `text`
+    pub fn set_text(&self, text: &str) -> FileSystemMcpResult<()> {
+        self.with_handle(|clipboard| clipboard.set_text(text))
+    }
+
+    /// Text currently on the clipboard; empty when it holds nothing or no text
+    pub fn get_text(&self) -> FileSystemMcpResult<String> {
+        self.with_handle(|clipboard| match clipboard.get_text() {
+            Err(arboard::Error::ContentNotAvailable) => Ok(String::new()),
+            result => result,
+        })
+    }
+
+    fn with_handle<T>(
+        &self,
+        operation: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>,
+    ) -> FileSystemMcpResult<T> {
+        let unavailable = |e: arboard::Error| FileSystemMcpError::ClipboardUnavailable {
+            message: e.to_string(),
+        };
+
+        let mut handle = self.handle.lock().unwrap_or_else(|e| e.into_inner());
+        let clipboard = match handle.as_mut() {
+            Some(clipboard) => clipboard,
+            None => handle.insert(arboard::Clipboard::new().map_err(unavailable)?),
+        };
+        operation(clipboard).map_err(unavailable)
+    }
+}
@@ -0,0 +1,233 @@
+use crate::models::responses::{DiffSummaryResponse, FileDiffStat};
+
+/// Count added and removed lines per file in a unified diff
+///
+/// Understands `git diff` output as well as plain `diff -u`. Hunk lengths from the
+/// `@@` headers decide which lines belong to a hunk, so removed lines starting with
+/// `--` are never mistaken for file headers. Anything that is not a diff is ignored.
+pub fn summarize_unified_diff(diff: &str) -> DiffSummaryResponse {
+    let mut files: Vec<FileDiffStat> = Vec::new();
+    // Whether the last file still expects its `---`/`+++` headers
+    let mut awaiting_headers = false;
+    let mut old_name: Option<&str> = None;
+    let mut old_remaining = 0u64;
+    let mut new_remaining = 0u64;
+
+    for line in diff.lines() {
+        if old_remaining > 0 || new_remaining > 0 {
+            let Some(file) = files.last_mut() else {
+                break;
+            };
+            match line.as_bytes().first() {
+                Some(b'+') => {
+                    file.additions += 1;
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+                Some(b'-') => {
+                    file.deletions += 1;
+                    old_remaining = old_remaining.saturating_sub(1);
+                }
+                // "\ No newline at end of file"
+                Some(b'\\') => {}
+                _ => {
+                    old_remaining = old_remaining.saturating_sub(1);
+                    new_remaining = new_remaining.saturating_sub(1);
+                }
+            }
+            continue;
+        }
+
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            let name = paths
+                .split_once(" b/")
+                .map_or(paths, |(_, new)| new)
+                .to_string();
+            files.push(file_stat(name));
+            awaiting_headers = true;
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            old_name = Some(header_path(path));
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            let new_name = header_path(path);
+            let name = match (new_name, old_name.take()) {
+                ("/dev/null", Some(old)) => old,
+                (new, _) => new,
+            };
+            match files.last_mut() {
+                Some(file) if awaiting_headers => file.file = name.to_string(),
+                _ => files.push(file_stat(name.to_string())),
+            }
+            awaiting_headers = false;
+        } else if let Some((old_len, new_len)) = hunk_lengths(line) {
+            if files.is_empty() {
+                files.push(file_stat(String::new()));
+            }
+            awaiting_headers = false;
+            old_remaining = old_len;
+            new_remaining = new_len;
+        }
+    }
+
+    let total_additions = files.iter().map(|file| file.additions).sum();
+    let total_deletions = files.iter().map(|file| file.deletions).sum();
+    let summary = describe(&files, total_additions, total_deletions);
+    DiffSummaryResponse {
+        files_changed: files,
+        total_additions,
+        total_deletions,
+        summary,
+    }
+}
+
+fn file_stat(file: String) -> FileDiffStat {
+    FileDiffStat {
+        file,
+        additions: 0,
+        deletions: 0,
+    }
+}
+
+/// Path from a `---`/`+++` header, without the `a/`/`b/` prefix or a trailing timestamp
+fn header_path(header: &str) -> &str {
+    let path = header.split('\t').next().unwrap_or(header).trim_end();
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+}
+
+/// Old and new line counts from a `@@ -start[,len] +start[,len] @@` header
+fn hunk_lengths(line: &str) -> Option<(u64, u64)> {
+    let ranges = line.strip_prefix("@@ ")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(' ')?;
+    let length = |range: &str| match range.split_once(',') {
+        Some((_, len)) => len.parse().ok(),
+        None => range.parse::<u64>().ok().map(|_| 1),
+    };
+    Some((
+        length(old.strip_prefix('-')?)?,
+        length(new.strip_prefix('+')?)?,
+    ))
+}
+
+fn describe(files: &[FileDiffStat], additions: u64, deletions: u64) -> String {
+    if files.is_empty() {
+        return "No changes found in the diff.".to_string();
+    }
+
+    let lines = |count: u64| if count == 1 { "line" } else { "lines" };
+    let names: Vec<&str> = files.iter().map(|file| file.file.as_str()).collect();
+    format!(
+        "Added {} {}, removed {} {} in {} {}: {}.",
+        additions,
+        lines(additions),
+        deletions,
+        lines(deletions),
+        files.len(),
+        if files.len() == 1 { "file" } else { "files" },
+        names.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIT_DIFF: &str = "\
+diff --git a/src/foo.rs b/src/foo.rs
+index 83db48f..bf269f4 100644
+--- a/src/foo.rs
++++ b/src/foo.rs
+@@ -1,3 +1,4 @@
+ fn main() {
+-    println!(\"old\");
++    println!(\"new\");
++    println!(\"more\");
+ }
+@@ -10,3 +11,2 @@ fn helper() {
+ let a = 1;
+--- removed line that looks like a header
+ let b = 2;
+diff --git a/README.md b/README.md
+new file mode 100644
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1,2 @@
++# Title
++Body
+\\ No newline at end of file
+diff --git a/old.txt b/old.txt
+deleted file mode 100644
+--- a/old.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+";
+
+    #[test]
+    fn test_summarize_git_diff_counts_per_file() {
+        let summary = summarize_unified_diff(GIT_DIFF);
+
+        assert_eq!(
+            summary.files_changed,
+            vec![
+                FileDiffStat {
+                    file: "src/foo.rs".to_string(),
+                    additions: 2,
+                    deletions: 2,
+                },
+                FileDiffStat {
+                    file: "README.md".to_string(),
+                    additions: 2,
+                    deletions: 0,
+                },
+                FileDiffStat {
+                    file: "old.txt".to_string(),
+                    additions: 0,
+                    deletions: 1,
+                },
+            ]
+        );
+        assert_eq!(summary.total_additions, 4);
+        assert_eq!(summary.total_deletions, 3);
+        assert_eq!(
+            summary.summary,
+            "Added 4 lines, removed 3 lines in 3 files: src/foo.rs, README.md, old.txt."
+        );
+    }
+
+    #[test]
+    fn test_summarize_plain_unified_diff() {
+        let diff = "\
+--- config.toml\t2024-01-01 10:00:00.000000000 +0000
++++ config.toml\t2024-01-02 10:00:00.000000000 +0000
+@@ -1,2 +1,2 @@
+-debug = false
++debug = true
+ name = \"app\"
+";
+        let summary = summarize_unified_diff(diff);
+
+        assert_eq!(summary.files_changed.len(), 1);
+        assert_eq!(summary.files_changed[0].file, "config.toml");
+        assert_eq!(summary.total_additions, 1);
+        assert_eq!(summary.total_deletions, 1);
+        assert_eq!(
+            summary.summary,
+            "Added 1 line, removed 1 line in 1 file: config.toml."
+        );
+    }
+
+    #[test]
+    fn test_summarize_text_without_hunks() {
+        let summary = summarize_unified_diff("just some text\nthat is not a diff\n");
+        assert!(summary.files_changed.is_empty());
+        assert_eq!(summary.total_additions, 0);
+        assert_eq!(summary.summary, "No changes found in the diff.");
+    }
+
+    #[test]
+    fn test_hunk_lengths() {
+        assert_eq!(hunk_lengths("@@ -1,4 +1,5 @@"), Some((4, 5)));
+        assert_eq!(hunk_lengths("@@ -3 +3,0 @@ fn f() {"), Some((1, 0)));
+        assert_eq!(hunk_lengths("@@ not a hunk"), None);
+    }
+}
@@ -0,0 +1,352 @@
+use std::path::Path;
+
+use crate::{
+    errors::{FileSystemMcpError, FileSystemMcpResult},
+    models::requests::{EditKind, EditOperation, LineEnding, RegexEditOperation},
+    utils::line_endings::convert_line_endings,
+};
+
+/// Apply `edits` to `original` in order and return the edited text
+///
+/// The content and every edit have their line endings normalized to `\n` first.
+/// A text edit replaces an exact match, or failing that a run of lines that
+/// matches when leading and trailing whitespace is ignored, re-indented to fit;
+/// more than one exact match is an error unless the edit allows it. A regex
+/// edit replaces its first match, or all of them with the `g` flag. `path` only
+/// labels the error.
+pub fn apply_edits_to_content(
+    original: &str,
+    edits: &[EditOperation],
+    path: &Path,
+) -> FileSystemMcpResult<String> {
+    let mut modified_content = convert_line_endings(original, LineEnding::Lf).into_owned();
+
+    // Apply edits sequentially
+    for edit in edits {
+        let edit = match edit.kind() {
+            EditKind::Text(edit) => edit,
+            EditKind::Regex(edit) => {
+                modified_content = apply_regex_edit(&modified_content, edit, path)?;
+                continue;
+            }
+        };
+
+        let normalized_old = convert_line_endings(edit.old_text(), LineEnding::Lf);
+        let normalized_new = convert_line_endings(edit.new_text(), LineEnding::Lf);
+
+        // Try exact match first
+        let match_lines = match_line_numbers(&modified_content, &normalized_old);
+        if match_lines.len() > 1 && !*edit.allow_multiple_matches() {
+            return Err(FileSystemMcpError::ValidationError {
+                message: format!(
+                    "Ambiguous edit: old_text matches {} locations",
+                    match_lines.len()
+                ),
+                path: path.display().to_string(),
+                operation: "apply_edit".to_string(),
+                data: serde_json::json!({
+                    "error": "Multiple matches found; add surrounding context or set allow_multiple_matches",
+                    "old_text": edit.old_text(),
+                    "lines": match_lines
+                }),
+            });
+        }
+        if !match_lines.is_empty() {
+            modified_content = modified_content.replace(&*normalized_old, &normalized_new);
+            continue;
+        }
+
+        // Try line-by-line matching with whitespace flexibility
+        let old_lines: Vec<&str> = normalized_old.split('\n').collect();
+        let content_lines: Vec<&str> = modified_content.split('\n').collect();
+        let mut match_found = false;
+
+        for i in 0..=(content_lines.len().saturating_sub(old_lines.len())) {
+            if i + old_lines.len() > content_lines.len() {
+                break;
+            }
+
+            let potential_match = &content_lines[i..i + old_lines.len()];
+
+            // Compare lines with normalized whitespace
+            let is_match = old_lines
+                .iter()
+                .zip(potential_match.iter())
+                .all(|(old_line, content_line)| old_line.trim() == content_line.trim());
+
+            if is_match {
+                // Preserve original indentation of first line
+                let original_indent = content_lines[i]
+                    .chars()
+                    .take_while(|c| c.is_whitespace())
+                    .collect::<String>();
+
+                // Calculate the base indentation of the new text (from first non-empty line)
+                let new_text_lines: Vec<&str> = normalized_new.split('\n').collect();
+                let base_new_indent = new_text_lines
+                    .iter()
+                    .find(|line| !line.trim().is_empty())
+                    .map(|line| {
+                        line.chars()
+                            .take_while(|c| c.is_whitespace())
+                            .collect::<String>()
+                    })
+                    .unwrap_or_default();
+
+                let new_lines: Vec<String> = new_text_lines
+                    .iter()
+                    .enumerate()
+                    .map(|(j, line)| {
+                        if j == 0 {
+                            // First line: use original indentation
+                            format!("{}{}", original_indent, line.trim_start())
+                        } else if line.trim().is_empty() {
+                            // Empty lines remain empty
+                            String::new()
+                        } else {
+                            // Subsequent lines: preserve relative indentation structure
+                            let line_indent = line
+                                .chars()
+                                .take_while(|c| c.is_whitespace())
+                                .collect::<String>();
+
+                            // Calculate relative indentation from the base indentation of new text
+                            let relative_indent_size = if line_indent.len() >= base_new_indent.len()
+                            {
+                                line_indent.len() - base_new_indent.len()
+                            } else {
+                                0
+                            };
+
+                            format!(
+                                "{}{}{}",
+                                original_indent,
+                                " ".repeat(relative_indent_size),
+                                line.trim_start()
+                            )
+                        }
+                    })
+                    .collect();
+
+                // Replace the matched lines
+                let mut new_content_lines = content_lines[..i].to_vec();
+                new_content_lines.extend(new_lines.iter().map(|s| s.as_str()));
+                new_content_lines.extend(&content_lines[i + old_lines.len()..]);
+
+                modified_content = new_content_lines.join("\n");
+                match_found = true;
+                break;
+            }
+        }
+
+        if !match_found {
+            return Err(FileSystemMcpError::ValidationError {
+                message: "Could not find exact match for edit".to_string(),
+                path: path.display().to_string(),
+                operation: "apply_edit".to_string(),
+                data: serde_json::json!({
+                    "error": "No matching text found",
+                    "old_text": edit.old_text()
+                }),
+            });
+        }
+    }
+
+    Ok(modified_content)
+}
+
+/// 1-based line numbers at which each non-overlapping occurrence of `needle` starts
+fn match_line_numbers(content: &str, needle: &str) -> Vec<usize> {
+    content
+        .match_indices(needle)
+        .map(|(offset, _)| content[..offset].matches('\n').count() + 1)
+        .collect()
+}
+
+/// Replace the first match of a regex edit, or every match with the `g` flag
+fn apply_regex_edit(
+    content: &str,
+    edit: &RegexEditOperation,
+    path: &Path,
+) -> FileSystemMcpResult<String> {
+    let regex = edit.regex()?;
+    if !regex.is_match(content) {
+        return Err(FileSystemMcpError::ValidationError {
+            message: "Could not find match for regex edit".to_string(),
+            path: path.display().to_string(),
+            operation: "apply_edit".to_string(),
+            data: serde_json::json!({
+                "error": "No matching text found",
+                "pattern": edit.pattern()
+            }),
+        });
+    }
+
+    let replacement = convert_line_endings(edit.replacement(), LineEnding::Lf);
+    let replaced = if edit.is_global() {
+        regex.replace_all(content, replacement.as_ref())
+    } else {
+        regex.replace(content, replacement.as_ref())
+    };
+    Ok(replaced.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(original: &str, edits: &[EditOperation]) -> FileSystemMcpResult<String> {
+        apply_edits_to_content(original, edits, Path::new("test.txt"))
+    }
+
+    fn edit(old_text: &str, new_text: &str) -> EditOperation {
+        EditOperation::new(old_text.to_string(), new_text.to_string())
+    }
+
+    #[test]
+    fn test_exact_match_edits() {
+        let cases = [
+            (
+                "Hello world\nThis is a test\nEnd of file",
+                vec![edit("Hello world", "Hello Rust")],
+                "Hello Rust\nThis is a test\nEnd of file",
+            ),
+            (
+                "let x = 1;\nlet y = 2;\nlet z = 3;",
+                vec![
+                    edit("let x = 1;", "let x = 10;"),
+                    edit("let y = 2;", "let y = 20;"),
+                    edit("let z = 3;", "let z = 30;"),
+                ],
+                "let x = 10;\nlet y = 20;\nlet z = 30;",
+            ),
+            (
+                "Keep this line\nDelete this line\nKeep this too",
+                vec![edit("Delete this line\n", "")],
+                "Keep this line\nKeep this too",
+            ),
+            (
+                "Line 1\nLine 3",
+                vec![edit("Line 1\nLine 3", "Line 1\nLine 2\nLine 3")],
+                "Line 1\nLine 2\nLine 3",
+            ),
+            ("", vec![edit("", "Hello world")], "Hello world"),
+            (
+                "Hello 世界\nRust is 🦀",
+                vec![edit("Hello 世界", "你好 World")],
+                "你好 World\nRust is 🦀",
+            ),
+            (
+                "Step 1\nStep 2\nStep 3",
+                vec![
+                    edit("Step 1", "Phase 1"),
+                    edit("Phase 1\nStep 2", "Phase 1\nPhase 2"),
+                    edit("Phase 2\nStep 3", "Phase 2\nPhase 3"),
+                ],
+                "Phase 1\nPhase 2\nPhase 3",
+            ),
+            (
+                "Hello\r\nWorld\r\nTest",
+                vec![edit("Hello\nWorld", "Hi\nEveryone")],
+                "Hi\nEveryone\nTest",
+            ),
+        ];
+
+        for (original, edits, expected) in cases {
+            assert_eq!(apply(original, &edits).unwrap(), expected, "{original:?}");
+        }
+    }
+
+    #[test]
+    fn test_whitespace_flexible_edits_keep_indentation() {
+        let cases = [
+            (
+                "    function test() {\n        return true;\n    }",
+                edit(
+                    "function test() {\n    return true;\n}",
+                    "function test() {\n    return false;\n}",
+                ),
+                "    function test() {\n        return false;\n    }",
+            ),
+            (
+                "class Test {\n    method1() {\n        console.log('test');\n    }\n}",
+                edit(
+                    "method1() {\n    console.log('test');\n}",
+                    "method1() {\n    console.log('updated');\n    return true;\n}",
+                ),
+                "class Test {\n    method1() {\n        console.log('updated');\n        return true;\n    }\n}",
+            ),
+            (
+                "    if (condition) {\n        doSomething();\n        doMore();\n    }",
+                edit(
+                    "if (condition) {\n    doSomething();\n    doMore();\n}",
+                    "if (condition) {\n    doSomething();\n    doMore();\n    doEvenMore();\n}",
+                ),
+                "    if (condition) {\n        doSomething();\n        doMore();\n        doEvenMore();\n    }",
+            ),
+        ];
+
+        for (original, edit, expected) in cases {
+            assert_eq!(apply(original, &[edit]).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_ambiguous_edits() {
+        let original = "let x = 1;\nfoo();\nlet x = 1;\nbar();\nlet x = 1;";
+
+        match apply(original, &[edit("let x = 1;", "let x = 2;")]) {
+            Err(FileSystemMcpError::ValidationError { message, data, .. }) => {
+                assert!(message.contains("Ambiguous edit"));
+                assert_eq!(data["lines"], serde_json::json!([1, 3, 5]));
+            }
+            other => panic!("Expected ValidationError, got {other:?}"),
+        }
+
+        let edits = [edit("let x = 1;", "let x = 2;").with_allow_multiple_matches(true)];
+        assert_eq!(
+            apply(original, &edits).unwrap(),
+            "let x = 2;\nfoo();\nlet x = 2;\nbar();\nlet x = 2;"
+        );
+    }
+
+    #[test]
+    fn test_unmatched_edit_is_rejected() {
+        match apply("Hello world", &[edit("Goodbye world", "Hi world")]) {
+            Err(FileSystemMcpError::ValidationError { message, path, .. }) => {
+                assert!(message.contains("Could not find exact match"));
+                assert_eq!(path, "test.txt");
+            }
+            other => panic!("Expected ValidationError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_regex_edits() {
+        let edits = [EditOperation::regex(
+            r#"(?<name>\w+) = "(\d+)\.(\d+)\.\d+""#,
+            r#"${name} = "$2.$3""#,
+            "",
+        )];
+        // Without the g flag only the first match is replaced
+        assert_eq!(
+            apply("serde = \"1.0.1\"\ntokio = \"1.2.3\"\n", &edits).unwrap(),
+            "serde = \"1.0\"\ntokio = \"1.2.3\"\n"
+        );
+
+        let edits = [
+            EditOperation::regex("^error", "WARN", "gim"),
+            EditOperation::regex("o", "0", "g"),
+        ];
+        assert_eq!(
+            apply("ERROR one\nerror two\nok\n", &edits).unwrap(),
+            "WARN 0ne\nWARN tw0\n0k\n"
+        );
+
+        let edits = [EditOperation::regex("missing", "x", "g")];
+        assert!(matches!(
+            apply("text", &edits),
+            Err(FileSystemMcpError::ValidationError { .. })
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+
+use crate::models::responses::EnvFileResponse;
+
+/// Parse the contents of a `.env` file
+///
+/// Supports `#` comments, blank lines, an `export` prefix, single-quoted (literal)
+/// and double-quoted values, double-quoted values spanning several lines, and
+/// backslash line continuation for unquoted values. `${VAR}` in unquoted and
+/// double-quoted values expands to a variable defined earlier in the same file;
+/// the server's own environment is never consulted. Lines that cannot be parsed
+/// are skipped and reported in `warnings`. A later definition of a key wins.
+pub fn parse_env_file(contents: &str) -> EnvFileResponse {
+    let mut variables = BTreeMap::new();
+    let mut warnings = Vec::new();
+    let mut lines = contents.lines().enumerate();
+
+    while let Some((index, line)) = lines.next() {
+        let line_number = index + 1;
+        let mut logical = line.trim().to_string();
+        while logical.ends_with('\\') && !logical.starts_with('#') {
+            logical.pop();
+            match lines.next() {
+                Some((_, next)) => logical.push_str(next.trim()),
+                None => break,
+            }
+        }
+
+        if logical.is_empty() || logical.starts_with('#') {
+            continue;
+        }
+
+        let statement = logical
+            .strip_prefix("export ")
+            .map_or(logical.as_str(), str::trim_start);
+        let Some((key, value)) = statement.split_once('=') else {
+            warnings.push(format!(
+                "Line {}: skipped '{}' because it has no '='",
+                line_number, statement
+            ));
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            warnings.push(format!(
+                "Line {}: skipped '{}' because the name is empty",
+                line_number, statement
+            ));
+            continue;
+        }
+
+        let mut value = value.trim().to_string();
+        if let Some(quoted) = value.strip_prefix('"')
+            && closing_quote(quoted).is_none()
+        {
+            for (_, next) in lines.by_ref() {
+                value.push('\n');
+                value.push_str(next);
+                if closing_quote(&value[1..]).is_some() {
+                    break;
+                }
+            }
+        }
+
+        let value = parse_value(&value, &variables, line_number, &mut warnings);
+        variables.insert(key.to_string(), value);
+    }
+
+    EnvFileResponse {
+        variables,
+        warnings,
+    }
+}
+
+/// Byte offset of the first `"` in `text` that is not escaped with a backslash
+fn closing_quote(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (offset, ch) in text.char_indices() {
+        match ch {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(offset),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn parse_value(
+    raw: &str,
+    variables: &BTreeMap<String, String>,
+    line_number: usize,
+    warnings: &mut Vec<String>,
+) -> String {
+    if let Some(quoted) = raw.strip_prefix('\'') {
+        return match quoted.split_once('\'') {
+            Some((literal, _)) => literal.to_string(),
+            None => {
+                warnings.push(format!("Line {}: unterminated quoted value", line_number));
+                quoted.to_string()
+            }
+        };
+    }
+
+    if let Some(quoted) = raw.strip_prefix('"') {
+        let end = closing_quote(quoted).unwrap_or_else(|| {
+            warnings.push(format!("Line {}: unterminated quoted value", line_number));
+            quoted.len()
+        });
+        return expand(&quoted[..end], true, variables, line_number, warnings);
+    }
+
+    // An unquoted value ends at a `#` that follows whitespace
+    let end = raw
+        .char_indices()
+        .find(|&(offset, ch)| ch == '#' && raw[..offset].ends_with(char::is_whitespace))
+        .map_or(raw.len(), |(offset, _)| offset);
+    expand(
+        raw[..end].trim_end(),
+        false,
+        variables,
+        line_number,
+        warnings,
+    )
+}
+
+/// Replace `${VAR}` references and, in double-quoted values, backslash escapes
+fn expand(
+    text: &str,
+    unescape: bool,
+    variables: &BTreeMap<String, String>,
+    line_number: usize,
+    warnings: &mut Vec<String>,
+) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if unescape => match chars.next() {
+                Some('n') => output.push('\n'),
+                Some('t') => output.push('\t'),
+                Some('r') => output.push('\r'),
+                Some(other) => output.push(other),
+                None => output.push('\\'),
+            },
+            '$' if chars.peek() == Some(&'{') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                match variables.get(&name) {
+                    Some(value) => output.push_str(value),
+                    None => warnings.push(format!(
+                        "Line {}: ${{{}}} is not defined earlier in the file",
+                        line_number, name
+                    )),
+                }
+            }
+            _ => output.push(ch),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(contents: &str) -> BTreeMap<String, String> {
+        parse_env_file(contents).variables
+    }
+
+    #[test]
+    fn test_simple_pairs_comments_and_export() {
+        let env = variables(
+            "# Database settings\n\
+             \n\
+             DB_HOST=localhost\n\
+             DB_PORT = 5432 # default port\n\
+             export API_KEY=abc#123\n\
+             \t# indented comment\n",
+        );
+
+        assert_eq!(env.len(), 3);
+        assert_eq!(env["DB_HOST"], "localhost");
+        assert_eq!(env["DB_PORT"], "5432");
+        assert_eq!(env["API_KEY"], "abc#123");
+    }
+
+    #[test]
+    fn test_quoted_values() {
+        let env = variables(
+            "GREETING=\"hello world\" # comment\n\
+             LITERAL='no ${EXPANSION} here \\n'\n\
+             ESCAPED=\"tab\\there \\\"quoted\\\"\"\n\
+             EMPTY=\n",
+        );
+
+        assert_eq!(env["GREETING"], "hello world");
+        assert_eq!(env["LITERAL"], "no ${EXPANSION} here \\n");
+        assert_eq!(env["ESCAPED"], "tab\there \"quoted\"");
+        assert_eq!(env["EMPTY"], "");
+    }
+
+    #[test]
+    fn test_interpolation_uses_earlier_variables() {
+        let result = parse_env_file(
+            "HOST=db.internal\n\
+             URL=postgres://${HOST}:5432\n\
+             QUOTED=\"${URL}/app\"\n\
+             MISSING=${NOT_SET}-suffix\n",
+        );
+
+        assert_eq!(result.variables["URL"], "postgres://db.internal:5432");
+        assert_eq!(
+            result.variables["QUOTED"],
+            "postgres://db.internal:5432/app"
+        );
+        assert_eq!(result.variables["MISSING"], "-suffix");
+        assert_eq!(
+            result.warnings,
+            vec!["Line 4: ${NOT_SET} is not defined earlier in the file"]
+        );
+    }
+
+    #[test]
+    fn test_multi_line_values() {
+        let env = variables(
+            "COMMAND=run --verbose \\\n    --port 8080\n\
+             CERT=\"-----BEGIN-----\nabc\n-----END-----\"\n\
+             AFTER=1\n",
+        );
+
+        assert_eq!(env["COMMAND"], "run --verbose --port 8080");
+        assert_eq!(env["CERT"], "-----BEGIN-----\nabc\n-----END-----");
+        assert_eq!(env["AFTER"], "1");
+    }
+
+    #[test]
+    fn test_lines_without_equals_are_reported() {
+        let result = parse_env_file("VALID=1\nJUST_A_WORD\n=orphan\n");
+
+        assert_eq!(result.variables.len(), 1);
+        assert_eq!(
+            result.warnings,
+            vec![
+                "Line 2: skipped 'JUST_A_WORD' because it has no '='",
+                "Line 3: skipped '=orphan' because the name is empty",
+            ]
+        );
+    }
+}
@@ -18,6 +18,8 @@
 //! - [`is_path_allowed`] - Checks if a path is within allowed directory boundaries
 //! - [`resolve_directories`] - Resolves and validates directory paths for configuration
 //! - [`validate_directories`] - Validates directory permissions and accessibility
+//! - [`disk_space`] - Reports total and free space of the filesystem holding a path
+//! - [`describe_directory`] - Reports existence, access and free space of a directory
 //!
 //! # Usage Example
 //!
@@ -44,10 +46,11 @@
 //! - [`FileSystemMcpError::PermissionDenied`] - Access denied or path outside allowed directories
 //! - [`FileSystemMcpError::ValidationError`] - Configuration or validation failure
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 use crate::errors::{FileSystemMcpError, FileSystemMcpResult};
+use crate::models::responses::{AllowedDirectoryInfo, DiskSpaceInfo};
 
 /// Resolve and canonicalize directory paths
 ///
@@ -206,6 +209,73 @@ pub async fn validate_directories(directories: &[PathBuf]) -> FileSystemMcpResul
     Ok(())
 }
 
+/// Report total, free and available space of the filesystem containing `path`
+///
+/// # Errors
+///
+/// * [`FileSystemMcpError::IoError`] - If the filesystem statistics cannot be read,
+///   or the platform does not support querying them
+pub fn disk_space(path: &Path) -> FileSystemMcpResult<DiskSpaceInfo> {
+    #[cfg(unix)]
+    {
+        let stats = rustix::fs::statvfs(path).map_err(|e| FileSystemMcpError::IoError {
+            message: format!("Failed to read filesystem statistics: {}", e),
+            path: path.display().to_string(),
+        })?;
+        let fragment_size = stats.f_frsize;
+
+        Ok(DiskSpaceInfo {
+            path: path.display().to_string(),
+            total_bytes: stats.f_blocks.saturating_mul(fragment_size),
+            free_bytes: stats.f_bfree.saturating_mul(fragment_size),
+            available_bytes: stats.f_bavail.saturating_mul(fragment_size),
+        })
+    }
+
+    #[cfg(not(unix))]
+    {
+        Err(FileSystemMcpError::IoError {
+            message: "Disk space reporting is not supported on this platform".to_string(),
+            path: path.display().to_string(),
+        })
+    }
+}
+
+/// Report the canonical path, existence, access and free space of a directory
+///
+/// Writability is checked against the directory's permissions without touching
+/// it, and reported as `false` when `writes_allowed` is not set.
+pub async fn describe_directory(path: &Path, writes_allowed: bool) -> AllowedDirectoryInfo {
+    let exists = fs::metadata(path)
+        .await
+        .is_ok_and(|metadata| metadata.is_dir());
+
+    AllowedDirectoryInfo {
+        path: path.display().to_string(),
+        canonical_path: fs::canonicalize(path)
+            .await
+            .ok()
+            .map(|canonical| canonical.display().to_string()),
+        exists: Some(exists),
+        readable: Some(exists && fs::read_dir(path).await.is_ok()),
+        writable: Some(exists && writes_allowed && is_writable(path)),
+        available_bytes: disk_space(path).ok().map(|space| space.available_bytes),
+    }
+}
+
+/// Whether the current user may create files in `directory`
+fn is_writable(directory: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        rustix::fs::access(directory, rustix::fs::Access::WRITE_OK).is_ok()
+    }
+
+    #[cfg(not(unix))]
+    {
+        std::fs::metadata(directory).is_ok_and(|metadata| !metadata.permissions().readonly())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -293,4 +363,22 @@ mod tests {
         let result = resolve_directories(vec![valid_path, invalid_path]).await;
         assert!(result.is_err()); // Should fail on first invalid path
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_space_reports_real_filesystem() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let info = disk_space(temp_dir.path()).unwrap();
+        assert_eq!(info.path, temp_dir.path().display().to_string());
+        assert!(info.total_bytes > 0);
+        assert!(info.free_bytes <= info.total_bytes);
+        assert!(info.available_bytes <= info.free_bytes);
+    }
+
+    #[test]
+    fn test_disk_space_missing_path() {
+        let result = disk_space(Path::new("/definitely/not/a/real/path"));
+        assert!(matches!(result, Err(FileSystemMcpError::IoError { .. })));
+    }
 }
@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+
+use crate::models::requests::LineEnding;
+
+/// Rewrite the line breaks in `content` for the requested style
+///
+/// `Lf` turns every `\r\n` into `\n`, `CrLf` turns every `\n` not already preceded
+/// by `\r` into `\r\n`, and `Preserve` leaves the content alone. A lone `\r` is
+/// never treated as a line break. Content that needs no change is borrowed.
+pub fn convert_line_endings(content: &str, line_ending: LineEnding) -> Cow<'_, str> {
+    match line_ending {
+        LineEnding::Preserve => Cow::Borrowed(content),
+        LineEnding::Lf if content.contains("\r\n") => Cow::Owned(content.replace("\r\n", "\n")),
+        LineEnding::Lf => Cow::Borrowed(content),
+        LineEnding::CrLf => {
+            let bare_newlines = content.matches('\n').count() - content.matches("\r\n").count();
+            if bare_newlines == 0 {
+                return Cow::Borrowed(content);
+            }
+
+            let mut converted = String::with_capacity(content.len() + bare_newlines);
+            let mut previous = None;
+            for ch in content.chars() {
+                if ch == '\n' && previous != Some('\r') {
+                    converted.push('\r');
+                }
+                converted.push(ch);
+                previous = Some(ch);
+            }
+            Cow::Owned(converted)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIXED: &str = "unix\nwindows\r\nold mac\rlast\n";
+
+    #[test]
+    fn test_crlf_converts_bare_newlines_only() {
+        let converted = convert_line_endings(MIXED, LineEnding::CrLf);
+
+        assert_eq!(converted, "unix\r\nwindows\r\nold mac\rlast\r\n");
+        assert_eq!(
+            convert_line_endings(&converted, LineEnding::CrLf),
+            converted,
+            "conversion is idempotent"
+        );
+        assert!(matches!(
+            convert_line_endings("a\r\nb", LineEnding::CrLf),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_lf_and_preserve() {
+        assert_eq!(
+            convert_line_endings(MIXED, LineEnding::Lf),
+            "unix\nwindows\nold mac\rlast\n"
+        );
+        assert_eq!(convert_line_endings(MIXED, LineEnding::Preserve), MIXED);
+        assert!(matches!(
+            convert_line_endings("no breaks", LineEnding::Lf),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+/// MIME type used when the extension is missing or not recognised
+pub const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// Extensions whose `mime_guess` type is overridden
+const OVERRIDES: &[(&str, &str)] = &[
+    // `mime_guess` says chemical/x-xyz, which no client renders; treat it as opaque data
+    ("xyz", DEFAULT_MIME_TYPE),
+];
+
+/// Determine the MIME type of a file from its extension
+///
+/// Matching is case-insensitive. [`OVERRIDES`] take precedence over `mime_guess`;
+/// extensions neither knows fall back to [`DEFAULT_MIME_TYPE`].
+pub fn mime_type_for_path(path: &Path) -> &'static str {
+    let Some(extension) = path.extension().and_then(|ext| ext.to_str()) else {
+        return DEFAULT_MIME_TYPE;
+    };
+    let extension = extension.to_ascii_lowercase();
+
+    OVERRIDES
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map(|(_, mime_type)| *mime_type)
+        .or_else(|| mime_guess::from_ext(&extension).first_raw())
+        .unwrap_or(DEFAULT_MIME_TYPE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_type_for_known_extensions() {
+        let cases = [
+            ("image.png", "image/png"),
+            ("photo.jpg", "image/jpeg"),
+            ("photo.jpeg", "image/jpeg"),
+            ("anim.gif", "image/gif"),
+            ("image.webp", "image/webp"),
+            ("icon.svg", "image/svg+xml"),
+            ("song.mp3", "audio/mpeg"),
+            ("clip.mp4", "video/mp4"),
+            ("sound.wav", "audio/wav"),
+            ("sound.ogg", "audio/ogg"),
+            ("doc.pdf", "application/pdf"),
+            ("bitmap.bmp", "image/bmp"),
+            ("scan.tiff", "image/tiff"),
+        ];
+
+        for (file, expected) in cases {
+            assert_eq!(mime_type_for_path(Path::new(file)), expected, "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_mime_type_is_case_insensitive() {
+        assert_eq!(mime_type_for_path(Path::new("IMAGE.PNG")), "image/png");
+        assert_eq!(mime_type_for_path(Path::new("photo.JpEg")), "image/jpeg");
+    }
+
+    #[test]
+    fn test_mime_type_fallback() {
+        assert_eq!(mime_type_for_path(Path::new("data.xyz")), DEFAULT_MIME_TYPE);
+        assert_eq!(mime_type_for_path(Path::new("Makefile")), DEFAULT_MIME_TYPE);
+        assert_eq!(mime_type_for_path(Path::new("data.XYZ")), DEFAULT_MIME_TYPE);
+    }
+}
@@ -1,3 +1,10 @@
+pub mod clipboard;
+pub mod diff;
+pub mod edit;
+pub mod env;
 pub mod fs;
+pub mod line_endings;
 pub mod logging;
+pub mod mime;
 pub mod path;
+pub mod yaml;
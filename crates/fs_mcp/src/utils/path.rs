@@ -31,6 +31,50 @@ pub fn normalize_path(path: &Path) -> PathBuf {
         })
 }
 
+/// Resolve a path against a base directory without touching the filesystem
+///
+/// Expands `~`, joins relative paths onto `base_directory` and removes `.` and `..`
+/// components. Symlinks are not followed.
+pub fn resolve_relative_path(raw_path: &str, base_directory: &Path) -> PathBuf {
+    let expanded = PathBuf::from(expand_home(raw_path));
+    if expanded.is_absolute() {
+        normalize_path(&expanded)
+    } else {
+        normalize_path(&base_directory.join(expanded))
+    }
+}
+
+/// Canonicalize the longest existing prefix of a path and append the rest
+///
+/// Lets paths that do not exist yet be compared against canonical allowed
+/// directories, with symlinks in the existing part resolved.
+pub fn canonicalize_existing_prefix(path: &Path) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return Some(
+                missing
+                    .iter()
+                    .rev()
+                    .fold(canonical, |acc, name| acc.join(name)),
+            );
+        }
+        missing.push(existing.file_name()?);
+        existing = existing.parent()?;
+    }
+}
+
+/// Check whether an already resolved path, which may not exist yet, lies inside an
+/// allowed directory once symlinks in its existing prefix are followed
+pub fn is_resolved_path_allowed(resolved: &Path, allowed_directories: &[PathBuf]) -> bool {
+    canonicalize_existing_prefix(resolved).is_some_and(|canonical| {
+        allowed_directories
+            .iter()
+            .any(|allowed| canonical.starts_with(allowed))
+    })
+}
+
 /// Check if a given path is within allowed directories
 ///
 /// This function performs a security check to determine if a path falls within
@@ -183,7 +227,7 @@ mod tests {
         let path = Path::new("path/to/dir/");
         let result = normalize_path(path);
         // The trailing separator handling depends on the platform
-        assert!(result == PathBuf::from("path/to/dir") || result == PathBuf::from("path/to/dir/"));
+        assert!(result == Path::new("path/to/dir") || result == Path::new("path/to/dir/"));
     }
 
     /// Test expand_home with special characters and Unicode
@@ -244,6 +288,42 @@ mod tests {
         assert_eq!(result, PathBuf::from("d"));
     }
 
+    /// Test resolve_relative_path against a base directory
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_relative_path() {
+        let base = Path::new("/srv/project");
+
+        assert_eq!(
+            resolve_relative_path("/etc/./hosts", base),
+            PathBuf::from("/etc/hosts")
+        );
+        assert_eq!(
+            resolve_relative_path("./src/../lib", base),
+            PathBuf::from("/srv/project/lib")
+        );
+        assert_eq!(
+            resolve_relative_path("../../etc/passwd", base),
+            PathBuf::from("/etc/passwd")
+        );
+    }
+
+    /// Test canonicalize_existing_prefix with existing and missing paths
+    #[test]
+    fn test_canonicalize_existing_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let canonical = temp_dir.path().canonicalize().unwrap();
+
+        assert_eq!(
+            canonicalize_existing_prefix(temp_dir.path()),
+            Some(canonical.clone())
+        );
+        assert_eq!(
+            canonicalize_existing_prefix(&temp_dir.path().join("new/file.txt")),
+            Some(canonical.join("new").join("file.txt"))
+        );
+    }
+
     /// Test path security validation
     #[tokio::test]
     async fn test_path_security_validation() {
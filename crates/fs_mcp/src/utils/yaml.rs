@@ -0,0 +1,156 @@
+use serde::Deserialize;
+
+use crate::{errors::FileSystemMcpError, models::requests::YamlOutputFormat};
+
+/// Convert YAML text to pretty-printed JSON or TOML
+///
+/// A single document becomes its own value and a stream of several `---`
+/// separated documents becomes a JSON array with one element per document; empty
+/// input is `null`. Merge keys (`<<`) are applied, non-string mapping keys are
+/// converted to strings and tagged values become a one-entry object keyed by the
+/// tag. TOML has no null and no top-level arrays, so it needs a single document
+/// whose top level is a mapping without null values. `path` and `operation` only
+/// label the error.
+pub fn convert_yaml(
+    contents: &str,
+    format: YamlOutputFormat,
+    path: &str,
+    operation: &str,
+) -> Result<String, FileSystemMcpError> {
+    let invalid =
+        |message: &str, error: String, line: Option<usize>| FileSystemMcpError::ValidationError {
+            message: message.to_string(),
+            path: path.to_string(),
+            operation: operation.to_string(),
+            data: serde_json::json!({"error": error, "line": line}),
+        };
+
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(contents) {
+        let mut value = serde_yaml::Value::deserialize(document).map_err(|e| {
+            let line = e.location().map(|location| location.line());
+            invalid("Invalid YAML", e.to_string(), line)
+        })?;
+        value
+            .apply_merge()
+            .map_err(|e| invalid("Invalid YAML", e.to_string(), None))?;
+        documents.push(
+            serde_json::to_value(value)
+                .map_err(|e| invalid("Unsupported YAML", e.to_string(), None))?,
+        );
+    }
+
+    let value = match documents.len() {
+        0 => serde_json::Value::Null,
+        1 => documents.remove(0),
+        _ => serde_json::Value::Array(documents),
+    };
+
+    match format {
+        YamlOutputFormat::Json => serde_json::to_string_pretty(&value)
+            .map_err(|e| invalid("Unsupported YAML", e.to_string(), None)),
+        YamlOutputFormat::Toml => {
+            if !value.is_object() {
+                return Err(invalid(
+                    "YAML cannot be represented as TOML",
+                    "TOML output needs a single YAML document whose top level is a mapping"
+                        .to_string(),
+                    None,
+                ));
+            }
+            toml::to_string_pretty(&value)
+                .map_err(|e| invalid("YAML cannot be represented as TOML", e.to_string(), None))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_json(contents: &str) -> serde_json::Value {
+        let json = convert_yaml(contents, YamlOutputFormat::Json, "", "test").unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn test_convert_yaml_to_equivalent_json() {
+        let yaml = "name: demo\nversion: 3\nenabled: true\nratio: 0.5\nmissing: ~\ntags:\n  - a\n  - b\nnested:\n  key: value\n";
+
+        assert_eq!(
+            to_json(yaml),
+            serde_json::json!({
+                "name": "demo",
+                "version": 3,
+                "enabled": true,
+                "ratio": 0.5,
+                "missing": null,
+                "tags": ["a", "b"],
+                "nested": {"key": "value"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_multi_document_yaml_to_array() {
+        let yaml = "---\nkind: first\n---\nkind: second\n---\n- 1\n- 2\n";
+
+        assert_eq!(
+            to_json(yaml),
+            serde_json::json!([{"kind": "first"}, {"kind": "second"}, [1, 2]])
+        );
+    }
+
+    #[test]
+    fn test_convert_yaml_applies_merge_keys_and_stringifies_keys() {
+        let yaml = "base: &base\n  a: 1\nderived:\n  <<: *base\n  b: 2\nports:\n  80: http\n";
+
+        assert_eq!(
+            to_json(yaml),
+            serde_json::json!({
+                "base": {"a": 1},
+                "derived": {"a": 1, "b": 2},
+                "ports": {"80": "http"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_yaml_to_toml() {
+        let yaml = "title: demo\nserver:\n  port: 8080\n  hosts: [a, b]\n";
+
+        let toml_text = convert_yaml(yaml, YamlOutputFormat::Toml, "", "test").unwrap();
+        let parsed: toml::Value = toml::from_str(&toml_text).unwrap();
+
+        assert_eq!(parsed["title"].as_str(), Some("demo"));
+        assert_eq!(parsed["server"]["port"].as_integer(), Some(8080));
+        assert_eq!(parsed["server"]["hosts"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_convert_yaml_to_toml_requires_a_mapping() {
+        for yaml in ["- 1\n- 2\n", "a: 1\n---\nb: 2\n", "a: ~\n"] {
+            let err = convert_yaml(yaml, YamlOutputFormat::Toml, "", "test").unwrap_err();
+            assert!(matches!(err, FileSystemMcpError::ValidationError { .. }));
+        }
+    }
+
+    #[test]
+    fn test_convert_invalid_yaml_reports_line() {
+        let err = convert_yaml(
+            "a: 1\nb: [unclosed\n",
+            YamlOutputFormat::Json,
+            "x.yaml",
+            "test",
+        )
+        .unwrap_err();
+
+        match err {
+            FileSystemMcpError::ValidationError { path, data, .. } => {
+                assert_eq!(path, "x.yaml");
+                assert!(data["line"].is_u64());
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use rmcp::ErrorData as McpError;
+use rmcp::serde_json::json;
+
+// Error codes
+const ERROR_PATH_NOT_ALLOWED: &str = "path_not_allowed";
+const ERROR_REPOSITORY_NOT_FOUND: &str = "repository_not_found";
+const ERROR_INVALID_REFERENCE: &str = "invalid_reference";
+const ERROR_INVALID_ARGUMENT: &str = "invalid_argument";
+const ERROR_GIT_ERROR: &str = "git_error";
+
+/// Custom error types for better error handling
+#[derive(Debug, thiserror::Error)]
+pub enum GitServerError {
+    #[error("Path is outside the allowed directories: {path}")]
+    PathNotAllowed { path: String },
+    #[error("Not a git repository: {path}")]
+    RepositoryNotFound { path: String },
+    #[error("Invalid reference: {reference}")]
+    InvalidReference { reference: String },
+    #[error("Invalid argument: {message}")]
+    InvalidArgument { message: String },
+    #[error("Git error: {message}")]
+    Git { message: String },
+}
+
+impl From<git2::Error> for GitServerError {
+    fn from(err: git2::Error) -> Self {
+        GitServerError::Git {
+            message: err.message().to_string(),
+        }
+    }
+}
+
+impl From<GitServerError> for McpError {
+    fn from(err: GitServerError) -> Self {
+        match err {
+            GitServerError::PathNotAllowed { path } => {
+                McpError::invalid_params(ERROR_PATH_NOT_ALLOWED, Some(json!({"path": path})))
+            }
+            GitServerError::RepositoryNotFound { path } => {
+                McpError::invalid_params(ERROR_REPOSITORY_NOT_FOUND, Some(json!({"path": path})))
+            }
+            GitServerError::InvalidReference { reference } => McpError::invalid_params(
+                ERROR_INVALID_REFERENCE,
+                Some(json!({"reference": reference})),
+            ),
+            GitServerError::InvalidArgument { message } => {
+                McpError::invalid_params(ERROR_INVALID_ARGUMENT, Some(json!({"message": message})))
+            }
+            GitServerError::Git { message } => {
+                McpError::internal_error(ERROR_GIT_ERROR, Some(json!({"message": message})))
+            }
+        }
+    }
+}
+
+pub type GitServerResult<T> = Result<T, GitServerError>;
+pub type McpResult<T> = Result<T, McpError>;
+
+#[cfg(test)]
+mod tests {
+    use super::GitServerError;
+    use crate::core::error::McpError;
+
+    #[test]
+    fn test_error_conversion() {
+        let error = GitServerError::PathNotAllowed {
+            path: "/etc".to_string(),
+        };
+        let mcp_error: McpError = error.into();
+
+        assert!(mcp_error.to_string().contains("path_not_allowed"));
+    }
+}
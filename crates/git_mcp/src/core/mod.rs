@@ -0,0 +1,18 @@
+//! # Git MCP Server Core
+//!
+//! This module provides read-only git repository operations for the MCP server.
+//!
+//! ## Features
+//! - Working tree status split into staged, unstaged and untracked files
+//! - Commit history with author, date and message
+//! - Unified diffs between the working tree, the index and arbitrary refs
+//! - Local and remote branch listing
+//!
+//! ## Modules
+//! - `error`: Custom error types and error handling
+//! - `models`: Data structures for requests and responses
+//! - `provider`: Repository access restricted to allowed directories
+
+pub mod error;
+pub mod models;
+pub mod provider;
@@ -0,0 +1,88 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+
+/// Number of commits `git_log` returns when `limit` is omitted
+pub const DEFAULT_LOG_LIMIT: usize = 10;
+
+/// Upper bound for the number of commits returned by `git_log`
+pub const MAX_LOG_LIMIT: usize = 1000;
+
+fn default_log_limit() -> usize {
+    DEFAULT_LOG_LIMIT
+}
+
+/// Request to show the working tree status of a repository
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GitStatusRequest {
+    /// Path to the repository root
+    pub repo_path: String,
+}
+
+/// Request to list recent commits reachable from HEAD
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GitLogRequest {
+    /// Path to the repository root
+    pub repo_path: String,
+    /// Maximum number of commits to return (1-1000, default 10)
+    #[serde(default = "default_log_limit")]
+    pub limit: usize,
+}
+
+/// Request for a unified diff
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GitDiffRequest {
+    /// Path to the repository root
+    pub repo_path: String,
+    /// Ref (branch, tag or commit) to diff from. When omitted, unstaged changes are shown.
+    #[serde(default)]
+    pub from_ref: Option<String>,
+    /// Ref to diff to. When omitted, diffs against the working tree. Requires `from_ref`.
+    #[serde(default)]
+    pub to_ref: Option<String>,
+}
+
+/// Request to list the branches of a repository
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GitBranchListRequest {
+    /// Path to the repository root
+    pub repo_path: String,
+}
+
+/// Working tree status grouped by change kind
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct StatusResult {
+    /// Current branch, or `None` for a detached or unborn HEAD
+    pub branch: Option<String>,
+    /// Files with changes recorded in the index
+    pub staged: Vec<String>,
+    /// Tracked files with changes not yet added to the index
+    pub unstaged: Vec<String>,
+    /// Files not tracked by git
+    pub untracked: Vec<String>,
+}
+
+/// A single commit from the history
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CommitInfo {
+    /// Full commit hash
+    pub hash: String,
+    /// Author as `Name <email>`
+    pub author: String,
+    /// Author date in RFC 3339 format, in the author's timezone
+    pub date: String,
+    /// Full commit message
+    pub message: String,
+}
+
+/// A local or remote-tracking branch
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BranchInfo {
+    /// Branch name, e.g. `main` or `origin/main`
+    pub name: String,
+    /// Whether HEAD points at this branch
+    pub is_current: bool,
+    /// Whether this is a remote-tracking branch
+    pub is_remote: bool,
+    /// Hash of the commit the branch points to
+    pub commit: String,
+}
@@ -0,0 +1,418 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, FixedOffset};
+use git2::{
+    BranchType, Commit, DiffFormat, ErrorCode, Repository, Sort, Status, StatusOptions, Tree,
+};
+
+use crate::core::{
+    error::{GitServerError, GitServerResult},
+    models::{BranchInfo, CommitInfo, MAX_LOG_LIMIT, StatusResult},
+};
+
+const STAGED: Status = Status::INDEX_NEW
+    .union(Status::INDEX_MODIFIED)
+    .union(Status::INDEX_DELETED)
+    .union(Status::INDEX_RENAMED)
+    .union(Status::INDEX_TYPECHANGE);
+
+const UNSTAGED: Status = Status::WT_MODIFIED
+    .union(Status::WT_DELETED)
+    .union(Status::WT_RENAMED)
+    .union(Status::WT_TYPECHANGE);
+
+/// Read-only git operations on repositories inside the allowed directories
+#[derive(Debug, Clone)]
+pub struct GitProvider {
+    allowed_directories: Arc<Vec<PathBuf>>,
+}
+
+impl GitProvider {
+    /// Create a provider; `allowed_directories` should already be canonical
+    pub fn new(allowed_directories: Vec<PathBuf>) -> Self {
+        Self {
+            allowed_directories: Arc::new(allowed_directories),
+        }
+    }
+
+    pub fn allowed_directories(&self) -> &[PathBuf] {
+        &self.allowed_directories
+    }
+
+    /// Canonicalize `repo_path` and ensure it lies within an allowed directory
+    pub fn validate_repo_path(&self, repo_path: &str) -> GitServerResult<PathBuf> {
+        let canonical = Path::new(repo_path.trim()).canonicalize().map_err(|_| {
+            GitServerError::RepositoryNotFound {
+                path: repo_path.to_string(),
+            }
+        })?;
+
+        if self
+            .allowed_directories
+            .iter()
+            .any(|allowed| canonical.starts_with(allowed))
+        {
+            Ok(canonical)
+        } else {
+            Err(GitServerError::PathNotAllowed {
+                path: repo_path.to_string(),
+            })
+        }
+    }
+
+    /// Open the repository rooted at `repo_path` after validating it
+    fn open(&self, repo_path: &str) -> GitServerResult<Repository> {
+        let path = self.validate_repo_path(repo_path)?;
+        Repository::open(&path).map_err(|_| GitServerError::RepositoryNotFound {
+            path: repo_path.to_string(),
+        })
+    }
+
+    /// Staged, unstaged and untracked files of the working tree
+    pub fn status(&self, repo_path: &str) -> GitServerResult<StatusResult> {
+        let repo = self.open(repo_path)?;
+
+        let mut options = StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+
+        let mut result = StatusResult {
+            branch: current_branch(&repo),
+            ..StatusResult::default()
+        };
+        for entry in repo.statuses(Some(&mut options))?.iter() {
+            let Some(path) = entry.path() else {
+                continue;
+            };
+            let status = entry.status();
+            if status.intersects(STAGED) {
+                result.staged.push(path.to_string());
+            }
+            if status.intersects(UNSTAGED) {
+                result.unstaged.push(path.to_string());
+            }
+            if status.contains(Status::WT_NEW) {
+                result.untracked.push(path.to_string());
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Up to `limit` commits reachable from HEAD, newest first
+    pub fn log(&self, repo_path: &str, limit: usize) -> GitServerResult<Vec<CommitInfo>> {
+        if limit == 0 || limit > MAX_LOG_LIMIT {
+            return Err(GitServerError::InvalidArgument {
+                message: format!("limit must be between 1 and {MAX_LOG_LIMIT}"),
+            });
+        }
+
+        let repo = self.open(repo_path)?;
+        match repo.head() {
+            Ok(_) => {}
+            // A repository without commits has an empty history
+            Err(e) if matches!(e.code(), ErrorCode::UnbornBranch | ErrorCode::NotFound) => {
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push_head()?;
+
+        revwalk
+            .take(limit)
+            .map(|oid| Ok(commit_info(&repo.find_commit(oid?)?)))
+            .collect()
+    }
+
+    /// Unified diff between refs, the index and the working tree
+    ///
+    /// - no refs: working tree against the index (unstaged changes)
+    /// - `from_ref` only: `from_ref` against the working tree, including staged changes
+    /// - both refs: `from_ref` against `to_ref`
+    pub fn diff(
+        &self,
+        repo_path: &str,
+        from_ref: Option<&str>,
+        to_ref: Option<&str>,
+    ) -> GitServerResult<String> {
+        let repo = self.open(repo_path)?;
+
+        let diff = match (from_ref, to_ref) {
+            (None, None) => repo.diff_index_to_workdir(None, None)?,
+            (Some(from), None) => {
+                let from_tree = resolve_tree(&repo, from)?;
+                repo.diff_tree_to_workdir_with_index(Some(&from_tree), None)?
+            }
+            (Some(from), Some(to)) => {
+                let from_tree = resolve_tree(&repo, from)?;
+                let to_tree = resolve_tree(&repo, to)?;
+                repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), None)?
+            }
+            (None, Some(_)) => {
+                return Err(GitServerError::InvalidArgument {
+                    message: "to_ref requires from_ref".to_string(),
+                });
+            }
+        };
+
+        let mut patch = String::new();
+        diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                patch.push(line.origin());
+            }
+            patch.push_str(&String::from_utf8_lossy(line.content()));
+            true
+        })?;
+
+        Ok(patch)
+    }
+
+    /// Local and remote-tracking branches
+    pub fn branches(&self, repo_path: &str) -> GitServerResult<Vec<BranchInfo>> {
+        let repo = self.open(repo_path)?;
+
+        let mut branches = Vec::new();
+        for branch in repo.branches(None)? {
+            let (branch, branch_type) = branch?;
+            let Some(name) = branch.name()?.map(str::to_string) else {
+                continue;
+            };
+            let commit = branch
+                .get()
+                .resolve()
+                .ok()
+                .and_then(|reference| reference.target())
+                .map(|oid| oid.to_string())
+                .unwrap_or_default();
+
+            branches.push(BranchInfo {
+                name,
+                is_current: branch.is_head(),
+                is_remote: branch_type == BranchType::Remote,
+                commit,
+            });
+        }
+
+        Ok(branches)
+    }
+}
+
+/// Short name of the checked-out branch, if HEAD points at one
+fn current_branch(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(str::to_string)
+}
+
+/// Resolve a branch, tag or commit expression to the tree it points at
+fn resolve_tree<'r>(repo: &'r Repository, reference: &str) -> GitServerResult<Tree<'r>> {
+    repo.revparse_single(reference)
+        .and_then(|object| object.peel_to_tree())
+        .map_err(|_| GitServerError::InvalidReference {
+            reference: reference.to_string(),
+        })
+}
+
+fn commit_info(commit: &Commit) -> CommitInfo {
+    let author = commit.author();
+    let when = author.when();
+    let date = FixedOffset::east_opt(when.offset_minutes() * 60)
+        .zip(DateTime::from_timestamp(when.seconds(), 0))
+        .map(|(offset, utc)| utc.with_timezone(&offset).to_rfc3339())
+        .unwrap_or_default();
+
+    CommitInfo {
+        hash: commit.id().to_string(),
+        author: format!(
+            "{} <{}>",
+            author.name().unwrap_or_default(),
+            author.email().unwrap_or_default()
+        ),
+        date,
+        message: commit.message().unwrap_or_default().trim_end().to_string(),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use git2::{Oid, Signature};
+    use tempfile::TempDir;
+
+    /// Write `content` to `file` and commit it on the current branch
+    pub(crate) fn commit_file(repo: &Repository, file: &str, content: &str, message: &str) -> Oid {
+        let workdir = repo.workdir().unwrap();
+        std::fs::write(workdir.join(file), content).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+        let signature = Signature::now("Test Author", "author@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    /// Temporary repository plus a provider allowed to access it
+    pub(crate) fn setup_repo() -> (TempDir, Repository, GitProvider) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let provider = GitProvider::new(vec![temp_dir.path().canonicalize().unwrap()]);
+        (temp_dir, repo, provider)
+    }
+
+    fn repo_path(temp_dir: &TempDir) -> String {
+        temp_dir.path().display().to_string()
+    }
+
+    #[test]
+    fn test_rejects_path_outside_allowed_directories() {
+        let (_temp_dir, _repo, provider) = setup_repo();
+        let outside = TempDir::new().unwrap();
+        Repository::init(outside.path()).unwrap();
+
+        let result = provider.status(&repo_path(&outside));
+        assert!(matches!(result, Err(GitServerError::PathNotAllowed { .. })));
+    }
+
+    #[test]
+    fn test_rejects_non_repository() {
+        let (temp_dir, _repo, provider) = setup_repo();
+        let plain = temp_dir.path().join("plain");
+        std::fs::create_dir(&plain).unwrap();
+
+        let result = provider.status(&plain.display().to_string());
+        assert!(matches!(
+            result,
+            Err(GitServerError::RepositoryNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_status_groups_changes() {
+        let (temp_dir, repo, provider) = setup_repo();
+        commit_file(&repo, "tracked.txt", "one\n", "initial");
+
+        std::fs::write(temp_dir.path().join("tracked.txt"), "two\n").unwrap();
+        std::fs::write(temp_dir.path().join("staged.txt"), "new\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::write(temp_dir.path().join("untracked.txt"), "?\n").unwrap();
+
+        let status = provider.status(&repo_path(&temp_dir)).unwrap();
+        assert_eq!(status.staged, vec!["staged.txt"]);
+        assert_eq!(status.unstaged, vec!["tracked.txt"]);
+        assert_eq!(status.untracked, vec!["untracked.txt"]);
+        assert!(status.branch.is_some());
+    }
+
+    #[test]
+    fn test_log_returns_newest_first_with_limit() {
+        let (temp_dir, repo, provider) = setup_repo();
+        commit_file(&repo, "a.txt", "a", "first commit");
+        commit_file(&repo, "b.txt", "b", "second commit");
+        let third = commit_file(&repo, "c.txt", "c", "third commit\n\nwith a body");
+
+        let log = provider.log(&repo_path(&temp_dir), 2).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].hash, third.to_string());
+        assert_eq!(log[0].message, "third commit\n\nwith a body");
+        assert_eq!(log[0].author, "Test Author <author@example.com>");
+        assert!(DateTime::parse_from_rfc3339(&log[0].date).is_ok());
+        assert_eq!(log[1].message, "second commit");
+    }
+
+    #[test]
+    fn test_log_empty_repository_and_limit_bounds() {
+        let (temp_dir, _repo, provider) = setup_repo();
+        assert!(provider.log(&repo_path(&temp_dir), 5).unwrap().is_empty());
+        assert!(provider.log(&repo_path(&temp_dir), 0).is_err());
+        assert!(
+            provider
+                .log(&repo_path(&temp_dir), MAX_LOG_LIMIT + 1)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_diff_modes() {
+        let (temp_dir, repo, provider) = setup_repo();
+        let first = commit_file(&repo, "file.txt", "alpha\n", "first");
+        let second = commit_file(&repo, "file.txt", "beta\n", "second");
+        std::fs::write(temp_dir.path().join("file.txt"), "gamma\n").unwrap();
+        let path = repo_path(&temp_dir);
+
+        let unstaged = provider.diff(&path, None, None).unwrap();
+        assert!(unstaged.contains("-beta"));
+        assert!(unstaged.contains("+gamma"));
+
+        let against_first = provider
+            .diff(&path, Some(&first.to_string()), None)
+            .unwrap();
+        assert!(against_first.contains("-alpha"));
+        assert!(against_first.contains("+gamma"));
+
+        let between = provider
+            .diff(&path, Some(&first.to_string()), Some(&second.to_string()))
+            .unwrap();
+        assert!(between.contains("diff --git a/file.txt b/file.txt"));
+        assert!(between.contains("-alpha"));
+        assert!(between.contains("+beta"));
+        assert!(!between.contains("gamma"));
+    }
+
+    #[test]
+    fn test_diff_rejects_bad_refs() {
+        let (temp_dir, repo, provider) = setup_repo();
+        commit_file(&repo, "file.txt", "alpha\n", "first");
+        let path = repo_path(&temp_dir);
+
+        assert!(matches!(
+            provider.diff(&path, Some("no-such-ref"), None),
+            Err(GitServerError::InvalidReference { .. })
+        ));
+        assert!(matches!(
+            provider.diff(&path, None, Some("HEAD")),
+            Err(GitServerError::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_branches_marks_current() {
+        let (temp_dir, repo, provider) = setup_repo();
+        let head = commit_file(&repo, "file.txt", "alpha\n", "first");
+        repo.branch("feature", &repo.find_commit(head).unwrap(), false)
+            .unwrap();
+
+        let branches = provider.branches(&repo_path(&temp_dir)).unwrap();
+        assert_eq!(branches.len(), 2);
+
+        let feature = branches.iter().find(|b| b.name == "feature").unwrap();
+        assert!(!feature.is_current);
+        assert!(!feature.is_remote);
+        assert_eq!(feature.commit, head.to_string());
+
+        let current: Vec<_> = branches.iter().filter(|b| b.is_current).collect();
+        assert_eq!(current.len(), 1);
+        assert_ne!(current[0].name, "feature");
+    }
+}
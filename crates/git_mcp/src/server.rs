@@ -0,0 +1,319 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use rmcp::transport::SseServer;
+use rmcp::{
+    ErrorData as McpError, RoleServer, ServerHandler,
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::*,
+    service::RequestContext,
+    tool, tool_handler, tool_router,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::{
+    error::{GitServerResult, McpResult},
+    models::{GitBranchListRequest, GitDiffRequest, GitLogRequest, GitStatusRequest},
+    provider::GitProvider,
+};
+
+/// Git MCP Server with read-only repository operations
+#[derive(Clone)]
+pub struct GitService {
+    provider: GitProvider,
+    tool_router: ToolRouter<GitService>,
+}
+
+impl GitService {
+    pub fn new(allowed_directories: Vec<PathBuf>) -> Self {
+        Self {
+            provider: GitProvider::new(allowed_directories),
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    /// Run a repository operation on the blocking thread pool
+    async fn blocking<T, F>(&self, operation: F) -> McpResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(GitProvider) -> GitServerResult<T> + Send + 'static,
+    {
+        let provider = self.provider.clone();
+        tokio::task::spawn_blocking(move || operation(provider))
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .map_err(Into::into)
+    }
+}
+
+#[tool_router]
+impl GitService {
+    #[tool(
+        description = "Show the working tree status of a repository as JSON with staged, unstaged and untracked file lists"
+    )]
+    pub(crate) async fn git_status(
+        &self,
+        Parameters(req): Parameters<GitStatusRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self
+            .blocking(move |provider| provider.status(&req.repo_path))
+            .await?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "List recent commits reachable from HEAD, newest first, with hash, author, date and message"
+    )]
+    pub(crate) async fn git_log(
+        &self,
+        Parameters(req): Parameters<GitLogRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self
+            .blocking(move |provider| provider.log(&req.repo_path, req.limit))
+            .await?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Show a unified diff. Without refs, shows unstaged changes. With from_ref only, compares that ref to the working tree. With both, compares the two refs."
+    )]
+    pub(crate) async fn git_diff(
+        &self,
+        Parameters(req): Parameters<GitDiffRequest>,
+    ) -> McpResult<CallToolResult> {
+        let diff = self
+            .blocking(move |provider| {
+                provider.diff(
+                    &req.repo_path,
+                    req.from_ref.as_deref(),
+                    req.to_ref.as_deref(),
+                )
+            })
+            .await?;
+        Ok(CallToolResult::success(vec![Content::text(diff)]))
+    }
+
+    #[tool(description = "List local and remote-tracking branches, marking the current one")]
+    pub(crate) async fn git_branch_list(
+        &self,
+        Parameters(req): Parameters<GitBranchListRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self
+            .blocking(move |provider| provider.branches(&req.repo_path))
+            .await?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for GitService {
+    fn get_info(&self) -> ServerInfo {
+        let allowed = self
+            .provider
+            .allowed_directories()
+            .iter()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(format!(
+                "Git MCP Server for read-only repository inspection. Tools: git_status (staged, unstaged and untracked files), git_log (commit history), git_diff (unified diffs between refs, index and working tree), git_branch_list (local and remote branches). repo_path must be a repository root inside the allowed directories: {}.",
+                allowed
+            )),
+        }
+    }
+
+    async fn initialize(
+        &self,
+        _request: InitializeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, McpError> {
+        tracing::info!("Git MCP Server initialized successfully");
+        Ok(self.get_info())
+    }
+}
+
+/// Transport the MCP server is served over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// JSON-RPC over stdin/stdout
+    Stdio,
+    /// Server-Sent Events over HTTP, listening on the given address
+    Sse(SocketAddr),
+}
+
+/// Start serving over SSE, with one `GitService` per client session
+///
+/// Returns the token that shuts the HTTP server down when cancelled.
+pub(crate) async fn serve_sse(
+    bind: SocketAddr,
+    allowed_directories: Vec<PathBuf>,
+) -> std::io::Result<CancellationToken> {
+    let server = SseServer::serve(bind).await?;
+    Ok(server.with_service(move || GitService::new(allowed_directories.clone())))
+}
+
+pub async fn run(
+    allowed_directories: Vec<PathBuf>,
+    transport: Transport,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use rmcp::{ServiceExt, transport::stdio};
+
+    match transport {
+        Transport::Stdio => {
+            let service = GitService::new(allowed_directories)
+                .serve(stdio())
+                .await
+                .inspect_err(|e| {
+                    tracing::error!("serving error: {:?}", e);
+                })?;
+
+            service.waiting().await?;
+        }
+        Transport::Sse(bind) => {
+            let ct = serve_sse(bind, allowed_directories).await?;
+            tracing::info!("Serving MCP over SSE at http://{}/sse", bind);
+
+            tokio::signal::ctrl_c().await?;
+            ct.cancel();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rmcp::handler::server::wrapper::Parameters;
+    use rmcp::model::ProtocolVersion;
+
+    use super::GitService;
+    use crate::core::models::{
+        GitBranchListRequest, GitDiffRequest, GitLogRequest, GitStatusRequest,
+    };
+    use crate::core::provider::tests::{commit_file, setup_repo};
+
+    fn text_of(result: &rmcp::model::CallToolResult) -> String {
+        result.content[0].as_text().unwrap().text.clone()
+    }
+
+    #[test]
+    fn test_tool_router_has_routes() {
+        let router = GitService::tool_router();
+        assert!(router.has_route("git_status"));
+        assert!(router.has_route("git_log"));
+        assert!(router.has_route("git_diff"));
+        assert!(router.has_route("git_branch_list"));
+    }
+
+    #[test]
+    fn test_server_info() {
+        use rmcp::ServerHandler;
+
+        let info = GitService::new(Vec::new()).get_info();
+        assert_eq!(info.protocol_version, ProtocolVersion::V_2024_11_05);
+        assert!(info.capabilities.tools.is_some());
+        assert!(info.instructions.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_tools_return_json_and_diff() {
+        let (temp_dir, repo, provider) = setup_repo();
+        commit_file(&repo, "file.txt", "alpha\n", "first");
+        std::fs::write(temp_dir.path().join("file.txt"), "beta\n").unwrap();
+        let service = GitService::new(provider.allowed_directories().to_vec());
+        let repo_path = temp_dir.path().display().to_string();
+
+        let status = service
+            .git_status(Parameters(GitStatusRequest {
+                repo_path: repo_path.clone(),
+            }))
+            .await
+            .unwrap();
+        let status: serde_json::Value = serde_json::from_str(&text_of(&status)).unwrap();
+        assert_eq!(status["unstaged"], serde_json::json!(["file.txt"]));
+
+        let log = service
+            .git_log(Parameters(GitLogRequest {
+                repo_path: repo_path.clone(),
+                limit: 10,
+            }))
+            .await
+            .unwrap();
+        let log: serde_json::Value = serde_json::from_str(&text_of(&log)).unwrap();
+        assert_eq!(log[0]["message"], "first");
+
+        let diff = service
+            .git_diff(Parameters(GitDiffRequest {
+                repo_path: repo_path.clone(),
+                from_ref: None,
+                to_ref: None,
+            }))
+            .await
+            .unwrap();
+        assert!(text_of(&diff).contains("+beta"));
+
+        let branches = service
+            .git_branch_list(Parameters(GitBranchListRequest { repo_path }))
+            .await
+            .unwrap();
+        let branches: serde_json::Value = serde_json::from_str(&text_of(&branches)).unwrap();
+        assert_eq!(branches[0]["is_current"], true);
+    }
+
+    #[tokio::test]
+    async fn test_tools_reject_paths_outside_allowlist() {
+        let (_temp_dir, _repo, provider) = setup_repo();
+        let outside = tempfile::TempDir::new().unwrap();
+        git2::Repository::init(outside.path()).unwrap();
+        let service = GitService::new(provider.allowed_directories().to_vec());
+
+        let result = service
+            .git_status(Parameters(GitStatusRequest {
+                repo_path: outside.path().display().to_string(),
+            }))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sse_transport_round_trip() {
+        use rmcp::{ServiceExt, model::CallToolRequestParam, transport::SseClientTransport};
+
+        let (temp_dir, repo, provider) = setup_repo();
+        commit_file(&repo, "file.txt", "alpha\n", "served over sse");
+
+        let bind = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .unwrap();
+        let ct = super::serve_sse(bind, provider.allowed_directories().to_vec())
+            .await
+            .unwrap();
+
+        let transport = SseClientTransport::start(format!("http://{}/sse", bind))
+            .await
+            .unwrap();
+        let client = ().serve(transport).await.unwrap();
+
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "git_log".into(),
+                arguments: serde_json::json!({"repo_path": temp_dir.path()})
+                    .as_object()
+                    .cloned(),
+            })
+            .await
+            .unwrap();
+        assert!(text_of(&result).contains("served over sse"));
+
+        client.cancel().await.unwrap();
+        ct.cancel();
+    }
+}
@@ -0,0 +1,62 @@
+use rmcp::ErrorData as McpError;
+use rmcp::serde_json::json;
+
+// Error codes
+const ERROR_INVALID_JSON: &str = "invalid_json";
+const ERROR_INVALID_EXPRESSION: &str = "invalid_expression";
+const ERROR_QUERY_FAILED: &str = "query_failed";
+const ERROR_INVALID_SCHEMA: &str = "invalid_schema";
+
+/// Custom error types for better error handling
+#[derive(Debug, thiserror::Error)]
+pub enum JsonServerError {
+    #[error("Invalid JSON in '{argument}': {message}")]
+    InvalidJson { argument: String, message: String },
+    #[error("Invalid jq expression: {message}")]
+    InvalidExpression { message: String },
+    #[error("jq evaluation failed: {message}")]
+    QueryFailed { message: String },
+    #[error("Invalid JSON Schema: {message}")]
+    InvalidSchema { message: String },
+}
+
+impl From<JsonServerError> for McpError {
+    fn from(err: JsonServerError) -> Self {
+        match err {
+            JsonServerError::InvalidJson { argument, message } => McpError::invalid_params(
+                ERROR_INVALID_JSON,
+                Some(json!({"argument": argument, "message": message})),
+            ),
+            JsonServerError::InvalidExpression { message } => McpError::invalid_params(
+                ERROR_INVALID_EXPRESSION,
+                Some(json!({"message": message})),
+            ),
+            JsonServerError::QueryFailed { message } => {
+                McpError::invalid_params(ERROR_QUERY_FAILED, Some(json!({"message": message})))
+            }
+            JsonServerError::InvalidSchema { message } => {
+                McpError::invalid_params(ERROR_INVALID_SCHEMA, Some(json!({"message": message})))
+            }
+        }
+    }
+}
+
+pub type JsonServerResult<T> = Result<T, JsonServerError>;
+pub type McpResult<T> = Result<T, McpError>;
+
+#[cfg(test)]
+mod tests {
+    use super::JsonServerError;
+    use crate::core::error::McpError;
+
+    #[test]
+    fn test_error_conversion() {
+        let error = JsonServerError::InvalidJson {
+            argument: "input".to_string(),
+            message: "EOF while parsing".to_string(),
+        };
+        let mcp_error: McpError = error.into();
+
+        assert!(mcp_error.to_string().contains("invalid_json"));
+    }
+}
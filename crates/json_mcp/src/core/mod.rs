@@ -0,0 +1,18 @@
+//! # JSON MCP Server Core
+//!
+//! This module provides JSON querying and transformation for the MCP server.
+//!
+//! ## Features
+//! - jq expressions evaluated with `jaq`
+//! - Pretty-printing and minifying
+//! - JSON Schema validation
+//! - Deep merging and structural diffs
+//!
+//! ## Modules
+//! - `error`: Custom error types and error handling
+//! - `models`: Data structures for requests and responses
+//! - `processor`: Parsing, querying and transformation functions
+
+pub mod error;
+pub mod models;
+pub mod processor;
@@ -0,0 +1,101 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Request to evaluate a jq expression
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct QueryJsonRequest {
+    /// JSON document to query
+    pub input: String,
+    /// jq expression, e.g. `.items[] | select(.price > 10) | .name`
+    pub expression: String,
+    /// Maximum number of values to return (default: 1000)
+    #[serde(default)]
+    pub max_results: Option<usize>,
+}
+
+/// Values produced by a jq expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResults {
+    /// Output values in the order the expression produced them
+    pub values: Vec<Value>,
+    /// Whether evaluation stopped early at the result count or size limit
+    pub truncated: bool,
+}
+
+/// Request to pretty-print or minify a document
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FormatJsonRequest {
+    /// JSON document to format
+    pub input: String,
+    /// Minify instead of pretty-printing
+    #[serde(default)]
+    pub compact: bool,
+}
+
+/// Request to validate a document against a JSON Schema
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ValidateJsonSchemaRequest {
+    /// JSON Schema to validate against
+    pub schema: String,
+    /// JSON document to validate
+    pub document: String,
+}
+
+/// Request to deep-merge two documents
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct MergeJsonRequest {
+    /// Document to merge into
+    pub base: String,
+    /// Document whose values win on conflicting keys
+    pub r#override: String,
+}
+
+/// Request to diff two documents
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct JsonDiffRequest {
+    /// Original document
+    pub a: String,
+    /// Changed document
+    pub b: String,
+}
+
+/// A single schema violation
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SchemaViolation {
+    /// JSON Pointer to the offending value in the document
+    pub instance_path: String,
+    /// JSON Pointer to the schema keyword that failed
+    pub schema_path: String,
+    pub message: String,
+}
+
+/// Outcome of a schema validation
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SchemaValidationResult {
+    pub valid: bool,
+    pub errors: Vec<SchemaViolation>,
+}
+
+/// Kind of change between two documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference between two documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DiffEntry {
+    /// JSON Pointer to the differing value
+    pub path: String,
+    pub op: DiffOp,
+    /// Value in `a`, absent for additions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old: Option<Value>,
+    /// Value in `b`, absent for removals
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new: Option<Value>,
+}
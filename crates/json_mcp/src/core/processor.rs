@@ -0,0 +1,396 @@
+use jaq_core::{
+    Compiler, Ctx, RcIter,
+    load::{self, Arena, File, Loader},
+};
+use jaq_json::Val;
+use serde_json::{Map, Value};
+
+use crate::core::{
+    error::{JsonServerError, JsonServerResult},
+    models::{DiffEntry, DiffOp, QueryResults, SchemaValidationResult, SchemaViolation},
+};
+
+/// Values `query_json` returns when `max_results` is not given
+pub const DEFAULT_MAX_RESULTS: usize = 1000;
+
+/// Upper bound on the serialized size of the values `query_json` returns, in bytes
+pub const MAX_RESULT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Parse `input`, naming the tool `argument` it came from in errors
+pub fn parse_json(argument: &str, input: &str) -> JsonServerResult<Value> {
+    serde_json::from_str(input).map_err(|e| JsonServerError::InvalidJson {
+        argument: argument.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Evaluate a jq `expression` against `input`, returning its output values
+///
+/// Evaluation stops once `max_results` values have been produced or their serialized
+/// size would pass [`MAX_RESULT_BYTES`], so expressions such as recursive descent over
+/// a large document cannot grow the output without bound. The work is CPU-bound;
+/// async callers should run it on a blocking thread.
+pub fn query_json(
+    input: Value,
+    expression: &str,
+    max_results: usize,
+) -> JsonServerResult<QueryResults> {
+    let program = File {
+        code: expression,
+        path: (),
+    };
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let arena = Arena::default();
+    let modules =
+        loader
+            .load(&arena, program)
+            .map_err(|errors| JsonServerError::InvalidExpression {
+                message: describe_load_errors(errors.into_iter().map(|(_, e)| e)),
+            })?;
+
+    let filter = Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(|errors| JsonServerError::InvalidExpression {
+            message: errors
+                .into_iter()
+                .flat_map(|(_, undefined)| undefined)
+                .map(|(name, kind)| format!("undefined {:?} '{}'", kind, name))
+                .collect::<Vec<_>>()
+                .join("; "),
+        })?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    let mut results = QueryResults {
+        values: Vec::new(),
+        truncated: false,
+    };
+    let mut total_bytes = 0;
+    for output in filter.run((Ctx::new([], &inputs), Val::from(input))) {
+        let value = Value::from(output.map_err(|e| JsonServerError::QueryFailed {
+            message: e.to_string(),
+        })?);
+        total_bytes += serde_json::to_vec(&value).map_or(0, |bytes| bytes.len());
+        if results.values.len() >= max_results || total_bytes > MAX_RESULT_BYTES {
+            results.truncated = true;
+            break;
+        }
+        results.values.push(value);
+    }
+    Ok(results)
+}
+
+fn describe_load_errors<'a>(errors: impl Iterator<Item = load::Error<&'a str>>) -> String {
+    let near = |rest: &str| match rest.chars().take(20).collect::<String>() {
+        s if s.is_empty() => "end of input".to_string(),
+        s => format!("'{}'", s),
+    };
+
+    errors
+        .flat_map(|error| match error {
+            load::Error::Io(errors) => errors
+                .into_iter()
+                .map(|(path, message)| format!("{}: {}", path, message))
+                .collect::<Vec<_>>(),
+            load::Error::Lex(errors) => errors
+                .into_iter()
+                .map(|(expect, rest)| format!("expected {} near {}", expect.as_str(), near(rest)))
+                .collect(),
+            load::Error::Parse(errors) => errors
+                .into_iter()
+                .map(|(expect, rest)| format!("expected {} near {}", expect.as_str(), near(rest)))
+                .collect(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Serialize `value` either indented or on a single line
+pub fn format_json(value: &Value, compact: bool) -> String {
+    if compact {
+        value.to_string()
+    } else {
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    }
+}
+
+/// Validate `document` against `schema`, collecting every violation
+pub fn validate_json_schema(
+    schema: &Value,
+    document: &Value,
+) -> JsonServerResult<SchemaValidationResult> {
+    let validator =
+        jsonschema::validator_for(schema).map_err(|e| JsonServerError::InvalidSchema {
+            message: e.to_string(),
+        })?;
+
+    let errors: Vec<_> = validator
+        .iter_errors(document)
+        .map(|error| SchemaViolation {
+            instance_path: error.instance_path.to_string(),
+            schema_path: error.schema_path.to_string(),
+            message: error.to_string(),
+        })
+        .collect();
+
+    Ok(SchemaValidationResult {
+        valid: errors.is_empty(),
+        errors,
+    })
+}
+
+/// Recursively merge `overlay` into `base`
+///
+/// Objects are merged key by key; for any other pair of values, including arrays,
+/// the value from `overlay` replaces the one in `base`.
+pub fn merge_json(base: Value, overlay: Value) -> Value {
+    match (base, overlay) {
+        (Value::Object(mut base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                let merged = match base.remove(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Value::Object(base)
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+/// Differences that turn `a` into `b`, addressed by JSON Pointer
+///
+/// Objects are compared by key and arrays by index; any other mismatch is a change
+/// of the whole value.
+pub fn json_diff(a: &Value, b: &Value) -> Vec<DiffEntry> {
+    let mut entries = Vec::new();
+    diff_values("", a, b, &mut entries);
+    entries
+}
+
+fn diff_values(path: &str, a: &Value, b: &Value, entries: &mut Vec<DiffEntry>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => diff_objects(path, a, b, entries),
+        (Value::Array(a), Value::Array(b)) => {
+            for index in 0..a.len().max(b.len()) {
+                let child = format!("{}/{}", path, index);
+                match (a.get(index), b.get(index)) {
+                    (Some(old), Some(new)) => diff_values(&child, old, new, entries),
+                    (Some(old), None) => entries.push(removed(child, old)),
+                    (None, Some(new)) => entries.push(added(child, new)),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        _ if a != b => entries.push(DiffEntry {
+            path: path.to_string(),
+            op: DiffOp::Changed,
+            old: Some(a.clone()),
+            new: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn diff_objects(
+    path: &str,
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+    entries: &mut Vec<DiffEntry>,
+) {
+    for (key, old) in a {
+        let child = format!("{}/{}", path, escape_pointer(key));
+        match b.get(key) {
+            Some(new) => diff_values(&child, old, new, entries),
+            None => entries.push(removed(child, old)),
+        }
+    }
+    for (key, new) in b {
+        if !a.contains_key(key) {
+            entries.push(added(format!("{}/{}", path, escape_pointer(key)), new));
+        }
+    }
+}
+
+fn added(path: String, value: &Value) -> DiffEntry {
+    DiffEntry {
+        path,
+        op: DiffOp::Added,
+        old: None,
+        new: Some(value.clone()),
+    }
+}
+
+fn removed(path: String, value: &Value) -> DiffEntry {
+    DiffEntry {
+        path,
+        op: DiffOp::Removed,
+        old: Some(value.clone()),
+        new: None,
+    }
+}
+
+/// Escape a key for use as a JSON Pointer segment (RFC 6901)
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_query_json_evaluates_expressions() {
+        let input = json!({"items": [
+            {"name": "pen", "price": 2},
+            {"name": "book", "price": 12},
+            {"name": "lamp", "price": 30}
+        ]});
+
+        let query = |expression| query_json(input.clone(), expression, DEFAULT_MAX_RESULTS);
+
+        let names = query(".items[] | select(.price > 10) | .name").unwrap();
+        assert_eq!(names.values, vec![json!("book"), json!("lamp")]);
+        assert!(!names.truncated);
+
+        let total = query("[.items[].price] | add").unwrap();
+        assert_eq!(total.values, vec![json!(44)]);
+
+        let keys = query(".items[0] | keys").unwrap();
+        assert_eq!(keys.values, vec![json!(["name", "price"])]);
+    }
+
+    #[test]
+    fn test_query_json_stops_at_limits() {
+        let input = json!({"a": {"b": [1, 2, 3]}, "c": [4, 5]});
+
+        let results = query_json(input.clone(), "..", 3).unwrap();
+        assert_eq!(results.values.len(), 3);
+        assert!(results.truncated);
+
+        // Infinite generators end at the count limit
+        let results = query_json(json!(null), "repeat(1)", 5).unwrap();
+        assert_eq!(results.values, vec![json!(1); 5]);
+        assert!(results.truncated);
+
+        let results = query_json(input, ".c[]", 2).unwrap();
+        assert_eq!(results.values, vec![json!(4), json!(5)]);
+        assert!(!results.truncated);
+
+        // A single value over the size limit is left out
+        let big = json!("x".repeat(MAX_RESULT_BYTES));
+        let results = query_json(big, ".", DEFAULT_MAX_RESULTS).unwrap();
+        assert!(results.values.is_empty());
+        assert!(results.truncated);
+    }
+
+    #[test]
+    fn test_query_json_reports_errors() {
+        let result = query_json(json!({}), ".items[", DEFAULT_MAX_RESULTS);
+        assert!(matches!(
+            result,
+            Err(JsonServerError::InvalidExpression { .. })
+        ));
+
+        let result = query_json(json!({}), "no_such_function", DEFAULT_MAX_RESULTS);
+        assert!(matches!(
+            result,
+            Err(JsonServerError::InvalidExpression { .. })
+        ));
+
+        let result = query_json(json!("text"), ".[0]", DEFAULT_MAX_RESULTS);
+        assert!(matches!(result, Err(JsonServerError::QueryFailed { .. })));
+    }
+
+    #[test]
+    fn test_parse_and_format_json() {
+        let value = parse_json("input", r#"{ "a" : [1, 2] }"#).unwrap();
+        assert_eq!(format_json(&value, true), r#"{"a":[1,2]}"#);
+        assert_eq!(
+            format_json(&value, false),
+            "{\n  \"a\": [\n    1,\n    2\n  ]\n}"
+        );
+
+        let error = parse_json("input", "{oops").unwrap_err();
+        assert!(
+            matches!(error, JsonServerError::InvalidJson { argument, .. } if argument == "input")
+        );
+    }
+
+    #[test]
+    fn test_validate_json_schema() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": "string"},
+                "age": {"type": "integer", "minimum": 0}
+            }
+        });
+
+        let result = validate_json_schema(&schema, &json!({"name": "Ada", "age": 36})).unwrap();
+        assert!(result.valid);
+        assert!(result.errors.is_empty());
+
+        let result = validate_json_schema(&schema, &json!({"age": -1})).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors.iter().any(|e| e.instance_path == "/age"));
+
+        let result = validate_json_schema(&json!({"type": 12}), &json!({}));
+        assert!(matches!(result, Err(JsonServerError::InvalidSchema { .. })));
+    }
+
+    #[test]
+    fn test_merge_json_deep_merges_conflicts() {
+        let base = json!({
+            "name": "app",
+            "server": {"host": "localhost", "port": 80, "tls": {"enabled": false}},
+            "tags": ["a", "b"]
+        });
+        let overlay = json!({
+            "server": {"port": 8080, "tls": {"enabled": true, "cert": "c.pem"}},
+            "tags": ["c"],
+            "debug": true
+        });
+
+        assert_eq!(
+            merge_json(base, overlay),
+            json!({
+                "name": "app",
+                "server": {"host": "localhost", "port": 8080, "tls": {"enabled": true, "cert": "c.pem"}},
+                "tags": ["c"],
+                "debug": true
+            })
+        );
+
+        assert_eq!(
+            merge_json(json!({"a": {"b": 1}}), json!({"a": 2})),
+            json!({"a": 2})
+        );
+    }
+
+    #[test]
+    fn test_json_diff() {
+        let a = json!({"name": "app", "port": 80, "tags": ["x", "y"], "a/b": 1});
+        let b = json!({"name": "app", "port": 8080, "tags": ["x"], "debug": true});
+
+        assert_eq!(
+            json_diff(&a, &b),
+            vec![
+                removed("/a~1b".to_string(), &json!(1)),
+                DiffEntry {
+                    path: "/port".to_string(),
+                    op: DiffOp::Changed,
+                    old: Some(json!(80)),
+                    new: Some(json!(8080)),
+                },
+                removed("/tags/1".to_string(), &json!("y")),
+                added("/debug".to_string(), &json!(true)),
+            ]
+        );
+        assert!(json_diff(&a, &a).is_empty());
+    }
+}
@@ -0,0 +1,35 @@
+use std::env;
+
+use tracing_subscriber::{self, EnvFilter};
+
+mod core;
+mod server;
+
+/// JSON MCP Server
+///
+/// Stateless JSON utilities:
+/// - Tools: jq queries, formatting, schema validation, deep merge and diff
+///
+/// Usage: npx @modelcontextprotocol/inspector cargo run --bin mcp-server-json
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize logging only if LOG_LEVEL environment variable is set
+    if let Ok(log_level) = env::var("LOG_LEVEL") {
+        tracing_subscriber::fmt()
+            .with_env_filter(
+                EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&log_level)),
+            )
+            .with_writer(std::io::stderr)
+            .with_ansi(false)
+            .init();
+
+        tracing::info!("Starting JSON MCP server with log level: {}", log_level);
+    }
+
+    if let Err(e) = server::run().await {
+        tracing::error!("Error running JSON MCP server: {}", e);
+        return Err(e);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,274 @@
+use rmcp::{
+    ErrorData as McpError, RoleServer, ServerHandler,
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::*,
+    service::RequestContext,
+    tool, tool_handler, tool_router,
+};
+
+use crate::core::{
+    error::{JsonServerError, McpResult},
+    models::{
+        FormatJsonRequest, JsonDiffRequest, MergeJsonRequest, QueryJsonRequest,
+        ValidateJsonSchemaRequest,
+    },
+    processor::{self, DEFAULT_MAX_RESULTS, parse_json},
+};
+
+/// JSON MCP Server with stateless query and transformation tools
+#[derive(Clone)]
+pub struct JsonService {
+    tool_router: ToolRouter<JsonService>,
+}
+
+impl Default for JsonService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonService {
+    pub fn new() -> Self {
+        Self {
+            tool_router: Self::tool_router(),
+        }
+    }
+}
+
+#[tool_router]
+impl JsonService {
+    #[tool(
+        description = "Evaluate a jq expression against a JSON document. Returns a JSON array with the values the expression produces, at most max_results (default 1000) and 10 MiB in total, followed by a notice when evaluation stopped early."
+    )]
+    pub(crate) async fn query_json(
+        &self,
+        Parameters(req): Parameters<QueryJsonRequest>,
+    ) -> McpResult<CallToolResult> {
+        let max_results = req.max_results.unwrap_or(DEFAULT_MAX_RESULTS).max(1);
+        // Parsing and evaluation are CPU-bound, so keep them off the async runtime
+        let results = tokio::task::spawn_blocking(move || {
+            let input = parse_json("input", &req.input)?;
+            processor::query_json(input, &req.expression, max_results)
+        })
+        .await
+        .map_err(|e| JsonServerError::QueryFailed {
+            message: e.to_string(),
+        })??;
+
+        let mut content = vec![Content::text(
+            serde_json::to_string_pretty(&results.values).unwrap(),
+        )];
+        if results.truncated {
+            content.push(Content::text(format!(
+                "Results truncated after {} values; the expression produced more than max_results ({}) values or {} bytes.",
+                results.values.len(),
+                max_results,
+                processor::MAX_RESULT_BYTES
+            )));
+        }
+        Ok(CallToolResult::success(content))
+    }
+
+    #[tool(description = "Pretty-print a JSON document, or minify it when compact is true")]
+    pub(crate) async fn format_json(
+        &self,
+        Parameters(req): Parameters<FormatJsonRequest>,
+    ) -> McpResult<CallToolResult> {
+        let input = parse_json("input", &req.input)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            processor::format_json(&input, req.compact),
+        )]))
+    }
+
+    #[tool(
+        description = "Validate a JSON document against a JSON Schema. Returns whether it is valid and every violation with its location."
+    )]
+    pub(crate) async fn validate_json_schema(
+        &self,
+        Parameters(req): Parameters<ValidateJsonSchemaRequest>,
+    ) -> McpResult<CallToolResult> {
+        let schema = parse_json("schema", &req.schema)?;
+        let document = parse_json("document", &req.document)?;
+        let result = processor::validate_json_schema(&schema, &document)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Deep-merge two JSON documents. Objects are merged recursively; on any other conflict, including arrays, the override value wins."
+    )]
+    pub(crate) async fn merge_json(
+        &self,
+        Parameters(req): Parameters<MergeJsonRequest>,
+    ) -> McpResult<CallToolResult> {
+        let base = parse_json("base", &req.base)?;
+        let overlay = parse_json("override", &req.r#override)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&processor::merge_json(base, overlay)).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Compare two JSON documents. Returns a list of added, removed and changed values addressed by JSON Pointer."
+    )]
+    pub(crate) async fn json_diff(
+        &self,
+        Parameters(req): Parameters<JsonDiffRequest>,
+    ) -> McpResult<CallToolResult> {
+        let a = parse_json("a", &req.a)?;
+        let b = parse_json("b", &req.b)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&processor::json_diff(&a, &b)).unwrap(),
+        )]))
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for JsonService {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(
+                "JSON MCP Server. All tools take raw JSON strings. Tools: query_json (jq expressions), format_json (pretty-print or minify), validate_json_schema (JSON Schema validation), merge_json (deep merge, override wins), json_diff (structural diff as JSON Pointer paths)."
+                    .to_string(),
+            ),
+        }
+    }
+
+    async fn initialize(
+        &self,
+        _request: InitializeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, McpError> {
+        tracing::info!("JSON MCP Server initialized successfully");
+        Ok(self.get_info())
+    }
+}
+
+pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    use rmcp::{ServiceExt, transport::stdio};
+
+    let service = JsonService::new().serve(stdio()).await.inspect_err(|e| {
+        tracing::error!("serving error: {:?}", e);
+    })?;
+
+    service.waiting().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rmcp::handler::server::wrapper::Parameters;
+    use rmcp::model::ProtocolVersion;
+
+    use super::JsonService;
+    use crate::core::models::{MergeJsonRequest, QueryJsonRequest, ValidateJsonSchemaRequest};
+
+    fn text_of(result: &rmcp::model::CallToolResult) -> String {
+        result.content[0].as_text().unwrap().text.clone()
+    }
+
+    #[test]
+    fn test_tool_router_has_routes() {
+        let router = JsonService::tool_router();
+        assert!(router.has_route("query_json"));
+        assert!(router.has_route("format_json"));
+        assert!(router.has_route("validate_json_schema"));
+        assert!(router.has_route("merge_json"));
+        assert!(router.has_route("json_diff"));
+    }
+
+    #[test]
+    fn test_server_info() {
+        use rmcp::ServerHandler;
+
+        let info = JsonService::new().get_info();
+        assert_eq!(info.protocol_version, ProtocolVersion::V_2024_11_05);
+        assert!(info.capabilities.tools.is_some());
+        assert!(info.instructions.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_tools_parse_raw_json_strings() {
+        let service = JsonService::new();
+
+        let result = service
+            .query_json(Parameters(QueryJsonRequest {
+                input: r#"{"users": [{"name": "ada"}, {"name": "alan"}]}"#.to_string(),
+                expression: ".users | map(.name)".to_string(),
+                max_results: None,
+            }))
+            .await
+            .unwrap();
+        let outputs: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(outputs, serde_json::json!([["ada", "alan"]]));
+
+        let result = service
+            .query_json(Parameters(QueryJsonRequest {
+                input: "[1, 2, 3]".to_string(),
+                expression: ".[]".to_string(),
+                max_results: Some(2),
+            }))
+            .await
+            .unwrap();
+        let outputs: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(outputs, serde_json::json!([1, 2]));
+        let notice = result.content[1].as_text().unwrap();
+        assert!(notice.text.contains("truncated after 2 values"));
+
+        let result = service
+            .validate_json_schema(Parameters(ValidateJsonSchemaRequest {
+                schema: r#"{"type": "array"}"#.to_string(),
+                document: "{}".to_string(),
+            }))
+            .await
+            .unwrap();
+        let result: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(result["valid"], false);
+
+        let result = service
+            .merge_json(Parameters(MergeJsonRequest {
+                base: r#"{"a": 1}"#.to_string(),
+                r#override: "not json".to_string(),
+            }))
+            .await;
+        assert!(result.unwrap_err().to_string().contains("invalid_json"));
+    }
+
+    #[tokio::test]
+    async fn test_client_round_trip() {
+        use rmcp::{ServiceExt, model::CallToolRequestParam};
+
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        tokio::spawn(async move {
+            JsonService::new()
+                .serve(server_io)
+                .await
+                .unwrap()
+                .waiting()
+                .await
+                .unwrap();
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "merge_json".into(),
+                arguments: serde_json::json!({
+                    "base": r#"{"a": {"b": 1, "c": 2}}"#,
+                    "override": r#"{"a": {"c": 3}}"#
+                })
+                .as_object()
+                .cloned(),
+            })
+            .await
+            .unwrap();
+        let merged: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(merged, serde_json::json!({"a": {"b": 1, "c": 3}}));
+
+        client.cancel().await.unwrap();
+    }
+}
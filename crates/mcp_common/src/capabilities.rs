@@ -0,0 +1,153 @@
+use rmcp::model::{Prompt, ProtocolVersion, Resource, Tool};
+use serde_json::{Value, json};
+
+/// Machine-readable description of a server's tools, prompts and resources
+///
+/// Built from the same routers and resource list the server uses to answer
+/// `tools/list`, `prompts/list` and `resources/list`, so it can't drift from them.
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    protocol_version: ProtocolVersion,
+    name: String,
+    version: String,
+    tools: Vec<Tool>,
+    prompts: Vec<Prompt>,
+    resources: Vec<Resource>,
+}
+
+impl Capabilities {
+    /// Describe a server by name and version, typically its `CARGO_PKG_NAME` and `CARGO_PKG_VERSION`
+    pub fn new(protocol_version: ProtocolVersion, name: &str, version: &str) -> Self {
+        Self {
+            protocol_version,
+            name: name.to_string(),
+            version: version.to_string(),
+            tools: Vec::new(),
+            prompts: Vec::new(),
+            resources: Vec::new(),
+        }
+    }
+
+    /// Set the tools, e.g. from `ToolRouter::list_all`
+    pub fn with_tools(mut self, tools: Vec<Tool>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Set the prompts, e.g. from `PromptRouter::list_all`
+    pub fn with_prompts(mut self, prompts: Vec<Prompt>) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
+    /// Set the static resources the server lists
+    pub fn with_resources(mut self, resources: Vec<Resource>) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// The document as JSON; tools and prompts are sorted by name, resources keep their order
+    pub fn to_json(&self) -> Value {
+        let mut tools: Vec<_> = self.tools.iter().collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut prompts: Vec<_> = self.prompts.iter().collect();
+        prompts.sort_by(|a, b| a.name.cmp(&b.name));
+
+        json!({
+            "protocol_version": self.protocol_version,
+            "server": {
+                "name": self.name,
+                "version": self.version,
+            },
+            "tools": tools
+                .into_iter()
+                .map(|tool| json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "input_schema": tool.input_schema,
+                }))
+                .collect::<Vec<_>>(),
+            "prompts": prompts
+                .into_iter()
+                .map(|prompt| json!({
+                    "name": prompt.name,
+                    "description": prompt.description,
+                    "arguments": prompt.arguments.as_deref().unwrap_or_default(),
+                }))
+                .collect::<Vec<_>>(),
+            "resources": self
+                .resources
+                .iter()
+                .map(|resource| json!({
+                    "uri": resource.uri,
+                    "name": resource.name,
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// The document as pretty-printed JSON, for a resource's text contents
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(&self.to_json()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rmcp::model::{AnnotateAble, PromptArgument, RawResource};
+
+    use super::*;
+
+    #[test]
+    fn test_capabilities_sorts_tools_and_prompts() {
+        let tool =
+            |name: &'static str| Tool::new(name, "description", Arc::new(Default::default()));
+        let prompt = |name: &str| {
+            Prompt::new(
+                name,
+                Some("description"),
+                Some(vec![PromptArgument {
+                    name: "url".to_string(),
+                    title: None,
+                    description: None,
+                    required: Some(true),
+                }]),
+            )
+        };
+
+        let capabilities = Capabilities::new(ProtocolVersion::V_2024_11_05, "mcp-test", "1.2.3")
+            .with_tools(vec![tool("zeta"), tool("alpha")])
+            .with_prompts(vec![prompt("summarize"), prompt("explain")])
+            .with_resources(vec![
+                RawResource::new("test://status", "server-status".to_string()).no_annotation(),
+                RawResource::new("test://help", "help-documentation".to_string()).no_annotation(),
+            ])
+            .to_json();
+
+        assert_eq!(capabilities["protocol_version"], "2024-11-05");
+        assert_eq!(
+            capabilities["server"],
+            json!({"name": "mcp-test", "version": "1.2.3"})
+        );
+        assert_eq!(capabilities["tools"][0]["name"], "alpha");
+        assert_eq!(capabilities["tools"][1]["name"], "zeta");
+        assert_eq!(capabilities["prompts"][0]["name"], "explain");
+        assert_eq!(capabilities["prompts"][0]["arguments"][0]["name"], "url");
+        assert_eq!(
+            capabilities["resources"],
+            json!([
+                {"uri": "test://status", "name": "server-status"},
+                {"uri": "test://help", "name": "help-documentation"}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_capabilities_without_prompts_lists_none() {
+        let capabilities = Capabilities::new(ProtocolVersion::V_2024_11_05, "mcp-test", "0.1.0");
+        assert_eq!(capabilities.to_json()["prompts"], json!([]));
+        assert_eq!(capabilities.to_json()["tools"], json!([]));
+    }
+}
@@ -0,0 +1,8 @@
+//! Helpers shared by the MCP servers in this workspace
+//!
+//! ## Modules
+//! - `capabilities`: JSON document served by each server's `<scheme>://capabilities` resource
+
+pub mod capabilities;
+
+pub use capabilities::Capabilities;
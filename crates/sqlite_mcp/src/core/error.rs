@@ -0,0 +1,78 @@
+use rmcp::ErrorData as McpError;
+use rmcp::serde_json::json;
+
+// Error codes
+const ERROR_PATH_NOT_ALLOWED: &str = "path_not_allowed";
+const ERROR_DATABASE_NOT_FOUND: &str = "database_not_found";
+const ERROR_TABLE_NOT_FOUND: &str = "table_not_found";
+const ERROR_INVALID_QUERY: &str = "invalid_query";
+const ERROR_DATABASE_ERROR: &str = "database_error";
+
+/// Custom error types for better error handling
+#[derive(Debug, thiserror::Error)]
+pub enum SqliteServerError {
+    #[error("Path is outside the allowed directories: {path}")]
+    PathNotAllowed { path: String },
+    #[error("Database file not found: {path}")]
+    DatabaseNotFound { path: String },
+    #[error("Table not found: {table}")]
+    TableNotFound { table: String },
+    #[error("Invalid query: {message}")]
+    InvalidQuery { message: String },
+    #[error("Database error: {message}")]
+    Database { message: String },
+}
+
+impl From<sqlx::Error> for SqliteServerError {
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::Database(db_err) => SqliteServerError::InvalidQuery {
+                message: db_err.message().to_string(),
+            },
+            other => SqliteServerError::Database {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
+impl From<SqliteServerError> for McpError {
+    fn from(err: SqliteServerError) -> Self {
+        match err {
+            SqliteServerError::PathNotAllowed { path } => {
+                McpError::invalid_params(ERROR_PATH_NOT_ALLOWED, Some(json!({"path": path})))
+            }
+            SqliteServerError::DatabaseNotFound { path } => {
+                McpError::invalid_params(ERROR_DATABASE_NOT_FOUND, Some(json!({"path": path})))
+            }
+            SqliteServerError::TableNotFound { table } => {
+                McpError::invalid_params(ERROR_TABLE_NOT_FOUND, Some(json!({"table": table})))
+            }
+            SqliteServerError::InvalidQuery { message } => {
+                McpError::invalid_params(ERROR_INVALID_QUERY, Some(json!({"message": message})))
+            }
+            SqliteServerError::Database { message } => {
+                McpError::internal_error(ERROR_DATABASE_ERROR, Some(json!({"message": message})))
+            }
+        }
+    }
+}
+
+pub type SqliteServerResult<T> = Result<T, SqliteServerError>;
+pub type McpResult<T> = Result<T, McpError>;
+
+#[cfg(test)]
+mod tests {
+    use super::SqliteServerError;
+    use crate::core::error::McpError;
+
+    #[test]
+    fn test_error_conversion() {
+        let error = SqliteServerError::TableNotFound {
+            table: "missing".to_string(),
+        };
+        let mcp_error: McpError = error.into();
+
+        assert!(mcp_error.to_string().contains("table_not_found"));
+    }
+}
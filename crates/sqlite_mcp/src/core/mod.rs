@@ -0,0 +1,18 @@
+//! # SQLite MCP Server Core
+//!
+//! This module provides SQLite database access for the MCP server.
+//!
+//! ## Features
+//! - Parameterized SQL execution with rows returned as JSON objects
+//! - Table listing, column descriptions and row counts
+//! - Read-only connections unless read-write mode is enabled
+//! - Database paths restricted to allowed directories
+//!
+//! ## Modules
+//! - `error`: Custom error types and error handling
+//! - `models`: Data structures for requests and responses
+//! - `provider`: Allowlisted connections and query execution
+
+pub mod error;
+pub mod models;
+pub mod provider;
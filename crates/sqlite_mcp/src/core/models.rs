@@ -0,0 +1,89 @@
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+
+/// Request to run a SQL statement
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExecuteQueryRequest {
+    /// Path to the SQLite database file
+    pub db_path: String,
+    /// SQL statement, using `?` placeholders for parameters
+    pub sql: String,
+    /// Values bound to the `?` placeholders in order
+    #[serde(default)]
+    pub params: Vec<serde_json::Value>,
+    /// Maximum number of rows to return (default: 1000)
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+}
+
+impl ExecuteQueryRequest {
+    /// Number of rows to return, applying the default
+    pub fn row_limit(&self) -> usize {
+        self.max_rows.unwrap_or(DEFAULT_MAX_ROWS).max(1)
+    }
+}
+
+/// Rows `execute_query` returns when `max_rows` is not given
+pub const DEFAULT_MAX_ROWS: usize = 1000;
+
+/// Request to list the tables of a database
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListTablesRequest {
+    /// Path to the SQLite database file
+    pub db_path: String,
+}
+
+/// Request to describe the columns of a table
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DescribeTableRequest {
+    /// Path to the SQLite database file
+    pub db_path: String,
+    /// Name of the table to describe
+    pub table_name: String,
+}
+
+/// Request to count the rows of a table
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetRowCountRequest {
+    /// Path to the SQLite database file
+    pub db_path: String,
+    /// Name of the table to count
+    pub table_name: String,
+}
+
+/// Outcome of a statement that changed rows without returning any
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExecuteSummary {
+    /// Number of rows inserted, updated or deleted
+    pub rows_affected: u64,
+    /// Rowid of the most recent successful insert
+    pub last_insert_rowid: i64,
+}
+
+/// Result of `execute_query`
+#[derive(Debug, Clone)]
+pub struct QueryOutput {
+    /// Rows as an array of objects, or an [`ExecuteSummary`] for mutations
+    pub result: serde_json::Value,
+    /// Whether rows beyond the row limit were left out
+    pub truncated: bool,
+}
+
+/// A column of a table, as reported by `pragma_table_info`
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// Declared type, e.g. `INTEGER` or `TEXT`; empty when undeclared
+    pub data_type: String,
+    pub not_null: bool,
+    /// Default value expression as written in the schema
+    pub default_value: Option<String>,
+    pub primary_key: bool,
+}
+
+/// Number of rows in a table
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RowCountResult {
+    pub table: String,
+    pub row_count: i64,
+}
@@ -0,0 +1,556 @@
+use std::ffi::{c_char, c_int, c_void};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use base64::{Engine, engine::general_purpose};
+use futures::TryStreamExt;
+use serde_json::{Map, Value};
+use sqlx::{
+    Column, ConnectOptions, Either, Executor, Row, Sqlite, SqliteConnection, TypeInfo, ValueRef,
+    query::Query,
+    sqlite::{SqliteArguments, SqliteConnectOptions, SqliteRow},
+};
+
+use crate::core::{
+    error::{SqliteServerError, SqliteServerResult},
+    models::{ColumnInfo, ExecuteSummary, QueryOutput, RowCountResult},
+};
+
+/// SQLite access restricted to database files inside the allowed directories
+#[derive(Debug, Clone)]
+pub struct SqliteProvider {
+    allowed_directories: Arc<Vec<PathBuf>>,
+    read_write: bool,
+}
+
+impl SqliteProvider {
+    /// Create a provider; `allowed_directories` should already be canonical
+    pub fn new(allowed_directories: Vec<PathBuf>, read_write: bool) -> Self {
+        Self {
+            allowed_directories: Arc::new(allowed_directories),
+            read_write,
+        }
+    }
+
+    pub fn allowed_directories(&self) -> &[PathBuf] {
+        &self.allowed_directories
+    }
+
+    pub fn read_write(&self) -> bool {
+        self.read_write
+    }
+
+    /// Canonicalize `db_path` and ensure it is an existing file within an allowed directory
+    pub fn validate_db_path(&self, db_path: &str) -> SqliteServerResult<PathBuf> {
+        let canonical = Path::new(db_path.trim())
+            .canonicalize()
+            .ok()
+            .filter(|path| path.is_file())
+            .ok_or_else(|| SqliteServerError::DatabaseNotFound {
+                path: db_path.to_string(),
+            })?;
+
+        if self
+            .allowed_directories
+            .iter()
+            .any(|allowed| canonical.starts_with(allowed))
+        {
+            Ok(canonical)
+        } else {
+            Err(SqliteServerError::PathNotAllowed {
+                path: db_path.to_string(),
+            })
+        }
+    }
+
+    /// Open a connection, read-only unless the provider is in read-write mode
+    pub async fn connect(&self, db_path: &str) -> SqliteServerResult<SqliteConnection> {
+        let path = self.validate_db_path(db_path)?;
+        let mut options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(false)
+            .read_only(!self.read_write);
+        if !self.read_write {
+            options = options.pragma("query_only", "ON");
+        }
+
+        Ok(options.connect().await?)
+    }
+
+    pub async fn execute_query(
+        &self,
+        db_path: &str,
+        sql: &str,
+        params: &[Value],
+        max_rows: usize,
+    ) -> SqliteServerResult<QueryOutput> {
+        let mut conn = self.connect(db_path).await?;
+        execute_query(&mut conn, sql, params, max_rows).await
+    }
+
+    pub async fn list_tables(&self, db_path: &str) -> SqliteServerResult<Vec<String>> {
+        let mut conn = self.connect(db_path).await?;
+        list_tables(&mut conn).await
+    }
+
+    pub async fn describe_table(
+        &self,
+        db_path: &str,
+        table_name: &str,
+    ) -> SqliteServerResult<Vec<ColumnInfo>> {
+        let mut conn = self.connect(db_path).await?;
+        describe_table(&mut conn, table_name).await
+    }
+
+    pub async fn get_row_count(
+        &self,
+        db_path: &str,
+        table_name: &str,
+    ) -> SqliteServerResult<RowCountResult> {
+        let mut conn = self.connect(db_path).await?;
+        get_row_count(&mut conn, table_name).await
+    }
+}
+
+/// Run `sql` with `params` bound to its `?` placeholders
+///
+/// Returns up to `max_rows` of the produced rows as an array of objects keyed by column
+/// name, stopping the statement once the limit is passed. Statements that change rows
+/// without returning any yield an [`ExecuteSummary`] instead.
+pub async fn execute_query(
+    conn: &mut SqliteConnection,
+    sql: &str,
+    params: &[Value],
+    max_rows: usize,
+) -> SqliteServerResult<QueryOutput> {
+    deny_attach(conn).await?;
+
+    let query = params
+        .iter()
+        .fold(sqlx::query(sql), |query, param| bind_json(query, param));
+
+    // sqlite3_changes() is not reset by SELECT, so measure the statement's own changes
+    let changes_before = total_changes(conn).await?;
+
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    let mut last_insert_rowid = 0;
+    {
+        let mut results = conn.fetch_many(query);
+        while let Some(result) = results.try_next().await? {
+            match result {
+                Either::Left(done) => last_insert_rowid = done.last_insert_rowid(),
+                Either::Right(_) if rows.len() >= max_rows => {
+                    truncated = true;
+                    break;
+                }
+                Either::Right(row) => rows.push(row_to_json(&row)?),
+            }
+        }
+    }
+
+    let summary = ExecuteSummary {
+        rows_affected: (total_changes(conn).await? - changes_before).max(0) as u64,
+        last_insert_rowid,
+    };
+    let result = if rows.is_empty() && summary.rows_affected > 0 {
+        serde_json::to_value(summary).unwrap_or_default()
+    } else {
+        Value::Array(rows)
+    };
+    Ok(QueryOutput { result, truncated })
+}
+
+/// Names of user tables, sorted alphabetically
+pub async fn list_tables(conn: &mut SqliteConnection) -> SqliteServerResult<Vec<String>> {
+    Ok(sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(conn)
+    .await?)
+}
+
+/// Columns of `table_name` in declaration order
+pub async fn describe_table(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+) -> SqliteServerResult<Vec<ColumnInfo>> {
+    let columns: Vec<(String, String, bool, Option<String>, i64)> = sqlx::query_as(
+        "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?) ORDER BY cid",
+    )
+    .bind(table_name)
+    .fetch_all(conn)
+    .await?;
+
+    if columns.is_empty() {
+        return Err(SqliteServerError::TableNotFound {
+            table: table_name.to_string(),
+        });
+    }
+
+    Ok(columns
+        .into_iter()
+        .map(
+            |(name, data_type, not_null, default_value, pk)| ColumnInfo {
+                name,
+                data_type,
+                not_null,
+                default_value,
+                primary_key: pk > 0,
+            },
+        )
+        .collect())
+}
+
+/// Number of rows in `table_name`
+pub async fn get_row_count(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+) -> SqliteServerResult<RowCountResult> {
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+    )
+    .bind(table_name)
+    .fetch_one(&mut *conn)
+    .await?;
+    if !exists {
+        return Err(SqliteServerError::TableNotFound {
+            table: table_name.to_string(),
+        });
+    }
+
+    // Identifiers cannot be bound, so quote the (now known to exist) table name
+    let sql = format!(
+        "SELECT COUNT(*) FROM \"{}\"",
+        table_name.replace('"', "\"\"")
+    );
+    let row_count: i64 = sqlx::query_scalar(&sql).fetch_one(conn).await?;
+
+    Ok(RowCountResult {
+        table: table_name.to_string(),
+        row_count,
+    })
+}
+
+async fn total_changes(conn: &mut SqliteConnection) -> SqliteServerResult<i64> {
+    Ok(sqlx::query_scalar("SELECT total_changes()")
+        .fetch_one(conn)
+        .await?)
+}
+
+/// Install an authorizer that refuses to prepare any statement that attaches a database
+///
+/// This covers `ATTACH` as well as `VACUUM`, which attaches its output file (for
+/// `VACUUM INTO`, any path on disk), so queries cannot reach files outside the allowlist.
+async fn deny_attach(conn: &mut SqliteConnection) -> SqliteServerResult<()> {
+    let mut handle = conn.lock_handle().await?;
+    // SAFETY: the handle is an open connection we hold the lock for, and the callback
+    // neither reads its user data nor calls back into SQLite
+    let rc = unsafe {
+        libsqlite3_sys::sqlite3_set_authorizer(
+            handle.as_raw_handle().as_ptr(),
+            Some(authorize),
+            std::ptr::null_mut(),
+        )
+    };
+    if rc != libsqlite3_sys::SQLITE_OK {
+        return Err(SqliteServerError::Database {
+            message: format!("Failed to install the SQLite authorizer (code {})", rc),
+        });
+    }
+    Ok(())
+}
+
+unsafe extern "C" fn authorize(
+    _user_data: *mut c_void,
+    action: c_int,
+    _arg1: *const c_char,
+    _arg2: *const c_char,
+    _database: *const c_char,
+    _trigger: *const c_char,
+) -> c_int {
+    if action == libsqlite3_sys::SQLITE_ATTACH {
+        libsqlite3_sys::SQLITE_DENY
+    } else {
+        libsqlite3_sys::SQLITE_OK
+    }
+}
+
+fn bind_json<'q>(
+    query: Query<'q, Sqlite, SqliteArguments<'q>>,
+    param: &Value,
+) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    match param {
+        Value::Null => query.bind(None::<String>),
+        Value::Bool(value) => query.bind(*value),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => query.bind(value),
+            None => query.bind(number.as_f64()),
+        },
+        Value::String(value) => query.bind(value.clone()),
+        // Arrays and objects are stored as their JSON text
+        other => query.bind(other.to_string()),
+    }
+}
+
+fn row_to_json(row: &SqliteRow) -> SqliteServerResult<Value> {
+    let mut object = Map::new();
+    for (index, column) in row.columns().iter().enumerate() {
+        let raw = row.try_get_raw(index)?;
+        let value = if raw.is_null() {
+            Value::Null
+        } else {
+            match raw.type_info().name() {
+                "INTEGER" => Value::from(row.try_get_unchecked::<i64, _>(index)?),
+                "REAL" => Value::from(row.try_get_unchecked::<f64, _>(index)?),
+                "BLOB" => Value::String(
+                    general_purpose::STANDARD.encode(row.try_get_unchecked::<Vec<u8>, _>(index)?),
+                ),
+                _ => Value::String(row.try_get_unchecked::<String, _>(index)?),
+            }
+        };
+        object.insert(column.name().to_string(), value);
+    }
+    Ok(Value::Object(object))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::core::models::DEFAULT_MAX_ROWS;
+    use serde_json::json;
+    use sqlx::Connection;
+    use tempfile::TempDir;
+
+    const SCHEMA: &str = "CREATE TABLE users (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            score REAL DEFAULT 0.0,
+            avatar BLOB
+        );
+        INSERT INTO users (name, score, avatar) VALUES ('alice', 9.5, x'0102');
+        INSERT INTO users (name, score, avatar) VALUES ('bob', 7.0, NULL);
+        CREATE TABLE \"odd \"\"name\" (value TEXT);";
+
+    async fn memory_db() -> SqliteConnection {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(SCHEMA).execute(&mut conn).await.unwrap();
+        conn
+    }
+
+    /// Database file seeded with the test schema, plus the directory allowing it
+    pub(crate) async fn file_db() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.db");
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        let mut conn = options.connect().await.unwrap();
+        sqlx::raw_sql(SCHEMA).execute(&mut conn).await.unwrap();
+        conn.close().await.unwrap();
+        (temp_dir, path.display().to_string())
+    }
+
+    fn provider_for(temp_dir: &TempDir, read_write: bool) -> SqliteProvider {
+        SqliteProvider::new(vec![temp_dir.path().canonicalize().unwrap()], read_write)
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_returns_objects() {
+        let mut conn = memory_db().await;
+
+        let rows = execute_query(
+            &mut conn,
+            "SELECT id, name, score, avatar FROM users WHERE score > ? ORDER BY id",
+            &[json!(5)],
+            DEFAULT_MAX_ROWS,
+        )
+        .await
+        .unwrap();
+
+        assert!(!rows.truncated);
+        assert_eq!(
+            rows.result,
+            json!([
+                {"id": 1, "name": "alice", "score": 9.5, "avatar": "AQI="},
+                {"id": 2, "name": "bob", "score": 7.0, "avatar": null}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_binds_parameters_safely() {
+        let mut conn = memory_db().await;
+
+        let rows = execute_query(
+            &mut conn,
+            "SELECT name FROM users WHERE name = ?",
+            &[json!("alice' OR '1'='1")],
+            DEFAULT_MAX_ROWS,
+        )
+        .await
+        .unwrap();
+        assert_eq!(rows.result, json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_reports_mutations() {
+        let mut conn = memory_db().await;
+
+        let summary = execute_query(
+            &mut conn,
+            "INSERT INTO users (name, score) VALUES (?, ?)",
+            &[json!("carol"), json!(3.25)],
+            DEFAULT_MAX_ROWS,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            summary.result,
+            json!({"rows_affected": 1, "last_insert_rowid": 3})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_truncates_at_row_limit() {
+        let mut conn = memory_db().await;
+
+        let rows = execute_query(&mut conn, "SELECT name FROM users ORDER BY id", &[], 1)
+            .await
+            .unwrap();
+        assert!(rows.truncated);
+        assert_eq!(rows.result, json!([{"name": "alice"}]));
+
+        let rows = execute_query(&mut conn, "SELECT name FROM users ORDER BY id", &[], 2)
+            .await
+            .unwrap();
+        assert!(!rows.truncated);
+        assert_eq!(rows.result.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_rejects_attach_and_bad_sql() {
+        let mut conn = memory_db().await;
+
+        let result = execute_query(
+            &mut conn,
+            "ATTACH DATABASE '/tmp/x.db' AS x",
+            &[],
+            DEFAULT_MAX_ROWS,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(SqliteServerError::InvalidQuery { .. })
+        ));
+
+        let result = execute_query(&mut conn, "SELECT * FROM nowhere", &[], DEFAULT_MAX_ROWS).await;
+        assert!(matches!(
+            result,
+            Err(SqliteServerError::InvalidQuery { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_query_refuses_vacuum_into() {
+        let (temp_dir, db_path) = file_db().await;
+        let copy = temp_dir.path().join("copy.db");
+        let sql = format!("VACUUM INTO '{}'", copy.display());
+
+        for read_write in [false, true] {
+            let result = provider_for(&temp_dir, read_write)
+                .execute_query(&db_path, &sql, &[], DEFAULT_MAX_ROWS)
+                .await;
+            assert!(
+                matches!(result, Err(SqliteServerError::InvalidQuery { .. })),
+                "{result:?}"
+            );
+            assert!(!copy.exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_and_describe_tables() {
+        let mut conn = memory_db().await;
+
+        let tables = list_tables(&mut conn).await.unwrap();
+        assert_eq!(tables, vec!["odd \"name", "users"]);
+
+        let columns = describe_table(&mut conn, "users").await.unwrap();
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "name", "score", "avatar"]);
+        assert!(columns[0].primary_key);
+        assert!(columns[1].not_null);
+        assert_eq!(columns[2].data_type, "REAL");
+        assert_eq!(columns[2].default_value.as_deref(), Some("0.0"));
+
+        let missing = describe_table(&mut conn, "nope").await;
+        assert!(matches!(
+            missing,
+            Err(SqliteServerError::TableNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_row_count() {
+        let mut conn = memory_db().await;
+
+        let count = get_row_count(&mut conn, "users").await.unwrap();
+        assert_eq!(count.row_count, 2);
+
+        let count = get_row_count(&mut conn, "odd \"name").await.unwrap();
+        assert_eq!(count.row_count, 0);
+
+        let missing = get_row_count(&mut conn, "users; DROP TABLE users").await;
+        assert!(matches!(
+            missing,
+            Err(SqliteServerError::TableNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_by_default() {
+        let (temp_dir, db_path) = file_db().await;
+
+        let read_only = provider_for(&temp_dir, false);
+        let result = read_only
+            .execute_query(&db_path, "DELETE FROM users", &[], DEFAULT_MAX_ROWS)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(
+            read_only
+                .get_row_count(&db_path, "users")
+                .await
+                .unwrap()
+                .row_count,
+            2
+        );
+
+        let read_write = provider_for(&temp_dir, true);
+        let summary = read_write
+            .execute_query(&db_path, "DELETE FROM users", &[], DEFAULT_MAX_ROWS)
+            .await
+            .unwrap();
+        assert_eq!(summary.result["rows_affected"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_paths_outside_allowlist() {
+        let (_temp_dir, db_path) = file_db().await;
+        let other = TempDir::new().unwrap();
+        let provider = provider_for(&other, false);
+
+        let result = provider.list_tables(&db_path).await;
+        assert!(matches!(
+            result,
+            Err(SqliteServerError::PathNotAllowed { .. })
+        ));
+
+        let missing = other.path().join("missing.db").display().to_string();
+        let result = provider.list_tables(&missing).await;
+        assert!(matches!(
+            result,
+            Err(SqliteServerError::DatabaseNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use std::{env, net::IpAddr, path::PathBuf};
+
+use clap::{Parser, ValueEnum};
+use tracing_subscriber::{self, EnvFilter};
+
+use crate::server::Transport;
+
+mod core;
+mod server;
+
+/// Transport selectable on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TransportKind {
+    Stdio,
+    Sse,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "mcp-server-sqlite")]
+#[command(about = "MCP SQLite Server for querying allowlisted database files")]
+struct Args {
+    /// Directories whose database files may be opened (defaults to the current directory)
+    #[arg(value_name = "DIRECTORY")]
+    directories: Vec<PathBuf>,
+
+    /// Allow statements that modify databases; connections are read-only otherwise
+    #[arg(long)]
+    read_write: bool,
+
+    /// Transport to serve the MCP protocol over
+    #[arg(long, value_enum, default_value_t = TransportKind::Stdio)]
+    transport: TransportKind,
+
+    /// Address to bind when using the SSE transport
+    #[arg(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+
+    /// Port to bind when using the SSE transport
+    #[arg(long, default_value_t = 8000)]
+    port: u16,
+}
+
+impl Args {
+    fn transport(&self) -> Transport {
+        match self.transport {
+            TransportKind::Stdio => Transport::Stdio,
+            TransportKind::Sse => Transport::Sse((self.host, self.port).into()),
+        }
+    }
+
+    /// Canonical allowed directories, falling back to the current directory
+    fn allowed_directories(&self) -> std::io::Result<Vec<PathBuf>> {
+        if self.directories.is_empty() {
+            return Ok(vec![env::current_dir()?.canonicalize()?]);
+        }
+
+        self.directories
+            .iter()
+            .map(|dir| {
+                dir.canonicalize()
+                    .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {}", dir.display(), e)))
+            })
+            .collect()
+    }
+}
+
+/// SQLite MCP Server
+///
+/// SQLite access for database files inside the allowed directories:
+/// - Tools: parameterized queries, table listing, table description and row counts
+///
+/// Usage: npx @modelcontextprotocol/inspector cargo run --bin mcp-server-sqlite -- /path/to/databases
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    // Initialize logging only if LOG_LEVEL environment variable is set
+    if let Ok(log_level) = env::var("LOG_LEVEL") {
+        tracing_subscriber::fmt()
+            .with_env_filter(
+                EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&log_level)),
+            )
+            .with_writer(std::io::stderr)
+            .with_ansi(false)
+            .init();
+
+        tracing::info!("Starting SQLite MCP server with log level: {}", log_level);
+    }
+
+    let allowed_directories = args.allowed_directories()?;
+    tracing::info!(
+        "Allowed directories: {:?} (read-write: {})",
+        allowed_directories,
+        args.read_write
+    );
+
+    if let Err(e) = server::run(allowed_directories, args.read_write, args.transport()).await {
+        tracing::error!("Error running SQLite MCP server: {}", e);
+        return Err(e);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use rmcp::transport::SseServer;
+use rmcp::{
+    ErrorData as McpError, RoleServer, ServerHandler,
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::*,
+    service::RequestContext,
+    tool, tool_handler, tool_router,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::core::{
+    error::McpResult,
+    models::{DescribeTableRequest, ExecuteQueryRequest, GetRowCountRequest, ListTablesRequest},
+    provider::SqliteProvider,
+};
+
+/// SQLite MCP Server with allowlisted database access
+#[derive(Clone)]
+pub struct SqliteService {
+    provider: SqliteProvider,
+    tool_router: ToolRouter<SqliteService>,
+}
+
+impl SqliteService {
+    pub fn new(allowed_directories: Vec<PathBuf>, read_write: bool) -> Self {
+        Self {
+            provider: SqliteProvider::new(allowed_directories, read_write),
+            tool_router: Self::tool_router(),
+        }
+    }
+}
+
+#[tool_router]
+impl SqliteService {
+    #[tool(
+        description = "Run a SQL statement with optional parameters bound to ? placeholders. Returns up to max_rows rows (default 1000) as a JSON array of objects, followed by a notice when more rows were left out, or rows_affected and last_insert_rowid for statements that change data. Mutations require the server to run in read-write mode; ATTACH and VACUUM are always refused."
+    )]
+    pub(crate) async fn execute_query(
+        &self,
+        Parameters(req): Parameters<ExecuteQueryRequest>,
+    ) -> McpResult<CallToolResult> {
+        let max_rows = req.row_limit();
+        let output = self
+            .provider
+            .execute_query(&req.db_path, &req.sql, &req.params, max_rows)
+            .await?;
+        let mut content = vec![Content::text(
+            serde_json::to_string_pretty(&output.result).unwrap(),
+        )];
+        if output.truncated {
+            content.push(Content::text(format!(
+                "Result truncated to the first {} rows; add a LIMIT or raise max_rows to see more.",
+                max_rows
+            )));
+        }
+        Ok(CallToolResult::success(content))
+    }
+
+    #[tool(description = "List the user tables of a database")]
+    pub(crate) async fn list_tables(
+        &self,
+        Parameters(req): Parameters<ListTablesRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.provider.list_tables(&req.db_path).await?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Describe the columns of a table: name, declared type, NOT NULL, default value and primary key membership"
+    )]
+    pub(crate) async fn describe_table(
+        &self,
+        Parameters(req): Parameters<DescribeTableRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self
+            .provider
+            .describe_table(&req.db_path, &req.table_name)
+            .await?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Count the rows of a table")]
+    pub(crate) async fn get_row_count(
+        &self,
+        Parameters(req): Parameters<GetRowCountRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self
+            .provider
+            .get_row_count(&req.db_path, &req.table_name)
+            .await?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for SqliteService {
+    fn get_info(&self) -> ServerInfo {
+        let allowed = self
+            .provider
+            .allowed_directories()
+            .iter()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mode = if self.provider.read_write() {
+            "read-write"
+        } else {
+            "read-only"
+        };
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2024_11_05,
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(format!(
+                "SQLite MCP Server ({} mode). Tools: execute_query (parameterized SQL using ? placeholders), list_tables, describe_table, get_row_count. db_path must be an existing database file inside the allowed directories: {}.",
+                mode, allowed
+            )),
+        }
+    }
+
+    async fn initialize(
+        &self,
+        _request: InitializeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, McpError> {
+        tracing::info!("SQLite MCP Server initialized successfully");
+        Ok(self.get_info())
+    }
+}
+
+/// Transport the MCP server is served over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// JSON-RPC over stdin/stdout
+    Stdio,
+    /// Server-Sent Events over HTTP, listening on the given address
+    Sse(SocketAddr),
+}
+
+/// Start serving over SSE, with one `SqliteService` per client session
+///
+/// Returns the token that shuts the HTTP server down when cancelled.
+pub(crate) async fn serve_sse(
+    bind: SocketAddr,
+    allowed_directories: Vec<PathBuf>,
+    read_write: bool,
+) -> std::io::Result<CancellationToken> {
+    let server = SseServer::serve(bind).await?;
+    Ok(server.with_service(move || SqliteService::new(allowed_directories.clone(), read_write)))
+}
+
+pub async fn run(
+    allowed_directories: Vec<PathBuf>,
+    read_write: bool,
+    transport: Transport,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use rmcp::{ServiceExt, transport::stdio};
+
+    match transport {
+        Transport::Stdio => {
+            let service = SqliteService::new(allowed_directories, read_write)
+                .serve(stdio())
+                .await
+                .inspect_err(|e| {
+                    tracing::error!("serving error: {:?}", e);
+                })?;
+
+            service.waiting().await?;
+        }
+        Transport::Sse(bind) => {
+            let ct = serve_sse(bind, allowed_directories, read_write).await?;
+            tracing::info!("Serving MCP over SSE at http://{}/sse", bind);
+
+            tokio::signal::ctrl_c().await?;
+            ct.cancel();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rmcp::handler::server::wrapper::Parameters;
+    use rmcp::model::ProtocolVersion;
+
+    use super::SqliteService;
+    use crate::core::models::{DescribeTableRequest, ExecuteQueryRequest, ListTablesRequest};
+    use crate::core::provider::tests::file_db;
+
+    fn text_of(result: &rmcp::model::CallToolResult) -> String {
+        result.content[0].as_text().unwrap().text.clone()
+    }
+
+    fn service_for(temp_dir: &tempfile::TempDir, read_write: bool) -> SqliteService {
+        SqliteService::new(vec![temp_dir.path().canonicalize().unwrap()], read_write)
+    }
+
+    #[test]
+    fn test_tool_router_has_routes() {
+        let router = SqliteService::tool_router();
+        assert!(router.has_route("execute_query"));
+        assert!(router.has_route("list_tables"));
+        assert!(router.has_route("describe_table"));
+        assert!(router.has_route("get_row_count"));
+    }
+
+    #[test]
+    fn test_server_info() {
+        use rmcp::ServerHandler;
+
+        let info = SqliteService::new(Vec::new(), false).get_info();
+        assert_eq!(info.protocol_version, ProtocolVersion::V_2024_11_05);
+        assert!(info.capabilities.tools.is_some());
+        assert!(info.instructions.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn test_tools_return_json() {
+        let (temp_dir, db_path) = file_db().await;
+        let service = service_for(&temp_dir, false);
+
+        let rows = service
+            .execute_query(Parameters(ExecuteQueryRequest {
+                db_path: db_path.clone(),
+                sql: "SELECT name FROM users WHERE id = ?".to_string(),
+                params: vec![serde_json::json!(2)],
+                max_rows: None,
+            }))
+            .await
+            .unwrap();
+        let rows: serde_json::Value = serde_json::from_str(&text_of(&rows)).unwrap();
+        assert_eq!(rows, serde_json::json!([{"name": "bob"}]));
+
+        let truncated = service
+            .execute_query(Parameters(ExecuteQueryRequest {
+                db_path: db_path.clone(),
+                sql: "SELECT name FROM users ORDER BY id".to_string(),
+                params: vec![],
+                max_rows: Some(1),
+            }))
+            .await
+            .unwrap();
+        let rows: serde_json::Value = serde_json::from_str(&text_of(&truncated)).unwrap();
+        assert_eq!(rows, serde_json::json!([{"name": "alice"}]));
+        let notice = truncated.content[1].as_text().unwrap();
+        assert!(notice.text.contains("truncated to the first 1 rows"));
+
+        let tables = service
+            .list_tables(Parameters(ListTablesRequest {
+                db_path: db_path.clone(),
+            }))
+            .await
+            .unwrap();
+        assert!(text_of(&tables).contains("users"));
+
+        let columns = service
+            .describe_table(Parameters(DescribeTableRequest {
+                db_path,
+                table_name: "users".to_string(),
+            }))
+            .await
+            .unwrap();
+        let columns: serde_json::Value = serde_json::from_str(&text_of(&columns)).unwrap();
+        assert_eq!(columns[0]["name"], "id");
+    }
+
+    #[tokio::test]
+    async fn test_client_round_trip() {
+        use rmcp::{ServiceExt, model::CallToolRequestParam};
+
+        let (temp_dir, db_path) = file_db().await;
+        let (server_io, client_io) = tokio::io::duplex(4096);
+        let service = service_for(&temp_dir, false);
+        tokio::spawn(async move {
+            service
+                .serve(server_io)
+                .await
+                .unwrap()
+                .waiting()
+                .await
+                .unwrap();
+        });
+        let client = ().serve(client_io).await.unwrap();
+
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "get_row_count".into(),
+                arguments: serde_json::json!({"db_path": db_path, "table_name": "users"})
+                    .as_object()
+                    .cloned(),
+            })
+            .await
+            .unwrap();
+        let count: serde_json::Value = serde_json::from_str(&text_of(&result)).unwrap();
+        assert_eq!(count["row_count"], 2);
+
+        let denied = client
+            .call_tool(CallToolRequestParam {
+                name: "execute_query".into(),
+                arguments: serde_json::json!({"db_path": db_path, "sql": "DROP TABLE users"})
+                    .as_object()
+                    .cloned(),
+            })
+            .await;
+        assert!(denied.is_err());
+
+        client.cancel().await.unwrap();
+    }
+}
@@ -0,0 +1,50 @@
+//! Compares uncached `chrono_tz` parsing with lookups through `TimezoneCache`
+//!
+//! Run with `cargo bench -p mcp-server-time`.
+
+use std::{hint::black_box, str::FromStr};
+
+use chrono_tz::Tz;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+// The server is a binary crate, so pull the core module in directly
+#[allow(dead_code, unused_imports)]
+#[path = "../src/core/mod.rs"]
+mod core;
+
+use crate::core::cache::TimezoneCache;
+
+const TIMEZONES: &[&str] = &[
+    "UTC",
+    "Europe/London",
+    "America/New_York",
+    "Asia/Kolkata",
+    "Australia/Lord_Howe",
+    "America/Argentina/ComodRivadavia",
+];
+
+fn timezone_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("timezone_lookup");
+
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            for name in TIMEZONES {
+                black_box(Tz::from_str(black_box(name)).unwrap());
+            }
+        })
+    });
+
+    let cache = TimezoneCache::new();
+    group.bench_function("cached", |b| {
+        b.iter(|| {
+            for name in TIMEZONES {
+                black_box(cache.parse_or_cache(black_box(name)).unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, timezone_lookup);
+criterion_main!(benches);
@@ -0,0 +1,98 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use chrono_tz::Tz;
+
+use crate::core::error::{TimeServerError, TimeServerResult};
+
+/// Shared memo of parsed timezone names
+///
+/// Only successfully parsed names are stored, so the map is bounded by the number of
+/// IANA timezones (about 600). Clones share the same underlying map.
+#[derive(Debug, Clone, Default)]
+pub struct TimezoneCache {
+    entries: Arc<RwLock<HashMap<String, Tz>>>,
+}
+
+impl TimezoneCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached timezone for `timezone_name`, parsing and storing it on first use
+    pub fn parse_or_cache(&self, timezone_name: &str) -> TimeServerResult<Tz> {
+        if let Some(timezone) = self.get(timezone_name) {
+            return Ok(timezone);
+        }
+
+        let timezone =
+            Tz::from_str(timezone_name).map_err(|_| TimeServerError::InvalidTimezone {
+                timezone: timezone_name.to_string(),
+            })?;
+        self.entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(timezone_name.to_string(), timezone);
+        Ok(timezone)
+    }
+
+    /// Cached timezone for `timezone_name`, without parsing on a miss
+    pub fn get(&self, timezone_name: &str) -> Option<Tz> {
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(timezone_name)
+            .copied()
+    }
+
+    /// Number of cached timezones
+    pub fn len(&self) -> usize {
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_lookup_hits_cache() {
+        let cache = TimezoneCache::new();
+        assert!(cache.get("Europe/Paris").is_none());
+
+        let first = cache.parse_or_cache("Europe/Paris").unwrap();
+        assert_eq!(cache.get("Europe/Paris"), Some(first));
+        assert_eq!(cache.len(), 1);
+
+        let second = cache.parse_or_cache("Europe/Paris").unwrap();
+        assert_eq!(first, second);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_names_are_not_cached() {
+        let cache = TimezoneCache::new();
+
+        assert!(matches!(
+            cache.parse_or_cache("Mars/Olympus_Mons"),
+            Err(TimeServerError::InvalidTimezone { .. })
+        ));
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_clones_share_entries() {
+        let cache = TimezoneCache::new();
+        let clone = cache.clone();
+        assert_eq!(Arc::strong_count(&cache.entries), 2);
+
+        clone.parse_or_cache("Asia/Tokyo").unwrap();
+        assert!(cache.get("Asia/Tokyo").is_some());
+    }
+}
@@ -0,0 +1,131 @@
+use std::str::FromStr;
+
+use cron::Schedule;
+
+use crate::core::error::{TimeServerError, TimeServerResult};
+
+/// Parse a cron expression into a schedule
+///
+/// Accepts the five-field crontab form (`minute hour day-of-month month
+/// day-of-week`), where day-of-week numbers run from 0 (Sunday) to 7 (Sunday
+/// again); the six- or seven-field form with a leading seconds field and an
+/// optional trailing year, where day-of-week numbers run from 1 (Sunday) to 7
+/// (Saturday); and the `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly`
+/// shorthands. Day and month names such as `MON-FRI` or `JAN` work in every form.
+pub fn parse_cron_expression(expression: &str) -> TimeServerResult<Schedule> {
+    let invalid = |message: String| TimeServerError::InvalidCronExpression {
+        expression: expression.to_string(),
+        message,
+    };
+
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let normalized = match fields.as_slice() {
+        [minute, hour, day_of_month, month, day_of_week] => format!(
+            "0 {} {} {} {} {}",
+            minute,
+            hour,
+            day_of_month,
+            month,
+            crontab_days_of_week(day_of_week).map_err(invalid)?
+        ),
+        _ => fields.join(" "),
+    };
+
+    Schedule::from_str(&normalized).map_err(|e| invalid(e.to_string()))
+}
+
+/// Renumber a crontab day-of-week field (0-7, Sunday is 0 and 7) for the `cron`
+/// crate (1-7, Sunday is 1)
+///
+/// Numeric lists, ranges and steps are expanded to explicit day numbers; names,
+/// `*` and `*/n` are the same in both numberings and are kept as written.
+fn crontab_days_of_week(field: &str) -> Result<String, String> {
+    let parse = |value: &str| {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|day| *day <= 7)
+            .ok_or_else(|| format!("'{}' is not a day of the week between 0 and 7", value))
+    };
+
+    let mut items = Vec::new();
+    for item in field.split(',') {
+        if !item.starts_with(|c: char| c.is_ascii_digit()) {
+            items.push(item.to_string());
+            continue;
+        }
+
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (item, None),
+        };
+        let (start, end) = match range.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            // `n/step` runs from n to the end of the week
+            None if step.is_some() => (parse(range)?, 6),
+            None => (parse(range)?, parse(range)?),
+        };
+        let step = match step {
+            Some(step) => step
+                .parse::<usize>()
+                .ok()
+                .filter(|step| *step > 0)
+                .ok_or_else(|| format!("'{}' is not a valid step", step))?,
+            None => 1,
+        };
+        if start > end {
+            return Err(format!("Day-of-week range '{}' runs backwards", range));
+        }
+
+        items.extend(
+            (start..=end)
+                .step_by(step)
+                .map(|day| (day % 7 + 1).to_string()),
+        );
+    }
+
+    Ok(items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crontab_days_of_week_are_renumbered() {
+        assert_eq!(crontab_days_of_week("1-5").unwrap(), "2,3,4,5,6");
+        assert_eq!(crontab_days_of_week("0,6").unwrap(), "1,7");
+        assert_eq!(crontab_days_of_week("7").unwrap(), "1");
+        assert_eq!(crontab_days_of_week("5-7").unwrap(), "6,7,1");
+        assert_eq!(crontab_days_of_week("1-5/2").unwrap(), "2,4,6");
+        assert_eq!(crontab_days_of_week("3/2").unwrap(), "4,6");
+        assert_eq!(crontab_days_of_week("MON-FRI").unwrap(), "MON-FRI");
+        assert_eq!(crontab_days_of_week("*/2").unwrap(), "*/2");
+        assert!(crontab_days_of_week("8").is_err());
+        assert!(crontab_days_of_week("5-1").is_err());
+        assert!(crontab_days_of_week("1/0").is_err());
+    }
+
+    #[test]
+    fn test_parse_cron_expression_forms() {
+        for expression in [
+            "0 9 * * MON-FRI",
+            "*/15 * * * *",
+            "30 0 9 * * 2-6",
+            "0 0 12 1 JAN * 2030",
+            "@daily",
+        ] {
+            assert!(parse_cron_expression(expression).is_ok(), "{expression}");
+        }
+
+        for expression in ["", "* * *", "61 * * * *", "0 9 * * 8"] {
+            assert!(
+                matches!(
+                    parse_cron_expression(expression),
+                    Err(TimeServerError::InvalidCronExpression { .. })
+                ),
+                "{expression}"
+            );
+        }
+    }
+}
@@ -6,6 +6,11 @@ const ERROR_INVALID_TIMEZONE: &str = "invalid_timezone";
 const ERROR_INVALID_TIME_FORMAT: &str = "invalid_time_format";
 const ERROR_AMBIGUOUS_TIME: &str = "ambiguous_time";
 const ERROR_RESOURCE_NOT_FOUND: &str = "resource_not_found";
+const ERROR_INVALID_DATETIME: &str = "invalid_datetime";
+const ERROR_INVALID_ARGUMENT: &str = "invalid_argument";
+const ERROR_INVALID_DURATION: &str = "invalid_duration";
+const ERROR_TIMEZONE_DATABASE: &str = "timezone_database_error";
+const ERROR_INVALID_CRON_EXPRESSION: &str = "invalid_cron_expression";
 
 /// Custom error types for better error handling
 #[derive(Debug, thiserror::Error)]
@@ -18,6 +23,18 @@ pub enum TimeServerError {
     AmbiguousTime { time: String },
     #[error("Resource not found: {uri}")]
     ResourceNotFound { uri: String },
+    #[error("Invalid datetime: {datetime}. Expected RFC 3339 or YYYY-MM-DD[THH:MM[:SS]] format")]
+    InvalidDateTime { datetime: String },
+    #[error("Invalid argument: {message}")]
+    InvalidArgument { message: String },
+    #[error(
+        "Invalid duration: {duration}. Expected e.g. '90s', '2h30m', '2 hours 30 minutes' or 'P2DT3H'"
+    )]
+    InvalidDuration { duration: String },
+    #[error("Failed to load timezone database from {path}: {message}")]
+    TimezoneDatabase { path: String, message: String },
+    #[error("Invalid cron expression '{expression}': {message}")]
+    InvalidCronExpression { expression: String, message: String },
 }
 
 impl From<TimeServerError> for McpError {
@@ -40,6 +57,28 @@ impl From<TimeServerError> for McpError {
                     "available_resources": crate::core::utils::AVAILABLE_RESOURCES
                 })),
             ),
+            TimeServerError::InvalidDateTime { datetime } => McpError::invalid_params(
+                ERROR_INVALID_DATETIME,
+                Some(json!({"datetime": datetime})),
+            ),
+            TimeServerError::InvalidArgument { message } => {
+                McpError::invalid_params(ERROR_INVALID_ARGUMENT, Some(json!({"message": message})))
+            }
+            TimeServerError::InvalidDuration { duration } => McpError::invalid_params(
+                ERROR_INVALID_DURATION,
+                Some(json!({"duration": duration})),
+            ),
+            TimeServerError::TimezoneDatabase { path, message } => McpError::internal_error(
+                ERROR_TIMEZONE_DATABASE,
+                Some(json!({"path": path, "message": message})),
+            ),
+            TimeServerError::InvalidCronExpression {
+                expression,
+                message,
+            } => McpError::invalid_params(
+                ERROR_INVALID_CRON_EXPRESSION,
+                Some(json!({"expression": expression, "message": message})),
+            ),
         }
     }
 }
@@ -9,12 +9,22 @@
 //! - Local timezone detection
 //!
 //! ## Modules
+//! - `cache`: Shared cache of parsed timezones
+//! - `cron`: Cron expression parsing for schedules
 //! - `error`: Custom error types and error handling
 //! - `models`: Data structures for requests and responses
 //! - `provider`: Core timezone operations and time calculations
+//! - `solar`: Sunrise, sunset and twilight calculations
+//! - `tzdb`: Timezone database loaded from TZif files at runtime
 //! - `utils`: Helper functions for formatting and calculations
+//! - `zone`: Timezone that is either compiled in or loaded at runtime
 
+pub mod cache;
+pub mod cron;
 pub mod error;
 pub mod models;
 pub mod provider;
+pub mod solar;
+pub mod tzdb;
 pub mod utils;
+pub mod zone;
@@ -1,4 +1,4 @@
-use chrono::{DateTime, TimeZone};
+use chrono::{DateTime, Datelike, Offset, TimeZone, Timelike};
 use chrono_tz::OffsetComponents;
 use rmcp::schemars;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -25,6 +25,18 @@ pub struct TimeResult {
     pub day_of_week: String,
     /// Whether daylight saving time is active
     pub is_dst: bool,
+    /// Calendar year of `datetime`
+    pub year: i32,
+    /// Month of the year, 1-12
+    pub month: u32,
+    /// Day of the month, 1-31
+    pub day: u32,
+    /// Hour of the day, 0-23
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    /// Offset from UTC in seconds, including any daylight saving adjustment
+    pub utc_offset_seconds: i32,
 }
 
 impl TimeResult {
@@ -41,6 +53,13 @@ impl TimeResult {
             datetime: dt.format(DATETIME_FORMAT).to_string(),
             day_of_week: dt.format(DAY_FORMAT).to_string(),
             is_dst,
+            year: dt.year(),
+            month: dt.month(),
+            day: dt.day(),
+            hour: dt.hour(),
+            minute: dt.minute(),
+            second: dt.second(),
+            utc_offset_seconds: dt.offset().fix().local_minus_utc(),
         }
     }
 }
@@ -56,6 +75,116 @@ pub struct TimeConversionResult {
     pub time_difference: String,
 }
 
+/// Current time as a Unix timestamp
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct UnixTimestampResult {
+    /// Seconds since 1970-01-01T00:00:00Z
+    pub unix_seconds: i64,
+    /// Milliseconds since 1970-01-01T00:00:00Z
+    pub unix_millis: i64,
+}
+
+/// Interval between two datetimes
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DurationResult {
+    /// Total length in seconds; negative when end is before start
+    pub total_seconds: i64,
+    /// Whole days component (carries the sign of the duration)
+    pub days: i64,
+    /// Hours component, 0-23 in magnitude
+    pub hours: i64,
+    /// Minutes component, 0-59 in magnitude
+    pub minutes: i64,
+    /// Seconds component, 0-59 in magnitude
+    pub seconds: i64,
+    /// Human-readable description, e.g. '1 day, 2 hours, 30 minutes'
+    pub human_readable: String,
+}
+
+/// Daylight saving time transitions in a timezone for one year
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DstTransitionsResult {
+    /// Whether the timezone observes DST in the requested year
+    pub has_dst: bool,
+    /// First instant DST is in effect, in ISO 8601 format
+    pub spring_forward: Option<String>,
+    /// First instant DST is no longer in effect, in ISO 8601 format
+    pub fall_back: Option<String>,
+}
+
+/// Working days in a date range
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BusinessDaysResult {
+    /// Days in the range that are neither excluded weekends nor holidays
+    pub business_days: i32,
+    /// Total days in the range, inclusive of both ends
+    pub calendar_days: i32,
+}
+
+/// Human-readable offset of a datetime from now
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RelativeTimeResult {
+    /// Relative description, e.g. '3 hours ago' or 'in 2 days'
+    pub relative: String,
+    /// Seconds from now to the datetime; negative for the past
+    pub delta_seconds: i64,
+}
+
+/// A datetime rendered with a strftime-style format string
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FormattedDatetimeResult {
+    /// The datetime rendered with the requested format, e.g. 'March 15, 2024 at 02:30 PM'
+    pub formatted: String,
+    /// IANA timezone the datetime was rendered in
+    pub input_timezone: String,
+}
+
+/// A parsed duration broken into days, hours, minutes and seconds
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ParsedDurationResult {
+    /// Total length in seconds
+    pub total_seconds: u64,
+    /// Whole days
+    pub days: u64,
+    /// Hours component, 0-23
+    pub hours: u64,
+    /// Minutes component, 0-59
+    pub minutes: u64,
+    /// Seconds component, 0-59
+    pub seconds: u64,
+}
+
+/// ISO 8601 calendar week of a date
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CalendarWeekResult {
+    /// ISO week number, 1-53
+    pub iso_week: u32,
+    /// ISO week-numbering year, which can differ from the calendar year near January 1st
+    pub iso_year: i32,
+    /// Day of the week
+    pub weekday: String,
+    /// Calendar quarter, 1-4
+    pub quarter: u8,
+}
+
+/// Sunrise, sunset and civil twilight for a date and place
+///
+/// Times are `null` when the sun does not cross the corresponding altitude that
+/// day, as happens near the poles.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SunriseSunsetResult {
+    /// ISO 8601 datetime of sunrise
+    pub sunrise: Option<String>,
+    /// ISO 8601 datetime of sunset
+    pub sunset: Option<String>,
+    /// Minutes between sunrise and sunset; 0 during polar night, 1440 under the midnight sun
+    pub day_length_minutes: u64,
+    /// ISO 8601 datetime when morning civil twilight begins (sun 6° below the horizon)
+    pub civil_twilight_start: Option<String>,
+    /// ISO 8601 datetime when evening civil twilight ends (sun 6° below the horizon)
+    pub civil_twilight_end: Option<String>,
+}
+
 /// Request to get current time in a timezone
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct GetCurrentTimeRequest {
@@ -70,7 +199,7 @@ pub struct ConvertTimeRequest {
     /// Source IANA timezone name
     #[serde(deserialize_with = "deserialize_trimmed_string")]
     pub source_timezone: String,
-    /// Time to convert in 24-hour format (HH:MM)
+    /// Time to convert: 24-hour HH:MM (today) or a full YYYY-MM-DDTHH:MM[:SS] datetime
     #[serde(deserialize_with = "deserialize_trimmed_string")]
     pub time: String,
     /// Target IANA timezone name
@@ -78,6 +207,218 @@ pub struct ConvertTimeRequest {
     pub target_timezone: String,
 }
 
+/// Request to list available IANA timezone names
+#[derive(Debug, Default, Deserialize, schemars::JsonSchema)]
+pub struct ListTimezonesRequest {
+    /// Fuzzy query matched against timezone names, e.g. 'york' or 'ny'
+    #[serde(default)]
+    pub filter: Option<String>,
+    /// Region prefix such as 'America', 'Europe' or 'Asia'
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
+/// Request to get the DST transitions of a timezone
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetDstTransitionsRequest {
+    /// IANA timezone name
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+    /// Calendar year to inspect, e.g. 2024
+    pub year: i32,
+}
+
+/// Request to count business days between two dates
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetBusinessDaysRequest {
+    /// First day of the range (inclusive), as YYYY-MM-DD or an RFC 3339 datetime
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub start_date: String,
+    /// Last day of the range (inclusive), as YYYY-MM-DD or an RFC 3339 datetime
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub end_date: String,
+    /// IANA timezone used to resolve RFC 3339 datetimes to calendar dates
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+    /// Whether Saturdays and Sundays are excluded (default: true)
+    #[serde(default = "default_true")]
+    pub exclude_weekends: bool,
+    /// Holiday dates to exclude, as YYYY-MM-DD
+    #[serde(default)]
+    pub holidays: Vec<String>,
+}
+
+/// Request to get the ISO week of a date
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetCalendarWeekRequest {
+    /// Date as YYYY-MM-DD or an RFC 3339 datetime
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub date: String,
+    /// IANA timezone used to resolve RFC 3339 datetimes to calendar dates
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+}
+
+/// Request to show one time in several timezones at once
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetMeetingTimeRequest {
+    /// Meeting time in 24-hour format (HH:MM), today in the source timezone
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub source_time: String,
+    /// IANA timezone the meeting time is given in
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub source_timezone: String,
+    /// IANA timezones of the participants
+    pub target_timezones: Vec<String>,
+}
+
+/// Request to get sunrise and sunset times for a place
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetSunriseSunsetRequest {
+    /// Date as YYYY-MM-DD or an RFC 3339 datetime
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub date: String,
+    /// Latitude in degrees, positive north (-90 to 90)
+    pub latitude: f64,
+    /// Longitude in degrees, positive east (-180 to 180)
+    pub longitude: f64,
+    /// IANA timezone the returned times are expressed in
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+}
+
+/// Request to list the next run times of a cron expression
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ScheduleFromCronRequest {
+    /// Cron expression: five crontab fields (e.g. '0 9 * * MON-FRI'), six or seven
+    /// fields with leading seconds and optional year, or @daily/@weekly/@monthly/@yearly/@hourly
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub expression: String,
+    /// IANA timezone the expression's times are read in
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+    /// Find runs after this datetime, RFC 3339 or local to `timezone`; defaults to now
+    #[serde(default)]
+    pub from_datetime: Option<String>,
+    /// Number of run times to return, 1-100 (default: 5)
+    #[serde(default = "default_cron_count")]
+    pub count: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_cron_count() -> usize {
+    5
+}
+
+/// Request to describe a datetime relative to now
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct FormatRelativeTimeRequest {
+    /// Datetime to describe, either RFC 3339 or local to `timezone` (e.g., '2025-01-31T09:00')
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub datetime: String,
+    /// IANA timezone used to interpret `datetime` when it has no offset
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+    /// IANA timezone of the observer; defaults to the server's local timezone
+    #[serde(default)]
+    pub reference_timezone: Option<String>,
+}
+
+/// Request to render a datetime with a strftime-style format string
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct FormatDatetimeRequest {
+    /// Datetime to format, either RFC 3339 or local to `timezone` (e.g., '2024-03-15T14:30')
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub datetime: String,
+    /// IANA timezone to render the datetime in; also used to interpret `datetime` when it has no offset
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+    /// strftime-style format, e.g. '%B %d, %Y at %I:%M %p' or '%A %Y-%m-%d'
+    pub format: String,
+}
+
+/// Request to parse a human-readable duration
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ParseDurationRequest {
+    /// Duration such as '90s', '2h30m', '1d12h', '2 hours 30 minutes' or ISO 8601 'P2DT3H'
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub duration: String,
+}
+
+/// Request to get the current Unix timestamp
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetUnixTimestampRequest {
+    /// IANA timezone name (validated only; Unix timestamps are timezone independent)
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+}
+
+/// Request to convert a Unix timestamp to a datetime
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct FromUnixTimestampRequest {
+    /// Seconds since 1970-01-01T00:00:00Z; negative values are before 1970
+    pub unix_seconds: i64,
+    /// IANA timezone name to express the datetime in
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub target_timezone: String,
+}
+
+/// Request to calculate the duration between two datetimes
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CalculateDurationRequest {
+    /// Start time: HH:MM (today in `start_timezone`), RFC 3339, or YYYY-MM-DD[THH:MM[:SS]]
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub start: String,
+    /// IANA timezone name for the start time
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub start_timezone: String,
+    /// End time: HH:MM (today in `end_timezone`), RFC 3339, or YYYY-MM-DD[THH:MM[:SS]]
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub end: String,
+    /// IANA timezone name for the end time
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub end_timezone: String,
+}
+
+/// Direction of a date arithmetic operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ArithmeticOperation {
+    Add,
+    Subtract,
+}
+
+/// Request to add or subtract a duration from a datetime
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DateArithmeticRequest {
+    /// Base datetime, either RFC 3339 (e.g., '2025-01-31T09:00:00Z') or local to `timezone` (e.g., '2025-01-31T09:00', '2025-01-31')
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub base_datetime: String,
+    /// IANA timezone the arithmetic is performed in
+    #[serde(deserialize_with = "deserialize_trimmed_string")]
+    pub timezone: String,
+    /// Whether to add or subtract the duration ('add' or 'subtract')
+    pub operation: ArithmeticOperation,
+    /// Calendar years
+    #[serde(default)]
+    pub years: Option<i32>,
+    /// Calendar months; day of month is clamped to the end of shorter months
+    #[serde(default)]
+    pub months: Option<i32>,
+    /// Calendar days; the local wall-clock time is preserved across DST changes
+    #[serde(default)]
+    pub days: Option<i64>,
+    /// Elapsed hours
+    #[serde(default)]
+    pub hours: Option<i64>,
+    /// Elapsed minutes
+    #[serde(default)]
+    pub minutes: Option<i64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +430,13 @@ mod tests {
             datetime: "2025-01-01T12:00:00+00:00".to_string(),
             day_of_week: "Monday".to_string(),
             is_dst: false,
+            year: 2025,
+            month: 1,
+            day: 1,
+            hour: 12,
+            minute: 0,
+            second: 0,
+            utc_offset_seconds: 0,
         };
 
         let json = serde_json::to_string(&time_result).unwrap();
@@ -96,6 +444,25 @@ mod tests {
         assert!(json.contains("Monday"));
     }
 
+    #[test]
+    fn test_time_result_components_follow_dst() {
+        let new_york: chrono_tz::Tz = "America/New_York".parse().unwrap();
+
+        let summer = new_york.with_ymd_and_hms(2024, 7, 4, 9, 5, 30).unwrap();
+        let result = TimeResult::from_datetime(&summer, "America/New_York");
+        assert_eq!(result.datetime, "2024-07-04T09:05:30-04:00");
+        assert_eq!((result.year, result.month, result.day), (2024, 7, 4));
+        assert_eq!((result.hour, result.minute, result.second), (9, 5, 30));
+        assert!(result.is_dst);
+        assert_eq!(result.utc_offset_seconds, -4 * 3600);
+
+        let winter = new_york.with_ymd_and_hms(2024, 1, 15, 23, 59, 0).unwrap();
+        let result = TimeResult::from_datetime(&winter, "America/New_York");
+        assert!(!result.is_dst);
+        assert_eq!(result.utc_offset_seconds, -5 * 3600);
+        assert_eq!((result.day, result.hour), (15, 23));
+    }
+
     #[test]
     fn test_timezone_trimming() {
         // Test GetCurrentTimeRequest with whitespace
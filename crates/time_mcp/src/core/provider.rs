@@ -1,18 +1,46 @@
-use std::str::FromStr;
+use std::{collections::HashSet, fmt::Write, path::Path, sync::Arc};
 
-use chrono::{DateTime, NaiveTime, TimeZone, Utc};
-use chrono_tz::Tz;
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+    format::{Item, StrftimeItems},
+};
+use chrono_tz::OffsetComponents;
 
 use crate::core::{
+    cache::TimezoneCache,
+    cron::parse_cron_expression,
     error::{TimeServerError, TimeServerResult},
-    models::{TimeConversionResult, TimeResult},
-    utils::{self, TIME_INPUT_FORMAT},
+    models::{
+        ArithmeticOperation, BusinessDaysResult, CalculateDurationRequest, CalendarWeekResult,
+        DateArithmeticRequest, DstTransitionsResult, DurationResult, FormatDatetimeRequest,
+        FormatRelativeTimeRequest, FormattedDatetimeResult, GetBusinessDaysRequest,
+        GetCalendarWeekRequest, GetMeetingTimeRequest, GetSunriseSunsetRequest,
+        ParsedDurationResult, RelativeTimeResult, ScheduleFromCronRequest, SunriseSunsetResult,
+        TimeConversionResult, TimeResult, UnixTimestampResult,
+    },
+    solar::{self, AltitudeCrossing},
+    tzdb::TzDatabase,
+    utils::{self, DATE_INPUT_FORMAT, DATETIME_INPUT_FORMATS, TIME_INPUT_FORMAT},
+    zone::Zone,
 };
 
+/// Longest date range accepted by [`TimeServer::count_business_days`] (about 100 years)
+const MAX_BUSINESS_DAYS_RANGE: i32 = 36_525;
+
+/// Longest format string accepted by [`TimeServer::format_datetime`], in bytes
+const MAX_FORMAT_LENGTH: usize = 256;
+
+/// Most run times returned by [`TimeServer::schedule_from_cron`]
+const MAX_CRON_OCCURRENCES: usize = 100;
+
 /// Time server implementation
 #[derive(Clone)]
 pub struct TimeServer {
-    pub(crate) local_timezone: Tz,
+    pub(crate) local_timezone: Zone,
+    pub(crate) timezone_cache: TimezoneCache,
+    /// Zones loaded by [`TimeServer::with_tzdb`], consulted before the compiled-in ones
+    pub(crate) tzdb: Option<Arc<TzDatabase>>,
 }
 
 impl TimeServer {
@@ -36,14 +64,37 @@ impl TimeServer {
         };
 
         Self {
-            local_timezone: local_tz,
+            local_timezone: Zone::Compiled(local_tz),
+            timezone_cache: TimezoneCache::new(),
+            tzdb: None,
         }
     }
 
-    pub(crate) fn parse_timezone(&self, timezone_name: &str) -> TimeServerResult<Tz> {
-        Tz::from_str(timezone_name).map_err(|_| TimeServerError::InvalidTimezone {
-            timezone: timezone_name.to_string(),
-        })
+    /// Create a server that also resolves timezones from the TZif files under `path`
+    ///
+    /// `path` is a zoneinfo directory such as `/usr/share/zoneinfo`. Zones found
+    /// there take precedence over the compiled-in database, so a newer tzdata
+    /// release can be dropped in without rebuilding; other names still resolve
+    /// from the compiled-in data.
+    pub fn with_tzdb(path: &Path) -> TimeServerResult<Self> {
+        let tzdb = TzDatabase::load(path)?;
+        let mut server = Self::new();
+        if let Ok(tz_name) = iana_time_zone::get_timezone()
+            && let Some(zone) = tzdb.get(&tz_name)
+        {
+            server.local_timezone = Zone::Loaded(zone.clone());
+        }
+        server.tzdb = Some(Arc::new(tzdb));
+        Ok(server)
+    }
+
+    pub(crate) fn parse_timezone(&self, timezone_name: &str) -> TimeServerResult<Zone> {
+        if let Some(zone) = self.tzdb.as_ref().and_then(|tzdb| tzdb.get(timezone_name)) {
+            return Ok(Zone::Loaded(zone.clone()));
+        }
+        self.timezone_cache
+            .parse_or_cache(timezone_name)
+            .map(Zone::Compiled)
     }
 
     pub fn get_current_time(&self, timezone_name: &str) -> TimeServerResult<TimeResult> {
@@ -53,6 +104,38 @@ impl TimeServer {
         Ok(TimeResult::from_datetime(&current_time, timezone_name))
     }
 
+    pub fn get_unix_timestamp(&self, timezone_name: &str) -> TimeServerResult<UnixTimestampResult> {
+        self.parse_timezone(timezone_name)?;
+        let now = Utc::now();
+
+        Ok(UnixTimestampResult {
+            unix_seconds: now.timestamp(),
+            unix_millis: now.timestamp_millis(),
+        })
+    }
+
+    pub fn datetime_from_unix_timestamp(
+        &self,
+        unix_seconds: i64,
+        target_tz: &str,
+    ) -> TimeServerResult<TimeResult> {
+        let timezone = self.parse_timezone(target_tz)?;
+        let datetime = DateTime::<Utc>::from_timestamp(unix_seconds, 0).ok_or_else(|| {
+            TimeServerError::InvalidArgument {
+                message: format!("Unix timestamp {} is out of range", unix_seconds),
+            }
+        })?;
+
+        Ok(TimeResult::from_datetime(
+            &datetime.with_timezone(&timezone),
+            target_tz,
+        ))
+    }
+
+    /// Convert `time_str` from `source_tz` to `target_tz`
+    ///
+    /// Accepts `HH:MM` (today in the source timezone) or a full
+    /// `YYYY-MM-DDTHH:MM[:SS]` datetime local to the source timezone.
     pub fn convert_time(
         &self,
         source_tz: &str,
@@ -74,21 +157,507 @@ impl TimeServer {
         })
     }
 
+    /// Add or subtract a duration from a datetime in the given timezone
+    ///
+    /// Years, months and days are applied to the local calendar date (clamping to
+    /// the end of shorter months and keeping the wall-clock time across DST
+    /// changes); hours and minutes are applied as elapsed time.
+    pub fn date_arithmetic(&self, req: &DateArithmeticRequest) -> TimeServerResult<TimeResult> {
+        let timezone = self.parse_timezone(&req.timezone)?;
+        let base = self.parse_datetime(&req.base_datetime, &timezone)?;
+
+        let sign = match req.operation {
+            ArithmeticOperation::Add => 1,
+            ArithmeticOperation::Subtract => -1,
+        };
+        let out_of_range = || TimeServerError::InvalidArgument {
+            message: "Resulting datetime is out of range".to_string(),
+        };
+
+        let total_months =
+            i64::from(req.years.unwrap_or(0)) * 12 + i64::from(req.months.unwrap_or(0));
+        let mut local = base.naive_local();
+        local = match (sign * total_months).cmp(&0) {
+            std::cmp::Ordering::Greater => {
+                let months = u32::try_from(sign * total_months).map_err(|_| out_of_range())?;
+                local.checked_add_months(Months::new(months))
+            }
+            std::cmp::Ordering::Less => {
+                let months = u32::try_from(-(sign * total_months)).map_err(|_| out_of_range())?;
+                local.checked_sub_months(Months::new(months))
+            }
+            std::cmp::Ordering::Equal => Some(local),
+        }
+        .ok_or_else(out_of_range)?;
+
+        let days = Duration::try_days(sign * req.days.unwrap_or(0)).ok_or_else(out_of_range)?;
+        local = local.checked_add_signed(days).ok_or_else(out_of_range)?;
+
+        let elapsed = Duration::try_hours(sign * req.hours.unwrap_or(0))
+            .zip(Duration::try_minutes(sign * req.minutes.unwrap_or(0)))
+            .and_then(|(hours, minutes)| hours.checked_add(&minutes))
+            .ok_or_else(out_of_range)?;
+
+        let result = Self::resolve_local_datetime(&timezone, local)
+            .ok_or_else(out_of_range)?
+            .checked_add_signed(elapsed)
+            .ok_or_else(out_of_range)?;
+
+        Ok(TimeResult::from_datetime(&result, &req.timezone))
+    }
+
+    /// Compute the signed interval from `start` to `end`
+    pub fn calculate_duration(
+        &self,
+        req: &CalculateDurationRequest,
+    ) -> TimeServerResult<DurationResult> {
+        let start_timezone = self.parse_timezone(&req.start_timezone)?;
+        let end_timezone = self.parse_timezone(&req.end_timezone)?;
+        let start = self.parse_time_or_datetime(&req.start, &start_timezone)?;
+        let end = self.parse_time_or_datetime(&req.end, &end_timezone)?;
+
+        let total_seconds = end.signed_duration_since(start).num_seconds();
+
+        Ok(DurationResult {
+            total_seconds,
+            days: total_seconds / 86_400,
+            hours: total_seconds % 86_400 / 3_600,
+            minutes: total_seconds % 3_600 / 60,
+            seconds: total_seconds % 60,
+            human_readable: utils::format_duration_human(total_seconds),
+        })
+    }
+
+    /// Find when DST starts and ends in `timezone_name` during `year`
+    ///
+    /// Scans the year hour by hour in UTC and narrows each change in DST state
+    /// down to the minute. Zones with more than one DST period per year report
+    /// the first transition in each direction.
+    pub fn get_dst_transitions(
+        &self,
+        timezone_name: &str,
+        year: i32,
+    ) -> TimeServerResult<DstTransitionsResult> {
+        let timezone = self.parse_timezone(timezone_name)?;
+        let year_start = |year: Option<i32>| {
+            NaiveDate::from_ymd_opt(year?, 1, 1).map(|date| date.and_time(NaiveTime::MIN).and_utc())
+        };
+        let (Some(start), Some(end)) = (year_start(Some(year)), year_start(year.checked_add(1)))
+        else {
+            return Err(TimeServerError::InvalidArgument {
+                message: format!("Year {} is out of range", year),
+            });
+        };
+
+        let is_dst = |instant: DateTime<Utc>| {
+            timezone
+                .offset_from_utc_datetime(&instant.naive_utc())
+                .dst_offset()
+                .num_seconds()
+                != 0
+        };
+
+        let mut spring_forward = None;
+        let mut fall_back = None;
+        let mut previous = start;
+        let mut previous_dst = is_dst(start);
+
+        while previous < end && (spring_forward.is_none() || fall_back.is_none()) {
+            let next = previous + Duration::hours(1);
+            let next_dst = is_dst(next);
+
+            if next_dst != previous_dst && next <= end {
+                let transition = (1..=60)
+                    .map(|minutes| previous + Duration::minutes(minutes))
+                    .find(|instant| is_dst(*instant) == next_dst)
+                    .unwrap_or(next)
+                    .with_timezone(&timezone)
+                    .to_rfc3339();
+
+                let slot = if next_dst {
+                    &mut spring_forward
+                } else {
+                    &mut fall_back
+                };
+                slot.get_or_insert(transition);
+            }
+
+            previous = next;
+            previous_dst = next_dst;
+        }
+
+        Ok(DstTransitionsResult {
+            has_dst: spring_forward.is_some() || fall_back.is_some(),
+            spring_forward,
+            fall_back,
+        })
+    }
+
+    /// Describe a datetime relative to the current time
+    pub fn format_relative_time(
+        &self,
+        req: &FormatRelativeTimeRequest,
+    ) -> TimeServerResult<RelativeTimeResult> {
+        self.format_relative_time_at(req, Utc::now())
+    }
+
+    /// Describe a datetime relative to `now`
+    pub(crate) fn format_relative_time_at(
+        &self,
+        req: &FormatRelativeTimeRequest,
+        now: DateTime<Utc>,
+    ) -> TimeServerResult<RelativeTimeResult> {
+        let timezone = self.parse_timezone(&req.timezone)?;
+        // The delta between two instants is the same in every timezone, so the
+        // observer's timezone is only checked for being a known zone
+        if let Some(name) = req.reference_timezone.as_deref().map(str::trim)
+            && !name.is_empty()
+        {
+            self.parse_timezone(name)?;
+        }
+        let datetime = self.parse_datetime(&req.datetime, &timezone)?;
+
+        let delta_seconds = datetime.signed_duration_since(now).num_seconds();
+
+        Ok(RelativeTimeResult {
+            relative: utils::format_relative_delta(delta_seconds),
+            delta_seconds,
+        })
+    }
+
+    /// Render a datetime in `req.timezone` with a strftime-style format string
+    pub fn format_datetime(
+        &self,
+        req: &FormatDatetimeRequest,
+    ) -> TimeServerResult<FormattedDatetimeResult> {
+        let invalid_format = |reason: &str| TimeServerError::InvalidArgument {
+            message: format!("Invalid format string: {}", reason),
+        };
+        if req.format.is_empty() {
+            return Err(invalid_format("format cannot be empty"));
+        }
+        if req.format.contains('\0') {
+            return Err(invalid_format("format cannot contain null bytes"));
+        }
+        if req.format.len() > MAX_FORMAT_LENGTH {
+            return Err(invalid_format(&format!(
+                "format is longer than {} bytes",
+                MAX_FORMAT_LENGTH
+            )));
+        }
+
+        let items: Vec<Item> = StrftimeItems::new(&req.format).collect();
+        if items.contains(&Item::Error) {
+            return Err(invalid_format(&format!(
+                "'{}' contains an unknown or incomplete specifier",
+                req.format
+            )));
+        }
+
+        let timezone = self.parse_timezone(&req.timezone)?;
+        let datetime = self.parse_datetime(&req.datetime, &timezone)?;
+
+        let mut formatted = String::new();
+        write!(
+            formatted,
+            "{}",
+            datetime.format_with_items(items.into_iter())
+        )
+        .map_err(|_| invalid_format("the datetime cannot be rendered with this format"))?;
+
+        Ok(FormattedDatetimeResult {
+            formatted,
+            input_timezone: req.timezone.clone(),
+        })
+    }
+
+    /// Parse a human-readable or ISO 8601 duration string
+    pub fn parse_duration(&self, duration: &str) -> TimeServerResult<ParsedDurationResult> {
+        let total_seconds = utils::parse_duration_string(duration).ok_or_else(|| {
+            TimeServerError::InvalidDuration {
+                duration: duration.to_string(),
+            }
+        })?;
+
+        Ok(ParsedDurationResult {
+            total_seconds,
+            days: total_seconds / 86_400,
+            hours: total_seconds % 86_400 / 3_600,
+            minutes: total_seconds % 3_600 / 60,
+            seconds: total_seconds % 60,
+        })
+    }
+
+    /// Count the working days between two dates, inclusive of both ends
+    pub fn count_business_days(
+        &self,
+        req: &GetBusinessDaysRequest,
+    ) -> TimeServerResult<BusinessDaysResult> {
+        let timezone = self.parse_timezone(&req.timezone)?;
+        let start = self
+            .parse_datetime(&req.start_date, &timezone)?
+            .date_naive();
+        let end = self.parse_datetime(&req.end_date, &timezone)?.date_naive();
+
+        if end < start {
+            return Err(TimeServerError::InvalidArgument {
+                message: format!(
+                    "end_date {} is before start_date {}",
+                    req.end_date, req.start_date
+                ),
+            });
+        }
+        let calendar_days = i32::try_from((end - start).num_days() + 1)
+            .ok()
+            .filter(|days| *days <= MAX_BUSINESS_DAYS_RANGE)
+            .ok_or_else(|| TimeServerError::InvalidArgument {
+                message: format!("Date range exceeds {} days", MAX_BUSINESS_DAYS_RANGE),
+            })?;
+
+        let holidays = req
+            .holidays
+            .iter()
+            .map(|holiday| {
+                NaiveDate::parse_from_str(holiday.trim(), DATE_INPUT_FORMAT).map_err(|_| {
+                    TimeServerError::InvalidDateTime {
+                        datetime: holiday.clone(),
+                    }
+                })
+            })
+            .collect::<TimeServerResult<HashSet<_>>>()?;
+
+        let business_days = start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .filter(|date| {
+                !(req.exclude_weekends && matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+            })
+            .filter(|date| !holidays.contains(date))
+            .count();
+
+        Ok(BusinessDaysResult {
+            business_days: business_days as i32,
+            calendar_days,
+        })
+    }
+
+    /// Get the ISO 8601 week, weekday and quarter of a date
+    pub fn get_calendar_week(
+        &self,
+        req: &GetCalendarWeekRequest,
+    ) -> TimeServerResult<CalendarWeekResult> {
+        let timezone = self.parse_timezone(&req.timezone)?;
+        let date = self.parse_datetime(&req.date, &timezone)?.date_naive();
+        let iso_week = date.iso_week();
+
+        Ok(CalendarWeekResult {
+            iso_week: iso_week.week(),
+            iso_year: iso_week.year(),
+            weekday: date.format(utils::DAY_FORMAT).to_string(),
+            quarter: (date.month0() / 3 + 1) as u8,
+        })
+    }
+
+    /// Show a meeting time in each target timezone, ordered from the westernmost UTC offset
+    pub fn get_meeting_time(
+        &self,
+        req: &GetMeetingTimeRequest,
+    ) -> TimeServerResult<Vec<TimeResult>> {
+        if req.target_timezones.is_empty() {
+            return Err(TimeServerError::InvalidArgument {
+                message: "target_timezones must name at least one timezone".to_string(),
+            });
+        }
+
+        let mut results = req
+            .target_timezones
+            .iter()
+            .map(|target| {
+                let conversion =
+                    self.convert_time(&req.source_timezone, &req.source_time, target.trim())?;
+                let offset = DateTime::parse_from_rfc3339(&conversion.target.datetime)
+                    .map(|datetime| datetime.offset().local_minus_utc())
+                    .unwrap_or_default();
+                Ok((offset, conversion.target))
+            })
+            .collect::<TimeServerResult<Vec<_>>>()?;
+        results.sort_by_key(|(offset, _)| *offset);
+
+        Ok(results.into_iter().map(|(_, result)| result).collect())
+    }
+
+    /// Get sunrise, sunset and civil twilight times for a date at a given position
+    pub fn get_sunrise_sunset(
+        &self,
+        req: &GetSunriseSunsetRequest,
+    ) -> TimeServerResult<SunriseSunsetResult> {
+        if !(-90.0..=90.0).contains(&req.latitude) {
+            return Err(TimeServerError::InvalidArgument {
+                message: format!("latitude {} must be between -90 and 90", req.latitude),
+            });
+        }
+        if !(-180.0..=180.0).contains(&req.longitude) {
+            return Err(TimeServerError::InvalidArgument {
+                message: format!("longitude {} must be between -180 and 180", req.longitude),
+            });
+        }
+
+        let timezone = self.parse_timezone(&req.timezone)?;
+        let date = self.parse_datetime(&req.date, &timezone)?.date_naive();
+        let format = |datetime: DateTime<Utc>| {
+            datetime
+                .with_timezone(&timezone)
+                .format(utils::DATETIME_FORMAT)
+                .to_string()
+        };
+
+        let (sunrise, sunset, day_length_minutes) = match solar::altitude_crossing(
+            date,
+            req.latitude,
+            req.longitude,
+            solar::SUNRISE_ALTITUDE,
+        ) {
+            AltitudeCrossing::Crossed { rising, setting } => (
+                Some(format(rising)),
+                Some(format(setting)),
+                ((setting - rising).num_seconds() as f64 / 60.0).round() as u64,
+            ),
+            AltitudeCrossing::AlwaysAbove => (None, None, 24 * 60),
+            AltitudeCrossing::AlwaysBelow => (None, None, 0),
+        };
+        let (civil_twilight_start, civil_twilight_end) = match solar::altitude_crossing(
+            date,
+            req.latitude,
+            req.longitude,
+            solar::CIVIL_TWILIGHT_ALTITUDE,
+        ) {
+            AltitudeCrossing::Crossed { rising, setting } => {
+                (Some(format(rising)), Some(format(setting)))
+            }
+            AltitudeCrossing::AlwaysAbove | AltitudeCrossing::AlwaysBelow => (None, None),
+        };
+
+        Ok(SunriseSunsetResult {
+            sunrise,
+            sunset,
+            day_length_minutes,
+            civil_twilight_start,
+            civil_twilight_end,
+        })
+    }
+
+    /// List the next run times of a cron expression, strictly after `from_datetime`
+    ///
+    /// Times are matched against the wall clock of the timezone, so a daily 09:00
+    /// job stays at 09:00 across DST changes. A time skipped by a spring-forward
+    /// gap does not run that day, and a time repeated when clocks fall back runs
+    /// at both instants.
+    pub fn schedule_from_cron(
+        &self,
+        req: &ScheduleFromCronRequest,
+    ) -> TimeServerResult<Vec<String>> {
+        if !(1..=MAX_CRON_OCCURRENCES).contains(&req.count) {
+            return Err(TimeServerError::InvalidArgument {
+                message: format!(
+                    "count {} must be between 1 and {}",
+                    req.count, MAX_CRON_OCCURRENCES
+                ),
+            });
+        }
+
+        let timezone = self.parse_timezone(&req.timezone)?;
+        let schedule = parse_cron_expression(&req.expression)?;
+        let from = match &req.from_datetime {
+            Some(from) => self.parse_datetime(from.trim(), &timezone)?,
+            None => Utc::now().with_timezone(&timezone),
+        };
+
+        Ok(schedule
+            .after(&from)
+            .take(req.count)
+            .map(|datetime| datetime.to_rfc3339())
+            .collect())
+    }
+
+    /// Parse `HH:MM` as today's date in `timezone`, falling back to [`Self::parse_datetime`]
+    pub(crate) fn parse_time_or_datetime(
+        &self,
+        input: &str,
+        timezone: &Zone,
+    ) -> TimeServerResult<DateTime<Zone>> {
+        match NaiveTime::parse_from_str(input, TIME_INPUT_FORMAT) {
+            Ok(time) => {
+                let today = Utc::now().with_timezone(timezone).date_naive();
+                Self::resolve_local_datetime(timezone, today.and_time(time)).ok_or_else(|| {
+                    TimeServerError::InvalidTimeFormat {
+                        time: input.to_string(),
+                    }
+                })
+            }
+            Err(_) => self.parse_datetime(input, timezone),
+        }
+    }
+
+    /// Parse an RFC 3339 datetime, or a naive datetime/date local to `timezone`
+    pub(crate) fn parse_datetime(
+        &self,
+        input: &str,
+        timezone: &Zone,
+    ) -> TimeServerResult<DateTime<Zone>> {
+        let invalid = || TimeServerError::InvalidDateTime {
+            datetime: input.to_string(),
+        };
+
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(input) {
+            return Ok(datetime.with_timezone(timezone));
+        }
+
+        let naive = DATETIME_INPUT_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(input, DATE_INPUT_FORMAT)
+                    .ok()
+                    .map(|date| date.and_time(NaiveTime::MIN))
+            })
+            .ok_or_else(invalid)?;
+
+        Self::resolve_local_datetime(timezone, naive).ok_or_else(invalid)
+    }
+
+    /// Resolve a local datetime, picking the earlier instant when it is ambiguous
+    /// and moving forward by an hour when it falls in a DST gap
+    fn resolve_local_datetime(timezone: &Zone, local: NaiveDateTime) -> Option<DateTime<Zone>> {
+        match timezone.from_local_datetime(&local) {
+            LocalResult::Single(datetime) => Some(datetime),
+            LocalResult::Ambiguous(earliest, _) => Some(earliest),
+            LocalResult::None => timezone
+                .from_local_datetime(&(local + Duration::hours(1)))
+                .earliest(),
+        }
+    }
+
     fn perform_time_conversion(
         &self,
-        source_tz: &Tz,
+        source_tz: &Zone,
         time_str: &str,
-        target_tz: &Tz,
-    ) -> TimeServerResult<(DateTime<Tz>, DateTime<Tz>)> {
-        let parsed_time = NaiveTime::parse_from_str(time_str, TIME_INPUT_FORMAT).map_err(|_| {
-            TimeServerError::InvalidTimeFormat {
-                time: time_str.to_string(),
+        target_tz: &Zone,
+    ) -> TimeServerResult<(DateTime<Zone>, DateTime<Zone>)> {
+        let local = match NaiveTime::parse_from_str(time_str, TIME_INPUT_FORMAT) {
+            Ok(parsed_time) => {
+                let now = Utc::now().with_timezone(source_tz);
+                now.date_naive().and_time(parsed_time)
             }
-        })?;
+            Err(_) => DATETIME_INPUT_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(time_str, format).ok())
+                .ok_or_else(|| TimeServerError::InvalidTimeFormat {
+                    time: time_str.to_string(),
+                })?,
+        };
 
-        let now = Utc::now().with_timezone(source_tz);
         let source_time = source_tz
-            .from_local_datetime(&now.date_naive().and_time(parsed_time))
+            .from_local_datetime(&local)
             .single()
             .ok_or_else(|| TimeServerError::AmbiguousTime {
                 time: time_str.to_string(),
@@ -104,3 +673,970 @@ impl Default for TimeServer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+    use serde_json::json;
+
+    fn arithmetic(request: serde_json::Value) -> TimeServerResult<TimeResult> {
+        let request: DateArithmeticRequest = serde_json::from_value(request).unwrap();
+        TimeServer::new().date_arithmetic(&request)
+    }
+
+    #[test]
+    fn test_current_time_components_match_datetime() {
+        for timezone in ["UTC", "Asia/Kolkata", "America/New_York"] {
+            let result = TimeServer::new().get_current_time(timezone).unwrap();
+            let parsed = DateTime::parse_from_rfc3339(&result.datetime).unwrap();
+
+            assert_eq!(
+                (result.year, result.month, result.day),
+                (parsed.year(), parsed.month(), parsed.day())
+            );
+            assert_eq!(
+                (result.hour, result.minute, result.second),
+                (parsed.hour(), parsed.minute(), parsed.second())
+            );
+            assert_eq!(result.utc_offset_seconds, parsed.offset().local_minus_utc());
+        }
+
+        let result = TimeServer::new().get_current_time("Asia/Kolkata").unwrap();
+        assert_eq!(result.utc_offset_seconds, 5 * 3600 + 30 * 60);
+    }
+
+    #[test]
+    fn test_convert_time_accepts_full_datetime() {
+        let server = TimeServer::new();
+
+        let result = server
+            .convert_time("America/New_York", "2024-03-15T22:30:00", "Asia/Tokyo")
+            .unwrap();
+        assert_eq!(result.source.datetime, "2024-03-15T22:30:00-04:00");
+        assert_eq!(result.target.datetime, "2024-03-16T11:30:00+09:00");
+        assert_eq!(
+            (result.target.year, result.target.month, result.target.day),
+            (2024, 3, 16)
+        );
+        assert_eq!(result.time_difference, "+13h");
+
+        // Winter date: New York is on EST, so the offset differs from the March case
+        let result = server
+            .convert_time("America/New_York", "2024-01-10 08:00", "UTC")
+            .unwrap();
+        assert_eq!(result.target.datetime, "2024-01-10T13:00:00+00:00");
+
+        let result = server.convert_time("UTC", "2024-03-15", "Asia/Tokyo");
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidTimeFormat { .. })
+        ));
+    }
+
+    fn format_datetime(datetime: &str, format: &str) -> TimeServerResult<String> {
+        let request: FormatDatetimeRequest = serde_json::from_value(json!({
+            "datetime": datetime,
+            "timezone": "America/New_York",
+            "format": format
+        }))
+        .unwrap();
+        TimeServer::new()
+            .format_datetime(&request)
+            .map(|result| result.formatted)
+    }
+
+    #[test]
+    fn test_format_datetime_with_strftime_patterns() {
+        let datetime = "2024-03-15T14:30:00";
+        assert_eq!(format_datetime(datetime, "%Y-%m-%d").unwrap(), "2024-03-15");
+        assert_eq!(
+            format_datetime(datetime, "%B %d, %Y").unwrap(),
+            "March 15, 2024"
+        );
+        assert_eq!(format_datetime(datetime, "%A").unwrap(), "Friday");
+        assert_eq!(format_datetime(datetime, "%I:%M %p").unwrap(), "02:30 PM");
+
+        // RFC 3339 input is converted to the requested timezone before formatting
+        assert_eq!(
+            format_datetime("2024-03-15T18:30:00Z", "%H:%M %Z").unwrap(),
+            "14:30 EDT"
+        );
+
+        let request: FormatDatetimeRequest = serde_json::from_value(json!({
+            "datetime": datetime,
+            "timezone": "Asia/Tokyo",
+            "format": "%d/%m/%Y"
+        }))
+        .unwrap();
+        let result = TimeServer::new().format_datetime(&request).unwrap();
+        assert_eq!(result.formatted, "15/03/2024");
+        assert_eq!(result.input_timezone, "Asia/Tokyo");
+    }
+
+    #[test]
+    fn test_format_datetime_rejects_invalid_formats() {
+        for format in ["%Q", "%Y-%m-%", "", "%Y\0", &"%Y".repeat(200)] {
+            assert!(
+                matches!(
+                    format_datetime("2024-03-15T14:30:00", format),
+                    Err(TimeServerError::InvalidArgument { .. })
+                ),
+                "{format:?}"
+            );
+        }
+        assert!(matches!(
+            format_datetime("15 March", "%Y"),
+            Err(TimeServerError::InvalidDateTime { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unix_timestamp_round_trip() {
+        let server = TimeServer::new();
+        let now = server.get_unix_timestamp("Asia/Tokyo").unwrap();
+        assert_eq!(now.unix_millis / 1000, now.unix_seconds);
+
+        let result = server
+            .datetime_from_unix_timestamp(now.unix_seconds, "Asia/Tokyo")
+            .unwrap();
+        let parsed = DateTime::parse_from_rfc3339(&result.datetime).unwrap();
+        assert_eq!(parsed.timestamp(), now.unix_seconds);
+
+        let result = server
+            .datetime_from_unix_timestamp(1_700_000_000, "UTC")
+            .unwrap();
+        assert_eq!(result.datetime, "2023-11-14T22:13:20+00:00");
+    }
+
+    #[test]
+    fn test_negative_unix_timestamp() {
+        let server = TimeServer::new();
+
+        let result = server.datetime_from_unix_timestamp(-86_400, "UTC").unwrap();
+        assert_eq!(result.datetime, "1969-12-31T00:00:00+00:00");
+
+        let result = server
+            .datetime_from_unix_timestamp(-14_182_940, "America/New_York")
+            .unwrap();
+        assert_eq!(result.datetime, "1969-07-20T16:17:40-04:00");
+        assert!(result.is_dst);
+    }
+
+    #[test]
+    fn test_unix_timestamp_invalid_input() {
+        let server = TimeServer::new();
+        assert!(server.get_unix_timestamp("Invalid/Zone").is_err());
+        assert!(
+            server
+                .datetime_from_unix_timestamp(0, "Invalid/Zone")
+                .is_err()
+        );
+        assert!(matches!(
+            server.datetime_from_unix_timestamp(i64::MAX, "UTC"),
+            Err(TimeServerError::InvalidArgument { .. })
+        ));
+    }
+
+    fn duration(request: serde_json::Value) -> TimeServerResult<DurationResult> {
+        let request: CalculateDurationRequest = serde_json::from_value(request).unwrap();
+        TimeServer::new().calculate_duration(&request)
+    }
+
+    #[test]
+    fn test_duration_exact_day_boundary() {
+        let result = duration(json!({
+            "start": "2024-01-01T00:00:00Z",
+            "start_timezone": "UTC",
+            "end": "2024-01-02T00:00:00Z",
+            "end_timezone": "UTC"
+        }))
+        .unwrap();
+
+        assert_eq!(result.total_seconds, 86_400);
+        assert_eq!(
+            (result.days, result.hours, result.minutes, result.seconds),
+            (1, 0, 0, 0)
+        );
+        assert_eq!(result.human_readable, "1 day");
+    }
+
+    #[test]
+    fn test_duration_across_dst_transition() {
+        // Midnight to midnight across the 2024-03-10 spring-forward is only 23 hours
+        let result = duration(json!({
+            "start": "2024-03-10",
+            "start_timezone": "America/New_York",
+            "end": "2024-03-11",
+            "end_timezone": "America/New_York"
+        }))
+        .unwrap();
+
+        assert_eq!(result.total_seconds, 23 * 3_600);
+        assert_eq!((result.days, result.hours), (0, 23));
+        assert_eq!(result.human_readable, "23 hours");
+
+        // Different timezones are compared as instants
+        let result = duration(json!({
+            "start": "2024-06-01T09:00:00",
+            "start_timezone": "Europe/London",
+            "end": "2024-06-01T09:00:00",
+            "end_timezone": "Asia/Tokyo"
+        }))
+        .unwrap();
+        assert_eq!(result.total_seconds, -8 * 3_600);
+    }
+
+    #[test]
+    fn test_negative_duration() {
+        let result = duration(json!({
+            "start": "2024-05-02T12:30:15+00:00",
+            "start_timezone": "UTC",
+            "end": "2024-05-01T10:00:00+00:00",
+            "end_timezone": "UTC"
+        }))
+        .unwrap();
+
+        assert_eq!(result.total_seconds, -95_415);
+        assert_eq!(
+            (result.days, result.hours, result.minutes, result.seconds),
+            (-1, -2, -30, -15)
+        );
+        assert_eq!(
+            result.human_readable,
+            "minus 1 day, 2 hours, 30 minutes, 15 seconds"
+        );
+    }
+
+    #[test]
+    fn test_duration_with_hh_mm_inputs() {
+        let result = duration(json!({
+            "start": "09:00",
+            "start_timezone": "UTC",
+            "end": "17:30",
+            "end_timezone": "UTC"
+        }))
+        .unwrap();
+        assert_eq!(result.total_seconds, 8 * 3_600 + 30 * 60);
+
+        let result = duration(json!({
+            "start": "9am",
+            "start_timezone": "UTC",
+            "end": "17:30",
+            "end_timezone": "UTC"
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidDateTime { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dst_transitions_new_york() {
+        let result = TimeServer::new()
+            .get_dst_transitions("America/New_York", 2024)
+            .unwrap();
+
+        assert!(result.has_dst);
+        assert_eq!(
+            result.spring_forward.as_deref(),
+            Some("2024-03-10T03:00:00-04:00")
+        );
+        assert_eq!(
+            result.fall_back.as_deref(),
+            Some("2024-11-03T01:00:00-05:00")
+        );
+    }
+
+    #[test]
+    fn test_dst_transitions_southern_hemisphere() {
+        let result = TimeServer::new()
+            .get_dst_transitions("Australia/Sydney", 2024)
+            .unwrap();
+
+        assert!(result.has_dst);
+        assert_eq!(
+            result.fall_back.as_deref(),
+            Some("2024-04-07T02:00:00+10:00")
+        );
+        assert_eq!(
+            result.spring_forward.as_deref(),
+            Some("2024-10-06T03:00:00+11:00")
+        );
+    }
+
+    #[test]
+    fn test_dst_transitions_without_dst() {
+        let server = TimeServer::new();
+
+        let result = server.get_dst_transitions("UTC", 2024).unwrap();
+        assert!(!result.has_dst);
+        assert!(result.spring_forward.is_none());
+        assert!(result.fall_back.is_none());
+
+        assert!(matches!(
+            server.get_dst_transitions("Invalid/Zone", 2024),
+            Err(TimeServerError::InvalidTimezone { .. })
+        ));
+        assert!(matches!(
+            server.get_dst_transitions("UTC", i32::MAX),
+            Err(TimeServerError::InvalidArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_with_tzdb_resolves_loaded_zones() {
+        use crate::core::tzdb::tests::{cet_2024, tzif};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("Test")).unwrap();
+        std::fs::write(
+            dir.path().join("Test/Fictional"),
+            tzif(&[(19_800, false, "+0530")], &[]),
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("Test/Seasonal"), cet_2024()).unwrap();
+        let server = TimeServer::with_tzdb(dir.path()).unwrap();
+
+        let current = server.get_current_time("Test/Fictional").unwrap();
+        assert_eq!(current.timezone, "Test/Fictional");
+        assert_eq!(current.utc_offset_seconds, 19_800);
+        assert!(!current.is_dst);
+
+        let result = server
+            .convert_time("Test/Seasonal", "2024-07-01T12:00:00", "Test/Fictional")
+            .unwrap();
+        assert_eq!(result.source.datetime, "2024-07-01T12:00:00+02:00");
+        assert!(result.source.is_dst);
+        assert_eq!(result.target.datetime, "2024-07-01T15:30:00+05:30");
+        assert_eq!(result.time_difference, "+3.5h");
+
+        let transitions = server.get_dst_transitions("Test/Seasonal", 2024).unwrap();
+        assert_eq!(
+            transitions.spring_forward.as_deref(),
+            Some("2024-03-31T03:00:00+02:00")
+        );
+        assert_eq!(
+            transitions.fall_back.as_deref(),
+            Some("2024-10-27T02:00:00+01:00")
+        );
+
+        // Names missing from the directory still resolve from the compiled-in data
+        assert!(server.get_current_time("Asia/Tokyo").is_ok());
+        assert!(matches!(
+            server.get_current_time("Invalid/Zone"),
+            Err(TimeServerError::InvalidTimezone { .. })
+        ));
+        assert!(matches!(
+            TimeServer::with_tzdb(&dir.path().join("missing")),
+            Err(TimeServerError::TimezoneDatabase { .. })
+        ));
+    }
+
+    #[test]
+    fn test_format_relative_time_with_fixed_now() {
+        let server = TimeServer::new();
+        let now = DateTime::parse_from_rfc3339("2024-06-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let relative = |datetime: &str| {
+            let request: FormatRelativeTimeRequest = serde_json::from_value(json!({
+                "datetime": datetime,
+                "timezone": "Europe/Paris",
+                "reference_timezone": "UTC"
+            }))
+            .unwrap();
+            server.format_relative_time_at(&request, now).unwrap()
+        };
+
+        let result = relative("2024-06-15T11:59:01Z");
+        assert_eq!(
+            (result.relative.as_str(), result.delta_seconds),
+            ("just now", -59)
+        );
+
+        // Naive input is local to Paris (UTC+2 in June)
+        let result = relative("2024-06-15T13:59:00");
+        assert_eq!(
+            (result.relative.as_str(), result.delta_seconds),
+            ("1 minute ago", -60)
+        );
+
+        assert_eq!(relative("2024-06-15T11:00:01Z").relative, "59 minutes ago");
+        assert_eq!(relative("2024-06-15T11:00:00Z").relative, "1 hour ago");
+        assert_eq!(relative("2024-06-14T12:00:01Z").relative, "23 hours ago");
+        assert_eq!(relative("2024-06-14T12:00:00Z").relative, "1 day ago");
+        assert_eq!(relative("2024-06-08T12:00:00Z").relative, "1 week ago");
+        assert_eq!(relative("2024-05-16T12:00:00Z").relative, "1 month ago");
+        assert_eq!(relative("2023-06-16T12:00:00Z").relative, "1 year ago");
+
+        assert_eq!(relative("2024-06-15T12:00:59Z").relative, "just now");
+        assert_eq!(relative("2024-06-15T15:00:00Z").relative, "in 3 hours");
+        assert_eq!(relative("2024-06-17T12:00:00Z").relative, "in 2 days");
+        assert_eq!(relative("2026-06-15T12:00:00Z").relative, "in 2 years");
+    }
+
+    #[test]
+    fn test_format_relative_time_invalid_timezone() {
+        let request: FormatRelativeTimeRequest = serde_json::from_value(json!({
+            "datetime": "2024-06-15T12:00:00",
+            "timezone": "Invalid/Zone"
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            TimeServer::new().format_relative_time(&request),
+            Err(TimeServerError::InvalidTimezone { .. })
+        ));
+    }
+
+    #[test]
+    fn test_format_relative_time_invalid_reference_timezone() {
+        let request: FormatRelativeTimeRequest = serde_json::from_value(json!({
+            "datetime": "2024-06-15T12:00:00Z",
+            "timezone": "UTC",
+            "reference_timezone": "Invalid/Zone"
+        }))
+        .unwrap();
+
+        assert!(matches!(
+            TimeServer::new().format_relative_time(&request),
+            Err(TimeServerError::InvalidTimezone { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_duration_components() {
+        let server = TimeServer::new();
+
+        let result = server.parse_duration("1d 2h 3m 4s").unwrap();
+        assert_eq!(result.total_seconds, 93_784);
+        assert_eq!(
+            (result.days, result.hours, result.minutes, result.seconds),
+            (1, 2, 3, 4)
+        );
+
+        assert!(matches!(
+            server.parse_duration("soon"),
+            Err(TimeServerError::InvalidDuration { .. })
+        ));
+    }
+
+    fn business_days(request: serde_json::Value) -> TimeServerResult<BusinessDaysResult> {
+        let request: GetBusinessDaysRequest = serde_json::from_value(request).unwrap();
+        TimeServer::new().count_business_days(&request)
+    }
+
+    #[test]
+    fn test_business_days_within_week() {
+        // Monday to Friday
+        let result = business_days(json!({
+            "start_date": "2024-06-03",
+            "end_date": "2024-06-07",
+            "timezone": "UTC"
+        }))
+        .unwrap();
+
+        assert_eq!((result.business_days, result.calendar_days), (5, 5));
+    }
+
+    #[test]
+    fn test_business_days_spanning_weekend() {
+        // Thursday to the following Tuesday
+        let request = json!({
+            "start_date": "2024-06-06",
+            "end_date": "2024-06-11",
+            "timezone": "UTC"
+        });
+        let result = business_days(request.clone()).unwrap();
+        assert_eq!((result.business_days, result.calendar_days), (4, 6));
+
+        let mut request = request;
+        request["exclude_weekends"] = json!(false);
+        let result = business_days(request).unwrap();
+        assert_eq!((result.business_days, result.calendar_days), (6, 6));
+    }
+
+    #[test]
+    fn test_business_days_with_holidays() {
+        // December 2024 with Christmas and Boxing Day off; a weekend holiday is not double counted
+        let result = business_days(json!({
+            "start_date": "2024-12-01",
+            "end_date": "2024-12-31",
+            "timezone": "Europe/London",
+            "holidays": ["2024-12-25", "2024-12-26", "2024-12-28"]
+        }))
+        .unwrap();
+
+        assert_eq!((result.business_days, result.calendar_days), (20, 31));
+    }
+
+    #[test]
+    fn test_business_days_invalid_input() {
+        let result = business_days(json!({
+            "start_date": "2024-06-11",
+            "end_date": "2024-06-06",
+            "timezone": "UTC"
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidArgument { .. })
+        ));
+
+        let result = business_days(json!({
+            "start_date": "2024-06-06",
+            "end_date": "2024-06-11",
+            "timezone": "UTC",
+            "holidays": ["June 7th"]
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidDateTime { .. })
+        ));
+    }
+
+    fn calendar_week(date: &str, timezone: &str) -> TimeServerResult<CalendarWeekResult> {
+        let request: GetCalendarWeekRequest =
+            serde_json::from_value(json!({ "date": date, "timezone": timezone })).unwrap();
+        TimeServer::new().get_calendar_week(&request)
+    }
+
+    #[test]
+    fn test_calendar_week_first_week_of_2024() {
+        // 2024 starts on a Monday, so January 1st opens ISO week 1
+        let result = calendar_week("2024-01-01", "UTC").unwrap();
+        assert_eq!((result.iso_week, result.iso_year), (1, 2024));
+        assert_eq!(result.weekday, "Monday");
+        assert_eq!(result.quarter, 1);
+
+        let result = calendar_week("2024-01-07", "UTC").unwrap();
+        assert_eq!((result.iso_week, result.iso_year), (1, 2024));
+    }
+
+    #[test]
+    fn test_calendar_week_across_year_boundary() {
+        // Sunday January 1st 2023 still belongs to the last ISO week of 2022
+        let result = calendar_week("2023-01-01", "UTC").unwrap();
+        assert_eq!((result.iso_week, result.iso_year), (52, 2022));
+        assert_eq!(result.weekday, "Sunday");
+
+        // Monday December 30th 2024 already belongs to ISO week 1 of 2025
+        let result = calendar_week("2024-12-30", "UTC").unwrap();
+        assert_eq!((result.iso_week, result.iso_year), (1, 2025));
+        assert_eq!(result.quarter, 4);
+
+        // The timezone decides which calendar date an instant falls on
+        let result = calendar_week("2024-12-29T20:00:00-05:00", "Asia/Tokyo").unwrap();
+        assert_eq!((result.iso_week, result.iso_year), (1, 2025));
+        assert_eq!(result.weekday, "Monday");
+    }
+
+    #[test]
+    fn test_calendar_week_quarters() {
+        for (date, quarter) in [
+            ("2024-03-31", 1),
+            ("2024-04-01", 2),
+            ("2024-06-30", 2),
+            ("2024-07-01", 3),
+            ("2024-09-30", 3),
+            ("2024-10-01", 4),
+        ] {
+            assert_eq!(
+                calendar_week(date, "UTC").unwrap().quarter,
+                quarter,
+                "{date}"
+            );
+        }
+
+        assert!(matches!(
+            calendar_week("week 12", "UTC"),
+            Err(TimeServerError::InvalidDateTime { .. })
+        ));
+    }
+
+    #[test]
+    fn test_add_months_across_year_boundary() {
+        let result = arithmetic(json!({
+            "base_datetime": "2024-11-30T10:00:00",
+            "timezone": "UTC",
+            "operation": "add",
+            "months": 3
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2025-02-28T10:00:00+00:00");
+
+        let result = arithmetic(json!({
+            "base_datetime": "2023-02-28",
+            "timezone": "UTC",
+            "operation": "add",
+            "years": 1
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-02-28T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_end_of_month_clamping() {
+        let result = arithmetic(json!({
+            "base_datetime": "2024-01-31T09:00",
+            "timezone": "Asia/Tokyo",
+            "operation": "add",
+            "months": 1
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-02-29T09:00:00+09:00");
+
+        let result = arithmetic(json!({
+            "base_datetime": "2024-03-31T09:00",
+            "timezone": "Asia/Tokyo",
+            "operation": "subtract",
+            "months": 1
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-02-29T09:00:00+09:00");
+    }
+
+    #[test]
+    fn test_subtract_days_from_first_of_month() {
+        let result = arithmetic(json!({
+            "base_datetime": "2024-03-01T12:00:00",
+            "timezone": "UTC",
+            "operation": "subtract",
+            "days": 1
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-02-29T12:00:00+00:00");
+        assert_eq!(result.day_of_week, "Thursday");
+
+        let result = arithmetic(json!({
+            "base_datetime": "2025-01-01",
+            "timezone": "UTC",
+            "operation": "subtract",
+            "days": 90
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-10-03T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_dst_crossing_arithmetic() {
+        // US spring-forward on 2024-03-10: a calendar day keeps the wall-clock time...
+        let result = arithmetic(json!({
+            "base_datetime": "2024-03-09T12:00:00",
+            "timezone": "America/New_York",
+            "operation": "add",
+            "days": 1
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-03-10T12:00:00-04:00");
+        assert!(result.is_dst);
+
+        // ...while 24 elapsed hours land an hour later on the wall clock
+        let result = arithmetic(json!({
+            "base_datetime": "2024-03-09T12:00:00",
+            "timezone": "America/New_York",
+            "operation": "add",
+            "hours": 24
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-03-10T13:00:00-04:00");
+
+        // RFC 3339 input is converted into the request timezone first
+        let result = arithmetic(json!({
+            "base_datetime": "2024-11-03T04:30:00Z",
+            "timezone": "America/New_York",
+            "operation": "add",
+            "hours": 1,
+            "minutes": 30
+        }))
+        .unwrap();
+        assert_eq!(result.datetime, "2024-11-03T01:00:00-05:00");
+        assert!(!result.is_dst);
+    }
+
+    #[test]
+    fn test_date_arithmetic_rejects_invalid_input() {
+        let result = arithmetic(json!({
+            "base_datetime": "not a date",
+            "timezone": "UTC",
+            "operation": "add",
+            "days": 1
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidDateTime { .. })
+        ));
+
+        let result = arithmetic(json!({
+            "base_datetime": "2024-01-01",
+            "timezone": "Invalid/Zone",
+            "operation": "add",
+            "days": 1
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidTimezone { .. })
+        ));
+
+        let result = arithmetic(json!({
+            "base_datetime": "2024-01-01",
+            "timezone": "UTC",
+            "operation": "add",
+            "years": i32::MAX
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidArgument { .. })
+        ));
+    }
+
+    fn meeting_time(request: serde_json::Value) -> TimeServerResult<Vec<TimeResult>> {
+        let request: GetMeetingTimeRequest = serde_json::from_value(request).unwrap();
+        TimeServer::new().get_meeting_time(&request)
+    }
+
+    #[test]
+    fn test_meeting_time_across_three_zones() {
+        let results = meeting_time(json!({
+            "source_time": "09:30",
+            "source_timezone": "Europe/London",
+            "target_timezones": ["Asia/Tokyo", "UTC", "America/New_York"]
+        }))
+        .unwrap();
+
+        let zones: Vec<_> = results.iter().map(|r| r.timezone.as_str()).collect();
+        assert_eq!(zones, ["America/New_York", "UTC", "Asia/Tokyo"]);
+
+        // Every entry names the same instant, shifted by its zone's UTC offset
+        let instants: Vec<_> = results
+            .iter()
+            .map(|r| DateTime::parse_from_rfc3339(&r.datetime).unwrap())
+            .collect();
+        assert!(instants.iter().all(|instant| *instant == instants[0]));
+
+        let offset_hours = |index: usize| instants[index].offset().local_minus_utc() / 3600;
+        assert_eq!(offset_hours(1), 0);
+        assert_eq!(offset_hours(2), 9);
+        assert!(matches!(offset_hours(0), -5 | -4));
+        assert_eq!(results[0].is_dst, offset_hours(0) == -4);
+        assert!(!results[1].is_dst && !results[2].is_dst);
+
+        let tokyo = instants[2].naive_local();
+        let utc = instants[1].naive_local();
+        assert_eq!(tokyo - utc, Duration::hours(9));
+        let new_york = instants[0].naive_local();
+        assert_eq!(utc - new_york, Duration::hours(-offset_hours(0) as i64));
+    }
+
+    #[test]
+    fn test_meeting_time_rejects_bad_input() {
+        let result = meeting_time(json!({
+            "source_time": "09:30",
+            "source_timezone": "UTC",
+            "target_timezones": []
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidArgument { .. })
+        ));
+
+        let result = meeting_time(json!({
+            "source_time": "09:30",
+            "source_timezone": "UTC",
+            "target_timezones": ["Asia/Tokyo", "Mars/Olympus"]
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidTimezone { .. })
+        ));
+
+        let result = meeting_time(json!({
+            "source_time": "9.30am",
+            "source_timezone": "UTC",
+            "target_timezones": ["Asia/Tokyo"]
+        }));
+        assert!(matches!(
+            result,
+            Err(TimeServerError::InvalidTimeFormat { .. })
+        ));
+    }
+
+    fn sunrise_sunset(date: &str, latitude: f64, longitude: f64) -> SunriseSunsetResult {
+        let request: GetSunriseSunsetRequest = serde_json::from_value(json!({
+            "date": date,
+            "latitude": latitude,
+            "longitude": longitude,
+            "timezone": "America/New_York"
+        }))
+        .unwrap();
+        TimeServer::new().get_sunrise_sunset(&request).unwrap()
+    }
+
+    /// Minutes since local midnight of an ISO 8601 datetime
+    fn minute_of_day(datetime: Option<&str>) -> i32 {
+        let datetime = DateTime::parse_from_rfc3339(datetime.unwrap()).unwrap();
+        (datetime.hour() * 60 + datetime.minute()) as i32
+    }
+
+    #[test]
+    fn test_sunrise_sunset_new_york_solstices() {
+        let summer = sunrise_sunset("2024-06-20", 40.7128, -74.0060);
+        let winter = sunrise_sunset("2024-12-21", 40.7128, -74.0060);
+
+        let summer_sunrise = summer.sunrise.as_deref().unwrap();
+        let winter_sunrise = winter.sunrise.as_deref().unwrap();
+        assert!(
+            summer_sunrise.starts_with("2024-06-20T"),
+            "{summer_sunrise}"
+        );
+        assert!(summer_sunrise.ends_with("-04:00"));
+        assert!(
+            winter_sunrise.starts_with("2024-12-21T"),
+            "{winter_sunrise}"
+        );
+        assert!(winter_sunrise.ends_with("-05:00"));
+        assert!(
+            minute_of_day(summer.sunrise.as_deref()) < minute_of_day(winter.sunrise.as_deref())
+        );
+
+        // Published times are 05:25-20:31 in June and 07:17-16:32 in December
+        let close_to = |actual: i32, expected: i32| (actual - expected).abs() <= 2;
+        assert!(close_to(
+            minute_of_day(summer.sunrise.as_deref()),
+            5 * 60 + 25
+        ));
+        assert!(close_to(
+            minute_of_day(summer.sunset.as_deref()),
+            20 * 60 + 31
+        ));
+        assert!(close_to(
+            minute_of_day(winter.sunrise.as_deref()),
+            7 * 60 + 17
+        ));
+        assert!(close_to(
+            minute_of_day(winter.sunset.as_deref()),
+            16 * 60 + 32
+        ));
+        assert!(close_to(summer.day_length_minutes as i32, 15 * 60 + 6));
+        assert!(close_to(winter.day_length_minutes as i32, 9 * 60 + 15));
+
+        // Civil twilight brackets sunrise and sunset
+        assert!(
+            minute_of_day(summer.civil_twilight_start.as_deref())
+                < minute_of_day(summer.sunrise.as_deref())
+        );
+        assert!(
+            minute_of_day(summer.civil_twilight_end.as_deref())
+                > minute_of_day(summer.sunset.as_deref())
+        );
+    }
+
+    #[test]
+    fn test_sunrise_sunset_polar_day_and_night() {
+        // Longyearbyen, Svalbard
+        let summer = sunrise_sunset("2024-06-20", 78.22, 15.65);
+        assert_eq!(summer.sunrise, None);
+        assert_eq!(summer.day_length_minutes, 24 * 60);
+        assert_eq!(summer.civil_twilight_start, None);
+
+        let winter = sunrise_sunset("2024-12-21", 78.22, 15.65);
+        assert_eq!(winter.sunset, None);
+        assert_eq!(winter.day_length_minutes, 0);
+    }
+
+    fn schedule(expression: &str, timezone: &str, from: &str, count: usize) -> Vec<String> {
+        let request: ScheduleFromCronRequest = serde_json::from_value(json!({
+            "expression": expression,
+            "timezone": timezone,
+            "from_datetime": from,
+            "count": count
+        }))
+        .unwrap();
+        TimeServer::new().schedule_from_cron(&request).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_from_cron_weekdays_across_dst() {
+        // Friday 2024-03-08 after 09:00; clocks spring forward on Sunday the 10th
+        let runs = schedule("0 9 * * MON-FRI", "America/New_York", "2024-03-08T10:00", 2);
+        assert_eq!(
+            runs,
+            vec!["2024-03-11T09:00:00-04:00", "2024-03-12T09:00:00-04:00"]
+        );
+
+        // Crontab day numbers mean the same days as the names
+        let numeric = schedule("0 9 * * 1-5", "America/New_York", "2024-03-08T10:00", 2);
+        assert_eq!(numeric, runs);
+
+        // Before 09:00 on the Friday, that morning is the next run, still in EST
+        let runs = schedule("0 9 * * MON-FRI", "America/New_York", "2024-03-08T08:00", 1);
+        assert_eq!(runs, vec!["2024-03-08T09:00:00-05:00"]);
+    }
+
+    #[test]
+    fn test_schedule_from_cron_skips_times_in_dst_gap() {
+        let runs = schedule("30 2 * * *", "America/New_York", "2024-03-09T00:00", 2);
+        assert_eq!(
+            runs,
+            vec!["2024-03-09T02:30:00-05:00", "2024-03-11T02:30:00-04:00"]
+        );
+    }
+
+    #[test]
+    fn test_schedule_from_cron_with_seconds_field() {
+        let runs = schedule("30 0 12 * * SAT", "UTC", "2024-06-01T12:00:00Z", 2);
+        assert_eq!(
+            runs,
+            vec!["2024-06-01T12:00:30+00:00", "2024-06-08T12:00:30+00:00"]
+        );
+    }
+
+    #[test]
+    fn test_schedule_from_cron_rejects_bad_input() {
+        let server = TimeServer::new();
+        let request = |expression: &str, count: usize| -> ScheduleFromCronRequest {
+            serde_json::from_value(json!({
+                "expression": expression,
+                "timezone": "UTC",
+                "count": count
+            }))
+            .unwrap()
+        };
+
+        assert!(matches!(
+            server.schedule_from_cron(&request("not a cron", 5)),
+            Err(TimeServerError::InvalidCronExpression { .. })
+        ));
+        for count in [0, 101] {
+            assert!(matches!(
+                server.schedule_from_cron(&request("@daily", count)),
+                Err(TimeServerError::InvalidArgument { .. })
+            ));
+        }
+        assert_eq!(
+            server
+                .schedule_from_cron(&request("@hourly", 100))
+                .unwrap()
+                .len(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_sunrise_sunset_rejects_bad_coordinates() {
+        let server = TimeServer::new();
+        for (latitude, longitude) in [(91.0, 0.0), (0.0, -181.0)] {
+            let request: GetSunriseSunsetRequest = serde_json::from_value(json!({
+                "date": "2024-06-20",
+                "latitude": latitude,
+                "longitude": longitude,
+                "timezone": "UTC"
+            }))
+            .unwrap();
+            assert!(matches!(
+                server.get_sunrise_sunset(&request),
+                Err(TimeServerError::InvalidArgument { .. })
+            ));
+        }
+    }
+}
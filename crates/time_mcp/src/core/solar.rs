@@ -0,0 +1,89 @@
+//! Sunrise and sunset times from the NOAA sunrise equation
+//!
+//! Accurate to about a minute away from the poles, which is enough for scheduling.
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Solar altitude at sunrise and sunset, allowing for refraction and the sun's radius
+pub const SUNRISE_ALTITUDE: f64 = -0.833;
+
+/// Solar altitude at the start of morning and end of evening civil twilight
+pub const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+
+/// Julian date of 2000-01-01 12:00 UTC
+const J2000: f64 = 2_451_545.0;
+
+/// Julian date of the Unix epoch
+const UNIX_EPOCH_JULIAN_DATE: f64 = 2_440_587.5;
+
+/// Obliquity of the ecliptic in degrees
+const EARTH_AXIAL_TILT: f64 = 23.4397;
+
+/// How the sun's centre relates to an altitude over one day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AltitudeCrossing {
+    /// The sun rises above the altitude and later sets below it
+    Crossed {
+        rising: DateTime<Utc>,
+        setting: DateTime<Utc>,
+    },
+    /// The sun stays above the altitude all day, e.g. the midnight sun
+    AlwaysAbove,
+    /// The sun stays below the altitude all day, e.g. the polar night
+    AlwaysBelow,
+}
+
+/// Find when the sun crosses `altitude` degrees on `date` at the given position
+///
+/// `latitude` is positive north and `longitude` positive east, both in degrees.
+/// `date` is the calendar date at the observer's longitude.
+pub fn altitude_crossing(
+    date: NaiveDate,
+    latitude: f64,
+    longitude: f64,
+    altitude: f64,
+) -> AltitudeCrossing {
+    let j2000_date = NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid date");
+    let day = (date - j2000_date).num_days() as f64;
+
+    // Mean solar noon, then the solar anomaly and ecliptic longitude at that time
+    let mean_noon = day - longitude / 360.0;
+    let anomaly = (357.5291 + 0.985_600_28 * mean_noon).rem_euclid(360.0);
+    let center = 1.9148 * sin_deg(anomaly)
+        + 0.0200 * sin_deg(2.0 * anomaly)
+        + 0.0003 * sin_deg(3.0 * anomaly);
+    let ecliptic_longitude = (anomaly + center + 180.0 + 102.9372).rem_euclid(360.0);
+    let transit =
+        J2000 + mean_noon + 0.0053 * sin_deg(anomaly) - 0.0069 * sin_deg(2.0 * ecliptic_longitude);
+
+    let declination = (sin_deg(ecliptic_longitude) * sin_deg(EARTH_AXIAL_TILT)).asin();
+    let latitude = latitude.to_radians();
+    let cos_hour_angle = (sin_deg(altitude) - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+
+    if cos_hour_angle < -1.0 {
+        return AltitudeCrossing::AlwaysAbove;
+    }
+    if cos_hour_angle > 1.0 {
+        return AltitudeCrossing::AlwaysBelow;
+    }
+
+    let half_day = cos_hour_angle.acos().to_degrees() / 360.0;
+    match (
+        from_julian_date(transit - half_day),
+        from_julian_date(transit + half_day),
+    ) {
+        (Some(rising), Some(setting)) => AltitudeCrossing::Crossed { rising, setting },
+        _ => AltitudeCrossing::AlwaysBelow,
+    }
+}
+
+fn sin_deg(degrees: f64) -> f64 {
+    degrees.to_radians().sin()
+}
+
+/// Convert a Julian date to UTC, rounded to the nearest second
+fn from_julian_date(julian_date: f64) -> Option<DateTime<Utc>> {
+    let unix_seconds = ((julian_date - UNIX_EPOCH_JULIAN_DATE) * 86_400.0).round();
+    DateTime::from_timestamp(unix_seconds as i64, 0)
+}
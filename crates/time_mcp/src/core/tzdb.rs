@@ -0,0 +1,301 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{Datelike, FixedOffset, NaiveDate, NaiveDateTime, Offset, TimeZone};
+use tzfile::{ArcTz, Tz};
+
+use crate::core::error::{TimeServerError, TimeServerResult};
+
+/// First bytes of every TZif file
+const TZIF_MAGIC: &[u8] = b"TZif";
+
+/// A timezone read from a TZif file, cheap to clone
+///
+/// Parsing is done by [`tzfile`], which applies the transitions stored in the
+/// file but not the POSIX rule in its footer, so the last transition stays in
+/// effect after it. Files built with `zic -b fat`, as Debian and Ubuntu ship
+/// them, list transitions up to 2037.
+#[derive(Debug, Clone)]
+pub struct LoadedZone {
+    name: Arc<str>,
+    tz: ArcTz,
+}
+
+impl LoadedZone {
+    /// IANA name of the zone, its path relative to the database directory
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The parsed zone, which implements [`TimeZone`]
+    pub fn tz(&self) -> &ArcTz {
+        &self.tz
+    }
+
+    /// Whether both handles point at the same loaded zone
+    pub fn same_zone(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.tz.0, &other.tz.0)
+    }
+
+    /// Daylight saving part of `offset`, in effect at `utc`
+    ///
+    /// TZif files flag daylight saving periods but `tzfile` doesn't expose the
+    /// flag, so the standard offset is taken to be the lower of the offsets on
+    /// 1 January and 1 July of that year.
+    pub fn dst_seconds(&self, utc: &NaiveDateTime, offset: FixedOffset) -> i32 {
+        let offset_on = |month| {
+            NaiveDate::from_ymd_opt(utc.year(), month, 1)
+                .map(|date| self.tz.offset_from_utc_date(&date).fix().local_minus_utc())
+                .unwrap_or(offset.local_minus_utc())
+        };
+        let standard = offset_on(1).min(offset_on(7));
+        (offset.local_minus_utc() - standard).max(0)
+    }
+}
+
+/// Timezones loaded from a directory of TZif files, keyed by IANA name
+#[derive(Debug, Default)]
+pub struct TzDatabase {
+    zones: HashMap<String, LoadedZone>,
+}
+
+impl TzDatabase {
+    /// Load every TZif file below `dir`, e.g. `/usr/share/zoneinfo`
+    ///
+    /// Zone names are the file paths relative to `dir`, such as `Europe/Paris`.
+    /// Other files (`zone.tab`, `tzdata.zi`, ...) are ignored, and TZif files
+    /// `tzfile` can't parse, including version 1 files, are skipped with a warning.
+    pub fn load(dir: &Path) -> TimeServerResult<Self> {
+        let error = |message: String| TimeServerError::TimezoneDatabase {
+            path: dir.display().to_string(),
+            message,
+        };
+        if !dir.is_dir() {
+            return Err(error("not a directory".to_string()));
+        }
+
+        let mut files = Vec::new();
+        collect_files(dir, &mut files).map_err(|e| error(e.to_string()))?;
+
+        let mut zones = HashMap::new();
+        for path in files {
+            let Some(name) = zone_name(dir, &path) else {
+                continue;
+            };
+            let Ok(data) = fs::read(&path) else {
+                continue;
+            };
+            if !data.starts_with(TZIF_MAGIC) {
+                continue;
+            }
+            match Tz::parse(&name, &data) {
+                Ok(tz) => {
+                    let zone = LoadedZone {
+                        name: name.as_str().into(),
+                        tz: ArcTz::new(tz),
+                    };
+                    zones.insert(name, zone);
+                }
+                Err(e) => tracing::warn!("Skipping timezone file {}: {}", path.display(), e),
+            }
+        }
+
+        if zones.is_empty() {
+            return Err(error("no TZif files found".to_string()));
+        }
+        Ok(Self { zones })
+    }
+
+    /// The zone called `name`, if the database has it
+    pub fn get(&self, name: &str) -> Option<&LoadedZone> {
+        self.zones.get(name)
+    }
+
+    /// Names of all loaded zones, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.zones.keys().map(String::as_str)
+    }
+
+    /// Number of loaded zones
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+}
+
+/// Collect the regular files below `dir`, following symlinked files but not directories
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Zone name of `path`: its components below `dir` joined with `/`
+fn zone_name(dir: &Path, path: &Path) -> Option<String> {
+    let components = path
+        .strip_prefix(dir)
+        .ok()?
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(components.join("/"))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use chrono::{LocalResult, Utc};
+
+    use super::*;
+
+    /// Build a version 2 TZif file with an empty 32-bit block and an empty footer
+    ///
+    /// `types` are `(utc_offset, is_dst, abbreviation)`; `transitions` pair UTC
+    /// seconds with an index into `types`.
+    pub(crate) fn tzif(types: &[(i32, bool, &str)], transitions: &[(i64, u8)]) -> Vec<u8> {
+        let header = |timecnt: usize, typecnt: usize, charcnt: usize| {
+            let mut header = b"TZif2".to_vec();
+            header.extend([0; 15]);
+            for count in [0, 0, 0, timecnt, typecnt, charcnt] {
+                header.extend((count as u32).to_be_bytes());
+            }
+            header
+        };
+
+        let mut chars = Vec::new();
+        let mut infos = Vec::new();
+        for &(offset, is_dst, abbreviation) in types {
+            infos.extend(offset.to_be_bytes());
+            infos.push(u8::from(is_dst));
+            infos.push(chars.len() as u8);
+            chars.extend(abbreviation.as_bytes());
+            chars.push(0);
+        }
+
+        let mut data = header(0, 1, 1);
+        data.extend([0, 0, 0, 0, 0, 0, 0]);
+        data.extend(header(transitions.len(), types.len(), chars.len()));
+        for (at, _) in transitions {
+            data.extend(at.to_be_bytes());
+        }
+        data.extend(transitions.iter().map(|&(_, index)| index));
+        data.extend(infos);
+        data.extend(chars);
+        data.extend(b"\n\n");
+        data
+    }
+
+    /// Central European time with the 2024 switches to and from summer time
+    pub(crate) fn cet_2024() -> Vec<u8> {
+        tzif(
+            &[(3600, false, "CET"), (7200, true, "CEST")],
+            &[
+                (utc("2023-10-29T01:00"), 0),
+                (utc("2024-03-31T01:00"), 1),
+                (utc("2024-10-27T01:00"), 0),
+            ],
+        )
+    }
+
+    fn utc(datetime: &str) -> i64 {
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M")
+            .unwrap()
+            .and_utc()
+            .timestamp()
+    }
+
+    fn load(name: &str, data: Vec<u8>) -> LoadedZone {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Test")).unwrap();
+        fs::write(dir.path().join(name), data).unwrap();
+        TzDatabase::load(dir.path())
+            .unwrap()
+            .get(name)
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn test_transitions_and_dst_offset() {
+        let zone = load("Test/Seasonal", cet_2024());
+        let at = |datetime: &str| {
+            let utc = Utc.timestamp_opt(utc(datetime), 0).unwrap().naive_utc();
+            let offset = zone.tz().offset_from_utc_datetime(&utc);
+            (
+                offset.to_string(),
+                zone.dst_seconds(&utc, offset.fix()),
+                offset.fix().local_minus_utc(),
+            )
+        };
+
+        assert_eq!(at("2024-03-31T00:59"), ("CET".to_string(), 0, 3600));
+        assert_eq!(at("2024-03-31T01:00"), ("CEST".to_string(), 3600, 7200));
+        assert_eq!(at("2024-10-27T00:59"), ("CEST".to_string(), 3600, 7200));
+        assert_eq!(at("2024-10-27T01:00"), ("CET".to_string(), 0, 3600));
+    }
+
+    #[test]
+    fn test_local_times_report_gaps_and_overlaps() {
+        let zone = load("Test/Seasonal", cet_2024());
+        let local =
+            |datetime: &str| NaiveDateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M").unwrap();
+
+        assert!(matches!(
+            zone.tz()
+                .offset_from_local_datetime(&local("2024-03-31T02:30")),
+            LocalResult::None
+        ));
+        let LocalResult::Ambiguous(earliest, latest) = zone
+            .tz()
+            .offset_from_local_datetime(&local("2024-10-27T02:30"))
+        else {
+            panic!("Expected an ambiguous local time");
+        };
+        assert_eq!(
+            (earliest.to_string(), latest.to_string()),
+            ("CEST".to_string(), "CET".to_string())
+        );
+    }
+
+    #[test]
+    fn test_load_directory_of_tzif_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Test")).unwrap();
+        fs::write(
+            dir.path().join("Test/Fictional"),
+            tzif(&[(19_800, false, "+0530")], &[]),
+        )
+        .unwrap();
+        fs::write(dir.path().join("zone.tab"), "# not a TZif file\n").unwrap();
+        fs::write(dir.path().join("Broken"), b"TZif2 but truncated").unwrap();
+
+        let database = TzDatabase::load(dir.path()).unwrap();
+        assert_eq!(database.len(), 1);
+        assert_eq!(database.names().collect::<Vec<_>>(), vec!["Test/Fictional"]);
+        let zone = database.get("Test/Fictional").unwrap();
+        assert_eq!(zone.name(), "Test/Fictional");
+        assert_eq!(
+            zone.tz()
+                .offset_from_utc_datetime(&chrono::DateTime::UNIX_EPOCH.naive_utc())
+                .fix()
+                .local_minus_utc(),
+            19_800
+        );
+        assert!(zone.same_zone(&database.get("Test/Fictional").unwrap().clone()));
+
+        let empty = tempfile::TempDir::new().unwrap();
+        assert!(matches!(
+            TzDatabase::load(empty.path()),
+            Err(TimeServerError::TimezoneDatabase { .. })
+        ));
+        assert!(TzDatabase::load(&dir.path().join("missing")).is_err());
+    }
+}
@@ -1,13 +1,29 @@
 use chrono::DateTime;
-use chrono_tz::{OffsetComponents, Tz};
+use chrono_tz::OffsetComponents;
+
+use crate::core::zone::Zone;
 
 // Constants for format strings and error codes
 pub const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%:z";
 pub const TIME_INPUT_FORMAT: &str = "%H:%M";
 pub const DAY_FORMAT: &str = "%A";
 
+/// Naive datetime formats accepted in addition to RFC 3339, interpreted in the request timezone
+pub const DATETIME_INPUT_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+pub const DATE_INPUT_FORMAT: &str = "%Y-%m-%d";
+
 /// Available resource URIs for the Time MCP Server
-pub const AVAILABLE_RESOURCES: &[&str] = &["time://status", "time://help", "time://timezones"];
+pub const AVAILABLE_RESOURCES: &[&str] = &[
+    "time://status",
+    "time://help",
+    "time://timezones",
+    "time://capabilities",
+];
 
 /// Format a time difference in hours
 ///
@@ -39,7 +55,10 @@ pub fn format_time_difference(hours_difference: f64) -> String {
 /// # Returns
 ///
 /// A formatted string representing the time difference
-pub fn calculate_time_difference(source_time: &DateTime<Tz>, target_time: &DateTime<Tz>) -> String {
+pub fn calculate_time_difference(
+    source_time: &DateTime<Zone>,
+    target_time: &DateTime<Zone>,
+) -> String {
     let source_offset = source_time.offset().base_utc_offset() + source_time.offset().dst_offset();
     let target_offset = target_time.offset().base_utc_offset() + target_time.offset().dst_offset();
     let hours_difference = (target_offset - source_offset).num_seconds() as f64 / 3600.0;
@@ -47,9 +66,229 @@ pub fn calculate_time_difference(source_time: &DateTime<Tz>, target_time: &DateT
     format_time_difference(hours_difference)
 }
 
+/// Describe a signed number of seconds as days, hours, minutes and seconds
+///
+/// Zero-valued units are omitted, e.g. `"1 day, 2 hours, 30 minutes"`.
+/// Negative durations are prefixed with `"minus "`.
+pub fn format_duration_human(total_seconds: i64) -> String {
+    let magnitude = total_seconds.unsigned_abs();
+    let units = [
+        (magnitude / 86_400, "day"),
+        (magnitude % 86_400 / 3_600, "hour"),
+        (magnitude % 3_600 / 60, "minute"),
+        (magnitude % 60, "second"),
+    ];
+
+    let parts: Vec<String> = units
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| pluralize(*value, unit))
+        .collect();
+
+    match (parts.is_empty(), total_seconds < 0) {
+        (true, _) => "0 seconds".to_string(),
+        (false, true) => format!("minus {}", parts.join(", ")),
+        (false, false) => parts.join(", "),
+    }
+}
+
+/// Describe a signed offset from now, e.g. `"3 hours ago"` or `"in 2 days"`
+///
+/// Offsets under a minute either way are `"just now"`; months and years are
+/// approximated as 30 and 365 days.
+pub fn format_relative_delta(delta_seconds: i64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const WEEK: u64 = 7 * DAY;
+    const MONTH: u64 = 30 * DAY;
+    const YEAR: u64 = 365 * DAY;
+
+    let magnitude = delta_seconds.unsigned_abs();
+    let (value, unit) = match magnitude {
+        m if m < MINUTE => return "just now".to_string(),
+        m if m < HOUR => (m / MINUTE, "minute"),
+        m if m < DAY => (m / HOUR, "hour"),
+        m if m < WEEK => (m / DAY, "day"),
+        m if m < MONTH => (m / WEEK, "week"),
+        m if m < YEAR => (m / MONTH, "month"),
+        m => (m / YEAR, "year"),
+    };
+
+    let amount = pluralize(value, unit);
+    if delta_seconds < 0 {
+        format!("{} ago", amount)
+    } else {
+        format!("in {}", amount)
+    }
+}
+
+/// Parse a human-readable or ISO 8601 duration into seconds
+///
+/// Accepts unit sequences such as `"90s"`, `"2h30m"`, `"1d 12h"` or
+/// `"2 hours and 30 minutes"` (units: w, d, h, m, s and their spelled-out
+/// forms), and ISO 8601 durations limited to weeks, days, hours, minutes and
+/// seconds such as `"P2DT3H"`. Returns `None` for malformed input or overflow.
+pub fn parse_duration_string(input: &str) -> Option<u64> {
+    let input = input.trim();
+    match input.strip_prefix(['P', 'p']) {
+        Some(iso) => parse_iso8601_duration(iso),
+        None => parse_unit_duration(input),
+    }
+}
+
+/// Parse the part of an ISO 8601 duration after the leading `P`
+fn parse_iso8601_duration(input: &str) -> Option<u64> {
+    let (date_part, time_part) = match input.split_once(['T', 't']) {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None => (input, None),
+    };
+
+    let date_seconds = parse_designators(date_part, &[('W', 604_800), ('D', 86_400)])?;
+    let time_seconds = match time_part {
+        Some(time) => parse_designators(time, &[('H', 3_600), ('M', 60), ('S', 1)])?,
+        None if date_part.is_empty() => return None,
+        None => 0,
+    };
+
+    date_seconds.checked_add(time_seconds)
+}
+
+/// Sum `<number><designator>` pairs that appear in the order given by `designators`
+fn parse_designators(input: &str, designators: &[(char, u64)]) -> Option<u64> {
+    let mut total: u64 = 0;
+    let mut remaining = designators;
+    let mut digits = String::new();
+
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let position = remaining
+            .iter()
+            .position(|(designator, _)| designator.eq_ignore_ascii_case(&c))?;
+        let value: u64 = digits.parse().ok()?;
+        total = total.checked_add(value.checked_mul(remaining[position].1)?)?;
+        remaining = &remaining[position + 1..];
+        digits.clear();
+    }
+
+    digits.is_empty().then_some(total)
+}
+
+/// Parse a sequence of `<number><unit>` tokens, optionally separated by spaces, commas or "and"
+fn parse_unit_duration(input: &str) -> Option<u64> {
+    let normalized = input
+        .to_lowercase()
+        .replace(',', " ")
+        .split_whitespace()
+        .filter(|word| *word != "and")
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut chars = normalized.chars().peekable();
+
+    let mut total: u64 = 0;
+    let mut parsed_any = false;
+
+    while chars.peek().is_some() {
+        let digits: String = std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)).collect();
+        while chars.next_if_eq(&' ').is_some() {}
+        let unit: String =
+            std::iter::from_fn(|| chars.next_if(char::is_ascii_alphabetic)).collect();
+        while chars.next_if_eq(&' ').is_some() {}
+
+        let value: u64 = digits.parse().ok()?;
+        let multiplier = match unit.as_str() {
+            "w" | "wk" | "wks" | "week" | "weeks" => 604_800,
+            "d" | "day" | "days" => 86_400,
+            "h" | "hr" | "hrs" | "hour" | "hours" => 3_600,
+            "m" | "min" | "mins" | "minute" | "minutes" => 60,
+            "s" | "sec" | "secs" | "second" | "seconds" => 1,
+            _ => return None,
+        };
+        total = total.checked_add(value.checked_mul(multiplier)?)?;
+        parsed_any = true;
+    }
+
+    parsed_any.then_some(total)
+}
+
+/// Format a count with a singular or plural unit, e.g. `"1 day"` or `"3 days"`
+pub fn pluralize(value: u64, unit: &str) -> String {
+    if value == 1 {
+        format!("{} {}", value, unit)
+    } else {
+        format!("{} {}s", value, unit)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::format_time_difference;
+    use super::{format_duration_human, format_time_difference, parse_duration_string};
+
+    #[test]
+    fn test_parse_compact_durations() {
+        assert_eq!(parse_duration_string("90s"), Some(90));
+        assert_eq!(parse_duration_string("2h30m"), Some(9_000));
+        assert_eq!(parse_duration_string("1d12h"), Some(129_600));
+        assert_eq!(parse_duration_string("1w 2d"), Some(777_600));
+        assert_eq!(parse_duration_string(" 45M "), Some(2_700));
+    }
+
+    #[test]
+    fn test_parse_verbose_durations() {
+        assert_eq!(parse_duration_string("2 hours 30 minutes"), Some(9_000));
+        assert_eq!(
+            parse_duration_string("1 day, 2 hours and 5 secs"),
+            Some(93_605)
+        );
+        assert_eq!(parse_duration_string("1 minute"), Some(60));
+    }
+
+    #[test]
+    fn test_parse_iso8601_durations() {
+        assert_eq!(parse_duration_string("P2DT3H"), Some(183_600));
+        assert_eq!(parse_duration_string("PT1H30M15S"), Some(5_415));
+        assert_eq!(parse_duration_string("P1W"), Some(604_800));
+        assert_eq!(parse_duration_string("pt90s"), Some(90));
+    }
+
+    #[test]
+    fn test_parse_malformed_durations() {
+        for input in [
+            "",
+            "90",
+            "h",
+            "2 fortnights",
+            "2h30",
+            "P",
+            "PT",
+            "P1Y",
+            "P1M",
+            "PT3M2H",
+            "P2D3H",
+            "-5m",
+            "1.5h",
+            "99999999999999999999s",
+        ] {
+            assert_eq!(parse_duration_string(input), None, "input: {input:?}");
+        }
+        assert_eq!(parse_duration_string(&format!("{}w", u64::MAX / 2)), None);
+    }
+
+    #[test]
+    fn test_format_duration_human() {
+        assert_eq!(format_duration_human(0), "0 seconds");
+        assert_eq!(format_duration_human(1), "1 second");
+        assert_eq!(format_duration_human(86_400), "1 day");
+        assert_eq!(format_duration_human(95_400), "1 day, 2 hours, 30 minutes");
+        assert_eq!(
+            format_duration_human(-3_661),
+            "minus 1 hour, 1 minute, 1 second"
+        );
+    }
 
     #[test]
     fn test_format_time_difference() {
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use chrono::{
+    Duration, FixedOffset, MappedLocalTime, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+};
+use chrono_tz::{OffsetComponents, Tz, TzOffset};
+use tzfile::ArcTz;
+
+use crate::core::tzdb::LoadedZone;
+
+/// A timezone from the compiled-in IANA database or from a [`TzDatabase`]
+///
+/// [`TzDatabase`]: crate::core::tzdb::TzDatabase
+#[derive(Debug, Clone)]
+pub enum Zone {
+    Compiled(Tz),
+    Loaded(LoadedZone),
+}
+
+impl Zone {
+    /// IANA name of the zone
+    pub fn name(&self) -> &str {
+        match self {
+            Zone::Compiled(tz) => tz.name(),
+            Zone::Loaded(zone) => zone.name(),
+        }
+    }
+}
+
+impl From<Tz> for Zone {
+    fn from(tz: Tz) -> Self {
+        Zone::Compiled(tz)
+    }
+}
+
+impl PartialEq for Zone {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Zone::Compiled(a), Zone::Compiled(b)) => a == b,
+            (Zone::Loaded(a), Zone::Loaded(b)) => a.same_zone(b),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Offset of a [`Zone`] at some instant
+#[derive(Clone)]
+pub enum ZoneOffset {
+    Compiled(TzOffset),
+    Loaded {
+        zone: LoadedZone,
+        offset: tzfile::Offset<ArcTz>,
+        /// Daylight saving part of the offset, in seconds
+        dst_offset: i32,
+    },
+}
+
+impl ZoneOffset {
+    fn loaded(zone: &LoadedZone, offset: tzfile::Offset<ArcTz>, utc: &NaiveDateTime) -> Self {
+        ZoneOffset::Loaded {
+            dst_offset: zone.dst_seconds(utc, offset.fix()),
+            zone: zone.clone(),
+            offset,
+        }
+    }
+}
+
+impl Offset for ZoneOffset {
+    fn fix(&self) -> FixedOffset {
+        match self {
+            ZoneOffset::Compiled(offset) => offset.fix(),
+            ZoneOffset::Loaded { offset, .. } => offset.fix(),
+        }
+    }
+}
+
+impl OffsetComponents for ZoneOffset {
+    fn base_utc_offset(&self) -> Duration {
+        match self {
+            ZoneOffset::Compiled(offset) => offset.base_utc_offset(),
+            ZoneOffset::Loaded {
+                offset, dst_offset, ..
+            } => Duration::seconds(i64::from(offset.fix().local_minus_utc() - dst_offset)),
+        }
+    }
+
+    fn dst_offset(&self) -> Duration {
+        match self {
+            ZoneOffset::Compiled(offset) => offset.dst_offset(),
+            ZoneOffset::Loaded { dst_offset, .. } => Duration::seconds(i64::from(*dst_offset)),
+        }
+    }
+}
+
+/// The abbreviation, e.g. `CEST`
+impl fmt::Display for ZoneOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoneOffset::Compiled(offset) => fmt::Display::fmt(offset, f),
+            ZoneOffset::Loaded { offset, .. } => fmt::Display::fmt(offset, f),
+        }
+    }
+}
+
+impl fmt::Debug for ZoneOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoneOffset::Compiled(offset) => fmt::Debug::fmt(offset, f),
+            ZoneOffset::Loaded { offset, .. } => fmt::Debug::fmt(offset, f),
+        }
+    }
+}
+
+impl TimeZone for Zone {
+    type Offset = ZoneOffset;
+
+    fn from_offset(offset: &ZoneOffset) -> Self {
+        match offset {
+            ZoneOffset::Compiled(offset) => Zone::Compiled(Tz::from_offset(offset)),
+            ZoneOffset::Loaded { zone, .. } => Zone::Loaded(zone.clone()),
+        }
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> MappedLocalTime<ZoneOffset> {
+        self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> MappedLocalTime<ZoneOffset> {
+        match self {
+            Zone::Compiled(tz) => tz
+                .offset_from_local_datetime(local)
+                .map(ZoneOffset::Compiled),
+            Zone::Loaded(zone) => zone.tz().offset_from_local_datetime(local).map(|offset| {
+                let utc = *local - Duration::seconds(i64::from(offset.fix().local_minus_utc()));
+                ZoneOffset::loaded(zone, offset, &utc)
+            }),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> ZoneOffset {
+        self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> ZoneOffset {
+        match self {
+            Zone::Compiled(tz) => ZoneOffset::Compiled(tz.offset_from_utc_datetime(utc)),
+            Zone::Loaded(zone) => {
+                ZoneOffset::loaded(zone, zone.tz().offset_from_utc_datetime(utc), utc)
+            }
+        }
+    }
+}
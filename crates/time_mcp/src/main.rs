@@ -1,9 +1,51 @@
-use std::env;
+use std::{env, net::IpAddr, path::PathBuf};
+
+use clap::{Parser, ValueEnum};
 use tracing_subscriber::{self, EnvFilter};
 
+use crate::server::Transport;
+
 mod core;
 mod server;
 
+/// Transport selectable on the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TransportKind {
+    Stdio,
+    Sse,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "mcp-server-time")]
+#[command(about = "MCP Time Server for timezone-aware time operations")]
+struct Args {
+    /// Transport to serve the MCP protocol over
+    #[arg(long, value_enum, default_value_t = TransportKind::Stdio)]
+    transport: TransportKind,
+
+    /// Address to bind when using the SSE transport
+    #[arg(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+
+    /// Port to bind when using the SSE transport
+    #[arg(long, default_value_t = 8000)]
+    port: u16,
+
+    /// Directory of TZif files (e.g. /usr/share/zoneinfo) whose timezones take
+    /// precedence over the compiled-in database
+    #[arg(long, value_name = "DIR")]
+    timezone_db_path: Option<PathBuf>,
+}
+
+impl Args {
+    fn transport(&self) -> Transport {
+        match self.transport {
+            TransportKind::Stdio => Transport::Stdio,
+            TransportKind::Sse => Transport::Sse((self.host, self.port).into()),
+        }
+    }
+}
+
 /// Time MCP Server
 ///
 /// A comprehensive example MCP server demonstrating:
@@ -13,6 +55,8 @@ mod server;
 /// Usage: npx @modelcontextprotocol/inspector cargo run --bin mcp_server_time
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
     // Initialize logging only if LOG_LEVEL environment variable is set
     if let Ok(log_level) = env::var("LOG_LEVEL") {
         // Initialize the tracing subscriber with file and stdout logging
@@ -27,7 +71,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("Starting Time MCP server with log level: {}", log_level);
     }
 
-    if let Err(e) = server::run().await {
+    if let Err(e) = server::run(args.transport(), args.timezone_db_path.as_deref()).await {
         // Only log error if logging is initialized
         if env::var("LOG_LEVEL").is_ok() {
             tracing::error!("Error running Time MCP server: {}", e);
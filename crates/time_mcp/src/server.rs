@@ -13,9 +13,34 @@ use rmcp::{
 use crate::core::provider::TimeServer;
 use crate::core::{
     error::McpResult,
-    models::{ConvertTimeRequest, GetCurrentTimeRequest},
+    models::{
+        CalculateDurationRequest, ConvertTimeRequest, DateArithmeticRequest, FormatDatetimeRequest,
+        FormatRelativeTimeRequest, FromUnixTimestampRequest, GetBusinessDaysRequest,
+        GetCalendarWeekRequest, GetCurrentTimeRequest, GetDstTransitionsRequest,
+        GetMeetingTimeRequest, GetSunriseSunsetRequest, GetUnixTimestampRequest,
+        ListTimezonesRequest, ParseDurationRequest, ScheduleFromCronRequest,
+    },
+    utils::AVAILABLE_RESOURCES,
 };
+use mcp_common::Capabilities;
+use rmcp::transport::SseServer;
 use serde::{Deserialize, Serialize};
+use std::{net::SocketAddr, path::Path};
+use tokio_util::sync::CancellationToken;
+
+/// Maximum number of suggestions returned for argument completion
+const COMPLETION_SUGGESTION_LIMIT: usize = 10;
+
+/// A candidate accepted by fuzzy matching, with how well it matched
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FuzzyMatch {
+    value: String,
+    /// Higher is better; an exact match scores 1000
+    score: u32,
+}
+
+/// Maximum number of timezones returned by a filtered `list_timezones` call
+const LIST_TIMEZONES_FILTER_LIMIT: usize = 50;
 
 /// Arguments for timezone conversion prompt with completion support
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -40,7 +65,11 @@ pub struct TimeService {
 
 impl TimeService {
     pub fn new() -> Self {
-        let time_server = TimeServer::new();
+        Self::with_time_server(TimeServer::new())
+    }
+
+    /// Serve tools backed by an already configured `TimeServer`
+    pub fn with_time_server(time_server: TimeServer) -> Self {
         let local_timezone_name = time_server.local_timezone.to_string();
 
         Self {
@@ -51,44 +80,60 @@ impl TimeService {
         }
     }
 
-    /// Fuzzy matching with scoring for completion suggestions
-    fn fuzzy_match(&self, query: &str, candidates: &[String]) -> Vec<String> {
+    /// Fuzzy matching with scoring, returning at most `limit` matches best first
+    ///
+    /// An empty query matches every candidate with a score of 0, in the given order.
+    fn fuzzy_match(&self, query: &str, candidates: &[String], limit: usize) -> Vec<FuzzyMatch> {
         if query.is_empty() {
-            return candidates.iter().take(10).map(|s| s.to_string()).collect();
+            return candidates
+                .iter()
+                .take(limit)
+                .map(|s| FuzzyMatch {
+                    value: s.to_string(),
+                    score: 0,
+                })
+                .collect();
         }
 
         let query_lower = query.to_lowercase();
-        let mut scored_matches = Vec::new();
-
-        for candidate in candidates {
-            let candidate_lower = candidate.to_lowercase();
-
-            let score = if candidate_lower == query_lower {
-                1000 // Exact match
-            } else if candidate_lower.starts_with(&query_lower) {
-                900 // Prefix match  
-            } else if candidate_lower.contains(&query_lower) {
-                800 // Contains substring
-            } else if self.is_acronym_match(&query_lower, candidate) {
-                700 // Acronym match (e.g., "ny" → "America/New_York")
-            } else if self.is_subsequence_match(&query_lower, &candidate_lower) {
-                680 // Subsequence match (e.g., "utc" → "UTC")
-            } else if self.is_single_letter_match(&query_lower, candidate) {
-                650 // Single letter match (e.g., "u" → "UTC")
-            } else {
-                continue; // No match
-            };
-
-            scored_matches.push((candidate.to_string(), score));
-        }
+        let mut scored_matches: Vec<FuzzyMatch> = candidates
+            .iter()
+            .filter_map(|candidate| {
+                self.fuzzy_score(&query_lower, candidate)
+                    .map(|score| FuzzyMatch {
+                        value: candidate.to_string(),
+                        score,
+                    })
+            })
+            .collect();
 
         // Sort by score (desc) then alphabetically
-        scored_matches.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored_matches.sort_by(|a, b| b.score.cmp(&a.score).then(a.value.cmp(&b.value)));
+        scored_matches.truncate(limit);
         scored_matches
-            .into_iter()
-            .take(10)
-            .map(|(name, _)| name)
-            .collect()
+    }
+
+    /// Score how well a lowercased query matches a candidate, or `None` if it does not
+    fn fuzzy_score(&self, query_lower: &str, candidate: &str) -> Option<u32> {
+        let candidate_lower = candidate.to_lowercase();
+
+        let score = if candidate_lower == query_lower {
+            1000 // Exact match
+        } else if candidate_lower.starts_with(query_lower) {
+            900 // Prefix match
+        } else if candidate_lower.contains(query_lower) {
+            800 // Contains substring
+        } else if self.is_acronym_match(query_lower, candidate) {
+            700 // Acronym match (e.g., "ny" → "America/New_York")
+        } else if self.is_subsequence_match(query_lower, &candidate_lower) {
+            680 // Subsequence match (e.g., "utc" → "UTC")
+        } else if self.is_single_letter_match(query_lower, candidate) {
+            650 // Single letter match (e.g., "u" → "UTC")
+        } else {
+            return None;
+        };
+
+        Some(score)
     }
 
     /// Check if query matches as acronym (first letters of words or camelCase)
@@ -178,7 +223,7 @@ impl TimeService {
         query_char == first_char
     }
 
-    /// Get timezone names from chrono-tz for completion
+    /// Get timezone names from chrono-tz and any loaded timezone database for completion
     fn get_timezone_candidates(&self) -> Vec<String> {
         use chrono_tz::TZ_VARIANTS;
 
@@ -186,15 +231,63 @@ impl TimeService {
         // We'll prioritize common ones and limit the total for performance
         let mut timezones: Vec<String> =
             TZ_VARIANTS.iter().map(|tz| tz.name().to_string()).collect();
+        if let Some(tzdb) = &self.time_server.tzdb {
+            timezones.extend(tzdb.names().map(str::to_string));
+        }
 
         // Sort alphabetically for consistent ordering
         timezones.sort();
+        timezones.dedup();
 
         // For completion performance, we can limit to a reasonable number
         // or implement smarter filtering based on popularity
         timezones
     }
 
+    /// Completion candidates for an argument name shared by the prompts and tools
+    ///
+    /// MCP completion references only prompts and resources (rmcp has no
+    /// `Reference::Tool`), so tool arguments cannot be completed directly; the
+    /// argument names used by the tools are recognised here so prompts that
+    /// reuse them get the same suggestions.
+    fn get_argument_candidates(&self, argument_name: &str) -> Vec<String> {
+        match argument_name {
+            "timezone" | "source_timezone" | "target_timezone" | "start_timezone"
+            | "end_timezone" | "reference_timezone" => self.get_timezone_candidates(),
+            "time" => self.get_time_format_candidates(),
+            _ => vec![],
+        }
+    }
+
+    /// Timezone names for the `list_timezones` tool, narrowed by region prefix and fuzzy filter
+    fn list_timezone_names(&self, req: &ListTimezonesRequest) -> Vec<String> {
+        let mut timezones = self.get_timezone_candidates();
+
+        if let Some(region) = req
+            .region
+            .as_deref()
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+        {
+            let prefix = format!("{}/", region.trim_end_matches('/').to_lowercase());
+            timezones.retain(|name| name.to_lowercase().starts_with(&prefix));
+        }
+
+        match req
+            .filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+        {
+            Some(filter) => self
+                .fuzzy_match(filter, &timezones, LIST_TIMEZONES_FILTER_LIMIT)
+                .into_iter()
+                .map(|m| m.value)
+                .collect(),
+            None => timezones,
+        }
+    }
+
     /// Get time format suggestions dynamically generated
     fn get_time_format_candidates(&self) -> Vec<String> {
         let mut times = Vec::new();
@@ -224,6 +317,16 @@ impl TimeService {
         RawResource::new(uri, name.to_string()).no_annotation()
     }
 
+    /// The static resources, as listed by `list_resources`
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            self.create_resource_text("time://status", "server-status"),
+            self.create_resource_text("time://help", "help-documentation"),
+            self.create_resource_text("time://timezones", "timezone-list"),
+            self.create_resource_text("time://capabilities", "server-capabilities"),
+        ]
+    }
+
     pub(crate) fn get_local_timezone_name(&self) -> &str {
         &self.local_timezone_name
     }
@@ -241,9 +344,11 @@ Local Timezone: {}
 Current Local Time: {}
 Day of Week: {}
 DST Active: {}
-Tools Available: 2
-Prompts Available: 1
-Resources Available: 3
+Cached Timezones: {}
+Loaded Timezones: {}
+Tools Available: {}
+Prompts Available: {}
+Resources Available: {}
 
 Capabilities:
 - Current time queries for any IANA timezone
@@ -253,7 +358,12 @@ Capabilities:
             current_time.timezone,
             current_time.datetime,
             current_time.day_of_week,
-            current_time.is_dst
+            current_time.is_dst,
+            self.time_server.timezone_cache.len(),
+            self.time_server.tzdb.as_ref().map_or(0, |tzdb| tzdb.len()),
+            self.tool_router.list_all().len(),
+            self.prompt_router.list_all().len(),
+            AVAILABLE_RESOURCES.len()
         ))
     }
 
@@ -268,9 +378,89 @@ TOOLS:
 
 - convert_time: Convert time between timezones
   - source_timezone: Source IANA timezone name (required)
-  - time: Time in 24-hour format HH:MM (required)
+  - time: 24-hour HH:MM for today, or a full YYYY-MM-DDTHH:MM[:SS] datetime (required)
   - target_timezone: Target IANA timezone name (required)
   - Example: {{"source_timezone": "America/New_York", "time": "14:30", "target_timezone": "Europe/London"}}
+  - Example: {{"source_timezone": "UTC", "time": "2024-03-15T14:30:00", "target_timezone": "Asia/Tokyo"}}
+
+- date_arithmetic: Add or subtract a duration from a datetime
+  - base_datetime: RFC 3339 or local YYYY-MM-DD[THH:MM[:SS]] datetime (required)
+  - timezone: IANA timezone name (required)
+  - operation: "add" or "subtract" (required)
+  - years, months, days, hours, minutes: Amounts to apply (optional)
+  - Example: {{"base_datetime": "2025-01-31", "timezone": "Asia/Tokyo", "operation": "add", "days": 90}}
+
+- get_unix_timestamp: Get the current Unix timestamp
+  - timezone: IANA timezone name (required)
+  - Example: {{"timezone": "UTC"}}
+
+- from_unix_timestamp: Convert a Unix timestamp to a datetime
+  - unix_seconds: Seconds since the Unix epoch, may be negative (required)
+  - target_timezone: Target IANA timezone name (required)
+  - Example: {{"unix_seconds": 1700000000, "target_timezone": "Europe/London"}}
+
+- calculate_duration: Calculate the interval between two datetimes
+  - start, end: HH:MM, RFC 3339 or YYYY-MM-DD[THH:MM[:SS]] (required)
+  - start_timezone, end_timezone: IANA timezone names (required)
+  - Example: {{"start": "09:00", "start_timezone": "America/New_York", "end": "17:30", "end_timezone": "Europe/London"}}
+
+- list_timezones: List IANA timezone names
+  - filter: Fuzzy query, returns at most 50 matches (optional)
+  - region: Region prefix such as 'America' or 'Europe' (optional)
+  - Example: {{"filter": "york", "region": "America"}}
+
+- get_dst_transitions: Get when DST starts and ends in a year
+  - timezone: IANA timezone name (required)
+  - year: Calendar year (required)
+  - Example: {{"timezone": "America/New_York", "year": 2024}}
+
+- get_business_days: Count working days between two dates (inclusive)
+  - start_date, end_date: YYYY-MM-DD or RFC 3339 (required)
+  - timezone: IANA timezone name (required)
+  - exclude_weekends: Skip Saturdays and Sundays (optional, default true)
+  - holidays: List of YYYY-MM-DD dates to skip (optional)
+  - Example: {{"start_date": "2024-12-01", "end_date": "2024-12-31", "timezone": "UTC", "holidays": ["2024-12-25"]}}
+
+- get_calendar_week: Get the ISO week number, ISO year, weekday and quarter of a date
+  - date: YYYY-MM-DD or RFC 3339 (required)
+  - timezone: IANA timezone name (required)
+  - Example: {{"date": "2024-12-30", "timezone": "Europe/Berlin"}}
+
+- get_meeting_time: Show one time in several timezones, ordered by UTC offset
+  - source_time: Time in 24-hour format HH:MM, today in the source timezone (required)
+  - source_timezone: IANA timezone name (required)
+  - target_timezones: List of IANA timezone names (required)
+  - Example: {{"source_time": "09:30", "source_timezone": "Europe/London", "target_timezones": ["America/New_York", "Asia/Tokyo"]}}
+
+- get_sunrise_sunset: Sunrise, sunset and civil twilight for a date and position
+  - date: YYYY-MM-DD or RFC 3339 (required)
+  - latitude: Degrees, positive north (required)
+  - longitude: Degrees, positive east (required)
+  - timezone: IANA timezone to report times in (required)
+  - Example: {{"date": "2024-06-20", "latitude": 40.7128, "longitude": -74.006, "timezone": "America/New_York"}}
+
+- schedule_from_cron: List the next run times of a cron expression
+  - expression: Five crontab fields, six/seven fields with seconds (and year), or @daily style shorthands (required)
+  - timezone: IANA timezone the expression runs in (required)
+  - from_datetime: Find runs after this RFC 3339 or local datetime (optional, default: now)
+  - count: Number of run times, 1-100 (optional, default: 5)
+  - Example: {{"expression": "0 9 * * MON-FRI", "timezone": "America/New_York", "count": 3}}
+
+- format_datetime: Render a datetime with a strftime-style format
+  - datetime: RFC 3339 or YYYY-MM-DD[THH:MM[:SS]] (required)
+  - timezone: IANA timezone to render in, also used for naive datetimes (required)
+  - format: strftime pattern such as '%Y-%m-%d', '%B %d, %Y' or '%I:%M %p' (required)
+  - Example: {{"datetime": "2024-03-15T14:30", "timezone": "Europe/Paris", "format": "%A %d %B %Y"}}
+
+- format_relative_time: Describe a datetime relative to now
+  - datetime: RFC 3339 or YYYY-MM-DD[THH:MM[:SS]] (required)
+  - timezone: IANA timezone for naive datetimes (required)
+  - reference_timezone: Observer timezone (optional, defaults to local)
+  - Example: {{"datetime": "2025-01-31T09:00", "timezone": "Asia/Tokyo"}}
+
+- parse_duration_string: Parse a human-readable duration
+  - duration: e.g. '90s', '2h30m', '1d12h', '2 hours 30 minutes', 'P2DT3H' (required)
+  - Example: {{"duration": "2h30m"}}
 
 PROMPTS:
 - timezone_guidance: Get best practices for timezone usage
@@ -279,6 +469,7 @@ RESOURCES:
 - time://status: Current server status and local time
 - time://help: This help documentation
 - time://timezones: List of common IANA timezone names
+- time://capabilities: Tool names and parameter schemas as JSON
 
 LOCAL TIMEZONE: {}
 
@@ -313,6 +504,19 @@ DST HANDLING:
         )
     }
 
+    /// Tool names, descriptions and input schemas as JSON, for clients that introspect the server
+    fn generate_capabilities_content(&self) -> String {
+        Capabilities::new(
+            self.get_info().protocol_version,
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+        )
+        .with_tools(self.tool_router.list_all())
+        .with_prompts(self.prompt_router.list_all())
+        .with_resources(self.resources())
+        .to_json_string()
+    }
+
     fn generate_timezone_list_content(&self) -> &'static str {
         r#"Common IANA Timezone Names
 
@@ -398,6 +602,186 @@ impl TimeService {
             serde_json::to_string_pretty(&result).unwrap(),
         )]))
     }
+
+    #[tool(
+        description = "Add or subtract years, months, days, hours and minutes from a datetime in a specific timezone"
+    )]
+    pub(crate) async fn date_arithmetic(
+        &self,
+        Parameters(req): Parameters<DateArithmeticRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.date_arithmetic(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Get the current time as a Unix timestamp in seconds and milliseconds")]
+    pub(crate) async fn get_unix_timestamp(
+        &self,
+        Parameters(req): Parameters<GetUnixTimestampRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.get_unix_timestamp(&req.timezone)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Convert a Unix timestamp in seconds to a datetime in a specific timezone"
+    )]
+    pub(crate) async fn from_unix_timestamp(
+        &self,
+        Parameters(req): Parameters<FromUnixTimestampRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self
+            .time_server
+            .datetime_from_unix_timestamp(req.unix_seconds, &req.target_timezone)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Calculate the duration between two datetimes, possibly in different timezones"
+    )]
+    pub(crate) async fn calculate_duration(
+        &self,
+        Parameters(req): Parameters<CalculateDurationRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.calculate_duration(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "List IANA timezone names, optionally filtered by a fuzzy query or region prefix"
+    )]
+    pub(crate) async fn list_timezones(
+        &self,
+        Parameters(req): Parameters<ListTimezonesRequest>,
+    ) -> McpResult<CallToolResult> {
+        let timezones = self.list_timezone_names(&req);
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&timezones).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Get the daylight saving time transitions of a timezone for a given year")]
+    pub(crate) async fn get_dst_transitions(
+        &self,
+        Parameters(req): Parameters<GetDstTransitionsRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self
+            .time_server
+            .get_dst_transitions(&req.timezone, req.year)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Count business days between two dates, excluding weekends and optional holidays"
+    )]
+    pub(crate) async fn get_business_days(
+        &self,
+        Parameters(req): Parameters<GetBusinessDaysRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.count_business_days(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Get the ISO 8601 week number and week-numbering year of a date, with its weekday and calendar quarter"
+    )]
+    pub(crate) async fn get_calendar_week(
+        &self,
+        Parameters(req): Parameters<GetCalendarWeekRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.get_calendar_week(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Show one meeting time (HH:MM, today in the source timezone) in several timezones at once, ordered by UTC offset"
+    )]
+    pub(crate) async fn get_meeting_time(
+        &self,
+        Parameters(req): Parameters<GetMeetingTimeRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.get_meeting_time(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Get sunrise, sunset, day length and civil twilight for a date at a latitude/longitude, with times in the given timezone"
+    )]
+    pub(crate) async fn get_sunrise_sunset(
+        &self,
+        Parameters(req): Parameters<GetSunriseSunsetRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.get_sunrise_sunset(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "List the next run times of a cron expression in a timezone as a JSON array of ISO 8601 datetimes. Accepts five crontab fields (e.g. '0 9 * * MON-FRI'), six or seven fields with leading seconds and optional year, or @daily/@weekly/@monthly/@yearly/@hourly. Runs follow the local wall clock across DST changes"
+    )]
+    pub(crate) async fn schedule_from_cron(
+        &self,
+        Parameters(req): Parameters<ScheduleFromCronRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.schedule_from_cron(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Render a datetime with a strftime-style format string, e.g. '%B %d, %Y at %I:%M %p' gives 'March 15, 2024 at 02:30 PM'"
+    )]
+    pub(crate) async fn format_datetime(
+        &self,
+        Parameters(req): Parameters<FormatDatetimeRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.format_datetime(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(description = "Describe a datetime relative to now, e.g. '3 hours ago' or 'in 2 days'")]
+    pub(crate) async fn format_relative_time(
+        &self,
+        Parameters(req): Parameters<FormatRelativeTimeRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.format_relative_time(&req)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
+
+    #[tool(
+        description = "Parse a duration such as '90s', '2h30m', '2 hours 30 minutes' or 'P2DT3H' into seconds"
+    )]
+    pub(crate) async fn parse_duration_string(
+        &self,
+        Parameters(req): Parameters<ParseDurationRequest>,
+    ) -> McpResult<CallToolResult> {
+        let result = self.time_server.parse_duration(&req.duration)?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result).unwrap(),
+        )]))
+    }
 }
 
 #[prompt_router]
@@ -521,7 +905,20 @@ impl ServerHandler for TimeService {
                 "Time MCP Server for timezone operations with smart completion:\n\n\
                  Tools:\n\
                  • get_current_time: Get current time (timezone completion available)\n\
-                 • convert_time: Convert between timezones (all fields have completion)\n\n\
+                 • convert_time: Convert between timezones (all fields have completion)\n\
+                 • date_arithmetic: Add or subtract durations from a datetime\n\
+                 • get_unix_timestamp / from_unix_timestamp: Convert to and from Unix time\n\
+                 • calculate_duration: Interval between two datetimes\n\
+                 • list_timezones: Discover valid IANA timezone names\n\
+                 • get_dst_transitions: DST start and end dates for a year\n\
+                 • get_business_days: Working days between two dates\n\
+                 • get_calendar_week: ISO week number and quarter of a date\n\
+                 • get_meeting_time: One time shown across several timezones\n\
+                 • get_sunrise_sunset: Sunrise, sunset and twilight for a location\n\
+                 • schedule_from_cron: Next run times of a cron expression\n\
+                 • format_datetime: Render a datetime with a strftime pattern\n\
+                 • format_relative_time: '3 hours ago' style descriptions\n\
+                 • parse_duration_string: Parse '2h30m' style durations\n\n\
                  Completion features:\n\
                  • Fuzzy matching for timezone names ('ny' → 'America/New_York')\n\
                  • Time format suggestions (HH:MM format)\n\
@@ -538,11 +935,7 @@ impl ServerHandler for TimeService {
         _: RequestContext<rmcp::RoleServer>,
     ) -> McpResult<ListResourcesResult> {
         Ok(ListResourcesResult {
-            resources: vec![
-                self.create_resource_text("time://status", "server-status"),
-                self.create_resource_text("time://help", "help-documentation"),
-                self.create_resource_text("time://timezones", "timezone-list"),
-            ],
+            resources: self.resources(),
             next_cursor: None,
         })
     }
@@ -571,6 +964,12 @@ impl ServerHandler for TimeService {
                     contents: vec![ResourceContents::text(common_timezones, uri)],
                 })
             }
+            "time://capabilities" => {
+                let capabilities = self.generate_capabilities_content();
+                Ok(ReadResourceResult {
+                    contents: vec![ResourceContents::text(capabilities, uri)],
+                })
+            }
             _ => Err(crate::core::error::TimeServerError::ResourceNotFound {
                 uri: uri.to_string(),
             }
@@ -619,22 +1018,8 @@ impl ServerHandler for TimeService {
                         // This prompt doesn't take arguments, so no completion needed
                         vec![]
                     }
-                    "timezone_conversion" => {
-                        // Provide completion for timezone conversion prompt arguments
-                        match request.argument.name.as_str() {
-                            "source_timezone" | "target_timezone" => self.get_timezone_candidates(),
-                            "time" => self.get_time_format_candidates(),
-                            _ => vec![],
-                        }
-                    }
-                    _ => {
-                        // For any future prompts that might have timezone-related arguments
-                        match request.argument.name.as_str() {
-                            "source_timezone" | "target_timezone" => self.get_timezone_candidates(),
-                            "time" => self.get_time_format_candidates(),
-                            _ => vec![],
-                        }
-                    }
+                    // timezone_conversion and any future prompts with time-related arguments
+                    _ => self.get_argument_candidates(&request.argument.name),
                 }
             }
             Reference::Resource(_resource_ref) => {
@@ -646,10 +1031,15 @@ impl ServerHandler for TimeService {
             }
         };
 
-        let suggestions = self.fuzzy_match(&request.argument.value, &candidates);
+        let suggestions = self.fuzzy_match(
+            &request.argument.value,
+            &candidates,
+            COMPLETION_SUGGESTION_LIMIT,
+        );
 
+        // MCP completion values are plain strings, so the scores stop here
         let completion = CompletionInfo {
-            values: suggestions,
+            values: suggestions.into_iter().map(|m| m.value).collect(),
             total: None,
             has_more: Some(false),
         };
@@ -658,14 +1048,68 @@ impl ServerHandler for TimeService {
     }
 }
 
-pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// Transport the MCP server is served over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// JSON-RPC over stdin/stdout
+    Stdio,
+    /// Server-Sent Events over HTTP, listening on the given address
+    Sse(SocketAddr),
+}
+
+/// Start serving over SSE, with one `TimeService` per client session
+///
+/// Returns the token that shuts the HTTP server down when cancelled.
+pub(crate) async fn serve_sse(
+    bind: SocketAddr,
+    time_server: TimeServer,
+) -> std::io::Result<CancellationToken> {
+    let server = SseServer::serve(bind).await?;
+    Ok(server.with_service(move || TimeService::with_time_server(time_server.clone())))
+}
+
+/// Serve the MCP protocol over `transport`
+///
+/// With `timezone_db_path`, timezones are also resolved from the TZif files in
+/// that directory (see [`TimeServer::with_tzdb`]).
+pub async fn run(
+    transport: Transport,
+    timezone_db_path: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
     use rmcp::{ServiceExt, transport::stdio};
 
-    let service = TimeService::new().serve(stdio()).await.inspect_err(|e| {
-        tracing::error!("serving error: {:?}", e);
-    })?;
+    let time_server = match timezone_db_path {
+        Some(path) => {
+            let time_server = TimeServer::with_tzdb(path)?;
+            tracing::info!(
+                "Loaded {} timezones from {}",
+                time_server.tzdb.as_ref().map_or(0, |tzdb| tzdb.len()),
+                path.display()
+            );
+            time_server
+        }
+        None => TimeServer::new(),
+    };
+
+    match transport {
+        Transport::Stdio => {
+            let service = TimeService::with_time_server(time_server)
+                .serve(stdio())
+                .await
+                .inspect_err(|e| {
+                    tracing::error!("serving error: {:?}", e);
+                })?;
+
+            service.waiting().await?;
+        }
+        Transport::Sse(bind) => {
+            let ct = serve_sse(bind, time_server).await?;
+            tracing::info!("Serving MCP over SSE at http://{}/sse", bind);
 
-    service.waiting().await?;
+            tokio::signal::ctrl_c().await?;
+            ct.cancel();
+        }
+    }
     Ok(())
 }
 
@@ -674,9 +1118,44 @@ mod tests {
     use rmcp::handler::server::wrapper::Parameters;
     use rmcp::model::ProtocolVersion;
 
-    use crate::core::models::{ConvertTimeRequest, GetCurrentTimeRequest};
+    use crate::core::models::{
+        CalculateDurationRequest, ConvertTimeRequest, DateArithmeticRequest,
+        FromUnixTimestampRequest, GetCurrentTimeRequest, GetUnixTimestampRequest,
+        ListTimezonesRequest,
+    };
     use crate::core::provider::TimeServer;
-    use crate::server::TimeService;
+    use crate::core::utils::AVAILABLE_RESOURCES;
+    use crate::server::{LIST_TIMEZONES_FILTER_LIMIT, TimeService};
+
+    #[tokio::test]
+    async fn test_sse_transport_round_trip() {
+        use rmcp::{ServiceExt, model::CallToolRequestParam, transport::SseClientTransport};
+
+        let bind = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .unwrap();
+        let ct = crate::server::serve_sse(bind, TimeServer::new())
+            .await
+            .unwrap();
+
+        let transport = SseClientTransport::start(format!("http://{}/sse", bind))
+            .await
+            .unwrap();
+        let client = ().serve(transport).await.unwrap();
+
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "get_current_time".into(),
+                arguments: serde_json::json!({"timezone": "UTC"}).as_object().cloned(),
+            })
+            .await
+            .unwrap();
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert!(text.contains("\"timezone\": \"UTC\""));
+
+        client.cancel().await.unwrap();
+        ct.cancel();
+    }
 
     #[tokio::test]
     async fn test_get_current_time() {
@@ -749,6 +1228,189 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_date_arithmetic() {
+        let service = TimeService::new();
+
+        let req: DateArithmeticRequest = serde_json::from_value(serde_json::json!({
+            "base_datetime": "2025-01-31T09:00",
+            "timezone": "Asia/Tokyo",
+            "operation": "add",
+            "days": 90
+        }))
+        .unwrap();
+
+        let result = service.date_arithmetic(Parameters(req)).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unix_timestamp_tools() {
+        let service = TimeService::new();
+
+        let req = GetUnixTimestampRequest {
+            timezone: "UTC".to_string(),
+        };
+        assert!(service.get_unix_timestamp(Parameters(req)).await.is_ok());
+
+        let req = FromUnixTimestampRequest {
+            unix_seconds: -1,
+            target_timezone: "Asia/Tokyo".to_string(),
+        };
+        assert!(service.from_unix_timestamp(Parameters(req)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_calculate_duration() {
+        let service = TimeService::new();
+
+        let req = CalculateDurationRequest {
+            start: "09:00".to_string(),
+            start_timezone: "America/New_York".to_string(),
+            end: "2025-01-01T00:00:00Z".to_string(),
+            end_timezone: "UTC".to_string(),
+        };
+        assert!(service.calculate_duration(Parameters(req)).await.is_ok());
+
+        let req = CalculateDurationRequest {
+            start: "09:00".to_string(),
+            start_timezone: "Invalid/Zone".to_string(),
+            end: "10:00".to_string(),
+            end_timezone: "UTC".to_string(),
+        };
+        assert!(service.calculate_duration(Parameters(req)).await.is_err());
+    }
+
+    #[test]
+    fn test_fuzzy_score_tiers() {
+        let service = TimeService::new();
+        let score = |query: &str, candidate: &str| service.fuzzy_score(query, candidate);
+
+        assert_eq!(score("utc", "UTC"), Some(1000));
+        assert_eq!(score("america/new", "America/New_York"), Some(900));
+        assert_eq!(score("york", "America/New_York"), Some(800));
+        assert_eq!(score("any", "America/New_York"), Some(700));
+        assert_eq!(score("amny", "America/New_York"), Some(680));
+        assert_eq!(score("xyz", "America/New_York"), None);
+        // A single letter matching the first letter is always also a prefix match
+        assert_eq!(score("u", "UTC"), Some(900));
+    }
+
+    #[test]
+    fn test_fuzzy_match_orders_by_score() {
+        let service = TimeService::new();
+        let candidates = [
+            "America/New_York",
+            "Europe/London",
+            "Pacific/Auckland",
+            "Antarctica/Troll",
+        ]
+        .map(String::from);
+
+        let matches = service.fuzzy_match("an", &candidates, 10);
+        let scored: Vec<_> = matches
+            .iter()
+            .map(|m| (m.value.as_str(), m.score))
+            .collect();
+        assert_eq!(
+            scored,
+            [
+                ("Antarctica/Troll", 900),
+                ("Pacific/Auckland", 800),
+                ("America/New_York", 680),
+            ]
+        );
+
+        let unfiltered = service.fuzzy_match("", &candidates, 2);
+        assert_eq!(unfiltered.len(), 2);
+        assert!(unfiltered.iter().all(|m| m.score == 0));
+    }
+
+    #[test]
+    fn test_argument_candidates() {
+        let service = TimeService::new();
+
+        for argument in [
+            "timezone",
+            "source_timezone",
+            "target_timezone",
+            "end_timezone",
+        ] {
+            assert!(
+                service
+                    .get_argument_candidates(argument)
+                    .contains(&"UTC".to_string())
+            );
+        }
+        assert!(
+            service
+                .get_argument_candidates("time")
+                .contains(&"14:30".to_string())
+        );
+        assert!(service.get_argument_candidates("year").is_empty());
+    }
+
+    #[test]
+    fn test_list_timezones_unfiltered() {
+        let service = TimeService::new();
+
+        let timezones = service.list_timezone_names(&ListTimezonesRequest::default());
+        assert!(timezones.contains(&"UTC".to_string()));
+        assert!(timezones.len() > LIST_TIMEZONES_FILTER_LIMIT);
+    }
+
+    #[test]
+    fn test_list_timezones_filter() {
+        let service = TimeService::new();
+
+        let req = ListTimezonesRequest {
+            filter: Some("york".to_string()),
+            region: None,
+        };
+        let timezones = service.list_timezone_names(&req);
+        assert_eq!(
+            timezones.first().map(String::as_str),
+            Some("America/New_York")
+        );
+        assert!(timezones.len() <= LIST_TIMEZONES_FILTER_LIMIT);
+
+        let req = ListTimezonesRequest {
+            filter: Some("a".to_string()),
+            region: None,
+        };
+        assert_eq!(
+            service.list_timezone_names(&req).len(),
+            LIST_TIMEZONES_FILTER_LIMIT
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_timezones_region() {
+        let service = TimeService::new();
+
+        let req = ListTimezonesRequest {
+            filter: None,
+            region: Some("europe".to_string()),
+        };
+        let timezones = service.list_timezone_names(&req);
+        assert!(timezones.contains(&"Europe/London".to_string()));
+        assert!(timezones.iter().all(|tz| tz.starts_with("Europe/")));
+
+        let req = ListTimezonesRequest {
+            filter: Some("new".to_string()),
+            region: Some("America".to_string()),
+        };
+        let timezones = service.list_timezone_names(&req);
+        assert!(!timezones.is_empty());
+        assert!(timezones.iter().all(|tz| tz.starts_with("America/")));
+
+        let req = ListTimezonesRequest {
+            filter: None,
+            region: Some("Asia".to_string()),
+        };
+        assert!(service.list_timezones(Parameters(req)).await.is_ok());
+    }
+
     #[test]
     fn test_time_server_creation() {
         let server = TimeServer::new();
@@ -770,6 +1432,47 @@ mod tests {
         assert!(info.instructions.is_some());
     }
 
+    #[test]
+    fn test_status_counts_registered_tools_and_prompts() {
+        let service = TimeService::new();
+        let status = service.generate_status_content().unwrap();
+
+        let tools = TimeService::tool_router().list_all().len();
+        let prompts = TimeService::prompt_router().list_all().len();
+        assert!(status.contains(&format!("Tools Available: {}\n", tools)));
+        assert!(status.contains(&format!("Prompts Available: {}\n", prompts)));
+        assert!(status.contains(&format!(
+            "Resources Available: {}\n",
+            AVAILABLE_RESOURCES.len()
+        )));
+    }
+
+    #[test]
+    fn test_capabilities_resource_lists_every_tool() {
+        let service = TimeService::new();
+        let capabilities: serde_json::Value =
+            serde_json::from_str(&service.generate_capabilities_content()).unwrap();
+
+        assert_eq!(capabilities["protocol_version"], "2024-11-05");
+        let tools = capabilities["tools"].as_array().unwrap();
+        assert_eq!(tools.len(), TimeService::tool_router().list_all().len());
+
+        let convert = tools
+            .iter()
+            .find(|tool| tool["name"] == "convert_time")
+            .unwrap();
+        let properties = convert["input_schema"]["properties"].as_object().unwrap();
+        assert!(properties.contains_key("source_timezone"));
+        assert!(properties.contains_key("target_timezone"));
+
+        let prompts = capabilities["prompts"].as_array().unwrap();
+        assert_eq!(prompts.len(), TimeService::prompt_router().list_all().len());
+        assert_eq!(
+            capabilities["resources"].as_array().unwrap().len(),
+            service.resources().len()
+        );
+    }
+
     #[test]
     fn test_timezone_parsing() {
         let server = TimeServer::new();